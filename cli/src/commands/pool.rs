@@ -327,6 +327,90 @@ fn close_pool(ctx: &CommandContext, handle: i32, name: &str) -> Result<(), ()> {
     }
 }
 
+pub mod validate_command {
+    use super::*;
+
+    command!(CommandMetadata::build("validate", "Validate genesis transactions file without connecting to the pool.")
+                .add_main_param("gen_txn_file", "Path to file with genesis transactions")
+                .add_optional_param("protocol-version", "Pool protocol version the transactions are checked against. One of: 1, 2. (2 or version of the connected pool by default)")
+                .add_example("pool validate /home/pool_genesis_transactions")
+                .add_example("pool validate /home/pool_genesis_transactions protocol-version=1")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let gen_txn_file = get_str_param("gen_txn_file", params).map_err(error_err!())?;
+        let protocol_version = get_opt_number_param::<usize>("protocol-version", params).map_err(error_err!())?;
+
+        // 0 makes libindy use the protocol version set on connection to the pool
+        let protocol_version = match (protocol_version, get_connected_pool(ctx)) {
+            (Some(protocol_version), _) => protocol_version,
+            (None, Some(_)) => 0,
+            (None, None) => PROTOCOL_VERSION
+        };
+
+        trace!(r#"Pool::validate_genesis try: gen_txn_file {}, protocol_version {}"#, gen_txn_file, protocol_version);
+
+        let res = Pool::validate_genesis(gen_txn_file, protocol_version);
+
+        trace!(r#"Pool::validate_genesis return: {:?}"#, res);
+
+        let res = match res {
+            Ok(report) => {
+                let report: serde_json::Value = serde_json::from_str(&report)
+                    .map_err(|_| println_err!("Wrong data has been received"))?;
+
+                let nodes = report["nodes"].as_array().cloned().unwrap_or(vec![]);
+
+                print_list_table(&nodes,
+                                 &[("alias", "Alias"),
+                                     ("validator", "Validator"),
+                                     ("client_address", "Client address"),
+                                     ("node_address", "Node address"),
+                                     ("bls_key_valid", "BLS key"),
+                                     ("bls_key_pop_valid", "BLS key proof")],
+                                 "There are no nodes in genesis transactions file");
+
+                println!("Validators: {}, f: {}, weak quorum: {}, strong quorum: {}",
+                         report["validators_count"], report["f"], report["weak_quorum"], report["strong_quorum"]);
+
+                for warning in report["warnings"].as_array().cloned().unwrap_or(vec![]) {
+                    println_warn!("{}", warning.as_str().unwrap_or_default());
+                }
+
+                for error in report["errors"].as_array().cloned().unwrap_or(vec![]) {
+                    println_err!("{}", error.as_str().unwrap_or_default());
+                }
+
+                if report["valid"].as_bool().unwrap_or(false) {
+                    println_succ!("Genesis transactions file \"{}\" is valid", gen_txn_file);
+                    Ok(())
+                } else {
+                    println_err!("Genesis transactions file \"{}\" is invalid", gen_txn_file);
+                    Err(())
+                }
+            }
+            Err(err) => {
+                match err.error_code {
+                    ErrorCode::CommonIOError => {
+                        println_err!("Pool genesis file does not exist.");
+                        Err(())
+                    }
+                    _ => {
+                        handle_indy_error(err, None, None, None);
+                        Err(())
+                    }
+                }
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
 pub mod delete_command {
     use super::*;
 
@@ -734,6 +818,60 @@ pub mod tests {
         }
     }
 
+    mod validate {
+        use super::*;
+
+        #[test]
+        pub fn validate_works() {
+            let ctx = setup();
+            {
+                let cmd = validate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("gen_txn_file", "docker_pool_transactions_genesis".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn validate_works_for_unknown_txn_file() {
+            let ctx = setup();
+            {
+                let cmd = validate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("gen_txn_file", "unknown_pool_transactions_genesis".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn validate_works_for_invalid_protocol_version() {
+            let ctx = setup();
+            {
+                let cmd = validate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("gen_txn_file", "docker_pool_transactions_genesis".to_string());
+                params.insert("protocol-version", "1".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn validate_works_for_unsupported_protocol_version() {
+            let ctx = setup();
+            {
+                let cmd = validate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("gen_txn_file", "docker_pool_transactions_genesis".to_string());
+                params.insert("protocol-version", "3".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
     mod delete {
         use super::*;
 
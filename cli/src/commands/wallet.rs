@@ -394,6 +394,106 @@ pub mod delete_command {
     }
 }
 
+pub mod check_command {
+    use super::*;
+
+    command!(CommandMetadata::build("check", "Check integrity of closed wallet.")
+                .add_main_param_with_dynamic_completion("name", "Identifier of the wallet", DynamicCompletionType::Wallet)
+                .add_required_deferred_param("key", "Key or passphrase used for wallet key derivation.
+                                               Look to key_derivation_method param for information about supported key derivation methods.")
+                .add_optional_param("key_derivation_method", "Algorithm to use for wallet key derivation. One of:
+                                    argon2m - derive secured wallet key (used by default)
+                                    argon2i - derive secured wallet key (less secured but faster)
+                                    raw - raw key provided (skip derivation)")
+                .add_optional_param("storage_credentials", "The list of key:value pairs defined by storage type.")
+                .add_optional_param("repair", "Remove invalid records and orphaned tags from the wallet (false by default)")
+                .add_optional_param("quarantine_path", "Path to the file where removed records are saved before repair")
+                .add_example("wallet check wallet1 key")
+                .add_example("wallet check wallet1 key repair=true quarantine_path=/home/indy/quarantine")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx: {:?} params {:?}", ctx, secret!(params));
+
+        let id = get_str_param("name", params).map_err(error_err!())?;
+        let key = get_str_param("key", params).map_err(error_err!())?;
+        let key_derivation_method = get_opt_str_param("key_derivation_method", params).map_err(error_err!())?;
+        let storage_credentials = get_opt_object_param("storage_credentials", params).map_err(error_err!())?;
+        let repair = get_opt_bool_param("repair", params).map_err(error_err!())?.unwrap_or(false);
+        let quarantine_path = get_opt_str_param("quarantine_path", params).map_err(error_err!())?;
+
+        let config = _read_wallet_config(id)
+            .map_err(|_| println_err!("Wallet \"{}\" isn't attached to CLI", id))?;
+
+        let credentials = build_credentials(
+            key,
+            &map_key_derivation_method(key_derivation_method)?.to_string(),
+            storage_credentials);
+
+        let options = json!({ "repair": repair, "quarantine_path": quarantine_path }).to_string();
+
+        let res = match Wallet::check_wallet(config.as_str(), credentials.as_str(), options.as_str()) {
+            Ok(report) => {
+                let report = serde_json::from_str::<JSONValue>(&report)
+                    .map_err(|_| println_err!("Wrong data has been received"))?;
+
+                let metadata_errors = report["metadata_errors"].as_array().cloned().unwrap_or_default();
+
+                if !metadata_errors.is_empty() {
+                    for error in metadata_errors {
+                        println_err!("{}", error.as_str().unwrap_or_default());
+                    }
+                    println_err!("Records of wallet \"{}\" can't be checked", id);
+                    return Err(());
+                }
+
+                let invalid_records = report["invalid_records"].as_array().cloned().unwrap_or_default();
+
+                print_list_table(&invalid_records,
+                                 &[("type", "Type"),
+                                     ("id", "Id"),
+                                     ("error", "Error")],
+                                 "There are no invalid records");
+
+                println!("Checked records: {}, orphaned tags: {}", report["checked_records"], report["orphaned_tags"]);
+
+                if report["valid"].as_bool().unwrap_or(false) {
+                    println_succ!("Wallet \"{}\" is valid", id);
+                } else if report["repaired"].as_bool().unwrap_or(false) {
+                    println_succ!("Wallet \"{}\" has been repaired", id);
+                } else {
+                    println_warn!("Wallet \"{}\" has problems. Use repair=true to remove them", id);
+                }
+                Ok(())
+            }
+            Err(err) => {
+                match err.error_code {
+                    ErrorCode::WalletNotFoundError => {
+                        println_err!("Wallet \"{}\" not found or unavailable", id);
+                        Err(())
+                    },
+                    ErrorCode::WalletAccessFailed => {
+                        println_err!("Cannot check wallet \"{}\". Invalid key has been provided ", id);
+                        Err(())
+                    },
+                    ErrorCode::WalletAlreadyOpenedError => {
+                        println_err!("Wallet \"{}\" is opened", id);
+                        Err(())
+                    },
+                    _ => {
+                        handle_indy_error(err, None, None, Some(&id));
+                        Err(())
+                    }
+                }
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
 pub mod detach_command {
     use super::*;
 
@@ -1080,6 +1180,87 @@ pub mod tests {
         }
     }
 
+    mod check {
+        use super::*;
+
+        #[test]
+        pub fn check_works() {
+            let ctx = setup();
+            create_wallet(&ctx);
+            {
+                let cmd = check_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            delete_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_repair() {
+            let ctx = setup();
+            create_wallet(&ctx);
+            {
+                let cmd = check_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                params.insert("repair", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            delete_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_opened() {
+            let ctx = setup();
+            create_and_open_wallet(&ctx);
+            {
+                let cmd = check_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            close_and_delete_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_wrong_key() {
+            let ctx = setup();
+            create_wallet(&ctx);
+            {
+                let cmd = check_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", "other_key".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            delete_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_not_attached() {
+            let ctx = setup();
+
+            let cmd = check_command::new();
+            let mut params = CommandParams::new();
+            params.insert("name", WALLET.to_string());
+            params.insert("key", WALLET_KEY.to_string());
+            cmd.execute(&ctx, &params).unwrap_err();
+
+            tear_down();
+        }
+    }
+
     mod detach {
         use super::*;
 
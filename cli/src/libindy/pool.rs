@@ -17,6 +17,10 @@ impl Pool {
         pool::refresh_pool_ledger(pool_handle).wait()
     }
 
+    pub fn validate_genesis(gen_txn_file: &str, protocol_version: usize) -> Result<String, IndyError> {
+        pool::validate_pool_genesis(gen_txn_file, protocol_version).wait()
+    }
+
     pub fn list() -> Result<String, IndyError> {
         pool::list_pools().wait()
     }
@@ -17,6 +17,10 @@ impl Wallet {
         wallet::delete_wallet(wallet_name, credentials).wait()
     }
 
+    pub fn check_wallet(config: &str, credentials: &str, options: &str) -> Result<String, IndyError> {
+        wallet::check_wallet(config, credentials, Some(options)).wait()
+    }
+
     pub fn close_wallet(wallet_handle: i32) -> Result<(), IndyError> {
         wallet::close_wallet(wallet_handle).wait()
     }
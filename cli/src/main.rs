@@ -140,6 +140,7 @@ fn build_executor() -> CommandExecutor {
         .add_command(pool::connect_command::new())
         .add_command(pool::refresh_command::new())
         .add_command(pool::list_command::new())
+        .add_command(pool::validate_command::new())
         .add_command(pool::disconnect_command::new())
         .add_command(pool::delete_command::new())
         .add_command(pool::show_taa_command::new())
@@ -151,6 +152,7 @@ fn build_executor() -> CommandExecutor {
         .add_command(wallet::list_command::new())
         .add_command(wallet::close_command::new())
         .add_command(wallet::delete_command::new())
+        .add_command(wallet::check_command::new())
         .add_command(wallet::detach_command::new())
         .add_command(wallet::export_command::new())
         .add_command(wallet::import_command::new())
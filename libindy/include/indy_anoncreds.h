@@ -88,7 +88,36 @@ extern "C" {
                                                                            const char*   cred_revoc_id,
                                                                            const char*   revoc_reg_delta_json)
                                                       );
+
+    extern indy_error_t indy_issuer_create_credential_with_tags(indy_handle_t command_handle,
+                                                                indy_handle_t wallet_handle,
+                                                                const char *  cred_offer_json,
+                                                                const char *  cred_req_json,
+                                                                const char *  cred_values_json,
+                                                                const char *  rev_reg_id,
+                                                                indy_handle_t blob_storage_reader_handle,
+                                                                const char *  record_tags_json,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     const char*   cred_json,
+                                                                                     const char*   cred_revoc_id,
+                                                                                     const char*   revoc_reg_delta_json)
+                                                                );
     
+    extern indy_error_t indy_issuer_create_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  cred_offer_json,
+                                                       const char *  cred_issuance_requests_json,
+                                                       const char *  rev_reg_id,
+                                                       indy_handle_t blob_storage_reader_handle,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   creds_json,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       indy_handle_t blob_storage_reader_handle,
@@ -100,6 +129,93 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_revoke_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       indy_handle_t blob_storage_reader_handle,
+                                                       const char *  rev_reg_id,
+                                                       const char *  cred_revoc_ids_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
+    extern indy_error_t indy_issuer_create_revocation_registry_pool(indy_handle_t command_handle,
+                                                                    indy_handle_t wallet_handle,
+                                                                    const char *  issuer_did,
+                                                                    const char *  cred_def_id,
+                                                                    const char *  config_json,
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err,
+                                                                                         const char*   rev_regs_json)
+                                                                    );
+
+    extern indy_error_t indy_issuer_create_credential_from_pool(indy_handle_t command_handle,
+                                                                indy_handle_t wallet_handle,
+                                                                const char *  cred_offer_json,
+                                                                const char *  cred_req_json,
+                                                                const char *  cred_values_json,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     const char*   issued_cred_json)
+                                                                );
+
+    extern indy_error_t indy_issuer_get_revocation_registry_pool(indy_handle_t command_handle,
+                                                                 indy_handle_t wallet_handle,
+                                                                 const char *  cred_def_id,
+
+                                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                                      indy_error_t  err,
+                                                                                      const char*   pool_json)
+                                                                 );
+
+    extern indy_error_t indy_issuer_revoke_credential_by_record(indy_handle_t command_handle,
+                                                                indy_handle_t wallet_handle,
+                                                                indy_handle_t blob_storage_reader_handle,
+                                                                const char *  record_id,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     const char*   revoc_reg_delta_json)
+                                                                );
+
+    extern indy_error_t indy_issuer_get_credential_record(indy_handle_t command_handle,
+                                                          indy_handle_t wallet_handle,
+                                                          const char *  record_id,
+
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   record_json)
+                                                          );
+
+    extern indy_error_t indy_issuer_search_credential_records(indy_handle_t command_handle,
+                                                              indy_handle_t wallet_handle,
+                                                              const char *  query_json,
+
+                                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                                   indy_error_t  err,
+                                                                                   indy_handle_t search_handle,
+                                                                                   indy_u32_t    total_count)
+                                                              );
+
+    extern indy_error_t indy_issuer_fetch_credential_records(indy_handle_t command_handle,
+                                                             indy_handle_t search_handle,
+                                                             indy_u32_t    count,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   records_json)
+                                                             );
+
+    extern indy_error_t indy_issuer_close_credential_records_search(indy_handle_t command_handle,
+                                                                    indy_handle_t search_handle,
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err)
+                                                                    );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -274,6 +390,19 @@ extern "C" {
                                                                         indy_bool_t   valid )
                                                    );
 
+    extern indy_error_t indy_verifier_verify_proof_with_report(indy_handle_t command_handle,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  schemas_json,
+                                                               const char *  credential_defs_jsons,
+                                                               const char *  rev_reg_defs_json,
+                                                               const char *  rev_regs_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   report_json)
+                                                               );
+
 
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
@@ -315,6 +444,13 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    extern indy_error_t indy_encode_credential_attr_values(indy_handle_t command_handle,
+                                                           const char *  raw_values_json,
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                const char*   cred_values_json)
+                                                           );
+
 #ifdef __cplusplus
 }
 #endif
@@ -6,6 +6,80 @@ extern "C" {
 #endif
 
 
+    /// Register custom key provider implementation.
+    ///
+    /// Keys created with the registered crypto type (See indy_create_key and indy_create_and_store_my_did)
+    /// live in the key provider (For example, in external KMS process) and only verkeys are stored in the wallet.
+    /// Signing and decryption with these keys are delegated to the key provider.
+    /// Keys are expected to be ed25519 keys, so signature verification and encryption
+    /// for these keys are performed by libindy itself.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// crypto_type: Crypto type name. Verkeys of the keys created by the provider have "<verkey>:<crypto_type>" form.
+    /// create_key: "create_key" operation handler
+    /// sign: "sign" operation handler
+    /// crypto_box: "crypto_box" (authenticated encryption) operation handler
+    /// crypto_box_open: "crypto_box_open" (authenticated decryption) operation handler
+    /// crypto_box_seal_open: "crypto_box_seal_open" (anonymous decryption) operation handler
+    /// free_result: frees results returned by the handlers above
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: command handle to map callback to caller context.
+    /// - err: Error code.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_register_key_provider(indy_handle_t  command_handle,
+                                                   const char*    crypto_type,
+
+                                                   indy_error_t (*create_key)(const indy_u8_t* seed,
+                                                                              indy_u32_t       seed_len,
+                                                                              const char**     verkey_p,
+                                                                              indy_handle_t*   result_handle_p),
+
+                                                   indy_error_t (*sign)(const char*       verkey,
+                                                                        const indy_u8_t*  message,
+                                                                        indy_u32_t        message_len,
+                                                                        const indy_u8_t** signature_p,
+                                                                        indy_u32_t*       signature_len_p,
+                                                                        indy_handle_t*    result_handle_p),
+
+                                                   indy_error_t (*crypto_box)(const char*       verkey,
+                                                                              const char*       their_vk,
+                                                                              const indy_u8_t*  message,
+                                                                              indy_u32_t        message_len,
+                                                                              const indy_u8_t*  nonce,
+                                                                              indy_u32_t        nonce_len,
+                                                                              const indy_u8_t** encrypted_p,
+                                                                              indy_u32_t*       encrypted_len_p,
+                                                                              indy_handle_t*    result_handle_p),
+
+                                                   indy_error_t (*crypto_box_open)(const char*       verkey,
+                                                                                   const char*       their_vk,
+                                                                                   const indy_u8_t*  encrypted,
+                                                                                   indy_u32_t        encrypted_len,
+                                                                                   const indy_u8_t*  nonce,
+                                                                                   indy_u32_t        nonce_len,
+                                                                                   const indy_u8_t** decrypted_p,
+                                                                                   indy_u32_t*       decrypted_len_p,
+                                                                                   indy_handle_t*    result_handle_p),
+
+                                                   indy_error_t (*crypto_box_seal_open)(const char*       verkey,
+                                                                                        const indy_u8_t*  encrypted,
+                                                                                        indy_u32_t        encrypted_len,
+                                                                                        const indy_u8_t** decrypted_p,
+                                                                                        indy_u32_t*       decrypted_len_p,
+                                                                                        indy_handle_t*    result_handle_p),
+
+                                                   indy_error_t (*free_result)(indy_handle_t result_handle),
+
+                                                   void (*cb)(indy_handle_t command_handle,
+                                                              indy_error_t  err)
+                                                  );
 
     /// Creates keys pair and stores in the wallet.
     ///
@@ -16,7 +90,8 @@ extern "C" {
     /// {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
-    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value
+    ///                            // and crypto types of key providers registered with indy_register_key_provider are supported for this field.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
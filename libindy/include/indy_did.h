@@ -23,7 +23,8 @@ extern "C" {
     ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               currently only 'ed25519' value and crypto types of key providers
+    ///               registered with indy_register_key_provider are supported for this field)
     ///     "cid": bool, (optional; if not set then false is used;)
     ///     "method_name": string, method name to create fully qualified did (Example:  `did:method_name:NcYxiDXkpYi6ov5FcYDi1e`).
     /// }
@@ -63,7 +64,8 @@ extern "C" {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               currently only 'ed25519' value and crypto types of key providers
+    ///               registered with indy_register_key_provider are supported for this field)
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
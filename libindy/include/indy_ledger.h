@@ -68,6 +68,49 @@ extern "C" {
                                                                  const char*   request_result_json)
                                            );
 
+    /// Publishes GET request message to validator pool using the persistent read cache of the pool.
+    ///
+    /// If a fresh enough reply on the same request is present inside of the cache, cached reply is returned.
+    /// Otherwise the request is sent to the validator pool as is and the reply is stored inside of the cache
+    /// if its state proof is verified.
+    ///
+    /// Cached requests: GET_NYM, GET_ATTRIB, GET_SCHEMA, GET_CRED_DEF, GET_REVOC_REG_DEF and GET_TXN.
+    /// Other requests are just sent to the pool.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// request_json: Request data json.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///              By default replies with schemas, credential definitions, revocation registry definitions and transactions
+    ///              are not checked and other replies are fresh during freshness threshold (see indy_set_runtime_config).
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_submit_cached_request(indy_handle_t command_handle,
+                                                   indy_handle_t pool_handle,
+                                                   const char *  request_json,
+                                                   const char *  options_json,
+
+                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        const char*   request_result_json)
+                                                  );
+
     /// Send action to particular nodes of validator pool.
     ///
     /// The list of requests can be send:
@@ -597,6 +640,37 @@ extern "C" {
                                                                         const char*   request_json)
                                                    );
 
+    /// Gets a transaction by its seq_no with the proof of its inclusion into the ledger.
+    ///
+    /// Sends GET_TXN request to the pool and checks the audit path of the reply against the ledger root hash
+    /// signed by the pool validators (BLS multi-signature), so a reply of a single node can be trusted.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// submitter_did: (Optional) DID of the read request sender.
+    /// ledger_type: (Optional) type of the ledger the requested transaction belongs to (DOMAIN by default).
+    /// seq_no: requested transaction sequence number as it's stored on Ledger.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Verified transaction json: {"txn": {...}, "seq_no": int, "root_hash": string, "verified": true}
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_get_verified_txn(indy_handle_t command_handle,
+                                              indy_handle_t pool_handle,
+                                              const char *  submitter_did,
+                                              const char *  ledger_type,
+                                              indy_i32_t    seq_no,
+
+                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                   indy_error_t  err,
+                                                                   const char*   txn_json)
+                                              );
+
     /// Builds a POOL_CONFIG request. Request to change Pool's configuration.
     ///
     /// #Params
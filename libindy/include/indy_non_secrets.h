@@ -144,6 +144,33 @@ extern "C" {
                                                                        indy_error_t err)
                                                  );
 
+    /// Apply a list of changes of non-secret wallet records atomically:
+    /// either all of them are applied or the wallet is left untouched.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// operations_json: list of operations applied in the given order:
+    ///   [
+    ///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": <optional json>},
+    ///     {"op": "update", "type": <str>, "id": <str>, "value": <str>},
+    ///     {"op": "add_tags", "type": <str>, "id": <str>, "tags": <json>},
+    ///     {"op": "update_tags", "type": <str>, "id": <str>, "tags": <json>},
+    ///     {"op": "delete_tags", "type": <str>, "id": <str>, "tag_names": [<str>, ...]},
+    ///     {"op": "delete", "type": <str>, "id": <str>},
+    ///   ]
+    ///   Tags format is the same as for indy_add_wallet_record.
+    ///
+    /// Note that for plugged storages the atomicity is emulated by restoring the changed records
+    /// if any of operations fails.
+
+    extern indy_error_t indy_batch_wallet_records(indy_handle_t  command_handle,
+                                                  indy_handle_t  wallet_handle,
+                                                  const char*    operations_json,
+                                                  void           (*fn)(indy_handle_t command_handle_,
+                                                                       indy_error_t err)
+                                                 );
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -200,6 +227,12 @@ extern "C" {
     ///    retrieveType: (optional, false by default) Retrieve record type,
     ///    retrieveValue: (optional, true by default) Retrieve record value,
     ///    retrieveTags: (optional, true by default) Retrieve record tags,
+    ///    sortBy: (optional) Name of the plaintext tag ("~" prefixed) to sort records by,
+    ///            records without this tag go first in ascending order
+    ///    sortOrder: (optional, "asc" by default) Sort order, "asc" or "desc",
+    ///    offset: (optional, 0 by default) Number of records to skip,
+    ///    limit: (optional) Maximum number of records to return,
+    ///    tagNames: (optional) Names of the tags to retrieve with retrieveTags, all tags by default,
     ///  }
     /// #Returns
     /// search_handle: Wallet search handle that can be used later
@@ -257,6 +290,56 @@ extern "C" {
                                                                       indy_error_t  err)
                                                 );
 
+    /// Subscribe to changes of wallet records.
+    /// The event callback is called after each change of the records of the type which tags
+    /// match the query until the subscription is removed or the wallet is closed.
+    /// Changes made by operations on whole wallet (import) aren't delivered.
+    ///
+    /// Record values are never delivered, so records of internal libindy types can be watched as well,
+    /// e.g. "Indy::Credential" records stored by indy_prover_store_credential.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: type of the records to watch
+    /// query_json: WQL query to the record tags, the same as for indy_open_wallet_search.
+    ///     Tags of the record after the change are matched (before the change for deleted records).
+    ///     {} matches all records of the type.
+    /// event_cb: Callback that takes the subscription handle and the event json:
+    ///  {
+    ///    "event": "add" | "update" | "add_tags" | "update_tags" | "delete_tags" | "delete",
+    ///    "type": "Some type",
+    ///    "id": "Some id",
+    ///    "tags": <tags json>, // tags after the change (before it for "delete")
+    ///  }
+    ///  Events of indy_batch_wallet_records are delivered after the whole batch is applied.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// subscription_handle: Wallet subscription handle that can be used to remove the subscription
+    ///   (with indy_unsubscribe_wallet_records)
+
+    extern indy_error_t indy_subscribe_wallet_records(indy_handle_t  command_handle,
+                                                      indy_handle_t  wallet_handle,
+                                                      const char*    type_,
+                                                      const char*    query_json,
+                                                      void           (*event_cb)(indy_handle_t subscription_handle,
+                                                                                 const char*   event_json),
+                                                      void           (*fn)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           indy_handle_t subscription_handle)
+                                                     );
+
+    /// Remove wallet records subscription (make subscription handle invalid)
+    ///
+    /// #Params
+    /// subscription_handle: wallet subscription handle (created by indy_subscribe_wallet_records)
+
+    extern indy_error_t indy_unsubscribe_wallet_records(indy_handle_t  command_handle,
+                                                        indy_handle_t  subscription_handle,
+                                                        void           (*fn)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err)
+                                                       );
+
 
 #ifdef __cplusplus
 }
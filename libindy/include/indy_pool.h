@@ -25,6 +25,31 @@ extern "C" {
                                                  void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
                                                  );
 
+    extern indy_error_t indy_get_pool_stats(indy_handle_t command_handle,
+                                            indy_handle_t handle,
+                                            void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char* stats_json)
+                                            );
+
+    extern indy_error_t indy_set_pool_nodes_update_callback(indy_handle_t command_handle,
+                                                            indy_handle_t handle,
+                                                            void          (*update_cb)(indy_handle_t pool_handle, const char* nodes_diff_json),
+                                                            void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                                            );
+
+    extern indy_error_t indy_verify_reply_state_proof(indy_handle_t command_handle,
+                                                      indy_handle_t handle,
+                                                      const char *  genesis_txn,
+                                                      const char *  request_json,
+                                                      const char *  reply_json,
+                                                      void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char* verdict_json)
+                                                      );
+
+    extern indy_error_t indy_validate_pool_genesis(indy_handle_t command_handle,
+                                                   const char *  genesis_txn,
+                                                   indy_u64_t    protocol_version,
+                                                   void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char* report_json)
+                                                   );
+
     extern indy_error_t indy_list_pools(indy_handle_t command_handle,
                                         void          (*fn)(indy_handle_t command_handle_, indy_error_t err, const char *const pools)
                                         );
@@ -33,6 +58,16 @@ extern "C" {
                                                indy_handle_t handle,
                                                void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
                                                );
+
+    extern indy_error_t indy_create_pool_group(indy_handle_t command_handle,
+                                               const char *  config,
+                                               void          (*cb)(indy_handle_t command_handle_, indy_error_t err, indy_handle_t pool_group_handle)
+                                               );
+
+    extern indy_error_t indy_close_pool_group(indy_handle_t command_handle,
+                                              indy_handle_t handle,
+                                              void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                              );
     
     extern indy_error_t indy_delete_pool_ledger_config(indy_handle_t command_handle,
                                                        const char *  config_name,
@@ -153,6 +153,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'in_memory' storage type keeps wallet data in the memory of the process.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -162,6 +163,11 @@ extern "C" {
     ///             Defaults to $HOME/.indy_client/wallet.
     ///             Wallet will be stored in the file {path}/{id}/sqlite.db
     ///   }
+    ///   For 'in_memory' storage type configuration is:
+    ///   {
+    ///     "snapshot_path": optional<string>, Path to the file the wallet is saved to on close.
+    ///                      Wallet is restored from this file on open if it isn't in memory yet.
+    ///   }
     /// }
     /// credentials: Wallet credentials json
     /// {
@@ -209,6 +215,11 @@ extern "C" {
     ///              "path": optional<string>, Path to the directory with wallet files.
     ///                      Defaults to $HOME/.indy_client/wallet.
     ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
+    ///              "locking": optional<string>, Locking mode of the wallet file:
+    ///                         "exclusive" - wallet is used by a single process (default),
+    ///                         "shared" - wallet can be opened by several processes at the same time.
+    ///              "busy_timeout": optional<int>, Time in milliseconds to wait for the wallet file locked by another process
+    ///                              in "shared" mode. Defaults to 5000.
     ///           }
     ///
     ///   }
@@ -260,6 +271,12 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "types": optional<[string]>, Types of records to export. All records are exported by default.
+    ///     "query": optional<object>, WQL query records of each of the types have to match (requires "types" to be set).
+    ///     "marker": optional<string>, Name of the export marker. If set, only records added or changed since
+    ///               the previous export with the same marker are exported, the first export with the marker contains all records.
+    ///               Records deleted since the previous export are exported as tombstones that delete them on import.
+    ///               Marker is moved only if the export succeeds.
     ///   }
     ///
     /// #Returns
@@ -275,6 +292,74 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Exports opened wallet passing the exported content to the write callback instead of the file.
+    /// The content has the same format as the file produced by indy_export_wallet.
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// export_config: JSON containing settings for input operation.
+    ///   {
+    ///     "key": <string>, Key or passphrase used for wallet export key derivation.
+    ///     "key_derivation_method": optional<string> Algorithm to use for wallet export key derivation:
+    ///                              ARGON2I_MOD (used by default), ARGON2I_INT or RAW
+    ///     "types": optional<[string]>, Types of records to export. All records are exported by default.
+    ///     "query": optional<object>, WQL query records of each of the types have to match (requires "types" to be set).
+    ///     "marker": optional<string>, Name of the export marker, see indy_export_wallet.
+    ///   }
+    /// stream_handle: caller defined handle passed to the write callback.
+    /// write_cb: callback receiving chunks of the exported content in order.
+    ///           Export fails with CommonIOError if it returns an error code.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_wallet_to_stream(indy_handle_t  command_handle,
+                                                     indy_handle_t  wallet_handle,
+                                                     const char*    export_config_json,
+                                                     indy_handle_t  stream_handle,
+                                                     indy_error_t   (*write_cb)(indy_handle_t stream_handle,
+                                                                                const indy_u8_t* data_raw,
+                                                                                indy_u32_t data_len),
+                                                     void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                     );
+
+    /// Rotates master key of opened wallet.
+    ///
+    /// Master key protects only the wallet encryption keys, so the wallet records aren't re-encrypted
+    /// and the time of the call doesn't depend on the wallet size. New key is applied by the single update
+    /// of wallet metadata, so the wallet is left accessible by either the previous or the new key
+    /// if the process is interrupted. The wallet stays opened and usable after the call.
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// credentials: JSON containing new wallet credentials.
+    ///   {
+    ///     "key": <string>, New key or passphrase used for wallet key derivation.
+    ///                     Look to key_derivation_method param for information about supported key derivation methods.
+    ///     "key_derivation_method": optional<string> Algorithm to use for new wallet key derivation:
+    ///                              ARGON2I_MOD - derive secured wallet master key (used by default)
+    ///                              ARGON2I_INT - derive secured wallet master key (less secured but faster)
+    ///                              RAW - raw wallet key master provided (skip derivation).
+    ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///   }
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_rekey_wallet(indy_handle_t  command_handle,
+                                          indy_handle_t  wallet_handle,
+                                          const char*    credentials,
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                          );
+
 
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
@@ -330,6 +415,72 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Imports content of the wallet export file into the opened wallet.
+    /// It allows to merge exports into existing wallet, for example to restore incremental exports made with the marker.
+    /// Records deleted since the previous export with the marker are deleted from the wallet.
+    ///
+    /// Conflicts are resolved before the wallet is changed and all records are applied at once,
+    /// so the wallet is left unchanged if the import fails. Imported records are kept in memory until then.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "path": <string>, path of the file that contains exported wallet content
+    ///   "key": <string>, key used for export of the wallet
+    ///   "on_conflict": optional<string>, what to do with records that already exist in the wallet:
+    ///                  "fail" - fail the import (used by default)
+    ///                  "skip" - keep the existing record
+    ///                  "overwrite" - replace value and tags of the existing record
+    /// }
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_records(indy_handle_t  command_handle,
+                                                   indy_handle_t  wallet_handle,
+                                                   const char*    import_config_json,
+                                                   void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                   );
+
+    /// Imports wallet content taken from the read callback into the opened wallet.
+    /// The content has the same format as the file produced by indy_export_wallet, see indy_import_wallet_records.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "key": <string>, key used for export of the wallet
+    ///   "on_conflict": optional<string>, what to do with records that already exist in the wallet,
+    ///                  see indy_import_wallet_records ("fail" by default)
+    /// }
+    /// stream_handle: caller defined handle passed to the read callback.
+    /// read_cb: callback filling the buffer with the next chunk of the content.
+    ///          It sets read_len to the size of the chunk, 0 means the end of the content.
+    ///          Import fails with CommonIOError if it returns an error code.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_records_from_stream(indy_handle_t  command_handle,
+                                                               indy_handle_t  wallet_handle,
+                                                               const char*    import_config_json,
+                                                               indy_handle_t  stream_handle,
+                                                               indy_error_t   (*read_cb)(indy_handle_t stream_handle,
+                                                                                         indy_u8_t* data_raw,
+                                                                                         indy_u32_t data_len,
+                                                                                         indy_u32_t* read_len),
+                                                               void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                               );
+
     /// Closes opened wallet and frees allocated resources.
     ///
     /// #Params
@@ -393,6 +544,48 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                           );
 
+    /// Checks integrity of created wallet.
+    /// All records are decrypted with the wallet keys, records that can't be decrypted or found
+    /// by their type and id are reported as invalid. Storage types that keep tags separately from records
+    /// also report tags that don't belong to any record.
+    /// The wallet has to be closed, otherwise WalletAlreadyOpenedError is returned.
+    ///
+    /// #Params
+    /// config: Wallet configuration json. The same as for indy_delete_wallet call.
+    /// credentials: Wallet credentials json. The same as for indy_delete_wallet call.
+    /// options: (optional) check options json.
+    /// {
+    ///   "repair": optional<bool>, Remove invalid records and orphaned tags from the wallet. Defaults to false.
+    ///   "quarantine_path": optional<string>, Path to the file where removed records are appended before repair,
+    ///                      one json per line with base64 encoded raw data.
+    /// }
+    ///
+    /// #Returns
+    /// report: check report json
+    /// {
+    ///   "valid": bool, true if no problems are found,
+    ///   "metadata_errors": [string], problems of wallet metadata, records aren't checked if there are any,
+    ///   "checked_records": int, number of checked records,
+    ///   "invalid_records": [{
+    ///       "type": optional<string>, record type if it can be decrypted,
+    ///       "id": optional<string>, record id if it can be decrypted,
+    ///       "error": string, description of the problem
+    ///   }],
+    ///   "orphaned_tags": int, number of tags that don't belong to any record,
+    ///   "repaired": bool, true if found problems have been removed
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_check_wallet(indy_handle_t  command_handle,
+                                          const char*    config,
+                                          const char*    credentials,
+                                          const char*    options,
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* report)
+                                         );
+
     /// Generate wallet master key.
     /// Returned key is compatible with "RAW" key derivation method.
     /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
@@ -38,9 +38,78 @@ pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
+    pub key_derivation_method: KeyDerivationMethod,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportStreamConfig {
+    pub key: String,
+    #[serde(default = "default_key_derivation_method")]
+    pub key_derivation_method: KeyDerivationMethod,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExportOptions {
+    // Record types to export, all records are exported if not set
+    pub types: Option<Vec<String>>,
+    // WQL query records of each of the types have to match
+    pub query: Option<Value>,
+    // Name of the export marker, only records changed since the previous export with the same marker are exported
+    pub marker: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportRecordsConfig {
+    pub key: String,
+    pub path: String,
+    #[serde(default)]
+    pub on_conflict: ImportConflictPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportRecordsStreamConfig {
+    pub key: String,
+    #[serde(default)]
+    pub on_conflict: ImportConflictPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportConflictPolicy {
+    // Import fails without changes if any record already exists in the wallet
+    Fail,
+    // Existing records are left untouched
+    Skip,
+    // Value and tags of existing records are replaced with the imported ones
+    Overwrite,
+}
+
+impl Default for ImportConflictPolicy {
+    fn default() -> Self {
+        ImportConflictPolicy::Fail
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RekeyCredentials {
+    pub key: String,
+    #[serde(default = "default_key_derivation_method")]
     pub key_derivation_method: KeyDerivationMethod
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CheckOptions {
+    // Records that fail the check and tags without record are removed
+    #[serde(default)]
+    pub repair: bool,
+    // File removed records are appended to before removal
+    pub quarantine_path: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct KeyConfig {
     pub seed: Option<String>
@@ -61,6 +130,60 @@ pub struct Record {
 
 pub type Tags = HashMap<String, String>;
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecordOperation {
+    Add {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: String,
+        tags: Option<Tags>,
+    },
+    Update {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: String,
+    },
+    AddTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tags: Tags,
+    },
+    UpdateTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tags: Tags,
+    },
+    DeleteTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tag_names: Vec<String>,
+    },
+    Delete {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+    },
+}
+
+impl RecordOperation {
+    pub fn type_(&self) -> &str {
+        match *self {
+            RecordOperation::Add { ref type_, .. } |
+            RecordOperation::Update { ref type_, .. } |
+            RecordOperation::AddTags { ref type_, .. } |
+            RecordOperation::UpdateTags { ref type_, .. } |
+            RecordOperation::DeleteTags { ref type_, .. } |
+            RecordOperation::Delete { ref type_, .. } => type_
+        }
+    }
+}
+
 impl Validatable for Config {
     fn validate(&self) -> Result<(), String> {
         if self.id.is_empty() {
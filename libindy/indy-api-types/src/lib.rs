@@ -28,6 +28,7 @@ pub const INVALID_COMMAND_HANDLE : CommandHandle = 0;
 
 pub type StorageHandle = i32;
 pub type SearchHandle = i32;
+pub type SubscriptionHandle = i32;
 
 pub mod domain;
 
@@ -576,4 +577,106 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: SearchHandle) -> ErrorCode;
 
-}
\ No newline at end of file
+}
+pub mod crypto {
+    use super::*;
+    use libc::c_char;
+
+    /// Create a new key pair inside of the external key provider.
+    /// The secret key never leaves the provider, only verkey is stored in the wallet.
+    ///
+    /// #Params
+    /// seed: optional 32 bytes seed for deterministic key creation (null if absent)
+    /// seed_len: length of the seed (0 if absent)
+    /// verkey_p: pointer to store base58 encoded ed25519 verkey of created key
+    /// result_handle_p: pointer to store handle of the result (See free_result handler)
+    pub type KeyProviderCreateKey = extern fn(seed: *const u8,
+                                              seed_len: u32,
+                                              verkey_p: *mut *const c_char,
+                                              result_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Sign a message with the secret key that corresponds to verkey
+    ///
+    /// #Params
+    /// verkey: base58 encoded verkey of the key (See create_key handler)
+    /// message: a pointer to first byte of message to be signed
+    /// message_len: a message length
+    /// signature_p: pointer to store ed25519 signature
+    /// signature_len_p: pointer to store length of the signature
+    /// result_handle_p: pointer to store handle of the result (See free_result handler)
+    pub type KeyProviderSign = extern fn(verkey: *const c_char,
+                                         message: *const u8,
+                                         message_len: u32,
+                                         signature_p: *mut *const u8,
+                                         signature_len_p: *mut u32,
+                                         result_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Encrypt a message with authenticated-encryption scheme (crypto_box)
+    /// using the secret key that corresponds to verkey
+    ///
+    /// #Params
+    /// verkey: base58 encoded verkey of the sender key (See create_key handler)
+    /// their_vk: base58 encoded ed25519 verkey of the recipient
+    /// message: a pointer to first byte of message to be encrypted
+    /// message_len: a message length
+    /// nonce: a pointer to first byte of nonce
+    /// nonce_len: a nonce length
+    /// encrypted_p: pointer to store encrypted message
+    /// encrypted_len_p: pointer to store length of the encrypted message
+    /// result_handle_p: pointer to store handle of the result (See free_result handler)
+    pub type KeyProviderCryptoBox = extern fn(verkey: *const c_char,
+                                              their_vk: *const c_char,
+                                              message: *const u8,
+                                              message_len: u32,
+                                              nonce: *const u8,
+                                              nonce_len: u32,
+                                              encrypted_p: *mut *const u8,
+                                              encrypted_len_p: *mut u32,
+                                              result_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Decrypt a message encrypted with authenticated-encryption scheme (crypto_box)
+    /// using the secret key that corresponds to verkey
+    ///
+    /// #Params
+    /// verkey: base58 encoded verkey of the recipient key (See create_key handler)
+    /// their_vk: base58 encoded ed25519 verkey of the sender
+    /// encrypted: a pointer to first byte of message to be decrypted
+    /// encrypted_len: a message length
+    /// nonce: a pointer to first byte of nonce
+    /// nonce_len: a nonce length
+    /// decrypted_p: pointer to store decrypted message
+    /// decrypted_len_p: pointer to store length of the decrypted message
+    /// result_handle_p: pointer to store handle of the result (See free_result handler)
+    pub type KeyProviderCryptoBoxOpen = extern fn(verkey: *const c_char,
+                                                  their_vk: *const c_char,
+                                                  encrypted: *const u8,
+                                                  encrypted_len: u32,
+                                                  nonce: *const u8,
+                                                  nonce_len: u32,
+                                                  decrypted_p: *mut *const u8,
+                                                  decrypted_len_p: *mut u32,
+                                                  result_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Decrypt a message encrypted with anonymous-encryption scheme (crypto_box_seal)
+    /// using the secret key that corresponds to verkey
+    ///
+    /// #Params
+    /// verkey: base58 encoded verkey of the recipient key (See create_key handler)
+    /// encrypted: a pointer to first byte of message to be decrypted
+    /// encrypted_len: a message length
+    /// decrypted_p: pointer to store decrypted message
+    /// decrypted_len_p: pointer to store length of the decrypted message
+    /// result_handle_p: pointer to store handle of the result (See free_result handler)
+    pub type KeyProviderCryptoBoxSealOpen = extern fn(verkey: *const c_char,
+                                                      encrypted: *const u8,
+                                                      encrypted_len: u32,
+                                                      decrypted_p: *mut *const u8,
+                                                      decrypted_len_p: *mut u32,
+                                                      result_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free the result returned by one of key provider handlers (make result handle invalid)
+    ///
+    /// #Params
+    /// result_handle: result handle (See create_key, sign, crypto_box, crypto_box_open and crypto_box_seal_open handlers)
+    pub type KeyProviderFreeResult = extern fn(result_handle: IndyHandle) -> ErrorCode;
+}
@@ -0,0 +1,235 @@
+extern crate libc;
+extern crate rust_base58;
+extern crate sodiumoxide;
+
+use super::ErrorCode;
+use super::sequence;
+
+use self::libc::{c_char, c_int};
+use self::rust_base58::{FromBase58, ToBase58};
+use self::sodiumoxide::crypto::{box_, sealedbox, sign};
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::slice;
+use std::sync::Mutex;
+
+extern {
+    // These functions aren't included to sodiumoxide rust wrappers,
+    // local binding is used to call libsodium-sys function
+    fn crypto_sign_ed25519_pk_to_curve25519(
+        curve25519_pk: *mut [u8; box_::PUBLICKEYBYTES],
+        ed25519_pk: *const [u8; sign::PUBLICKEYBYTES]) -> c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(
+        curve25519_sk: *mut [u8; box_::SECRETKEYBYTES],
+        ed25519_sk: *const [u8; sign::SECRETKEYBYTES]) -> c_int;
+}
+
+lazy_static! {
+    static ref INMEM_KEYS: Mutex<HashMap<String, sign::SecretKey>> = Default::default();
+}
+
+lazy_static! {
+    static ref ACTIVE_RESULTS: Mutex<HashMap<i32, Vec<u8>>> = Default::default();
+}
+
+/// Software stand-in of external key provider.
+/// Keeps secret keys in the process memory and is intended for testing only.
+pub struct InmemKeyProvider {}
+
+impl InmemKeyProvider {
+    pub extern "C" fn create_key(seed: *const u8,
+                                 seed_len: u32,
+                                 verkey_p: *mut *const c_char,
+                                 result_handle_p: *mut i32) -> ErrorCode {
+        let (vk, sk) = if seed.is_null() || seed_len == 0 {
+            sign::gen_keypair()
+        } else {
+            let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+
+            match sign::Seed::from_slice(seed) {
+                Some(seed) => sign::keypair_from_seed(&seed),
+                None => return ErrorCode::CommonInvalidStructure
+            }
+        };
+
+        let verkey = vk[..].to_base58();
+        INMEM_KEYS.lock().unwrap().insert(verkey.clone(), sk);
+
+        let verkey = CString::new(verkey).unwrap().into_bytes_with_nul();
+
+        unsafe { *verkey_p = verkey.as_ptr() as *const c_char };
+        InmemKeyProvider::_store_result(verkey, result_handle_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn sign(verkey: *const c_char,
+                           message: *const u8,
+                           message_len: u32,
+                           signature_p: *mut *const u8,
+                           signature_len_p: *mut u32,
+                           result_handle_p: *mut i32) -> ErrorCode {
+        let sk = match InmemKeyProvider::_secret_key(verkey) {
+            Some(sk) => sk,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let message = InmemKeyProvider::_bytes(message, message_len);
+
+        let signature = sign::sign_detached(message, &sk);
+
+        InmemKeyProvider::_return_result(signature[..].to_vec(), signature_p, signature_len_p, result_handle_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn crypto_box(verkey: *const c_char,
+                                 their_vk: *const c_char,
+                                 message: *const u8,
+                                 message_len: u32,
+                                 nonce: *const u8,
+                                 nonce_len: u32,
+                                 encrypted_p: *mut *const u8,
+                                 encrypted_len_p: *mut u32,
+                                 result_handle_p: *mut i32) -> ErrorCode {
+        let (sk, their_pk, nonce) = match InmemKeyProvider::_box_params(verkey, their_vk, nonce, nonce_len) {
+            Some(params) => params,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let message = InmemKeyProvider::_bytes(message, message_len);
+
+        let encrypted = box_::seal(message, &nonce, &their_pk, &sk);
+
+        InmemKeyProvider::_return_result(encrypted, encrypted_p, encrypted_len_p, result_handle_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn crypto_box_open(verkey: *const c_char,
+                                      their_vk: *const c_char,
+                                      encrypted: *const u8,
+                                      encrypted_len: u32,
+                                      nonce: *const u8,
+                                      nonce_len: u32,
+                                      decrypted_p: *mut *const u8,
+                                      decrypted_len_p: *mut u32,
+                                      result_handle_p: *mut i32) -> ErrorCode {
+        let (sk, their_pk, nonce) = match InmemKeyProvider::_box_params(verkey, their_vk, nonce, nonce_len) {
+            Some(params) => params,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let encrypted = InmemKeyProvider::_bytes(encrypted, encrypted_len);
+
+        let decrypted = match box_::open(encrypted, &nonce, &their_pk, &sk) {
+            Ok(decrypted) => decrypted,
+            Err(_) => return ErrorCode::CommonInvalidStructure
+        };
+
+        InmemKeyProvider::_return_result(decrypted, decrypted_p, decrypted_len_p, result_handle_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn crypto_box_seal_open(verkey: *const c_char,
+                                           encrypted: *const u8,
+                                           encrypted_len: u32,
+                                           decrypted_p: *mut *const u8,
+                                           decrypted_len_p: *mut u32,
+                                           result_handle_p: *mut i32) -> ErrorCode {
+        let (pk, sk) = match (InmemKeyProvider::_public_key(verkey), InmemKeyProvider::_secret_key(verkey)) {
+            (Some(pk), Some(sk)) => (pk, InmemKeyProvider::_sk_to_curve25519(&sk)),
+            _ => return ErrorCode::CommonInvalidStructure
+        };
+
+        let encrypted = InmemKeyProvider::_bytes(encrypted, encrypted_len);
+
+        let decrypted = match sealedbox::open(encrypted, &pk, &sk) {
+            Ok(decrypted) => decrypted,
+            Err(_) => return ErrorCode::CommonInvalidStructure
+        };
+
+        InmemKeyProvider::_return_result(decrypted, decrypted_p, decrypted_len_p, result_handle_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn free_result(result_handle: i32) -> ErrorCode {
+        match ACTIVE_RESULTS.lock().unwrap().remove(&result_handle) {
+            Some(_) => ErrorCode::Success,
+            None => ErrorCode::CommonInvalidState
+        }
+    }
+
+    pub fn cleanup() {
+        INMEM_KEYS.lock().unwrap().clear();
+        ACTIVE_RESULTS.lock().unwrap().clear();
+    }
+
+    fn _bytes<'a>(ptr: *const u8, len: u32) -> &'a [u8] {
+        if ptr.is_null() || len == 0 {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(ptr, len as usize) }
+    }
+
+    fn _verkey(verkey: *const c_char) -> Option<String> {
+        if verkey.is_null() {
+            return None;
+        }
+
+        unsafe { CStr::from_ptr(verkey) }.to_str().ok().map(String::from)
+    }
+
+    fn _secret_key(verkey: *const c_char) -> Option<sign::SecretKey> {
+        InmemKeyProvider::_verkey(verkey)
+            .and_then(|verkey| INMEM_KEYS.lock().unwrap().get(&verkey).cloned())
+    }
+
+    fn _public_key(verkey: *const c_char) -> Option<box_::PublicKey> {
+        InmemKeyProvider::_verkey(verkey)
+            .and_then(|verkey| verkey.from_base58().ok())
+            .and_then(|verkey| sign::PublicKey::from_slice(&verkey))
+            .map(|pk| InmemKeyProvider::_pk_to_curve25519(&pk))
+    }
+
+    fn _box_params(verkey: *const c_char, their_vk: *const c_char, nonce: *const u8, nonce_len: u32) -> Option<(box_::SecretKey, box_::PublicKey, box_::Nonce)> {
+        let sk = InmemKeyProvider::_secret_key(verkey)?;
+        let their_pk = InmemKeyProvider::_public_key(their_vk)?;
+        let nonce = box_::Nonce::from_slice(InmemKeyProvider::_bytes(nonce, nonce_len))?;
+
+        Some((InmemKeyProvider::_sk_to_curve25519(&sk), their_pk, nonce))
+    }
+
+    fn _pk_to_curve25519(pk: &sign::PublicKey) -> box_::PublicKey {
+        let mut to = [0; box_::PUBLICKEYBYTES];
+        unsafe { crypto_sign_ed25519_pk_to_curve25519(&mut to, &pk.0); }
+        box_::PublicKey(to)
+    }
+
+    fn _sk_to_curve25519(sk: &sign::SecretKey) -> box_::SecretKey {
+        let mut to = [0; box_::SECRETKEYBYTES];
+        unsafe { crypto_sign_ed25519_sk_to_curve25519(&mut to, &sk.0); }
+        box_::SecretKey(to)
+    }
+
+    fn _store_result(result: Vec<u8>, result_handle_p: *mut i32) {
+        let result_handle = sequence::get_next_id();
+        ACTIVE_RESULTS.lock().unwrap().insert(result_handle, result);
+
+        unsafe { *result_handle_p = result_handle };
+    }
+
+    fn _return_result(result: Vec<u8>, result_p: *mut *const u8, result_len_p: *mut u32, result_handle_p: *mut i32) {
+        unsafe {
+            *result_p = result.as_ptr();
+            *result_len_p = result.len() as u32;
+        }
+
+        // Moving Vec doesn't move its heap buffer, so the pointer stays valid until free_result
+        InmemKeyProvider::_store_result(result, result_handle_p);
+    }
+}
@@ -28,6 +28,7 @@ macro_rules! secret {
 pub mod crypto;
 pub mod ctypes;
 pub mod environment;
+pub mod inmem_key_provider;
 pub mod inmem_wallet;
 pub mod sequence;
 #[macro_use]
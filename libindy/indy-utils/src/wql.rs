@@ -18,6 +18,15 @@ pub enum Query {
     Lte(String, String),
     Like(String, String),
     In(String, Vec<String>),
+    // case-insensitive $like
+    ILike(String, String),
+    // tag has any value
+    Exist(String),
+    // comparisons of integer values of plaintext tags
+    NumGt(String, i64),
+    NumGte(String, i64),
+    NumLt(String, i64),
+    NumLte(String, i64),
 }
 
 impl Serialize for Query {
@@ -103,6 +112,12 @@ impl Query {
             Query::Lte(ref tag_name, ref tag_value) => json!({tag_name: {"$lte": tag_value}}),
             Query::Like(ref tag_name, ref tag_value) => json!({tag_name: {"$like": tag_value}}),
             Query::In(ref tag_name, ref tag_values) => json!({tag_name: {"$in": tag_values}}),
+            Query::ILike(ref tag_name, ref tag_value) => json!({tag_name: {"$ilike": tag_value}}),
+            Query::Exist(ref tag_name) => json!({tag_name: {"$exist": true}}),
+            Query::NumGt(ref tag_name, tag_value) => json!({tag_name: {"$gt": tag_value}}),
+            Query::NumGte(ref tag_name, tag_value) => json!({tag_name: {"$gte": tag_value}}),
+            Query::NumLt(ref tag_name, tag_value) => json!({tag_name: {"$lt": tag_value}}),
+            Query::NumLte(ref tag_name, tag_value) => json!({tag_name: {"$lte": tag_value}}),
             Query::And(ref operators) => {
                 if !operators.is_empty() {
                     json!({
@@ -194,15 +209,24 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
         ("$neq", serde_json::Value::String(value_)) => Ok(Query::Neq(key, value_)),
         ("$neq", _) => Err("$neq must be used with string"),
         ("$gt", serde_json::Value::String(value_)) => Ok(Query::Gt(key, value_)),
-        ("$gt", _) => Err("$gt must be used with string"),
+        ("$gt", serde_json::Value::Number(ref value_)) if value_.is_i64() => Ok(Query::NumGt(key, value_.as_i64().unwrap())),
+        ("$gt", _) => Err("$gt must be used with string or integer"),
         ("$gte", serde_json::Value::String(value_)) => Ok(Query::Gte(key, value_)),
-        ("$gte", _) => Err("$gte must be used with string"),
+        ("$gte", serde_json::Value::Number(ref value_)) if value_.is_i64() => Ok(Query::NumGte(key, value_.as_i64().unwrap())),
+        ("$gte", _) => Err("$gte must be used with string or integer"),
         ("$lt", serde_json::Value::String(value_)) => Ok(Query::Lt(key, value_)),
-        ("$lt", _) => Err("$lt must be used with string"),
+        ("$lt", serde_json::Value::Number(ref value_)) if value_.is_i64() => Ok(Query::NumLt(key, value_.as_i64().unwrap())),
+        ("$lt", _) => Err("$lt must be used with string or integer"),
         ("$lte", serde_json::Value::String(value_)) => Ok(Query::Lte(key, value_)),
-        ("$lte", _) => Err("$lte must be used with string"),
+        ("$lte", serde_json::Value::Number(ref value_)) if value_.is_i64() => Ok(Query::NumLte(key, value_.as_i64().unwrap())),
+        ("$lte", _) => Err("$lte must be used with string or integer"),
         ("$like", serde_json::Value::String(value_)) => Ok(Query::Like(key, value_)),
         ("$like", _) => Err("$like must be used with string"),
+        ("$ilike", serde_json::Value::String(value_)) => Ok(Query::ILike(key, value_)),
+        ("$ilike", _) => Err("$ilike must be used with string"),
+        ("$exist", serde_json::Value::Bool(true)) => Ok(Query::Exist(key)),
+        ("$exist", serde_json::Value::Bool(false)) => Ok(Query::Not(Box::new(Query::Exist(key)))),
+        ("$exist", _) => Err("$exist must be used with boolean"),
         ("$in", serde_json::Value::Array(values)) => {
             let mut target_values: Vec<String> = Vec::with_capacity(values.len());
 
@@ -221,6 +245,20 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
     }
 }
 
+// Matches the value with SQL LIKE pattern: `%` is any sequence of characters, `_` is any single character
+pub fn like_matches(value: &str, pattern: &str) -> bool {
+    fn matches(value: &[char], pattern: &[char]) -> bool {
+        match pattern.split_first() {
+            None => value.is_empty(),
+            Some((&'%', rest)) => (0..=value.len()).any(|i| matches(&value[i..], rest)),
+            Some((&'_', rest)) => !value.is_empty() && matches(&value[1..], rest),
+            Some((c, rest)) => value.first() == Some(c) && matches(&value[1..], rest),
+        }
+    }
+
+    matches(&value.chars().collect::<Vec<char>>(), &pattern.chars().collect::<Vec<char>>())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2992,6 +3030,121 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    fn test_simple_operator_ilike_parse() {
+        let name1 = _random_string(10);
+        let value1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$ilike":"{}"}}}}"#, name1, value1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::ILike(name1, value1);
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_exist_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":true}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::Exist(name1);
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_not_exist_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":false}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::Not(Box::new(Query::Exist(name1)));
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_exist_parse_for_invalid_value() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":"true"}}}}"#, name1);
+
+        let res = ::serde_json::from_str::<Query>(&json);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_numeric_operators_parse() {
+        let name1 = _random_string(10);
+
+        let json = json!({
+            "$and": [
+                {name1.clone(): {"$gt": 1}},
+                {name1.clone(): {"$gte": -2}},
+                {name1.clone(): {"$lt": 3}},
+                {name1.clone(): {"$lte": 4}},
+            ]
+        }).to_string();
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::And(
+            vec![
+                Query::NumGt(name1.clone(), 1),
+                Query::NumGte(name1.clone(), -2),
+                Query::NumLt(name1.clone(), 3),
+                Query::NumLte(name1.clone(), 4),
+            ]
+        );
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_numeric_operators_parse_for_float() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$gt":1.5}}}}"#, name1);
+
+        let res = ::serde_json::from_str::<Query>(&json);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_new_operators_to_string() {
+        let name1 = _random_string(10);
+        let value1 = _random_string(10);
+
+        let query = Query::And(
+            vec![
+                Query::ILike(name1.clone(), value1.clone()),
+                Query::Exist(name1.clone()),
+                Query::NumGt(name1.clone(), 1),
+                Query::NumLte(name1.clone(), -4),
+            ]
+        );
+
+        let json = ::serde_json::to_string(&query).unwrap();
+
+        let expected = format!(r#"{{"$and":[{{"{}":{{"$ilike":"{}"}}}},{{"{}":{{"$exist":true}}}},{{"{}":{{"$gt":1}}}},{{"{}":{{"$lte":-4}}}}]}}"#,
+                               name1, value1, name1, name1, name1);
+
+        assert_eq!(json, expected);
+
+        let parsed: Query = ::serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, query);
+    }
+
     #[test]
     fn test_old_format() {
         let name1 = _random_string(10);
@@ -3040,5 +3193,15 @@ mod tests {
 
         assert_eq!(query, expected);
     }
-}
 
+    #[test]
+    fn like_matches_works() {
+        assert!(like_matches("value", "value"));
+        assert!(like_matches("value", "%"));
+        assert!(like_matches("value", "v%e"));
+        assert!(like_matches("value", "_alu_"));
+        assert!(like_matches("", "%"));
+        assert!(!like_matches("value", "val"));
+        assert!(!like_matches("value", "_value"));
+    }
+}
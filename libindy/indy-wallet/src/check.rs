@@ -0,0 +1,202 @@
+use std::fs::{DirBuilder, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use indy_api_types::domain::wallet::CheckOptions;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::{base64, chacha20poly1305_ietf, hmacsha256};
+
+use serde_json;
+
+use super::encryption::{decrypt_merged, encrypt_as_searchable};
+use super::storage::{StorageOperation, StorageRecord, Tag, WalletStorage};
+use super::wallet::Keys;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct WalletCheckReport {
+    // true if no problems are found, repair doesn't change it
+    pub valid: bool,
+    // records aren't checked if wallet keys can't be taken from metadata
+    pub metadata_errors: Vec<String>,
+    pub checked_records: usize,
+    pub invalid_records: Vec<InvalidRecord>,
+    pub orphaned_tags: usize,
+    // true if invalid records and orphaned tags have been removed
+    pub repaired: bool,
+}
+
+impl WalletCheckReport {
+    pub(super) fn for_metadata_error(error: String) -> WalletCheckReport {
+        WalletCheckReport {
+            valid: false,
+            metadata_errors: vec![error],
+            checked_records: 0,
+            invalid_records: Vec::new(),
+            orphaned_tags: 0,
+            repaired: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct InvalidRecord {
+    // type and id are set if they can be decrypted
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub id: Option<String>,
+    pub error: String,
+}
+
+// Raw record saved to the quarantine file, encrypted parts are base64 encoded
+#[derive(Debug, Serialize)]
+struct QuarantinedRecord {
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    id: String,
+    value: Option<String>,
+    encrypted_tags: Vec<(String, String)>,
+    plaintext_tags: Vec<(String, String)>,
+}
+
+impl QuarantinedRecord {
+    fn new(record: &StorageRecord) -> QuarantinedRecord {
+        let mut encrypted_tags = Vec::new();
+        let mut plaintext_tags = Vec::new();
+
+        for tag in record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]) {
+            match *tag {
+                Tag::Encrypted(ref name, ref value) => encrypted_tags.push((base64::encode(name), base64::encode(value))),
+                Tag::PlainText(ref name, ref value) => plaintext_tags.push((base64::encode(name), value.clone())),
+            }
+        }
+
+        QuarantinedRecord {
+            type_: record.type_.as_ref().map(|type_| base64::encode(type_)),
+            id: base64::encode(&record.id),
+            value: record.value.as_ref().map(|value| base64::encode(&value.to_bytes())),
+            encrypted_tags,
+            plaintext_tags,
+        }
+    }
+}
+
+/// Decrypts all records of the storage and checks that they can be found with the wallet keys.
+/// Invalid records and tags that don't belong to any record are removed if repair is requested.
+pub(super) fn check_storage(storage: &dyn WalletStorage, keys: &Keys, options: &CheckOptions) -> IndyResult<WalletCheckReport> {
+    let mut records = storage.get_all()?;
+
+    let mut checked_records = 0;
+    let mut invalid_records = Vec::new();
+    let mut quarantined = Vec::new();
+
+    while let Some(record) = records.next()? {
+        checked_records += 1;
+
+        if let Err(invalid_record) = _check_record(&record, keys) {
+            invalid_records.push(invalid_record);
+            quarantined.push(record);
+        }
+    }
+
+    let orphaned_tags = storage.check_orphaned_tags(false)?;
+
+    let repaired = options.repair && (!quarantined.is_empty() || orphaned_tags > 0);
+
+    if repaired {
+        match options.quarantine_path {
+            Some(ref path) if !quarantined.is_empty() => _quarantine(Path::new(path), &quarantined)?,
+            _ => {}
+        }
+
+        let operations = quarantined.iter()
+            .map(|record| match record.type_ {
+                Some(ref type_) => Ok(StorageOperation::Delete(type_.clone(), record.id.clone())),
+                None => Err(err_msg(IndyErrorKind::InvalidState, "Storage returned record without type"))
+            })
+            .collect::<IndyResult<Vec<StorageOperation>>>()?;
+
+        storage.batch(&operations)?;
+        storage.check_orphaned_tags(true)?;
+    }
+
+    Ok(WalletCheckReport {
+        valid: invalid_records.is_empty() && orphaned_tags == 0,
+        metadata_errors: Vec::new(),
+        checked_records,
+        invalid_records,
+        orphaned_tags,
+        repaired,
+    })
+}
+
+fn _check_record(record: &StorageRecord, keys: &Keys) -> Result<(), InvalidRecord> {
+    let mut invalid_record = InvalidRecord { type_: None, id: None, error: String::new() };
+
+    _check_record_parts(record, keys, &mut invalid_record)
+        .map_err(|error| InvalidRecord { error, ..invalid_record })
+}
+
+// Decrypted type and id are set to the report entry as soon as they are checked
+fn _check_record_parts(record: &StorageRecord, keys: &Keys, invalid_record: &mut InvalidRecord) -> Result<(), String> {
+    let type_ = record.type_.as_ref()
+        .ok_or_else(|| "Record type isn't set".to_string())?;
+
+    invalid_record.type_ = Some(_decrypt_searchable(type_, &keys.type_key, &keys.item_hmac_key, "Record type")?);
+    invalid_record.id = Some(_decrypt_searchable(&record.id, &keys.name_key, &keys.item_hmac_key, "Record id")?);
+
+    record.value.as_ref()
+        .ok_or_else(|| "Record value isn't set".to_string())?
+        .decrypt(&keys.value_key)
+        .map_err(|_| "Record value can't be decrypted".to_string())?;
+
+    for tag in record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]) {
+        match *tag {
+            Tag::Encrypted(ref name, ref value) => {
+                _decrypt_searchable(name, &keys.tag_name_key, &keys.tags_hmac_key, "Tag name")?;
+                _decrypt_searchable(value, &keys.tag_value_key, &keys.tags_hmac_key, "Tag value")?;
+            }
+            Tag::PlainText(ref name, _) => {
+                _decrypt_searchable(name, &keys.tag_name_key, &keys.tags_hmac_key, "Tag name")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Searchable data has to be encrypted the same way again, otherwise the record can't be found by it
+fn _decrypt_searchable(data: &[u8], key: &chacha20poly1305_ietf::Key, hmac_key: &hmacsha256::Key, name: &str) -> Result<String, String> {
+    let decrypted = decrypt_merged(data, key)
+        .map_err(|_| format!("{} can't be decrypted", name))?;
+
+    if encrypt_as_searchable(&decrypted, key, hmac_key) != data {
+        return Err(format!("{} isn't searchable with wallet keys", name));
+    }
+
+    String::from_utf8(decrypted)
+        .map_err(|_| format!("{} is invalid utf8", name))
+}
+
+fn _quarantine(path: &Path, records: &[StorageRecord]) -> IndyResult<()> {
+    if let Some(parent) = path.parent() {
+        DirBuilder::new()
+            .recursive(true)
+            .create(parent)?;
+    }
+
+    // records of several checks are appended to the same file, one json per line
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    for record in records {
+        let line = serde_json::to_string(&QuarantinedRecord::new(record))
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize quarantined record")?;
+
+        writeln!(file, "{}", line)?;
+    }
+
+    file.sync_all()?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::rc::Rc;
+
+use indy_api_types::domain::wallet::Tags;
+use indy_api_types::errors::prelude::*;
+use indy_api_types::{SubscriptionHandle, WalletHandle};
+
+use super::language::Operator;
+use super::storage::inmem::{check_operator, matches};
+use super::storage::Tag;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletEventKind {
+    Add,
+    Update,
+    AddTags,
+    UpdateTags,
+    DeleteTags,
+    Delete,
+}
+
+/// Change of a wallet record delivered to subscribers.
+/// Values aren't delivered, only the record tags after the change (before it for deleted records).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletEvent {
+    pub event: WalletEventKind,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
+    pub tags: Tags,
+}
+
+pub type WalletEventCallback = Rc<dyn Fn(SubscriptionHandle, &WalletEvent)>;
+
+pub(super) struct Subscription {
+    wallet_handle: WalletHandle,
+    type_: String,
+    // query encrypted with the wallet keys, so it is evaluated against encrypted tags as storages do
+    query: Operator,
+    cb: WalletEventCallback,
+}
+
+impl Subscription {
+    pub(super) fn new(wallet_handle: WalletHandle, type_: &str, query: Operator, cb: WalletEventCallback) -> IndyResult<Subscription> {
+        check_operator(&query)?;
+
+        Ok(Subscription {
+            wallet_handle,
+            type_: type_.to_string(),
+            query,
+            cb,
+        })
+    }
+
+    pub(super) fn wallet_handle(&self) -> WalletHandle {
+        self.wallet_handle
+    }
+
+    pub(super) fn is_subscribed(&self, wallet_handle: WalletHandle, type_: &str) -> bool {
+        self.wallet_handle == wallet_handle && self.type_ == type_
+    }
+
+    pub(super) fn matches(&self, wallet_handle: WalletHandle, type_: &str, encrypted_tags: &[Tag]) -> bool {
+        self.is_subscribed(wallet_handle, type_) && matches(&self.query, encrypted_tags)
+    }
+
+    pub(super) fn callback(&self) -> WalletEventCallback {
+        Rc::clone(&self.cb)
+    }
+}
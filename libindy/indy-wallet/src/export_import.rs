@@ -1,21 +1,114 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
+use serde_json;
 
-use indy_api_types::domain::wallet::Record;
+use indy_api_types::domain::wallet::{ExportOptions, ImportConflictPolicy, Record, RecordOperation};
 use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
-use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
+use indy_utils::crypto::{base64, chacha20poly1305_ietf, pwhash_argon2i13};
 use indy_utils::crypto::hash::{hash, HASHBYTES};
 
-use super::{Wallet, WalletRecord};
+use super::{RecordOptions, SearchOptions, Wallet, WalletRecord};
+use super::iterator::WalletIterator;
 
 const CHUNK_SIZE: usize = 1024;
 
+// Type of records keeping digests of records exported with the marker, one per exported record.
+// Records of this type are never exported themselves.
+pub(super) const EXPORT_MARKER_TYPE: &str = "Indy::ExportMarker";
+
+// Type of export entries telling that the record was deleted since the previous export with the marker.
+// Entry id is the id of the deleted record and entry value is its type. Tombstones are never stored in the wallet.
+pub(super) const TOMBSTONE_TYPE: &str = "Indy::Tombstone";
+
+struct ExportMarker {
+    name: String,
+    // Changes of the marker records, applied at once after the whole export is written
+    operations: Vec<RecordOperation>,
+}
+
+impl ExportMarker {
+    fn new(name: &str) -> ExportMarker {
+        ExportMarker { name: name.to_string(), operations: Vec::new() }
+    }
+
+    // Remembers digest of the record, returns false if the record wasn't changed since the previous export
+    fn update(&mut self, wallet: &Wallet, record: &Record) -> IndyResult<bool> {
+        let tags: BTreeMap<&String, &String> = record.tags.iter().collect();
+
+        let digest = rmp_serde::to_vec(&(&record.value, tags))
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+        let digest = base64::encode(&hash(&digest)?);
+
+        let id = self._marker_record_id(&record.type_, &record.id)?;
+
+        match wallet.get(EXPORT_MARKER_TYPE, &id, &RecordOptions::id_value()) {
+            Ok(ref marker_record) if marker_record.value.as_ref() == Some(&digest) => return Ok(false),
+            Ok(_) => {
+                self.operations.push(RecordOperation::Update { type_: EXPORT_MARKER_TYPE.to_string(), id, value: digest });
+            }
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {
+                let mut tags = HashMap::new();
+                tags.insert("marker".to_string(), self.name.clone());
+                tags.insert("type".to_string(), record.type_.clone());
+                tags.insert("id".to_string(), record.id.clone());
+
+                self.operations.push(RecordOperation::Add { type_: EXPORT_MARKER_TYPE.to_string(), id, value: digest, tags: Some(tags) });
+            }
+            Err(err) => return Err(err)
+        }
+
+        Ok(true)
+    }
+
+    // Returns types and ids of records exported with the marker before but deleted from the wallet since then
+    fn deleted(&mut self, wallet: &Wallet) -> IndyResult<Vec<(String, String)>> {
+        let query = serde_json::json!({"marker": self.name}).to_string();
+        let mut marker_records = wallet.search(EXPORT_MARKER_TYPE, &query, Some(&SearchOptions::full()))?;
+
+        let mut deleted = Vec::new();
+
+        while let Some(WalletRecord { id, tags, .. }) = marker_records.next()? {
+            let (type_, record_id) = match tags.as_ref().map(|tags| (tags.get("type"), tags.get("id"))) {
+                Some((Some(type_), Some(record_id))) => (type_.to_string(), record_id.to_string()),
+                _ => return Err(err_msg(IndyErrorKind::InvalidState, "Export marker record is malformed"))
+            };
+
+            match wallet.get(&type_, &record_id, &RecordOptions::id()) {
+                Ok(_) => {}
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {
+                    self.operations.push(RecordOperation::Delete { type_: EXPORT_MARKER_TYPE.to_string(), id });
+                    deleted.push((type_, record_id));
+                }
+                Err(err) => return Err(err)
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    fn save(&self, wallet: &Wallet) -> IndyResult<()> {
+        if self.operations.is_empty() {
+            return Ok(());
+        }
+
+        wallet.batch(&self.operations)
+    }
+
+    fn _marker_record_id(&self, type_: &str, id: &str) -> IndyResult<String> {
+        let id = rmp_serde::to_vec(&(&self.name, type_, id))
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize export marker record id")?;
+
+        Ok(base64::encode(&hash(&id)?))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EncryptionMethod {
     // **ChaCha20-Poly1305-IETF** cypher in blocks per chunk_size bytes
@@ -69,7 +162,15 @@ pub struct Header {
 //   "version": ..,
 // }
 
-pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, options: &ExportOptions, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+    let query = match (&options.types, &options.query) {
+        (None, Some(_)) => return Err(err_msg(IndyErrorKind::InvalidStructure, "Export query requires record types to be set")),
+        (_, Some(query)) => query.to_string(),
+        (_, None) => "{}".to_string(),
+    };
+
+    let mut marker = options.marker.as_ref().map(|marker| ExportMarker::new(marker));
+
     let nonce = chacha20poly1305_ietf::gen_nonce();
     let chunk_size = CHUNK_SIZE;
 
@@ -112,8 +213,37 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
     writer.write_all(&hash(&header)?)?;
 
-    let mut records = wallet.get_all()?;
+    match options.types {
+        Some(ref types) => {
+            for type_ in types {
+                let mut records = wallet.search(type_, &query, Some(&SearchOptions::full()))?;
+                _export_records(wallet, &mut records, &mut writer, &mut marker)?;
+            }
+        }
+        None => {
+            let mut records = wallet.get_all()?;
+            _export_records(wallet, &mut records, &mut writer, &mut marker)?;
+        }
+    }
+
+    if let Some(ref mut marker) = marker {
+        for (type_, id) in marker.deleted(wallet)? {
+            _write_record(&mut writer, &Record { type_: TOMBSTONE_TYPE.to_string(), id, value: type_, tags: HashMap::new() })?;
+        }
+    }
+
+    writer.write_u32::<LittleEndian>(0)?; // END message
+    writer.flush()?;
+
+    // Marker is moved only after the whole export is written
+    if let Some(marker) = marker {
+        marker.save(wallet)?;
+    }
 
+    Ok(())
+}
+
+fn _export_records(wallet: &Wallet, records: &mut WalletIterator, writer: &mut dyn Write, marker: &mut Option<ExportMarker>) -> IndyResult<()> {
     while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
         let record = Record {
             type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
@@ -122,15 +252,29 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
             tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
         };
 
-        let record = rmp_serde::to_vec(&record)
-            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+        if record.type_ == EXPORT_MARKER_TYPE {
+            continue;
+        }
+
+        if let Some(ref mut marker) = *marker {
+            if !marker.update(wallet, &record)? {
+                continue;
+            }
+        }
 
-        writer.write_u32::<LittleEndian>(record.len() as u32)?;
-        writer.write_all(&record)?;
+        _write_record(writer, &record)?;
     }
 
-    writer.write_u32::<LittleEndian>(0)?; // END message
-    writer.flush()?;
+    Ok(())
+}
+
+fn _write_record(writer: &mut dyn Write, record: &Record) -> IndyResult<()> {
+    let record = rmp_serde::to_vec(record)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+
+    writer.write_u32::<LittleEndian>(record.len() as u32)?;
+    writer.write_all(&record)?;
+
     Ok(())
 }
 
@@ -141,6 +285,39 @@ fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> whe
     finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes)
 }
 
+// Export content with the parsed header. The import key is derived from the header in a separate command,
+// so the content is kept until the key is ready as streams can't be read again.
+pub struct PreparedImport<T> where T: Read {
+    reader: BufReader<T>,
+    key_derivation_data: KeyDerivationData,
+    nonce: chacha20poly1305_ietf::Nonce,
+    chunk_size: usize,
+    header_bytes: Vec<u8>,
+}
+
+impl<T> PreparedImport<T> where T: Read {
+    pub fn key_derivation_data(&self) -> &KeyDerivationData {
+        &self.key_derivation_data
+    }
+}
+
+impl<T> PreparedImport<T> where T: Read + 'static {
+    // Hides the type of the source, so imports from files and streams can be kept together
+    pub fn into_boxed(self) -> PreparedImport<Box<dyn Read>> {
+        let PreparedImport { reader, key_derivation_data, nonce, chunk_size, header_bytes } = self;
+
+        // Data buffered by the reader is consumed through the boxed reader as well
+        let reader: Box<dyn Read> = Box::new(reader);
+
+        PreparedImport { reader: BufReader::new(reader), key_derivation_data, nonce, chunk_size, header_bytes }
+    }
+}
+
+pub(super) fn prepare_import<T>(reader: T, passphrase: &str) -> IndyResult<PreparedImport<T>> where T: Read {
+    let (reader, key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
+    Ok(PreparedImport { reader, key_derivation_data, nonce, chunk_size, header_bytes })
+}
+
 pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
     // Reads plain
     let mut reader = BufReader::new(reader);
@@ -200,7 +377,59 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     Ok((reader, import_key_derivation_data, nonce, chunk_size, header_bytes))
 }
 
+// Imports records into the new wallet, the wallet is deleted by the caller if the import fails
 pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>) -> IndyResult<()> where T: Read {
+    _read_records(reader, key, nonce, chunk_size, header_bytes, |record| {
+        // There is nothing to delete in the new wallet
+        if record.type_ == TOMBSTONE_TYPE {
+            return Ok(());
+        }
+
+        wallet.add(&record.type_, &record.id, &record.value, &record.tags)
+    })
+}
+
+// Merges records into the existing wallet. Conflicts are resolved before the wallet is touched
+// and all changes are applied in a single batch, so the wallet is left unchanged if the import fails.
+pub(super) fn merge_import<T>(wallet: &Wallet, import: PreparedImport<T>, key: chacha20poly1305_ietf::Key, on_conflict: &ImportConflictPolicy) -> IndyResult<()> where T: Read {
+    let PreparedImport { reader, nonce, chunk_size, header_bytes, .. } = import;
+
+    let mut operations = Vec::new();
+
+    _read_records(reader, key, nonce, chunk_size, header_bytes, |record| {
+        let Record { type_, id, value, tags } = record;
+
+        if type_ == TOMBSTONE_TYPE {
+            // Value of the tombstone is the type of the deleted record
+            if _record_exists(wallet, &value, &id)? {
+                operations.push(RecordOperation::Delete { type_: value, id });
+            }
+            return Ok(());
+        }
+
+        match (_record_exists(wallet, &type_, &id)?, on_conflict) {
+            (false, _) => operations.push(RecordOperation::Add { type_, id, value, tags: Some(tags) }),
+            (true, &ImportConflictPolicy::Fail) =>
+                return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, format!("Imported record of type \"{}\" already exists in the wallet", type_))),
+            (true, &ImportConflictPolicy::Skip) => {}
+            (true, &ImportConflictPolicy::Overwrite) => {
+                operations.push(RecordOperation::Update { type_: type_.clone(), id: id.clone(), value });
+                operations.push(RecordOperation::UpdateTags { type_, id, tags });
+            }
+        }
+
+        Ok(())
+    })?;
+
+    if operations.is_empty() {
+        return Ok(());
+    }
+
+    wallet.batch(&operations)
+}
+
+fn _read_records<T, F>(reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>, mut import_record: F) -> IndyResult<()>
+    where T: Read, F: FnMut(Record) -> IndyResult<()> {
     // Reads encrypted
     let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
 
@@ -224,12 +453,20 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
         let record: Record = rmp_serde::from_slice(&record)
             .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
 
-        wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
+        import_record(record)?;
     }
 
     Ok(())
 }
 
+fn _record_exists(wallet: &Wallet, type_: &str, id: &str) -> IndyResult<bool> {
+    match wallet.get(type_, id, &RecordOptions::id()) {
+        Ok(_) => Ok(true),
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => Ok(false),
+        Err(err) => Err(err)
+    }
+}
+
 fn _map_io_err(e: io::Error) -> IndyError {
     match e {
         ref e if e.kind() == io::ErrorKind::UnexpectedEof
@@ -263,7 +500,7 @@ mod tests {
         let key_data = KeyDerivationData::from_passphrase_with_new_salt(passphrase, key_derivation_method);
         let key = key_data.calc_master_key()?;
 
-        export_continue(wallet, writer, version, key, &key_data)
+        export_continue(wallet, writer, &ExportOptions::default(), version, key, &key_data)
     }
 
     #[test]
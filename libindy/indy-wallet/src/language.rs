@@ -67,6 +67,12 @@ pub enum Operator {
     Lte(TagName, TargetValue),
     Like(TagName, TargetValue),
     In(TagName, Vec<TargetValue>),
+    ILike(TagName, TargetValue),
+    Exist(TagName),
+    NumGt(TagName, i64),
+    NumGte(TagName, i64),
+    NumLt(TagName, i64),
+    NumLte(TagName, i64),
 }
 
 impl string::ToString for Operator {
@@ -79,6 +85,12 @@ impl string::ToString for Operator {
             Operator::Lt(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lt":{}}}}}"#, tag_name.to_string(), tag_value.to_string()),
             Operator::Lte(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lte":{}}}}}"#, tag_name.to_string(), tag_value.to_string()),
             Operator::Like(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$like":{}}}}}"#, tag_name.to_string(), tag_value.to_string()),
+            Operator::ILike(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$ilike":{}}}}}"#, tag_name.to_string(), tag_value.to_string()),
+            Operator::Exist(ref tag_name) => format!(r#"{{{}:{{"$exist":true}}}}"#, tag_name.to_string()),
+            Operator::NumGt(ref tag_name, tag_value) => format!(r#"{{{}:{{"$gt":{}}}}}"#, tag_name.to_string(), tag_value),
+            Operator::NumGte(ref tag_name, tag_value) => format!(r#"{{{}:{{"$gte":{}}}}}"#, tag_name.to_string(), tag_value),
+            Operator::NumLt(ref tag_name, tag_value) => format!(r#"{{{}:{{"$lt":{}}}}}"#, tag_name.to_string(), tag_value),
+            Operator::NumLte(ref tag_name, tag_value) => format!(r#"{{{}:{{"$lte":{}}}}}"#, tag_name.to_string(), tag_value),
             Operator::In(ref tag_name, ref tag_values) => {
                 format!(
                     r#"{{{}:{{"$in":[{}]}}}}"#,
@@ -16,7 +16,7 @@ extern crate serde_json;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
-use std::io::BufReader;
+use std::io::{BufReader, Read, Write};
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -25,18 +25,23 @@ use serde_json::Value as SValue;
 
 use indy_api_types::wallet::*;
 
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, Tags};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, ExportOptions, ImportConflictPolicy, ImportRecordsConfig, RecordOperation, Tags};
 use indy_api_types::errors::prelude::*;
 pub use crate::encryption::KeyDerivationData;
+pub use crate::check::{InvalidRecord, WalletCheckReport};
+pub use crate::events::{WalletEvent, WalletEventCallback, WalletEventKind};
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
-use self::export_import::{export_continue, finish_import, preparse_file_to_import};
+use self::export_import::{export_continue, finish_import, merge_import, prepare_import, preparse_file_to_import};
+pub use self::export_import::PreparedImport;
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
+use self::storage::inmem::InmemStorageType;
 use self::storage::plugged::PluggedStorageType;
+use self::events::Subscription;
 use self::wallet::{Keys, Wallet};
-use indy_api_types::{WalletHandle};
+use indy_api_types::{SubscriptionHandle, WalletHandle};
 
 mod storage;
 mod encryption;
@@ -46,12 +51,15 @@ mod iterator;
 pub mod language;
 mod export_import;
 mod wallet;
+mod check;
+mod events;
 
 pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
     pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    subscriptions: RefCell<HashMap<SubscriptionHandle, Subscription>>,
 }
 
 impl WalletService {
@@ -59,6 +67,7 @@ impl WalletService {
         let storage_types = {
             let mut map: HashMap<String, Box<dyn WalletStorageType>> = HashMap::new();
             map.insert("default".to_string(), Box::new(SQLiteStorageType::new()));
+            map.insert("in_memory".to_string(), Box::new(InmemStorageType::new()));
             RefCell::new(map)
         };
 
@@ -67,6 +76,7 @@ impl WalletService {
             wallets: RefCell::new(HashMap::new()),
             pending_for_open: RefCell::new(HashMap::new()),
             pending_for_import: RefCell::new(HashMap::new()),
+            subscriptions: RefCell::new(HashMap::new()),
         }
     }
 
@@ -190,6 +200,46 @@ impl WalletService {
         Ok(())
     }
 
+    pub fn check_wallet_prepare(&self, config: &Config, credentials: &Credentials) -> IndyResult<CheckPreparation> {
+        trace!("check_wallet >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
+
+        self._is_id_from_config_not_used(config)?;
+
+        let storage = self._open_storage(config, credentials)?;
+
+        let metadata = match WalletService::_fetch_metadata(storage.as_ref()) {
+            Ok(metadata) => metadata,
+            Err(err) => return Ok(CheckPreparation::Report(WalletCheckReport::for_metadata_error(format!("Wallet metadata can't be read: {}", err))))
+        };
+
+        let key_derivation_data = KeyDerivationData::from_passphrase_and_metadata(&credentials.key, &metadata, &credentials.key_derivation_method)?;
+
+        Ok(CheckPreparation::DeriveKey(metadata, key_derivation_data))
+    }
+
+    pub fn check_wallet_continue(&self, config: &Config, credentials: &Credentials, options: &CheckOptions, metadata: &Metadata, master_key: &MasterKey) -> IndyResult<WalletCheckReport> {
+        trace!("check_wallet >>> config: {:?}, credentials: {:?}, options: {:?}", config, secret!(credentials), options);
+
+        let keys = self._restore_keys(metadata, &master_key)?;
+
+        let mut storage = self._open_storage(config, credentials)?;
+
+        // keys can be rotated by another process while the master key is derived,
+        // records can't be checked with keys that don't match the stored ones
+        let res = match WalletService::_fetch_metadata(storage.as_ref()) {
+            Ok(ref current_metadata) if current_metadata.get_keys() == metadata.get_keys() =>
+                check::check_storage(storage.as_ref(), &keys, options),
+            Ok(_) => Ok(WalletCheckReport::for_metadata_error(
+                "Wallet master key has been changed by another process. Try to check wallet again".to_string())),
+            Err(err) => Ok(WalletCheckReport::for_metadata_error(format!("Wallet metadata can't be read: {}", err)))
+        };
+
+        storage.close()?;
+
+        trace!("check_wallet <<< res: {:?}", res);
+        res
+    }
+
     pub fn open_wallet_prepare(&self, config: &Config, credentials: &Credentials) -> IndyResult<(WalletHandle, KeyDerivationData, Option<KeyDerivationData>)> {
         trace!("open_wallet >>> config: {:?}, credentials: {:?}", config, secret!(&credentials));
 
@@ -216,8 +266,17 @@ impl WalletService {
 
         // Rotate master key
         if let (Some(rekey), Some(rekey_data)) = (rekey, rekey_data) {
-            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys)?;
-            storage.set_storage_metadata(&metadata)?;
+            // storage can be shared with another process that has rotated the key while it was derived,
+            // so the current metadata is checked in the same transaction it is replaced in
+            storage.update_storage_metadata(&|current_metadata| {
+                let current_metadata = WalletService::_parse_metadata(current_metadata)?;
+
+                if current_metadata.get_keys() != metadata.get_keys() {
+                    return Err(err_msg(IndyErrorKind::WalletAccessFailed, "Wallet master key has been changed by another process. Try to open wallet again"));
+                }
+
+                self._prepare_metadata(rekey, &rekey_data, &keys)
+            })?;
         }
 
         let wallet = Wallet::new(id, storage, Rc::new(keys));
@@ -229,17 +288,41 @@ impl WalletService {
         Ok(wallet_handle)
     }
 
+    pub fn rekey_wallet(&self, wallet_handle: WalletHandle, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+        trace!("rekey_wallet >>> wallet_handle: {:?}", wallet_handle);
+
+        let (key_data, master_key) = key;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        // Master key encrypts only the wallet keys stored in metadata, so records are left untouched
+        // and the single metadata update either applies the new key or keeps the previous one valid.
+        let metadata = self._prepare_metadata(master_key, key_data, wallet.get_keys())?;
+        wallet.set_storage_metadata(&metadata)?;
+
+        trace!("rekey_wallet <<<");
+        Ok(())
+    }
+
     fn _open_storage_and_fetch_metadata(&self, config: &Config, credentials: &Credentials) -> IndyResult<(Box<dyn WalletStorage>, Metadata, KeyDerivationData)> {
         let storage = self._open_storage(config, credentials)?;
-        let metadata: Metadata = {
-            let metadata = storage.get_storage_metadata()?;
-            serde_json::from_slice(&metadata)
-                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?
-        };
+        let metadata = WalletService::_fetch_metadata(storage.as_ref())?;
         let key_derivation_data = KeyDerivationData::from_passphrase_and_metadata(&credentials.key, &metadata, &credentials.key_derivation_method)?;
         Ok((storage, metadata, key_derivation_data))
     }
 
+    fn _fetch_metadata(storage: &dyn WalletStorage) -> IndyResult<Metadata> {
+        WalletService::_parse_metadata(&storage.get_storage_metadata()?)
+    }
+
+    fn _parse_metadata(metadata: &[u8]) -> IndyResult<Metadata> {
+        serde_json::from_slice(metadata)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")
+    }
+
     pub fn close_wallet(&self, handle: WalletHandle) -> IndyResult<()> {
         trace!("close_wallet >>> handle: {:?}", handle);
 
@@ -248,6 +331,8 @@ impl WalletService {
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }?;
 
+        self.subscriptions.borrow_mut().retain(|_, subscription| subscription.wallet_handle() != handle);
+
         trace!("close_wallet <<<");
         Ok(())
     }
@@ -265,7 +350,10 @@ impl WalletService {
             Some(wallet) => wallet.add(type_, name, value, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Add, type_, name, tags);
+        Ok(())
     }
 
     pub fn add_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str, value: &str, tags: &Tags)
@@ -288,12 +376,15 @@ impl WalletService {
                 wallet.update(type_, name, value)
                     .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify_with_current_tags(wallet_handle, WalletEventKind::Update, type_, name);
+        Ok(())
     }
 
     pub fn update_indy_object<T>(&self, wallet_handle: WalletHandle, name: &str, object: &T) -> IndyResult<String> where T: ::serde::Serialize + Sized + NamedType {
         let type_ = T::short_type_name();
-        match self.wallets.borrow().get(&wallet_handle) {
+        let object_json = match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => {
                 let object_json = serde_json::to_string(object)
                     .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", type_))?;
@@ -301,7 +392,10 @@ impl WalletService {
                 Ok(object_json)
             }
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify_with_current_tags(wallet_handle, WalletEventKind::Update, &self.add_prefix(type_), name);
+        Ok(object_json)
     }
 
     pub fn add_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
@@ -309,7 +403,10 @@ impl WalletService {
             Some(wallet) => wallet.add_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify_with_current_tags(wallet_handle, WalletEventKind::AddTags, type_, name);
+        Ok(())
     }
 
     pub fn update_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
@@ -317,7 +414,10 @@ impl WalletService {
             Some(wallet) => wallet.update_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify_with_current_tags(wallet_handle, WalletEventKind::UpdateTags, type_, name);
+        Ok(())
     }
 
     pub fn delete_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
@@ -325,14 +425,152 @@ impl WalletService {
             Some(wallet) => wallet.delete_tags(type_, name, tag_names)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify_with_current_tags(wallet_handle, WalletEventKind::DeleteTags, type_, name);
+        Ok(())
     }
 
     pub fn delete_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str) -> IndyResult<()> {
+        // tags are unavailable after deletion
+        let tags = self._subscribed_record_tags(wallet_handle, type_, name);
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.delete(type_, name)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        if let Some(tags) = tags {
+            self._notify(wallet_handle, WalletEventKind::Delete, type_, name, &tags);
+        }
+        Ok(())
+    }
+
+    pub fn batch_records(&self, wallet_handle: WalletHandle, operations: &[RecordOperation]) -> IndyResult<()> {
+        let deleted_tags = operations.iter()
+            .filter_map(|operation| match *operation {
+                RecordOperation::Delete { ref type_, ref id } =>
+                    self._subscribed_record_tags(wallet_handle, type_, id).map(|tags| ((type_.as_str(), id.as_str()), tags)),
+                _ => None
+            })
+            .collect::<HashMap<(&str, &str), Tags>>();
+
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.batch(operations),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        // events are delivered after the whole batch is applied, so tags are the final ones
+        for operation in operations {
+            match *operation {
+                RecordOperation::Add { ref type_, ref id, ref tags, .. } =>
+                    self._notify(wallet_handle, WalletEventKind::Add, type_, id, tags.as_ref().unwrap_or(&Tags::new())),
+                RecordOperation::Update { ref type_, ref id, .. } =>
+                    self._notify_with_current_tags(wallet_handle, WalletEventKind::Update, type_, id),
+                RecordOperation::AddTags { ref type_, ref id, .. } =>
+                    self._notify_with_current_tags(wallet_handle, WalletEventKind::AddTags, type_, id),
+                RecordOperation::UpdateTags { ref type_, ref id, .. } =>
+                    self._notify_with_current_tags(wallet_handle, WalletEventKind::UpdateTags, type_, id),
+                RecordOperation::DeleteTags { ref type_, ref id, .. } =>
+                    self._notify_with_current_tags(wallet_handle, WalletEventKind::DeleteTags, type_, id),
+                RecordOperation::Delete { ref type_, ref id } =>
+                    if let Some(tags) = deleted_tags.get(&(type_.as_str(), id.as_str())) {
+                        self._notify(wallet_handle, WalletEventKind::Delete, type_, id, tags);
+                    },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Subscribes to changes of the records of the type which tags match the WQL query.
+    /// The callback is called after each change made through this service until
+    /// the subscription is removed or the wallet is closed.
+    pub fn subscribe_records(&self, wallet_handle: WalletHandle, type_: &str, query_json: &str, cb: WalletEventCallback) -> IndyResult<SubscriptionHandle> {
+        trace!("subscribe_records >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}", wallet_handle, type_, query_json);
+
+        let query = match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.encrypt_query(query_json),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        let subscription = Subscription::new(wallet_handle, type_, query, cb)?;
+
+        let subscription_handle: SubscriptionHandle = indy_utils::sequence::get_next_id();
+        self.subscriptions.borrow_mut().insert(subscription_handle, subscription);
+
+        trace!("subscribe_records <<< subscription_handle: {:?}", subscription_handle);
+        Ok(subscription_handle)
+    }
+
+    pub fn unsubscribe_records(&self, subscription_handle: SubscriptionHandle) -> IndyResult<()> {
+        trace!("unsubscribe_records >>> subscription_handle: {:?}", subscription_handle);
+
+        match self.subscriptions.borrow_mut().remove(&subscription_handle) {
+            Some(_) => Ok(()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown wallet subscription handle: {}", subscription_handle)))
+        }?;
+
+        trace!("unsubscribe_records <<<");
+        Ok(())
+    }
+
+    // Returns None if nobody is subscribed to the record type, so tags aren't fetched in vain
+    fn _subscribed_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str) -> Option<Tags> {
+        if !self.subscriptions.borrow().values().any(|subscription| subscription.is_subscribed(wallet_handle, type_)) {
+            return None;
+        }
+
+        match self.get_record(wallet_handle, type_, name, &RecordOptions::id_tags()) {
+            Ok(record) => Some(record.tags.unwrap_or_default()),
+            Err(err) => {
+                // the record can be deleted by a later operation of the same batch
+                warn!("Can't fetch tags of changed record type: {:?}, id: {:?}, err: {:?}", type_, name, err);
+                Some(Tags::new())
+            }
+        }
+    }
+
+    fn _notify_with_current_tags(&self, wallet_handle: WalletHandle, event: WalletEventKind, type_: &str, name: &str) {
+        if let Some(tags) = self._subscribed_record_tags(wallet_handle, type_, name) {
+            self._notify(wallet_handle, event, type_, name, &tags);
+        }
+    }
+
+    fn _notify(&self, wallet_handle: WalletHandle, event: WalletEventKind, type_: &str, name: &str, tags: &Tags) {
+        let callbacks = {
+            let subscriptions = self.subscriptions.borrow();
+
+            if !subscriptions.values().any(|subscription| subscription.is_subscribed(wallet_handle, type_)) {
+                return;
+            }
+
+            let encrypted_tags = match self.wallets.borrow().get(&wallet_handle) {
+                Some(wallet) => wallet.encrypt_tags(tags),
+                None => return
+            };
+
+            subscriptions.iter()
+                .filter(|&(_, subscription)| subscription.matches(wallet_handle, type_, &encrypted_tags))
+                .map(|(subscription_handle, subscription)| (*subscription_handle, subscription.callback()))
+                .collect::<Vec<(SubscriptionHandle, WalletEventCallback)>>()
+        };
+
+        if callbacks.is_empty() {
+            return;
+        }
+
+        let event = WalletEvent {
+            event,
+            type_: type_.to_string(),
+            id: name.to_string(),
+            tags: tags.clone(),
+        };
+
+        // subscriptions aren't borrowed here, so callbacks are free to use the service
+        for (subscription_handle, cb) in callbacks {
+            cb(subscription_handle, &event);
         }
     }
 
@@ -460,13 +698,34 @@ impl WalletService {
                 .create_new(true)
                 .open(export_config.path.clone())?;
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+        let res = export_continue(wallet, &mut export_file, &export_config.options, version, key.clone(), key_data);
 
         trace!("export_wallet <<<");
 
         res
     }
 
+    pub fn export_wallet_to_writer(&self, wallet_handle: WalletHandle, writer: &mut dyn Write, options: &ExportOptions, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+        trace!("export_wallet_to_writer >>> wallet_handle: {:?}, options: {:?}, version: {:?}", wallet_handle, options, version);
+
+        if version != 0 {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
+        }
+
+        let (key_data, key) = key;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let res = export_continue(wallet, writer, options, version, key.clone(), key_data);
+
+        trace!("export_wallet_to_writer <<<");
+
+        res
+    }
+
     pub fn import_wallet_prepare(&self,
                                  config: &Config,
                                  credentials: &Credentials,
@@ -520,6 +779,50 @@ impl WalletService {
         res
     }
 
+    pub fn import_records_prepare(&self, wallet_handle: WalletHandle, import_config: &ImportRecordsConfig) -> IndyResult<PreparedImport<fs::File>> {
+        trace!("import_records_prepare >>> wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        self.check(wallet_handle)?;
+
+        let exported_file_to_import =
+            fs::OpenOptions::new()
+                .read(true)
+                .open(&import_config.path)?;
+
+        let res = prepare_import(exported_file_to_import, &import_config.key);
+
+        trace!("import_records_prepare <<<");
+
+        res
+    }
+
+    pub fn import_records_prepare_from_reader<T>(&self, wallet_handle: WalletHandle, reader: T, key: &str) -> IndyResult<PreparedImport<T>> where T: Read {
+        trace!("import_records_prepare_from_reader >>> wallet_handle: {:?}", wallet_handle);
+
+        self.check(wallet_handle)?;
+
+        let res = prepare_import(reader, key);
+
+        trace!("import_records_prepare_from_reader <<<");
+
+        res
+    }
+
+    pub fn import_records_continue<T>(&self, wallet_handle: WalletHandle, import: PreparedImport<T>, import_key: &MasterKey, on_conflict: &ImportConflictPolicy) -> IndyResult<()> where T: Read {
+        trace!("import_records_continue >>> wallet_handle: {:?}, on_conflict: {:?}", wallet_handle, on_conflict);
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let res = merge_import(wallet, import, import_key.clone(), on_conflict);
+
+        trace!("import_records_continue <<<");
+
+        res
+    }
+
     fn _get_config_and_cred_for_storage<'a>(config: &Config, credentials: &Credentials, storage_types: &'a HashMap<String, Box<dyn WalletStorageType>>) -> IndyResult<(&'a Box<dyn WalletStorageType>, Option<String>, Option<String>)> {
         let storage_type = {
             let storage_type = config.storage_type
@@ -602,6 +905,14 @@ impl WalletService {
     }
 }
 
+/// Result of the first step of the wallet check.
+pub enum CheckPreparation {
+    /// The master key must be derived to check records.
+    DeriveKey(Metadata, KeyDerivationData),
+    /// Records can't be checked as the wallet keys can't be taken from metadata.
+    Report(WalletCheckReport),
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum Metadata {
@@ -714,6 +1025,16 @@ impl RecordOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn id_tags() -> String {
+        let options = RecordOptions {
+            retrieve_type: false,
+            retrieve_value: false,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for RecordOptions {
@@ -740,7 +1061,7 @@ impl WalletSearch {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchOptions {
     #[serde(default = "default_true")]
@@ -753,6 +1074,18 @@ pub struct SearchOptions {
     retrieve_value: bool,
     #[serde(default = "default_false")]
     retrieve_tags: bool,
+    // Name of the plaintext tag to sort records by, records without this tag go first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort_by: Option<String>,
+    #[serde(default)]
+    sort_order: SortOrder,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+    // Names of the tags to retrieve if retrieve_tags is set, all tags are retrieved if omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag_names: Option<Vec<String>>,
 }
 
 impl SearchOptions {
@@ -763,10 +1096,28 @@ impl SearchOptions {
             retrieve_type: true,
             retrieve_value: true,
             retrieve_tags: false,
+            ..SearchOptions::default()
         };
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn full() -> String {
+        let options = SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: false,
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+            ..SearchOptions::default()
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
+
+    fn is_paged(&self) -> bool {
+        self.offset.is_some() || self.limit.is_some()
+    }
 }
 
 impl Default for SearchOptions {
@@ -777,10 +1128,28 @@ impl Default for SearchOptions {
             retrieve_type: false,
             retrieve_value: true,
             retrieve_tags: false,
+            sort_by: None,
+            sort_order: SortOrder::Asc,
+            offset: None,
+            limit: None,
+            tag_names: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Asc
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -857,6 +1226,17 @@ mod tests {
 
             self.delete_wallet_continue(config, credentials, &metadata, &master_key)
         }
+
+        pub fn check_wallet(&self, config: &Config, credentials: &Credentials, options: &CheckOptions) -> IndyResult<WalletCheckReport> {
+            let (metadata, key_derivation_data) = match self.check_wallet_prepare(config, credentials)? {
+                CheckPreparation::DeriveKey(metadata, key_derivation_data) => (metadata, key_derivation_data),
+                CheckPreparation::Report(report) => return Ok(report)
+            };
+
+            let master_key = key_derivation_data.calc_master_key()?;
+
+            self.check_wallet_continue(config, credentials, options, &metadata, &master_key)
+        }
     }
 
     #[test]
@@ -1233,66 +1613,192 @@ mod tests {
     }
 
     #[test]
-    fn wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle() {
-        test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
-        {
-            let wallet_service = WalletService::new();
-            wallet_service.create_wallet(&_config("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle"), &RAW_CREDENTIAL).unwrap();
+    fn wallet_service_close_works_for_inmem() {
+        let wallet_service = WalletService::new();
 
-            let res = wallet_service.close_wallet(INVALID_WALLET_HANDLE);
-            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+        let config = _config_inmem_storage("wallet_service_close_works_for_inmem");
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.close_wallet(wallet_handle).unwrap();
 
-            wallet_service.close_wallet(wallet_handle).unwrap();
-        }
-        test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false)).unwrap();
+        assert_eq!("value1", record.get_value().unwrap());
+        wallet_service.close_wallet(wallet_handle).unwrap();
+
+        wallet_service.delete_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let res = wallet_service.open_wallet(&config, &RAW_CREDENTIAL);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
     }
 
     #[test]
-    fn wallet_service_add_record_works() {
-        test::cleanup_wallet("wallet_service_add_record_works");
+    fn wallet_service_check_wallet_works() {
+        test::cleanup_wallet("wallet_service_check_wallet_works");
         {
+            let config: &Config = &_config("wallet_service_check_wallet_works");
             let wallet_service = WalletService::new();
-            wallet_service.create_wallet(&_config("wallet_service_add_record_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_record_works"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
 
-            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &serde_json::from_str(r#"{"tag1":"some", "~tag2":"3"}"#).unwrap()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let report = wallet_service.check_wallet(config, &RAW_CREDENTIAL, &CheckOptions::default()).unwrap();
+
+            assert_eq!(WalletCheckReport {
+                valid: true,
+                metadata_errors: vec![],
+                checked_records: 2,
+                invalid_records: vec![],
+                orphaned_tags: 0,
+                repaired: false,
+            }, report);
         }
-        test::cleanup_wallet("wallet_service_add_record_works");
+        test::cleanup_wallet("wallet_service_check_wallet_works");
     }
 
     #[test]
-    fn wallet_service_add_record_works_for_plugged() {
-        _cleanup("wallet_service_add_record_works_for_plugged");
+    fn wallet_service_check_wallet_works_for_invalid_record_and_repair() {
+        let name = "wallet_service_check_wallet_works_for_invalid_record_and_repair";
+        let quarantine_path = _export_file_path(name);
+        let _ = fs::remove_file(&quarantine_path);
 
+        let config = _config_inmem_storage(name);
         let wallet_service = WalletService::new();
-        _register_inmem_wallet(&wallet_service);
-
-        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
 
-        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
-    }
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &serde_json::from_str(r#"{"tag1":"some", "~tag2":"3"}"#).unwrap()).unwrap();
+        wallet_service.close_wallet(wallet_handle).unwrap();
 
-    #[test]
-    fn wallet_service_get_record_works_for_id_only() {
-        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
         {
-            let wallet_service = WalletService::new();
-            wallet_service.create_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL).unwrap();
+            let storage = wallet_service.storage_types.borrow().get("in_memory").unwrap().open_storage(name, None, None).unwrap();
+            storage.add(b"type", b"garbage", &crate::wallet::EncryptedValue::new(vec![1, 2, 3], vec![4, 5, 6]), &[]).unwrap();
+        }
 
-            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, false, false)).unwrap();
+        let report = wallet_service.check_wallet(&config, &RAW_CREDENTIAL, &CheckOptions::default()).unwrap();
+        assert!(!report.valid);
+        assert!(!report.repaired);
+        assert_eq!(2, report.checked_records);
+        assert_eq!(1, report.invalid_records.len());
+        assert_eq!(None, report.invalid_records[0].type_);
 
-            assert!(record.get_value().is_none());
-            assert!(record.get_type().is_none());
-            assert!(record.get_tags().is_none());
-        }
-        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
-    }
+        let options = CheckOptions { repair: true, quarantine_path: Some(quarantine_path.to_str().unwrap().to_string()) };
+        let report = wallet_service.check_wallet(&config, &RAW_CREDENTIAL, &options).unwrap();
+        assert!(!report.valid);
+        assert!(report.repaired);
+        assert_eq!(1, fs::read_to_string(&quarantine_path).unwrap().lines().count());
+
+        let report = wallet_service.check_wallet(&config, &RAW_CREDENTIAL, &CheckOptions::default()).unwrap();
+        assert!(report.valid);
+        assert_eq!(1, report.checked_records);
+
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false)).unwrap();
+        assert_eq!("value1", record.get_value().unwrap());
+        wallet_service.close_wallet(wallet_handle).unwrap();
+
+        fs::remove_file(&quarantine_path).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_works_for_broken_metadata() {
+        let name = "wallet_service_check_wallet_works_for_broken_metadata";
+
+        let config = _config_inmem_storage(name);
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        {
+            let storage = wallet_service.storage_types.borrow().get("in_memory").unwrap().open_storage(name, None, None).unwrap();
+            storage.set_storage_metadata(b"garbage").unwrap();
+        }
+
+        let report = wallet_service.check_wallet(&config, &RAW_CREDENTIAL, &CheckOptions::default()).unwrap();
+        assert!(!report.valid);
+        assert_eq!(1, report.metadata_errors.len());
+        assert_eq!(0, report.checked_records);
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_returns_error_if_wallet_opened() {
+        test::cleanup_wallet("wallet_service_check_wallet_returns_error_if_wallet_opened");
+        {
+            let config: &Config = &_config("wallet_service_check_wallet_returns_error_if_wallet_opened");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.check_wallet(config, &RAW_CREDENTIAL, &CheckOptions::default());
+            assert_kind!(IndyErrorKind::WalletAlreadyOpened, res);
+        }
+        test::cleanup_wallet("wallet_service_check_wallet_returns_error_if_wallet_opened");
+    }
+
+    #[test]
+    fn wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle() {
+        test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle"), &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.close_wallet(INVALID_WALLET_HANDLE);
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
+    }
+
+    #[test]
+    fn wallet_service_add_record_works() {
+        test::cleanup_wallet("wallet_service_add_record_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_add_record_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_record_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_add_record_works");
+    }
+
+    #[test]
+    fn wallet_service_add_record_works_for_plugged() {
+        _cleanup("wallet_service_add_record_works_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+    }
+
+    #[test]
+    fn wallet_service_get_record_works_for_id_only() {
+        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, false, false)).unwrap();
+
+            assert!(record.get_value().is_none());
+            assert!(record.get_type().is_none());
+            assert!(record.get_tags().is_none());
+        }
+        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
+    }
 
     #[test]
     fn wallet_service_get_record_works_for_plugged_for_id_only() {
@@ -1533,6 +2039,247 @@ mod tests {
         assert_kind!(IndyErrorKind::WalletItemNotFound, res);
     }
 
+    /**
+     * Batch tests
+     */
+    #[test]
+    fn wallet_service_batch_records_works() {
+        test::cleanup_wallet("wallet_service_batch_records_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_batch_records_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_batch_records_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "name2", "value2", &HashMap::new()).unwrap();
+
+            wallet_service.batch_records(wallet_handle, &_batch_operations()).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "name1", &_fetch_options(false, true, true)).unwrap();
+            assert_eq!("new_value1", record.get_value().unwrap());
+            assert_eq!(&_batch_tags(), record.get_tags().unwrap());
+
+            let res = wallet_service.get_record(wallet_handle, "type", "name2", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_failed_operation() {
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_batch_records_works_for_failed_operation"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_batch_records_works_for_failed_operation"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "name2", "value2", &HashMap::new()).unwrap();
+
+            let res = wallet_service.batch_records(wallet_handle, &_failed_batch_operations());
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            _check_batch_rolled_back(&wallet_service, wallet_handle);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_plugged_and_failed_operation() {
+        _cleanup("wallet_service_batch_records_works_for_plugged_and_failed_operation");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "name2", "value2", &HashMap::new()).unwrap();
+
+        let res = wallet_service.batch_records(wallet_handle, &_failed_batch_operations());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        _check_batch_rolled_back(&wallet_service, wallet_handle);
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.batch_records(INVALID_WALLET_HANDLE, &_batch_operations());
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_subscribe_records_works() {
+        let config = _config_inmem_storage("wallet_service_subscribe_records_works");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let (events, cb) = _events_collector();
+        let subscription_handle = wallet_service.subscribe_records(wallet_handle, "type", "{}", cb).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &_event_tags(&[("tag1", "value1")])).unwrap();
+        wallet_service.add_record(wallet_handle, "other_type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.update_record_value(wallet_handle, "type", "key1", "value2").unwrap();
+        wallet_service.add_record_tags(wallet_handle, "type", "key1", &_event_tags(&[("~tag2", "value2")])).unwrap();
+        wallet_service.update_record_tags(wallet_handle, "type", "key1", &_event_tags(&[("tag1", "value3")])).unwrap();
+        wallet_service.delete_record_tags(wallet_handle, "type", "key1", &["tag1"]).unwrap();
+        wallet_service.add_record_tags(wallet_handle, "type", "key1", &_event_tags(&[("tag3", "value4")])).unwrap();
+        wallet_service.delete_record(wallet_handle, "type", "key1").unwrap();
+
+        assert_eq!(vec![
+            _event(WalletEventKind::Add, "key1", &[("tag1", "value1")]),
+            _event(WalletEventKind::Update, "key1", &[("tag1", "value1")]),
+            _event(WalletEventKind::AddTags, "key1", &[("tag1", "value1"), ("~tag2", "value2")]),
+            _event(WalletEventKind::UpdateTags, "key1", &[("tag1", "value3")]),
+            _event(WalletEventKind::DeleteTags, "key1", &[]),
+            _event(WalletEventKind::AddTags, "key1", &[("tag3", "value4")]),
+            _event(WalletEventKind::Delete, "key1", &[("tag3", "value4")]),
+        ], *events.borrow());
+
+        wallet_service.unsubscribe_records(subscription_handle).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key2", "value1", &HashMap::new()).unwrap();
+        assert_eq!(7, events.borrow().len());
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_subscribe_records_works_for_query() {
+        let config = _config_inmem_storage("wallet_service_subscribe_records_works_for_query");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let (events, cb) = _events_collector();
+        wallet_service.subscribe_records(wallet_handle, "type", r#"{"tag1": "value1", "~tag2": {"$gt": "5"}}"#, cb).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &_event_tags(&[("tag1", "value1"), ("~tag2", "3")])).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &_event_tags(&[("tag1", "value2"), ("~tag2", "7")])).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key3", "value3", &_event_tags(&[("tag1", "value1"), ("~tag2", "7")])).unwrap();
+        wallet_service.update_record_tags(wallet_handle, "type", "key1", &_event_tags(&[("tag1", "value1"), ("~tag2", "6")])).unwrap();
+
+        assert_eq!(vec![
+            _event(WalletEventKind::Add, "key3", &[("tag1", "value1"), ("~tag2", "7")]),
+            _event(WalletEventKind::UpdateTags, "key1", &[("tag1", "value1"), ("~tag2", "6")]),
+        ], *events.borrow());
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_subscribe_records_works_for_batch() {
+        let config = _config_inmem_storage("wallet_service_subscribe_records_works_for_batch");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "name2", "value2", &HashMap::new()).unwrap();
+
+        let (events, cb) = _events_collector();
+        wallet_service.subscribe_records(wallet_handle, "type", "{}", cb).unwrap();
+
+        let res = wallet_service.batch_records(wallet_handle, &_failed_batch_operations());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        assert!(events.borrow().is_empty());
+
+        wallet_service.batch_records(wallet_handle, &_batch_operations()).unwrap();
+
+        let batch_tags = _batch_tags();
+        let batch_tags = batch_tags.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect::<Vec<(&str, &str)>>();
+
+        assert_eq!(vec![
+            _event(WalletEventKind::Add, "name1", &[]),
+            _event(WalletEventKind::Update, "name1", &batch_tags),
+            _event(WalletEventKind::AddTags, "name1", &batch_tags),
+            _event(WalletEventKind::Delete, "name2", &[]),
+        ], *events.borrow());
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_subscribe_records_works_for_closed_wallet() {
+        let config = _config_inmem_storage("wallet_service_subscribe_records_works_for_closed_wallet");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let (_, cb) = _events_collector();
+        let subscription_handle = wallet_service.subscribe_records(wallet_handle, "type", "{}", cb).unwrap();
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+
+        let res = wallet_service.unsubscribe_records(subscription_handle);
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+
+        let (_, cb) = _events_collector();
+        let res = wallet_service.subscribe_records(wallet_handle, "type", "{}", cb);
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_subscribe_records_works_for_invalid_query() {
+        let config = _config_inmem_storage("wallet_service_subscribe_records_works_for_invalid_query");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let (_, cb) = _events_collector();
+        let res = wallet_service.subscribe_records(wallet_handle, "type", r#"{"tag1": {"$gt": "5"}}"#, cb);
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+
+        let (_, cb) = _events_collector();
+        let res = wallet_service.subscribe_records(wallet_handle, "type", r#"{"tag1": {"$unknown": "5"}}"#, cb);
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+    }
+
+    fn _events_collector() -> (Rc<RefCell<Vec<WalletEvent>>>, WalletEventCallback) {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let collected = events.clone();
+        (events, Rc::new(move |_, event: &WalletEvent| collected.borrow_mut().push(event.clone())))
+    }
+
+    fn _event(event: WalletEventKind, id: &str, tags: &[(&str, &str)]) -> WalletEvent {
+        WalletEvent { event, type_: "type".to_string(), id: id.to_string(), tags: _event_tags(tags) }
+    }
+
+    fn _event_tags(tags: &[(&str, &str)]) -> Tags {
+        tags.iter().map(|&(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn _batch_operations() -> Vec<RecordOperation> {
+        vec![
+            RecordOperation::Add { type_: "type".to_string(), id: "name1".to_string(), value: "value1".to_string(), tags: None },
+            RecordOperation::Update { type_: "type".to_string(), id: "name1".to_string(), value: "new_value1".to_string() },
+            RecordOperation::AddTags { type_: "type".to_string(), id: "name1".to_string(), tags: _batch_tags() },
+            RecordOperation::Delete { type_: "type".to_string(), id: "name2".to_string() },
+        ]
+    }
+
+    fn _batch_tags() -> Tags {
+        let mut tags = HashMap::new();
+        tags.insert("tag_name".to_string(), "tag_value".to_string());
+        tags
+    }
+
+    fn _failed_batch_operations() -> Vec<RecordOperation> {
+        let mut operations = _batch_operations();
+        operations.push(RecordOperation::Update { type_: "type".to_string(), id: "unknown".to_string(), value: "value".to_string() });
+        operations
+    }
+
+    fn _check_batch_rolled_back(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        let res = wallet_service.get_record(wallet_handle, "type", "name1", &_fetch_options(false, true, false));
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let record = wallet_service.get_record(wallet_handle, "type", "name2", &_fetch_options(false, true, true)).unwrap();
+        assert_eq!("value2", record.get_value().unwrap());
+        assert_eq!(&HashMap::new(), record.get_tags().unwrap());
+    }
+
     /**
      * Add tags tests
      */
@@ -1753,6 +2500,108 @@ mod tests {
         assert!(search.fetch_next_record().unwrap().is_none());
     }
 
+    #[test]
+    fn wallet_service_search_records_works_for_sorting_paging_and_projection() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_sorting_paging_and_projection");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_sorting_paging_and_projection"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_sorting_paging_and_projection"), &RAW_CREDENTIAL).unwrap();
+
+            _check_sorting_paging_and_projection(&wallet_service, wallet_handle);
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_sorting_paging_and_projection");
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_sorting_paging_and_projection_for_plugged_wallet() {
+        _cleanup("wallet_service_search_records_works_for_sorting_paging_and_projection_for_plugged_wallet");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        _check_sorting_paging_and_projection(&wallet_service, wallet_handle);
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_sorting_paging_and_projection_for_inmem_wallet() {
+        let wallet_service = WalletService::new();
+
+        let config = _config_inmem_storage("wallet_service_search_records_works_for_sorting_paging_and_projection_for_inmem_wallet");
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        _check_sorting_paging_and_projection(&wallet_service, wallet_handle);
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_exist_ilike_and_numeric_operators() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_exist_ilike_and_numeric_operators");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_exist_ilike_and_numeric_operators"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_exist_ilike_and_numeric_operators"), &RAW_CREDENTIAL).unwrap();
+
+            for (id, age, name) in [("key1", "9", "Alice"), ("key2", "10", "alex"), ("key3", "-3", "Bob"), ("key4", "ten", "ALINA")].iter() {
+                let mut tags = HashMap::new();
+                tags.insert("~age".to_string(), age.to_string());
+                tags.insert("~name".to_string(), name.to_string());
+                wallet_service.add_record(wallet_handle, "type", id, "value", &tags).unwrap();
+            }
+            wallet_service.add_record(wallet_handle, "type", "key5", "value", &_tags("tag_value")).unwrap();
+
+            let fetch_ids = |query: serde_json::Value| -> Vec<String> {
+                let mut search = wallet_service.search_records(wallet_handle, "type", &query.to_string(), &_fetch_options(false, false, false)).unwrap();
+                let mut ids = Vec::new();
+
+                while let Some(record) = search.fetch_next_record().unwrap() {
+                    ids.push(record.get_id().to_string());
+                }
+
+                ids.sort();
+                ids
+            };
+
+            assert_eq!(vec!["key1", "key2", "key3", "key4"], fetch_ids(json!({"~age": {"$exist": true}})));
+            assert_eq!(vec!["key5"], fetch_ids(json!({"~age": {"$exist": false}})));
+            assert_eq!(vec!["key5"], fetch_ids(json!({"tag1": {"$exist": true}})));
+
+            // lexicographic comparison for string value
+            assert_eq!(vec!["key1", "key4"], fetch_ids(json!({"~age": {"$gt": "2"}})));
+            assert_eq!(vec!["key2"], fetch_ids(json!({"~age": {"$gt": 9}})));
+            assert_eq!(vec!["key1", "key2"], fetch_ids(json!({"~age": {"$gte": 9}})));
+            assert_eq!(vec!["key1", "key3"], fetch_ids(json!({"~age": {"$lt": 10}})));
+            assert_eq!(vec!["key3"], fetch_ids(json!({"~age": {"$lte": -3}})));
+
+            assert_eq!(vec!["key1", "key2", "key4"], fetch_ids(json!({"~name": {"$ilike": "al%"}})));
+            assert_eq!(vec!["key4"], fetch_ids(json!({"~name": {"$ilike": "%LIN_"}})));
+
+            let res = wallet_service.search_records(wallet_handle, "type", &json!({"tag1": {"$gt": 1}}).to_string(), &_fetch_options(false, false, false));
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+
+            let res = wallet_service.search_records(wallet_handle, "type", &json!({"tag1": {"$ilike": "tag%"}}).to_string(), &_fetch_options(false, false, false));
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_exist_ilike_and_numeric_operators");
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_sorting_by_encrypted_tag() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_sorting_by_encrypted_tag");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_sorting_by_encrypted_tag"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_sorting_by_encrypted_tag"), &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.search_records(wallet_handle, "type", "{}", &json!({"sortBy": "tag1"}).to_string());
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_sorting_by_encrypted_tag");
+    }
+
     /**
         Key rotation test
     */
@@ -1791,6 +2640,43 @@ mod tests {
         test::cleanup_wallet("wallet_service_key_rotation");
     }
 
+    #[test]
+    fn wallet_service_rekey_wallet_works() {
+        test::cleanup_wallet("wallet_service_rekey_wallet_works");
+        {
+            let config: &Config = &_config("wallet_service_rekey_wallet_works");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let new_kdd = KeyDerivationData::from_passphrase_with_new_salt("my_new_key", &KeyDerivationMethod::ARGON2I_MOD);
+            let new_master_key = new_kdd.calc_master_key().unwrap();
+            wallet_service.rekey_wallet(wallet_handle, (&new_kdd, &new_master_key)).unwrap();
+
+            // wallet stays usable after rekey
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_moderate()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_rekey_wallet_works");
+    }
+
+    #[test]
+    fn wallet_service_rekey_wallet_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.rekey_wallet(INVALID_WALLET_HANDLE, (&RAW_KDD, &RAW_MASTER_KEY));
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
     #[test]
     fn wallet_service_key_rotation_for_rekey_interactive_method() {
         test::cleanup_wallet("wallet_service_key_rotation_for_rekey_interactive_method");
@@ -1861,6 +2747,38 @@ mod tests {
         test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
     }
 
+    #[test]
+    fn wallet_service_key_rotation_works_for_key_rotated_by_another_connection() {
+        test::cleanup_wallet("wallet_service_key_rotation_works_for_key_rotated_by_another_connection");
+        {
+            let config = Config {
+                id: "wallet_service_key_rotation_works_for_key_rotated_by_another_connection".to_string(),
+                storage_type: None,
+                storage_config: Some(json!({"locking": "shared"})),
+            };
+
+            let wallet_service1 = WalletService::new();
+            let wallet_service2 = WalletService::new();
+            wallet_service1.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+            let (wallet_handle, key_data, rekey_data) = wallet_service1.open_wallet_prepare(&config, &_rekey_credentials_raw()).unwrap();
+
+            // another connection rotates the key while the first one derives it
+            let wallet_handle2 = wallet_service2.open_wallet(&config, &_rekey_credentials_raw()).unwrap();
+            wallet_service2.close_wallet(wallet_handle2).unwrap();
+
+            let master_key = key_data.calc_master_key().unwrap();
+            let rekey = rekey_data.unwrap().calc_master_key().unwrap();
+            let res = wallet_service1.open_wallet_continue(wallet_handle, (&master_key, Some(&rekey)));
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // key set by another connection stays valid
+            let wallet_handle = wallet_service1.open_wallet(&config, &_credentials_for_new_key_raw()).unwrap();
+            wallet_service1.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_key_rotation_works_for_key_rotated_by_another_connection");
+    }
+
     fn remove_exported_wallet(export_config: &ExportConfig) -> &Path {
         let export_path = Path::new(&export_config.path);
         if export_path.exists() {
@@ -2179,6 +3097,311 @@ mod tests {
         _cleanup("wallet_service_export_import_returns_error_if_path_missing");
     }
 
+    #[test]
+    fn wallet_service_export_import_wallet_works_for_types_and_query() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_types_and_query");
+        let mut export_config = _export_config_raw("wallet_service_export_import_wallet_works_for_types_and_query");
+        export_config.options.types = Some(vec!["type1".to_string()]);
+        export_config.options.query = Some(json!({"tag1": "a"}));
+        {
+            let config: &Config = &_config("wallet_service_export_import_wallet_works_for_types_and_query");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &_tags("a")).unwrap();
+            wallet_service.add_record(wallet_handle, "type1", "key2", "value2", &_tags("b")).unwrap();
+            wallet_service.add_record(wallet_handle, "type2", "key3", "value3", &_tags("a")).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("wallet_service_export_import_wallet_works_for_types_and_query");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.delete_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type1", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            assert_eq!(&_tags("a"), record.get_tags().unwrap());
+
+            let res = wallet_service.get_record(wallet_handle, "type1", "key2", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type2", "key3", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_types_and_query");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_returns_error_for_query_without_types() {
+        test::cleanup_wallet("wallet_service_export_wallet_returns_error_for_query_without_types");
+        {
+            let config: &Config = &_config("wallet_service_export_wallet_returns_error_for_query_without_types");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let options = ExportOptions { query: Some(json!({"tag1": "a"})), ..ExportOptions::default() };
+
+            let (kdd, master_key) = _export_key_raw("wallet_service_export_wallet_returns_error_for_query_without_types");
+            let mut output: Vec<u8> = Vec::new();
+            let res = wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+        test::cleanup_wallet("wallet_service_export_wallet_returns_error_for_query_without_types");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_to_writer_works_for_marker() {
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker");
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_target");
+        let export_config = _export_config_raw("wallet_service_export_wallet_to_writer_works_for_marker");
+        {
+            let wallet_service = WalletService::new();
+
+            let config: &Config = &_config("wallet_service_export_wallet_to_writer_works_for_marker");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let target_config: &Config = &_config("wallet_service_export_wallet_to_writer_works_for_marker_target");
+            wallet_service.create_wallet(target_config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let target_wallet_handle = wallet_service.open_wallet(target_config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let options = ExportOptions { marker: Some("nightly".to_string()), ..ExportOptions::default() };
+            let (kdd, master_key) = _export_key_raw("wallet_service_export_wallet_to_writer_works_for_marker");
+
+            // The first export with the marker contains all records
+            let mut output: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key)).unwrap();
+
+            wallet_service.update_record_value(wallet_handle, "type", "key1", "value1_updated").unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key3", "value3", &HashMap::new()).unwrap();
+
+            // The next one contains only changed records
+            let mut output: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key)).unwrap();
+
+            remove_exported_wallet(&export_config);
+            fs::write(&export_config.path, &output).unwrap();
+
+            let import_config = _import_records_config(&export_config, ImportConflictPolicy::Fail);
+            _import_records(&wallet_service, target_wallet_handle, &import_config).unwrap();
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value1_updated", record.get_value().unwrap());
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key3", "{}").unwrap();
+            assert_eq!("value3", record.get_value().unwrap());
+
+            let res = wallet_service.get_record(target_wallet_handle, "type", "key2", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            // Marker records are never exported
+            let mut search = wallet_service.search_records(target_wallet_handle, export_import::EXPORT_MARKER_TYPE, "{}", &_fetch_options(true, true, true)).unwrap();
+            assert!(search.fetch_next_record().unwrap().is_none());
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker");
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_target");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records() {
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records");
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records_target");
+        let export_config = _export_config_raw("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records");
+        {
+            let wallet_service = WalletService::new();
+
+            let config: &Config = &_config("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let target_config: &Config = &_config("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records_target");
+            wallet_service.create_wallet(target_config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let target_wallet_handle = wallet_service.open_wallet(target_config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let options = ExportOptions { marker: Some("nightly".to_string()), ..ExportOptions::default() };
+            let (kdd, master_key) = _export_key_raw("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records");
+            let import_config = _import_records_config(&export_config, ImportConflictPolicy::Fail);
+
+            let mut output: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key)).unwrap();
+
+            remove_exported_wallet(&export_config);
+            fs::write(&export_config.path, &output).unwrap();
+            _import_records(&wallet_service, target_wallet_handle, &import_config).unwrap();
+
+            wallet_service.delete_record(wallet_handle, "type", "key2").unwrap();
+
+            // The next export contains the tombstone of the deleted record
+            let mut output: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key)).unwrap();
+
+            remove_exported_wallet(&export_config);
+            fs::write(&export_config.path, &output).unwrap();
+            _import_records(&wallet_service, target_wallet_handle, &import_config).unwrap();
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+
+            let res = wallet_service.get_record(target_wallet_handle, "type", "key2", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            // The tombstone is emitted only once
+            let mut output: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut output, &options, 0, (&kdd, &master_key)).unwrap();
+
+            let mut search = wallet_service.search_records(wallet_handle, export_import::EXPORT_MARKER_TYPE, "{}", &_fetch_options(true, true, true)).unwrap();
+            assert!(search.fetch_next_record().unwrap().is_some());
+            assert!(search.fetch_next_record().unwrap().is_none());
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records");
+        test::cleanup_wallet("wallet_service_export_wallet_to_writer_works_for_marker_and_deleted_records_target");
+    }
+
+    #[test]
+    fn wallet_service_import_records_works_for_conflict_policies() {
+        test::cleanup_wallet("wallet_service_import_records_works_for_conflict_policies");
+        test::cleanup_wallet("wallet_service_import_records_works_for_conflict_policies_target");
+        let export_config = _export_config_raw("wallet_service_import_records_works_for_conflict_policies");
+        {
+            let wallet_service = WalletService::new();
+
+            let config: &Config = &_config("wallet_service_import_records_works_for_conflict_policies");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &_tags("a")).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &_tags("a")).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("wallet_service_import_records_works_for_conflict_policies");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+
+            let target_config: &Config = &_config("wallet_service_import_records_works_for_conflict_policies_target");
+            wallet_service.create_wallet(target_config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let target_wallet_handle = wallet_service.open_wallet(target_config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(target_wallet_handle, "type", "key2", "other_value", &_tags("b")).unwrap();
+
+            let import_records = |on_conflict: ImportConflictPolicy| {
+                let import_config = _import_records_config(&export_config, on_conflict);
+                _import_records(&wallet_service, target_wallet_handle, &import_config)
+            };
+
+            let res = import_records(ImportConflictPolicy::Fail);
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            // Failed import leaves the wallet untouched including records preceding the conflicting one
+            let res = wallet_service.get_record(target_wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            import_records(ImportConflictPolicy::Skip).unwrap();
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key2", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("other_value", record.get_value().unwrap());
+            assert_eq!(&_tags("b"), record.get_tags().unwrap());
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+
+            import_records(ImportConflictPolicy::Overwrite).unwrap();
+
+            let record = wallet_service.get_record(target_wallet_handle, "type", "key2", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+            assert_eq!(&_tags("a"), record.get_tags().unwrap());
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_import_records_works_for_conflict_policies");
+        test::cleanup_wallet("wallet_service_import_records_works_for_conflict_policies_target");
+    }
+
+    #[test]
+    fn wallet_service_import_records_returns_error_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let export_config = _export_config_raw("wallet_service_import_records_returns_error_for_invalid_handle");
+        let import_config = _import_records_config(&export_config, ImportConflictPolicy::Fail);
+        let res = wallet_service.import_records_prepare(INVALID_WALLET_HANDLE, &import_config);
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    fn _import_records(wallet_service: &WalletService, wallet_handle: WalletHandle, import_config: &ImportRecordsConfig) -> IndyResult<()> {
+        let import = wallet_service.import_records_prepare(wallet_handle, import_config)?;
+        let import_key = import.key_derivation_data().calc_master_key()?;
+        wallet_service.import_records_continue(wallet_handle, import, &import_key, &import_config.on_conflict)
+    }
+
+    fn _import_records_config(export_config: &ExportConfig, on_conflict: ImportConflictPolicy) -> ImportRecordsConfig {
+        ImportRecordsConfig {
+            key: export_config.key.clone(),
+            path: export_config.path.clone(),
+            on_conflict,
+        }
+    }
+
+    fn _tags(value: &str) -> Tags {
+        let mut tags = HashMap::new();
+        tags.insert("tag1".to_string(), value.to_string());
+        tags
+    }
+
+    fn _check_sorting_paging_and_projection(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        for (id, order) in [("key1", "2"), ("key2", "3"), ("key3", "1")].iter() {
+            let mut tags = _tags("tag_value");
+            tags.insert("~order".to_string(), order.to_string());
+            wallet_service.add_record(wallet_handle, "type", id, "value", &tags).unwrap();
+        }
+        wallet_service.add_record(wallet_handle, "type", "key4", "value", &_tags("tag_value")).unwrap();
+
+        let fetch_ids = |options: serde_json::Value| -> Vec<String> {
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options.to_string()).unwrap();
+            let mut ids = Vec::new();
+
+            while let Some(record) = search.fetch_next_record().unwrap() {
+                ids.push(record.get_id().to_string());
+            }
+
+            ids
+        };
+
+        // record without the tag goes first
+        assert_eq!(vec!["key4", "key3", "key1", "key2"], fetch_ids(json!({"sortBy": "~order"})));
+        assert_eq!(vec!["key2", "key1", "key3", "key4"], fetch_ids(json!({"sortBy": "~order", "sortOrder": "desc"})));
+        assert_eq!(vec!["key1", "key2"], fetch_ids(json!({"sortBy": "~order", "offset": 2, "limit": 5})));
+        assert_eq!(vec!["key3"], fetch_ids(json!({"sortBy": "~order", "offset": 1, "limit": 1})));
+        assert_eq!(3, fetch_ids(json!({"offset": 1})).len());
+        assert!(fetch_ids(json!({"limit": 0})).is_empty());
+
+        let options = json!({"sortBy": "~order", "limit": 1, "retrieveTotalCount": true, "retrieveTags": true, "tagNames": ["~order"]}).to_string();
+        let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options).unwrap();
+        assert_eq!(Some(4), search.get_total_count().unwrap());
+
+        let record = search.fetch_next_record().unwrap().unwrap();
+        assert_eq!("key4", record.get_id());
+        assert_eq!(&HashMap::new(), record.get_tags().unwrap());
+
+        let options = json!({"sortBy": "~order", "offset": 1, "retrieveTags": true, "tagNames": ["~order"]}).to_string();
+        let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options).unwrap();
+
+        let record = search.fetch_next_record().unwrap().unwrap();
+        let mut expected_tags = HashMap::new();
+        expected_tags.insert("~order".to_string(), "1".to_string());
+        assert_eq!(&expected_tags, record.get_tags().unwrap());
+    }
+
     fn _fetch_options(type_: bool, value: bool, tags: bool) -> String {
         json!({
           "retrieveType": type_,
@@ -2211,6 +3434,14 @@ mod tests {
         }
     }
 
+    fn _config_inmem_storage(name: &str) -> Config {
+        Config {
+            id: name.to_string(),
+            storage_type: Some("in_memory".to_string()),
+            storage_config: None,
+        }
+    }
+
     fn _config_unknown(name: &str) -> Config {
         Config {
             id: name.to_string(),
@@ -2364,6 +3595,7 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            options: ExportOptions::default(),
         }
     }
 
@@ -2382,6 +3614,7 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            options: ExportOptions::default(),
         }
     }
 
@@ -2394,6 +3627,7 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            options: ExportOptions::default(),
         }
     }
 
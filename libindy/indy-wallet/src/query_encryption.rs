@@ -42,17 +42,7 @@ fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
             Ok(Operator::Like(encrypted_name, encrypted_value))
         }
         Query::In(name, values) => {
-            let ename = TagName::from(name.clone())?;
-            let ename = match ename {
-                TagName::EncryptedTagName(ref name) => {
-                    let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
-                    TagName::EncryptedTagName(encrypted_name)
-                }
-                TagName::PlainTagName(ref name) => {
-                    let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
-                    TagName::PlainTagName(encrypted_name)
-                }
-            };
+            let ename = encrypt_name(name.clone(), keys)?;
             let mut encrypted_values: Vec<TargetValue> = Vec::with_capacity(values.len());
 
             for value in values {
@@ -60,6 +50,15 @@ fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
             }
             Ok(Operator::In(ename, encrypted_values))
         }
+        Query::ILike(name, value) => {
+            let (encrypted_name, encrypted_value) = encrypt_name_value(name, value, keys)?;
+            Ok(Operator::ILike(encrypted_name, encrypted_value))
+        }
+        Query::Exist(name) => Ok(Operator::Exist(encrypt_name(name, keys)?)),
+        Query::NumGt(name, value) => Ok(Operator::NumGt(encrypt_plain_name(name, keys)?, value)),
+        Query::NumGte(name, value) => Ok(Operator::NumGte(encrypt_plain_name(name, keys)?, value)),
+        Query::NumLt(name, value) => Ok(Operator::NumLt(encrypt_plain_name(name, keys)?, value)),
+        Query::NumLte(name, value) => Ok(Operator::NumLte(encrypt_plain_name(name, keys)?, value)),
         Query::And(operators) => Ok(Operator::And(transform_list_operators(operators, keys)?)),
         Query::Or(operators) => Ok(Operator::Or(transform_list_operators(operators, keys)?)),
         Query::Not(boxed_operator) => Ok(Operator::Not(Box::new(transform(*boxed_operator, keys)?)))
@@ -77,6 +76,27 @@ fn transform_list_operators(operators: Vec<Query>, keys: &Keys) -> IndyResult<Ve
     Ok(transformed)
 }
 
+fn encrypt_name(name: String, keys: &Keys) -> IndyResult<TagName> {
+    match TagName::from(name)? {
+        TagName::EncryptedTagName(ref name) => {
+            let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
+            Ok(TagName::EncryptedTagName(encrypted_name))
+        }
+        TagName::PlainTagName(ref name) => {
+            let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
+            Ok(TagName::PlainTagName(encrypted_name))
+        }
+    }
+}
+
+// Values of encrypted tags can't be compared as numbers
+fn encrypt_plain_name(name: String, keys: &Keys) -> IndyResult<TagName> {
+    match encrypt_name(name, keys)? {
+        TagName::EncryptedTagName(_) => Err(err_msg(IndyErrorKind::WalletQueryError, "Numeric comparison can be used only with plaintext tags")),
+        plain_name => Ok(plain_name)
+    }
+}
+
 // Encrypts a single tag name, tag value pair.
 // If the tag name is EncryptedTagName enum variant, encrypts both the tag name and the tag value
 // If the tag name is PlainTagName enum variant, encrypts only the tag name
@@ -4,6 +4,7 @@ extern crate sodiumoxide;
 use std;
 use std::fs;
 use std::rc::Rc;
+use std::time::Duration;
 
 use rusqlite;
 use serde_json;
@@ -12,7 +13,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::environment;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{EncryptedValue, StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType, project_tags};
 use super::super::{RecordOptions, SearchOptions};
 
 use self::owning_ref::OwningHandle;
@@ -21,8 +22,10 @@ mod query;
 mod transaction;
 
 const _SQLITE_DB: &str = "sqlite.db";
+const _DEFAULT_BUSY_TIMEOUT: u64 = 5000;
 const _PLAIN_TAGS_QUERY: &str = "SELECT name, value from tags_plaintext where item_id = ?";
 const _ENCRYPTED_TAGS_QUERY: &str = "SELECT name, value from tags_encrypted where item_id = ?";
+const _TAGS_TABLES: [&str; 2] = ["tags_encrypted", "tags_plaintext"];
 const _CREATE_SCHEMA: &str = "
     PRAGMA locking_mode=EXCLUSIVE;
     PRAGMA foreign_keys=ON;
@@ -141,6 +144,8 @@ struct SQLiteStorageIterator {
             Box<rusqlite::Rows<'static>>>>,
     tag_retriever: Option<TagRetrieverOwned>,
     options: RecordOptions,
+    // tags to retrieve if not all of them are requested
+    tag_names: Option<Vec<TagName>>,
     total_count: Option<usize>,
 }
 
@@ -150,11 +155,13 @@ impl SQLiteStorageIterator {
            args: &[&dyn rusqlite::types::ToSql],
            options: RecordOptions,
            tag_retriever: Option<TagRetrieverOwned>,
+           tag_names: Option<Vec<TagName>>,
            total_count: Option<usize>) -> IndyResult<SQLiteStorageIterator> {
         let mut iter = SQLiteStorageIterator {
             rows: None,
             tag_retriever,
             options,
+            tag_names,
             total_count,
         };
 
@@ -192,7 +199,14 @@ impl StorageIterator for SQLiteStorageIterator {
 
                 let tags = if self.options.retrieve_tags {
                     match self.tag_retriever {
-                        Some(ref mut tag_retriever) => Some(tag_retriever.retrieve(row.get(0)?)?),
+                        Some(ref mut tag_retriever) => {
+                            let tags = tag_retriever.retrieve(row.get(0)?)?;
+
+                            match self.tag_names {
+                                Some(ref tag_names) => Some(project_tags(tags, tag_names)),
+                                None => Some(tags)
+                            }
+                        }
                         None => return Err(err_msg(IndyErrorKind::InvalidState, "Fetch tags option set and tag retriever is None"))
                     }
                 } else {
@@ -219,11 +233,31 @@ impl StorageIterator for SQLiteStorageIterator {
 #[derive(Deserialize, Debug)]
 struct Config {
     path: Option<String>,
+    #[serde(default)]
+    locking: Locking,
+    // milliseconds to wait for the lock held by another connection in shared mode
+    busy_timeout: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Locking {
+    // wallet file is used by a single process
+    Exclusive,
+    // wallet file can be used by several processes at the same time
+    Shared,
+}
+
+impl Default for Locking {
+    fn default() -> Self {
+        Locking::Exclusive
+    }
 }
 
 #[derive(Debug)]
 struct SQLiteStorage {
     conn: Rc<rusqlite::Connection>,
+    locking: Locking,
 }
 
 pub struct SQLiteStorageType {}
@@ -236,7 +270,7 @@ impl SQLiteStorageType {
 
     fn _db_path(id: &str, config: Option<&Config>) -> std::path::PathBuf {
         let mut path = match config {
-            Some(Config { path: Some(ref path) }) => std::path::PathBuf::from(path),
+            Some(Config { path: Some(ref path), .. }) => std::path::PathBuf::from(path),
             _ => environment::wallet_home_path()
         };
 
@@ -349,107 +383,33 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
-            .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
-
-        let id = match res {
-            Ok(entity) => entity,
-            Err(err) => return Err(IndyError::from(err))
-        };
-
-        if !tags.is_empty() {
-            let mut stmt_e = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut stmt_p = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
-                };
-            }
-        }
-
+        let tx: transaction::Transaction = self._begin_write()?;
+        _add(&tx, type_, id, value, tags)?;
         tx.commit()?;
         Ok(())
     }
 
     fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
-        let res = self.conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
-            .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
-
-        match res {
-            Ok(1) => Ok(()),
-            Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
-            Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
-            Err(err) => Err(err.into()),
-        }
+        _update(&self.conn, type_, id, value)
     }
 
     fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-
-        let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        if !tags.is_empty() {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
-                };
-            }
-        }
+        let tx: transaction::Transaction = self._begin_write()?;
+        _add_tags(&tx, type_, id, tags)?;
         tx.commit()?;
-
         Ok(())
     }
 
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-
-        let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
-        tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
-
-        if !tags.is_empty() {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
-                };
-            }
-        }
+        let tx: transaction::Transaction = self._begin_write()?;
+        _update_tags(&tx, type_, id, tags)?;
         tx.commit()?;
-
         Ok(())
     }
 
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
-        let item_id: i64 = self.conn.prepare_cached("SELECT id FROM items WHERE type =?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        {
-            let mut enc_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")?;
-            let mut plain_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")?;
-
-            for tag_name in tag_names {
-                match *tag_name {
-                    TagName::OfEncrypted(ref tag_name) => enc_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
-                    TagName::OfPlain(ref tag_name) => plain_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
-                };
-            }
-        }
-
+        let tx: transaction::Transaction = self._begin_write()?;
+        _delete_tags(&tx, type_, id, tag_names)?;
         tx.commit()?;
         Ok(())
     }
@@ -481,16 +441,29 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
-        let row_count = self.conn.execute(
-            "DELETE FROM items where type = ?1 AND name = ?2",
-            &[&type_.to_vec(), &id.to_vec()],
-        )?;
+        _delete(&self.conn, type_, id)
+    }
 
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    ///
+    /// applies all operations in a single SQLite transaction.
+    /// Nothing is changed if any of operations fails.
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let tx: transaction::Transaction = self._begin_write()?;
+
+        for operation in operations {
+            match *operation {
+                StorageOperation::Add(ref type_, ref id, ref value, ref tags) => _add(&tx, type_, id, value, tags),
+                StorageOperation::Update(ref type_, ref id, ref value) => _update(&tx, type_, id, value),
+                StorageOperation::AddTags(ref type_, ref id, ref tags) => _add_tags(&tx, type_, id, tags),
+                StorageOperation::UpdateTags(ref type_, ref id, ref tags) => _update_tags(&tx, type_, id, tags),
+                StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => _delete_tags(&tx, type_, id, tag_names),
+                StorageOperation::Delete(ref type_, ref id) => _delete(&tx, type_, id),
+            }?;
         }
+
+        tx.commit()?;
+        Ok(())
     }
 
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -506,6 +479,22 @@ impl WalletStorage for SQLiteStorage {
         Ok(())
     }
 
+    fn update_storage_metadata(&self, update: &dyn Fn(&[u8]) -> IndyResult<Vec<u8>>) -> IndyResult<()> {
+        let tx: transaction::Transaction = self._begin_write()?;
+
+        let metadata: Vec<u8> = tx.query_row(
+            "SELECT value FROM metadata",
+            rusqlite::NO_PARAMS,
+            |row| { row.get(0) },
+        )?;
+
+        let metadata = update(&metadata)?;
+
+        tx.execute("UPDATE metadata SET value = ?1", &[&metadata])?;
+        tx.commit()?;
+        Ok(())
+    }
+
     fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
         let statement = self._prepare_statement("SELECT id, name, value, key, type FROM items;")?;
 
@@ -516,7 +505,7 @@ impl WalletStorage for SQLiteStorage {
         };
 
         let tag_retriever = Some(TagRetriever::new_owned(self.conn.clone())?);
-        let storage_iterator = SQLiteStorageIterator::new(Some(statement), &[], fetch_options, tag_retriever, None)?;
+        let storage_iterator = SQLiteStorageIterator::new(Some(statement), &[], fetch_options, tag_retriever, None, None)?;
 
         Ok(Box::new(storage_iterator))
     }
@@ -552,7 +541,22 @@ impl WalletStorage for SQLiteStorage {
                 retrieve_type: search_options.retrieve_type,
             };
 
-            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, options)?;
+            let sort_by = match search_options.sort_by {
+                Some(ref sort_by) => match TagName::from_search_option(sort_by)? {
+                    TagName::OfPlain(name) => Some(name),
+                    TagName::OfEncrypted(_) => return Err(err_msg(IndyErrorKind::WalletQueryError, "Records can be sorted only by plaintext tag"))
+                },
+                None => None
+            };
+
+            let tag_names = match search_options.tag_names {
+                Some(ref tag_names) => Some(tag_names.iter()
+                    .map(|tag_name| TagName::from_search_option(tag_name))
+                    .collect::<IndyResult<Vec<TagName>>>()?),
+                None => None
+            };
+
+            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, sort_by.as_ref(), &search_options)?;
 
             let statement = self._prepare_statement(&query_string)?;
             let tag_retriever = if fetch_options.retrieve_tags {
@@ -560,10 +564,10 @@ impl WalletStorage for SQLiteStorage {
             } else {
                 None
             };
-            let storage_iterator = SQLiteStorageIterator::new(Some(statement), &query_arguments, fetch_options, tag_retriever, total_count)?;
+            let storage_iterator = SQLiteStorageIterator::new(Some(statement), &query_arguments, fetch_options, tag_retriever, tag_names, total_count)?;
             Ok(Box::new(storage_iterator))
         } else {
-            let storage_iterator = SQLiteStorageIterator::new(None, &[], RecordOptions::default(), None, total_count)?;
+            let storage_iterator = SQLiteStorageIterator::new(None, &[], RecordOptions::default(), None, None, total_count)?;
             Ok(Box::new(storage_iterator))
         }
     }
@@ -571,9 +575,51 @@ impl WalletStorage for SQLiteStorage {
     fn close(&mut self) -> IndyResult<()> {
         Ok(())
     }
+
+    ///
+    /// Tags can lose their records only if foreign keys have been disabled
+    /// while the records were deleted, e.g. by an external tool.
+    ///
+    fn check_orphaned_tags(&self, repair: bool) -> IndyResult<usize> {
+        let mut count = 0;
+
+        for table in &_TAGS_TABLES {
+            let orphaned: i64 = self.conn.query_row(
+                &format!("SELECT COUNT(*) FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table),
+                rusqlite::NO_PARAMS,
+                |row| row.get(0),
+            )?;
+
+            count += orphaned as usize;
+        }
+
+        if repair && count > 0 {
+            let tx: transaction::Transaction = self._begin_write()?;
+
+            for table in &_TAGS_TABLES {
+                tx.execute(&format!("DELETE FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table), rusqlite::NO_PARAMS)?;
+            }
+
+            tx.commit()?;
+        }
+
+        Ok(count)
+    }
 }
 
 impl SQLiteStorage {
+    // In shared mode write transactions take the lock at the beginning,
+    // otherwise upgrading the read lock may fail with SQLITE_BUSY without waiting for the busy timeout
+    // if another process has committed in the meantime.
+    fn _begin_write(&self) -> IndyResult<transaction::Transaction> {
+        let behavior = match self.locking {
+            Locking::Exclusive => rusqlite::TransactionBehavior::Deferred,
+            Locking::Shared => rusqlite::TransactionBehavior::Immediate,
+        };
+
+        Ok(transaction::Transaction::new(&self.conn, behavior)?)
+    }
+
     fn _prepare_statement(&self, sql: &str) -> IndyResult<OwningHandle<Rc<rusqlite::Connection>, Box<rusqlite::Statement<'static>>>> {
         OwningHandle::try_new(self.conn.clone(), |conn| {
             unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(IndyError::from)
@@ -581,6 +627,112 @@ impl SQLiteStorage {
     }
 }
 
+fn _add(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+    let res = conn.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
+        .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
+
+    let id = match res {
+        Ok(entity) => entity,
+        Err(err) => return Err(IndyError::from(err))
+    };
+
+    if !tags.is_empty() {
+        let mut stmt_e = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut stmt_p = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn _update(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+    let res = conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
+        .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
+
+    match res {
+        Ok(1) => Ok(()),
+        Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
+        Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn _add_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+    let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
+        .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+    if !tags.is_empty() {
+        let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn _update_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+    let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
+        .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+    conn.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+    conn.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+
+    if !tags.is_empty() {
+        let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn _delete_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+    let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type =?1 AND name = ?2")?
+        .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+    let mut enc_tag_delete_stmt = conn.prepare_cached("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")?;
+    let mut plain_tag_delete_stmt = conn.prepare_cached("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")?;
+
+    for tag_name in tag_names {
+        match *tag_name {
+            TagName::OfEncrypted(ref tag_name) => enc_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
+            TagName::OfPlain(ref tag_name) => plain_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
+        };
+    }
+
+    Ok(())
+}
+
+fn _delete(conn: &rusqlite::Connection, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+    let row_count = conn.execute(
+        "DELETE FROM items where type = ?1 AND name = ?2",
+        &[&type_.to_vec(), &id.to_vec()],
+    )?;
+
+    if row_count == 1 {
+        Ok(())
+    } else {
+        Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    }
+}
 
 impl WalletStorageType for SQLiteStorageType {
     ///
@@ -725,6 +877,15 @@ impl WalletStorageType for SQLiteStorageType {
 
         let conn = rusqlite::Connection::open(db_file_path.as_path())?;
 
+        let locking = config.as_ref().map(|config| config.locking).unwrap_or_default();
+
+        if locking == Locking::Shared {
+            let busy_timeout = config.as_ref().and_then(|config| config.busy_timeout).unwrap_or(_DEFAULT_BUSY_TIMEOUT);
+
+            conn.busy_timeout(Duration::from_millis(busy_timeout))?;
+            conn.execute_batch("PRAGMA locking_mode = NORMAL")?;
+        }
+
         // set journal mode to WAL, because it provides better performance.
         let journal_mode: String = conn.query_row(
             "PRAGMA journal_mode = WAL",
@@ -732,13 +893,18 @@ impl WalletStorageType for SQLiteStorageType {
             |row| { row.get(0) },
         )?;
 
+        // readers and writer from different processes don't block each other only in WAL mode.
+        if locking == Locking::Shared && journal_mode.to_lowercase() != "wal" {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Shared locking requires WAL journal mode, but {} is used", journal_mode)));
+        }
+
         // if journal mode is set to WAL, set synchronous to FULL for safety reasons.
         // (synchronous = NORMAL with journal_mode = WAL does not guaranties durability).
         if journal_mode.to_lowercase() == "wal" {
             conn.execute("PRAGMA synchronous = FULL", rusqlite::NO_PARAMS)?;
         }
 
-        Ok(Box::new(SQLiteStorage { conn: Rc::new(conn) }))
+        Ok(Box::new(SQLiteStorage { conn: Rc::new(conn), locking }))
     }
 }
 
@@ -867,6 +1033,78 @@ mod tests {
         assert_kind!(IndyErrorKind::WalletNotFound, res);
     }
 
+    #[test]
+    fn sqlite_storage_type_open_works_for_shared_locking() {
+        _cleanup("sqlite_storage_type_open_works_for_shared_locking");
+        {
+            let storage_type = SQLiteStorageType::new();
+            storage_type.create_storage("sqlite_storage_type_open_works_for_shared_locking", None, None, &_metadata()).unwrap();
+
+            let config = json!({"locking": "shared", "busy_timeout": 1000}).to_string();
+            let storage1 = storage_type.open_storage("sqlite_storage_type_open_works_for_shared_locking", Some(&config), None).unwrap();
+            let storage2 = storage_type.open_storage("sqlite_storage_type_open_works_for_shared_locking", Some(&config), None).unwrap();
+
+            storage1.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let record = storage2.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+            storage2.batch(&[
+                StorageOperation::Update(_type1(), _id1(), _value2()),
+                StorageOperation::Add(_type1(), _id2(), _value2(), _new_tags()),
+            ]).unwrap();
+
+            let record = storage1.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let record = storage1.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+            assert_eq!(storage1.get_storage_metadata().unwrap(), storage2.get_storage_metadata().unwrap());
+        }
+        _cleanup("sqlite_storage_type_open_works_for_shared_locking");
+    }
+
+    #[test]
+    fn sqlite_storage_update_storage_metadata_works_for_shared_locking() {
+        _cleanup("sqlite_storage_update_storage_metadata_works_for_shared_locking");
+        {
+            let storage_type = SQLiteStorageType::new();
+            storage_type.create_storage("sqlite_storage_update_storage_metadata_works_for_shared_locking", None, None, &_metadata()).unwrap();
+
+            let config = json!({"locking": "shared", "busy_timeout": 100}).to_string();
+            let storage1 = storage_type.open_storage("sqlite_storage_update_storage_metadata_works_for_shared_locking", Some(&config), None).unwrap();
+            let storage2 = storage_type.open_storage("sqlite_storage_update_storage_metadata_works_for_shared_locking", Some(&config), None).unwrap();
+
+            storage1.update_storage_metadata(&|metadata| {
+                assert_eq!(metadata.to_vec(), _metadata());
+
+                // metadata is read inside the write transaction, so another connection can't change it meanwhile
+                assert!(storage2.set_storage_metadata(&[1, 1, 1]).is_err());
+
+                Ok(vec![2, 2, 2])
+            }).unwrap();
+
+            assert_eq!(storage2.get_storage_metadata().unwrap(), vec![2, 2, 2]);
+        }
+        _cleanup("sqlite_storage_update_storage_metadata_works_for_shared_locking");
+    }
+
+    #[test]
+    fn sqlite_storage_type_open_works_for_unknown_locking() {
+        _cleanup("sqlite_storage_type_open_works_for_unknown_locking");
+
+        let storage_type = SQLiteStorageType::new();
+        storage_type.create_storage("sqlite_storage_type_open_works_for_unknown_locking", None, None, &_metadata()).unwrap();
+
+        let res = storage_type.open_storage("sqlite_storage_type_open_works_for_unknown_locking", Some(r#"{"locking": "unknown"}"#), None);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        _cleanup("sqlite_storage_type_open_works_for_unknown_locking");
+    }
+
     #[test]
     fn sqlite_storage_add_works_for_is_802() {
         _cleanup("sqlite_storage_add_works_for_is_802");
@@ -1314,6 +1552,80 @@ mod tests {
         _cleanup("sqlite_storage_delete_tags_works_for_non_existing_id");
     }
 
+    #[test]
+    fn sqlite_storage_batch_works() {
+        _cleanup("sqlite_storage_batch_works");
+        {
+            let storage = _storage("sqlite_storage_batch_works");
+            storage.add(&_type1(), &_id2(), &_value1(), &_tags()).unwrap();
+
+            storage.batch(&[
+                StorageOperation::Add(_type1(), _id1(), _value1(), _tags()),
+                StorageOperation::Update(_type1(), _id1(), _value2()),
+                StorageOperation::UpdateTags(_type1(), _id1(), _new_tags()),
+                StorageOperation::Delete(_type1(), _id2()),
+            ]).unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+            let res = storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sqlite_storage_batch_works");
+    }
+
+    #[test]
+    fn sqlite_storage_batch_works_for_failed_operation() {
+        _cleanup("sqlite_storage_batch_works_for_failed_operation");
+        {
+            let storage = _storage("sqlite_storage_batch_works_for_failed_operation");
+            storage.add(&_type1(), &_id2(), &_value1(), &_tags()).unwrap();
+
+            let res = storage.batch(&[
+                StorageOperation::Add(_type1(), _id1(), _value1(), _tags()),
+                StorageOperation::Delete(_type1(), _id2()),
+                StorageOperation::Update(_type2(), _id1(), _value2()),
+            ]);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        }
+        _cleanup("sqlite_storage_batch_works_for_failed_operation");
+    }
+
+    #[test]
+    fn sqlite_storage_check_orphaned_tags_works() {
+        _cleanup("sqlite_storage_check_orphaned_tags_works");
+        {
+            let storage_type = SQLiteStorageType::new();
+            storage_type.create_storage("sqlite_storage_check_orphaned_tags_works", None, None, &_metadata()).unwrap();
+
+            {
+                // foreign keys are disabled by default for new connections
+                let conn = rusqlite::Connection::open(SQLiteStorageType::_db_path("sqlite_storage_check_orphaned_tags_works", None)).unwrap();
+                conn.execute("INSERT INTO tags_plaintext (item_id, name, value) VALUES (100, ?1, 'value')", &[&vec![1, 2, 3]]).unwrap();
+            }
+
+            let storage = storage_type.open_storage("sqlite_storage_check_orphaned_tags_works", None, None).unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            assert_eq!(1, storage.check_orphaned_tags(false).unwrap());
+            assert_eq!(1, storage.check_orphaned_tags(true).unwrap());
+            assert_eq!(0, storage.check_orphaned_tags(false).unwrap());
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        }
+        _cleanup("sqlite_storage_check_orphaned_tags_works");
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
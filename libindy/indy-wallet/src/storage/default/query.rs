@@ -1,26 +1,46 @@
 use indy_api_types::errors::prelude::*;
 use rusqlite::types::ToSql;
 use crate::language::{Operator, TagName, TargetValue};
+use crate::{SearchOptions, SortOrder};
 
 
 // Translates Wallet Query Language to SQL
 // WQL input is provided as a reference to a top level Operator
+// sort_by is the encrypted name of the plaintext tag to order records by
 // Result is a tuple of query string and query arguments
-pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&str>) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, sort_by: Option<&'a Vec<u8>>, options: &SearchOptions) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
+    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i".to_string();
+
+    if let Some(sort_by) = sort_by {
+        // outer join keeps records without the tag, their value is NULL that goes first in ascending order
+        query_string.push_str(" LEFT JOIN tags_plaintext as s ON s.item_id = i.id AND s.name = ?");
+        arguments.push(sort_by);
+    }
+
+    query_string.push_str(" WHERE i.type = ?");
     arguments.push(class);
+
     let clause_string = operator_to_sql(op, &mut arguments)?;
-    const BASE: &str = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?";
     if !clause_string.is_empty() {
-        let mut query_string = String::with_capacity(BASE.len() + 5 + clause_string.len());
-        query_string.push_str(BASE);
         query_string.push_str(" AND ");
         query_string.push_str(&clause_string);
-        Ok((query_string, arguments))
-    } else {
-        Ok((BASE.to_string(), arguments))
     }
 
+    if sort_by.is_some() {
+        query_string.push_str(match options.sort_order {
+            SortOrder::Asc => " ORDER BY s.value ASC, i.id ASC",
+            SortOrder::Desc => " ORDER BY s.value DESC, i.id DESC",
+        });
+    }
+
+    if options.is_paged() {
+        // negative limit means no limit in SQLite
+        let limit = options.limit.map(|limit| limit as i64).unwrap_or(-1);
+        query_string.push_str(&format!(" LIMIT {} OFFSET {}", limit, options.offset.unwrap_or(0)));
+    }
+
+    Ok((query_string, arguments))
 }
 
 
@@ -47,6 +67,12 @@ fn operator_to_sql<'a>(op: &'a Operator, arguments: &mut Vec<&'a dyn ToSql>) ->
         Operator::Lte(ref tag_name, ref target_value) => lte_to_sql(tag_name, target_value, arguments),
         Operator::Like(ref tag_name, ref target_value) => like_to_sql(tag_name, target_value, arguments),
         Operator::In(ref tag_name, ref target_values) => in_to_sql(tag_name, target_values, arguments),
+        Operator::ILike(ref tag_name, ref target_value) => ilike_to_sql(tag_name, target_value, arguments),
+        Operator::Exist(ref tag_name) => exist_to_sql(tag_name, arguments),
+        Operator::NumGt(ref tag_name, ref target_value) => numeric_to_sql(tag_name, ">", target_value, arguments),
+        Operator::NumGte(ref tag_name, ref target_value) => numeric_to_sql(tag_name, ">=", target_value, arguments),
+        Operator::NumLt(ref tag_name, ref target_value) => numeric_to_sql(tag_name, "<", target_value, arguments),
+        Operator::NumLte(ref tag_name, ref target_value) => numeric_to_sql(tag_name, "<=", target_value, arguments),
         Operator::And(ref suboperators) => and_to_sql(suboperators, arguments),
         Operator::Or(ref suboperators) => or_to_sql(suboperators, arguments),
         Operator::Not(ref suboperator) => not_to_sql(suboperator, arguments),
@@ -148,6 +174,47 @@ fn like_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Ve
 }
 
 
+fn ilike_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
+            arguments.push(queried_name);
+            arguments.push(queried_value);
+            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = ? AND lower(value) LIKE lower(?)))".to_string())
+        },
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for $ilike operator"))
+    }
+}
+
+
+fn exist_to_sql<'a>(name: &'a TagName, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
+    match *name {
+        TagName::PlainTagName(ref queried_name) => {
+            arguments.push(queried_name);
+            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = ?))".to_string())
+        },
+        TagName::EncryptedTagName(ref queried_name) => {
+            arguments.push(queried_name);
+            Ok("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = ?))".to_string())
+        },
+    }
+}
+
+
+// Only values that are integers in decimal notation are compared, others don't match
+fn numeric_to_sql<'a>(name: &'a TagName, op_str: &str, value: &'a i64, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
+    match *name {
+        TagName::PlainTagName(ref queried_name) => {
+            arguments.push(queried_name);
+            arguments.push(value);
+            Ok(format!("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = ? \
+                        AND (value GLOB '[0-9]*' OR value GLOB '-[0-9]*') AND substr(value, 2) NOT GLOB '*[^0-9]*' \
+                        AND CAST(value AS INTEGER) {} ?))", op_str))
+        },
+        TagName::EncryptedTagName(_) => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for numeric comparison"))
+    }
+}
+
+
 fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
     let mut in_string = String::new();
     match *name {
@@ -241,6 +308,52 @@ mod tests {
         ]);
         let query = Operator::Or(vec![condition_1, condition_2]);
         let class = vec![100,100,100];
-        let (_query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
+        let (_query, _arguments) = wql_to_sql(&class, &query, None, &SearchOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn numeric_comparison_for_encrypted_tag() {
+        let query = Operator::NumGt(TagName::EncryptedTagName(vec![1, 2, 3]), 5);
+        let class = vec![100, 100, 100];
+
+        let res = wql_to_sql(&class, &query, None, &SearchOptions::default());
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+
+    #[test]
+    fn exist_for_encrypted_and_plain_tags() {
+        let query = Operator::And(vec![
+            Operator::Exist(TagName::EncryptedTagName(vec![1, 2, 3])),
+            Operator::Exist(TagName::PlainTagName(vec![4, 5, 6])),
+        ]);
+        let class = vec![100, 100, 100];
+
+        let (query, arguments) = wql_to_sql(&class, &query, None, &SearchOptions::default()).unwrap();
+        assert!(query.contains("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = ?)) AND (i.id in (SELECT item_id FROM tags_plaintext WHERE name = ?))"));
+        assert_eq!(3, arguments.len());
+    }
+
+    #[test]
+    fn sorted_and_paged() {
+        let query = Operator::Eq(TagName::PlainTagName(vec![1, 2, 3]), TargetValue::Unencrypted("spam".to_string()));
+        let class = vec![100, 100, 100];
+        let sort_by = vec![4, 5, 6];
+        let options: SearchOptions = serde_json::from_str(r#"{"sortOrder": "desc", "offset": 10, "limit": 5}"#).unwrap();
+
+        let (query, arguments) = wql_to_sql(&class, &query, Some(&sort_by), &options).unwrap();
+        assert!(query.contains(" LEFT JOIN tags_plaintext as s ON s.item_id = i.id AND s.name = ? WHERE i.type = ?"));
+        assert!(query.ends_with(" ORDER BY s.value DESC, i.id DESC LIMIT 5 OFFSET 10"));
+        assert_eq!(4, arguments.len());
+    }
+
+    #[test]
+    fn offset_without_limit() {
+        let query = Operator::And(vec![]);
+        let class = vec![100, 100, 100];
+        let options: SearchOptions = serde_json::from_str(r#"{"offset": 10}"#).unwrap();
+
+        let (query, _arguments) = wql_to_sql(&class, &query, None, &options).unwrap();
+        assert!(!query.contains("ORDER BY"));
+        assert!(query.ends_with(" LIMIT -1 OFFSET 10"));
     }
 }
\ No newline at end of file
@@ -0,0 +1,885 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use rmp_serde;
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::wql::like_matches;
+
+use crate::language::{Operator, TagName as QueryTagName, TargetValue};
+
+use super::{EncryptedValue, StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType, project_tags};
+use super::super::{RecordOptions, SearchOptions, SortOrder};
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    // file the storage content is saved to on close and restored from on open
+    snapshot_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct InmemRecord {
+    value: EncryptedValue,
+    tags: Vec<Tag>,
+}
+
+// Storage content, it is already encrypted by the wallet so snapshots keep it encrypted at rest
+#[derive(Debug, Serialize, Deserialize)]
+struct InmemData {
+    metadata: Vec<u8>,
+    records: BTreeMap<(Vec<u8>, Vec<u8>), InmemRecord>,
+}
+
+impl InmemData {
+    fn new(metadata: &[u8]) -> InmemData {
+        InmemData {
+            metadata: metadata.to_vec(),
+            records: BTreeMap::new(),
+        }
+    }
+
+    fn load(path: &Path) -> IndyResult<InmemData> {
+        let snapshot = fs::read(path)?;
+
+        rmp_serde::from_slice(&snapshot)
+            .to_indy(IndyErrorKind::InvalidState, "Wallet snapshot is malformed")
+    }
+
+    fn save(&self, path: &Path) -> IndyResult<()> {
+        let snapshot = rmp_serde::to_vec(self)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet snapshot")?;
+
+        if let Some(parent) = path.parent() {
+            fs::DirBuilder::new()
+                .recursive(true)
+                .create(parent)?;
+        }
+
+        // snapshot is replaced at once so it isn't left truncated if writing fails
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, snapshot)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    fn add(&mut self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        let key = (type_.to_vec(), id.to_vec());
+
+        if self.records.contains_key(&key) {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Item already exists"));
+        }
+
+        self.records.insert(key, InmemRecord { value: value.clone(), tags: tags.to_vec() });
+        Ok(())
+    }
+
+    fn update(&mut self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        self._record_mut(type_, id, "Item to update not found")?.value = value.clone();
+        Ok(())
+    }
+
+    fn add_tags(&mut self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let record = self._record_mut(type_, id, "Item to add tags not found")?;
+
+        for tag in tags {
+            // tag with the same name is replaced as it happens in the default storage
+            record.tags.retain(|existing| _tag_name(existing) != _tag_name(tag));
+            record.tags.push(tag.clone());
+        }
+
+        Ok(())
+    }
+
+    fn update_tags(&mut self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self._record_mut(type_, id, "Item to update tags not found")?.tags = tags.to_vec();
+        Ok(())
+    }
+
+    fn delete_tags(&mut self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let record = self._record_mut(type_, id, "Item to delete tags not found")?;
+        record.tags.retain(|tag| !tag_names.iter().any(|name| name.is_name_of(tag)));
+        Ok(())
+    }
+
+    fn delete(&mut self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.records.remove(&(type_.to_vec(), id.to_vec()))
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    }
+
+    fn apply(&mut self, operation: &StorageOperation) -> IndyResult<()> {
+        match *operation {
+            StorageOperation::Add(ref type_, ref id, ref value, ref tags) => self.add(type_, id, value, tags),
+            StorageOperation::Update(ref type_, ref id, ref value) => self.update(type_, id, value),
+            StorageOperation::AddTags(ref type_, ref id, ref tags) => self.add_tags(type_, id, tags),
+            StorageOperation::UpdateTags(ref type_, ref id, ref tags) => self.update_tags(type_, id, tags),
+            StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => self.delete_tags(type_, id, tag_names),
+            StorageOperation::Delete(ref type_, ref id) => self.delete(type_, id),
+        }
+    }
+
+    fn _record_mut(&mut self, type_: &[u8], id: &[u8], msg: &str) -> IndyResult<&mut InmemRecord> {
+        self.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, msg.to_string()))
+    }
+}
+
+struct InmemStorageIterator {
+    records: std::vec::IntoIter<StorageRecord>,
+    total_count: Option<usize>,
+}
+
+impl InmemStorageIterator {
+    fn new(records: Vec<StorageRecord>, total_count: Option<usize>) -> InmemStorageIterator {
+        InmemStorageIterator {
+            records: records.into_iter(),
+            total_count,
+        }
+    }
+}
+
+impl StorageIterator for InmemStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        Ok(self.records.next())
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        Ok(self.total_count)
+    }
+}
+
+struct InmemStorage {
+    data: Rc<RefCell<InmemData>>,
+    snapshot_path: Option<PathBuf>,
+}
+
+///
+/// Storage keeping wallets in the process memory. Records live until the wallet is deleted or
+/// the process exits. If `snapshot_path` is set in the storage config the wallet is saved to this
+/// file on close and restored from it on open when it isn't in memory yet.
+///
+pub struct InmemStorageType {
+    storages: RefCell<HashMap<String, Rc<RefCell<InmemData>>>>,
+}
+
+impl InmemStorageType {
+    pub fn new() -> InmemStorageType {
+        InmemStorageType {
+            storages: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn _snapshot_path(config: Option<&str>) -> IndyResult<Option<PathBuf>> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        Ok(config.and_then(|config| config.snapshot_path).map(PathBuf::from))
+    }
+}
+
+impl WalletStorage for InmemStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = if options == "{}" {
+            RecordOptions::default()
+        } else {
+            serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?
+        };
+
+        let data = self.data.borrow();
+
+        let record = data.records.get(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        Ok(_storage_record(type_, id, record, &options, None))
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().add(type_, id, value, tags)
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        self.data.borrow_mut().update(type_, id, value)
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().add_tags(type_, id, tags)
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().update_tags(type_, id, tags)
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        self.data.borrow_mut().delete_tags(type_, id, tag_names)
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.data.borrow_mut().delete(type_, id)
+    }
+
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut data = self.data.borrow_mut();
+
+        // previous state of the touched records to roll back if an operation fails
+        let mut undo: Vec<((Vec<u8>, Vec<u8>), Option<InmemRecord>)> = Vec::new();
+
+        for operation in operations {
+            let (type_, id) = operation.type_and_id();
+            let key = (type_.to_vec(), id.to_vec());
+            let previous = data.records.get(&key).cloned();
+
+            if let Err(err) = data.apply(operation) {
+                for (key, previous) in undo.into_iter().rev() {
+                    match previous {
+                        Some(record) => data.records.insert(key, record),
+                        None => data.records.remove(&key),
+                    };
+                }
+                return Err(err);
+            }
+
+            undo.push((key, previous));
+        }
+
+        Ok(())
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        Ok(self.data.borrow().metadata.clone())
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.data.borrow_mut().metadata = metadata.to_vec();
+        Ok(())
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let fetch_options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let records = self.data.borrow().records.iter()
+            .map(|(&(ref type_, ref id), record)| _storage_record(type_, id, record, &fetch_options, None))
+            .collect();
+
+        Ok(Box::new(InmemStorageIterator::new(records, None)))
+    }
+
+    fn search(&self, type_: &[u8], query: &Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        check_operator(query)?;
+
+        let data = self.data.borrow();
+
+        let mut found: Vec<(&Vec<u8>, &InmemRecord)> = data.records.iter()
+            .filter(|&(&(ref record_type, _), record)| record_type.as_slice() == type_ && matches(query, &record.tags))
+            .map(|(&(_, ref id), record)| (id, record))
+            .collect();
+
+        let total_count = if search_options.retrieve_total_count { Some(found.len()) } else { None };
+
+        if !search_options.retrieve_records {
+            return Ok(Box::new(InmemStorageIterator::new(Vec::new(), total_count)));
+        }
+
+        if let Some(ref sort_by) = search_options.sort_by {
+            let sort_by = match TagName::from_search_option(sort_by)? {
+                TagName::OfPlain(name) => name,
+                TagName::OfEncrypted(_) => return Err(err_msg(IndyErrorKind::WalletQueryError, "Records can be sorted only by plaintext tag"))
+            };
+
+            // records without the tag go first in ascending order as it happens in the default storage
+            found.sort_by(|&(id1, record1), &(id2, record2)| {
+                let ordering = _plain_tag_value(&record1.tags, &sort_by).cmp(&_plain_tag_value(&record2.tags, &sort_by))
+                    .then_with(|| id1.cmp(id2));
+
+                match search_options.sort_order {
+                    SortOrder::Asc => ordering,
+                    SortOrder::Desc => ordering.reverse(),
+                }
+            });
+        }
+
+        let tag_names = match search_options.tag_names {
+            Some(ref tag_names) => Some(tag_names.iter()
+                .map(|tag_name| TagName::from_search_option(tag_name))
+                .collect::<IndyResult<Vec<TagName>>>()?),
+            None => None
+        };
+
+        let fetch_options = RecordOptions {
+            retrieve_value: search_options.retrieve_value,
+            retrieve_tags: search_options.retrieve_tags,
+            retrieve_type: search_options.retrieve_type,
+        };
+
+        let records = found.into_iter()
+            .skip(search_options.offset.unwrap_or(0))
+            .take(search_options.limit.unwrap_or(usize::max_value()))
+            .map(|(id, record)| _storage_record(type_, id, record, &fetch_options, tag_names.as_ref()))
+            .collect();
+
+        Ok(Box::new(InmemStorageIterator::new(records, total_count)))
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        match self.snapshot_path {
+            Some(ref path) => self.data.borrow().save(path),
+            None => Ok(())
+        }
+    }
+}
+
+impl WalletStorageType for InmemStorageType {
+    fn create_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        let snapshot_path = InmemStorageType::_snapshot_path(config)?;
+
+        if self.storages.borrow().contains_key(id) || snapshot_path.as_ref().map(|path| path.exists()).unwrap_or(false) {
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, format!("Wallet already exists: {}", id)));
+        }
+
+        let data = InmemData::new(metadata);
+
+        if let Some(ref path) = snapshot_path {
+            data.save(path)?;
+        }
+
+        self.storages.borrow_mut().insert(id.to_string(), Rc::new(RefCell::new(data)));
+        Ok(())
+    }
+
+    fn open_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+        let snapshot_path = InmemStorageType::_snapshot_path(config)?;
+
+        let data = self.storages.borrow().get(id).cloned();
+
+        let data = match (data, snapshot_path.as_ref()) {
+            (Some(data), _) => data,
+            (None, Some(path)) if path.exists() => {
+                let data = Rc::new(RefCell::new(InmemData::load(path)?));
+                self.storages.borrow_mut().insert(id.to_string(), data.clone());
+                data
+            }
+            _ => return Err(err_msg(IndyErrorKind::WalletNotFound, format!("No wallet exists: {}", id)))
+        };
+
+        Ok(Box::new(InmemStorage { data, snapshot_path }))
+    }
+
+    fn delete_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+        let snapshot_path = InmemStorageType::_snapshot_path(config)?;
+
+        let removed = self.storages.borrow_mut().remove(id).is_some();
+
+        let snapshot_removed = match snapshot_path {
+            Some(ref path) if path.exists() => {
+                fs::remove_file(path)?;
+                true
+            }
+            _ => false
+        };
+
+        if removed || snapshot_removed {
+            Ok(())
+        } else {
+            Err(err_msg(IndyErrorKind::WalletNotFound, format!("No wallet exists: {}", id)))
+        }
+    }
+}
+
+fn _storage_record(type_: &[u8], id: &[u8], record: &InmemRecord, options: &RecordOptions, tag_names: Option<&Vec<TagName>>) -> StorageRecord {
+    let value = if options.retrieve_value { Some(record.value.clone()) } else { None };
+    let type_ = if options.retrieve_type { Some(type_.to_vec()) } else { None };
+
+    let tags = if options.retrieve_tags {
+        match tag_names {
+            Some(tag_names) => Some(project_tags(record.tags.clone(), tag_names)),
+            None => Some(record.tags.clone())
+        }
+    } else { None };
+
+    StorageRecord::new(id.to_vec(), value, type_, tags)
+}
+
+fn _tag_name(tag: &Tag) -> (bool, &[u8]) {
+    match *tag {
+        Tag::Encrypted(ref name, _) => (true, name),
+        Tag::PlainText(ref name, _) => (false, name),
+    }
+}
+
+fn _plain_tag_value<'a>(tags: &'a [Tag], name: &[u8]) -> Option<&'a str> {
+    tags.iter()
+        .find_map(|tag| match *tag {
+            Tag::PlainText(ref tag_name, ref value) if tag_name.as_slice() == name => Some(value.as_str()),
+            _ => None
+        })
+}
+
+fn _encrypted_tag_value<'a>(tags: &'a [Tag], name: &[u8]) -> Option<&'a [u8]> {
+    tags.iter()
+        .find_map(|tag| match *tag {
+            Tag::Encrypted(ref tag_name, ref value) if tag_name.as_slice() == name => Some(value.as_slice()),
+            _ => None
+        })
+}
+
+// Rejects the same combinations of tag names and values as the default storage does
+pub(crate) fn check_operator(op: &Operator) -> IndyResult<()> {
+    match *op {
+        Operator::Eq(ref name, ref value) => _check_name_and_value(name, value, "$eq"),
+        Operator::Neq(ref name, ref value) => _check_name_and_value(name, value, "$neq"),
+        Operator::In(ref name, ref values) => values.iter().map(|value| _check_name_and_value(name, value, "$in")).collect(),
+        Operator::Gt(ref name, ref value) => _check_plain_name_and_value(name, value, "$gt"),
+        Operator::Gte(ref name, ref value) => _check_plain_name_and_value(name, value, "$gte"),
+        Operator::Lt(ref name, ref value) => _check_plain_name_and_value(name, value, "$lt"),
+        Operator::Lte(ref name, ref value) => _check_plain_name_and_value(name, value, "$lte"),
+        Operator::Like(ref name, ref value) => _check_plain_name_and_value(name, value, "$like"),
+        Operator::ILike(ref name, ref value) => _check_plain_name_and_value(name, value, "$ilike"),
+        Operator::NumGt(ref name, _) |
+        Operator::NumGte(ref name, _) |
+        Operator::NumLt(ref name, _) |
+        Operator::NumLte(ref name, _) => match *name {
+            QueryTagName::PlainTagName(_) => Ok(()),
+            QueryTagName::EncryptedTagName(_) => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for numeric comparison"))
+        },
+        Operator::Exist(_) => Ok(()),
+        Operator::And(ref suboperators) |
+        Operator::Or(ref suboperators) => suboperators.iter().map(check_operator).collect(),
+        Operator::Not(ref suboperator) => check_operator(suboperator),
+    }
+}
+
+fn _check_name_and_value(name: &QueryTagName, value: &TargetValue, operator: &str) -> IndyResult<()> {
+    match (name, value) {
+        (&QueryTagName::PlainTagName(_), &TargetValue::Unencrypted(_)) |
+        (&QueryTagName::EncryptedTagName(_), &TargetValue::Encrypted(_)) => Ok(()),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", operator)))
+    }
+}
+
+fn _check_plain_name_and_value(name: &QueryTagName, value: &TargetValue, operator: &str) -> IndyResult<()> {
+    match (name, value) {
+        (&QueryTagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => Ok(()),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", operator)))
+    }
+}
+
+// Evaluates the operator checked by `check_operator` against the record tags.
+// Wallet events are filtered with it as well, so it is shared with the wallet service
+pub(crate) fn matches(op: &Operator, tags: &[Tag]) -> bool {
+    match *op {
+        Operator::Eq(ref name, ref value) => _compare(name, value, tags, |ordering| ordering == Ordering::Equal),
+        Operator::Neq(ref name, ref value) => _compare(name, value, tags, |ordering| ordering != Ordering::Equal),
+        Operator::Gt(ref name, ref value) => _compare(name, value, tags, |ordering| ordering == Ordering::Greater),
+        Operator::Gte(ref name, ref value) => _compare(name, value, tags, |ordering| ordering != Ordering::Less),
+        Operator::Lt(ref name, ref value) => _compare(name, value, tags, |ordering| ordering == Ordering::Less),
+        Operator::Lte(ref name, ref value) => _compare(name, value, tags, |ordering| ordering != Ordering::Greater),
+        // LIKE of SQLite used by the default storage ignores case of ASCII characters
+        Operator::Like(ref name, TargetValue::Unencrypted(ref pattern)) |
+        Operator::ILike(ref name, TargetValue::Unencrypted(ref pattern)) => match _plain_value(name, tags) {
+            Some(value) => like_matches(&value.to_ascii_lowercase(), &pattern.to_ascii_lowercase()),
+            None => false
+        },
+        Operator::In(ref name, ref values) => values.iter().any(|value| _compare(name, value, tags, |ordering| ordering == Ordering::Equal)),
+        Operator::Exist(QueryTagName::PlainTagName(ref name)) => _plain_tag_value(tags, name).is_some(),
+        Operator::Exist(QueryTagName::EncryptedTagName(ref name)) => _encrypted_tag_value(tags, name).is_some(),
+        Operator::NumGt(ref name, value) => _compare_numeric(name, tags, |number| number > value),
+        Operator::NumGte(ref name, value) => _compare_numeric(name, tags, |number| number >= value),
+        Operator::NumLt(ref name, value) => _compare_numeric(name, tags, |number| number < value),
+        Operator::NumLte(ref name, value) => _compare_numeric(name, tags, |number| number <= value),
+        Operator::And(ref suboperators) => suboperators.iter().all(|suboperator| matches(suboperator, tags)),
+        Operator::Or(ref suboperators) => suboperators.iter().any(|suboperator| matches(suboperator, tags)),
+        Operator::Not(ref suboperator) => !matches(suboperator, tags),
+        _ => false
+    }
+}
+
+// Compares the tag value with the target one, records without the tag don't match
+fn _compare<F>(name: &QueryTagName, value: &TargetValue, tags: &[Tag], check: F) -> bool where F: Fn(Ordering) -> bool {
+    match (name, value) {
+        (&QueryTagName::PlainTagName(ref name), &TargetValue::Unencrypted(ref value)) =>
+            _plain_tag_value(tags, name).map(|tag_value| check(tag_value.cmp(value.as_str()))).unwrap_or(false),
+        (&QueryTagName::EncryptedTagName(ref name), &TargetValue::Encrypted(ref value)) =>
+            _encrypted_tag_value(tags, name).map(|tag_value| check(tag_value.cmp(value.as_slice()))).unwrap_or(false),
+        _ => false
+    }
+}
+
+// Only values that are integers in decimal notation are compared, others don't match
+fn _compare_numeric<F>(name: &QueryTagName, tags: &[Tag], check: F) -> bool where F: Fn(i64) -> bool {
+    let value = match _plain_value(name, tags) {
+        Some(value) => value,
+        None => return false
+    };
+
+    let digits = if value.starts_with('-') { &value[1..] } else { value };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    value.parse::<i64>().map(check).unwrap_or(false)
+}
+
+fn _plain_value<'a>(name: &QueryTagName, tags: &'a [Tag]) -> Option<&'a str> {
+    match *name {
+        QueryTagName::PlainTagName(ref name) => _plain_tag_value(tags, name),
+        QueryTagName::EncryptedTagName(_) => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indy_utils::environment;
+
+    use super::*;
+
+    #[test]
+    fn inmem_storage_type_create_works_for_twice() {
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage("inmem_storage_type_create_works_for_twice", None, None, &_metadata()).unwrap();
+
+        let res = storage_type.create_storage("inmem_storage_type_create_works_for_twice", None, None, &_metadata());
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+    }
+
+    #[test]
+    fn inmem_storage_type_open_works_for_not_created() {
+        let storage_type = InmemStorageType::new();
+
+        let res = storage_type.open_storage("inmem_storage_type_open_works_for_not_created", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn inmem_storage_type_delete_works() {
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage("inmem_storage_type_delete_works", None, None, &_metadata()).unwrap();
+
+        storage_type.delete_storage("inmem_storage_type_delete_works", None, None).unwrap();
+
+        let res = storage_type.open_storage("inmem_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+        let res = storage_type.delete_storage("inmem_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn inmem_storage_set_get_metadata_works() {
+        let storage = _storage("inmem_storage_set_get_metadata_works");
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+        storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+        assert_eq!(storage.get_storage_metadata().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn inmem_storage_set_get_works_for_reopen() {
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage("inmem_storage_set_get_works_for_reopen", None, None, &_metadata()).unwrap();
+
+        {
+            let mut storage = storage_type.open_storage("inmem_storage_set_get_works_for_reopen", None, None).unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.close().unwrap();
+        }
+
+        let storage = storage_type.open_storage("inmem_storage_set_get_works_for_reopen", None, None).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+    }
+
+    #[test]
+    fn inmem_storage_add_works_for_twice() {
+        let storage = _storage("inmem_storage_add_works_for_twice");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.add(&_type1(), &_id1(), &_value2(), &_tags());
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+    }
+
+    #[test]
+    fn inmem_storage_update_works_for_non_existing_id() {
+        let storage = _storage("inmem_storage_update_works_for_non_existing_id");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.update(&_type1(), &_id2(), &_value2());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    #[test]
+    fn inmem_storage_add_tags_works_for_already_existing() {
+        let storage = _storage("inmem_storage_add_tags_works_for_already_existing");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let tags_with_existing = vec![
+            Tag::PlainText(vec![1, 5, 8, 1], "New plain value".to_string()),
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+        ];
+        storage.add_tags(&_type1(), &_id1(), &tags_with_existing).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+
+        let expected_tags = vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "New plain value".to_string()),
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+        ];
+        assert_eq!(_sort(record.tags.unwrap()), _sort(expected_tags));
+    }
+
+    #[test]
+    fn inmem_storage_delete_tags_works() {
+        let storage = _storage("inmem_storage_delete_tags_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        storage.delete_tags(&_type1(), &_id1(), &[TagName::OfEncrypted(vec![1, 5, 8])]).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.tags.unwrap(), vec![Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string())]);
+    }
+
+    #[test]
+    fn inmem_storage_batch_works_for_failed_operation() {
+        let storage = _storage("inmem_storage_batch_works_for_failed_operation");
+        storage.add(&_type1(), &_id2(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.batch(&[
+            StorageOperation::Add(_type1(), _id1(), _value1(), _tags()),
+            StorageOperation::Delete(_type1(), _id2()),
+            StorageOperation::Update(_type2(), _id1(), _value2()),
+        ]);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let res = storage.get(&_type1(), &_id1(), "{}");
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let record = storage.get(&_type1(), &_id2(), "{}").unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+    }
+
+    #[test]
+    fn inmem_storage_batch_works_for_same_record_operations() {
+        let storage = _storage("inmem_storage_batch_works_for_same_record_operations");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.batch(&[
+            StorageOperation::Update(_type1(), _id1(), _value2()),
+            StorageOperation::UpdateTags(_type1(), _id1(), _new_tags()),
+            StorageOperation::Delete(_type1(), _id1()),
+            StorageOperation::Delete(_type1(), _id1()),
+        ]);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+    }
+
+    #[test]
+    fn inmem_storage_get_all_works() {
+        let storage = _storage("inmem_storage_get_all_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
+
+        let mut storage_iterator = storage.get_all().unwrap();
+
+        let record = storage_iterator.next().unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type1());
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        let record = storage_iterator.next().unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type2());
+        assert_eq!(record.value.unwrap(), _value2());
+
+        assert!(storage_iterator.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn inmem_storage_search_works() {
+        let storage = _storage("inmem_storage_search_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &_new_tags()).unwrap();
+        storage.add(&_type2(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let query = Operator::Or(vec![
+            Operator::Eq(QueryTagName::EncryptedTagName(vec![1, 5, 8]), TargetValue::Encrypted(vec![3, 5, 6])),
+            Operator::Like(QueryTagName::PlainTagName(vec![1, 1, 1]), TargetValue::Unencrypted("TAG%".to_string())),
+        ]);
+
+        let mut storage_iterator = storage.search(&_type1(), &query, Some(r##"{"retrieveTotalCount": true}"##)).unwrap();
+        assert_eq!(storage_iterator.get_total_count().unwrap(), Some(2));
+
+        assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id1());
+        assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id2());
+        assert!(storage_iterator.next().unwrap().is_none());
+
+        let query = Operator::Not(Box::new(Operator::Exist(QueryTagName::PlainTagName(vec![1, 1, 1]))));
+
+        let mut storage_iterator = storage.search(&_type1(), &query, None).unwrap();
+        assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id1());
+        assert!(storage_iterator.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn inmem_storage_search_works_for_numeric_comparison() {
+        let storage = _storage("inmem_storage_search_works_for_numeric_comparison");
+        storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![1], "9".to_string())]).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &[Tag::PlainText(vec![1], "10".to_string())]).unwrap();
+        storage.add(&_type1(), &_id(3), &_value2(), &[Tag::PlainText(vec![1], "10a".to_string())]).unwrap();
+
+        let query = Operator::NumGt(QueryTagName::PlainTagName(vec![1]), 9);
+
+        let mut storage_iterator = storage.search(&_type1(), &query, None).unwrap();
+        assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id2());
+        assert!(storage_iterator.next().unwrap().is_none());
+
+        let query = Operator::NumGt(QueryTagName::EncryptedTagName(vec![1]), 9);
+
+        let res = storage.search(&_type1(), &query, None);
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+
+    #[test]
+    fn inmem_storage_search_works_for_sorting_and_paging() {
+        let storage = _storage("inmem_storage_search_works_for_sorting_and_paging");
+        storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![1], "b".to_string())]).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &[Tag::PlainText(vec![1], "a".to_string())]).unwrap();
+        storage.add(&_type1(), &_id(3), &_value2(), &[]).unwrap();
+
+        let options = json!({
+            "retrieveTotalCount": true,
+            "sortBy": TagName::OfPlain(vec![1]).to_search_option(),
+            "sortOrder": "desc",
+            "offset": 1,
+            "limit": 1,
+        }).to_string();
+
+        let mut storage_iterator = storage.search(&_type1(), &Operator::And(vec![]), Some(&options)).unwrap();
+        assert_eq!(storage_iterator.get_total_count().unwrap(), Some(3));
+
+        assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id2());
+        assert!(storage_iterator.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn inmem_storage_type_open_works_for_snapshot() {
+        let mut path = environment::tmp_path();
+        path.push("inmem_storage_type_open_works_for_snapshot");
+        path.push("snapshot");
+        let _ = fs::remove_file(&path);
+
+        let config = json!({"snapshot_path": path}).to_string();
+
+        {
+            let storage_type = InmemStorageType::new();
+            storage_type.create_storage("inmem_storage_type_open_works_for_snapshot", Some(&config), None, &_metadata()).unwrap();
+
+            let mut storage = storage_type.open_storage("inmem_storage_type_open_works_for_snapshot", Some(&config), None).unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.close().unwrap();
+        }
+
+        let storage_type = InmemStorageType::new();
+
+        let storage = storage_type.open_storage("inmem_storage_type_open_works_for_snapshot", Some(&config), None).unwrap();
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        assert!(!PathBuf::from(tmp_path).exists());
+
+        storage_type.delete_storage("inmem_storage_type_open_works_for_snapshot", Some(&config), None).unwrap();
+        assert!(!path.exists());
+    }
+
+    fn _storage(name: &str) -> Box<dyn WalletStorage> {
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage(name, None, None, &_metadata()).unwrap();
+        storage_type.open_storage(name, None, None).unwrap()
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![1, 2, 3, 4, 5, 6, 7, 8]
+    }
+
+    fn _type(i: u8) -> Vec<u8> {
+        vec![i, 1 + i, 2 + i]
+    }
+
+    fn _type1() -> Vec<u8> {
+        _type(1)
+    }
+
+    fn _type2() -> Vec<u8> {
+        _type(2)
+    }
+
+    fn _id(i: u8) -> Vec<u8> {
+        vec![3 + i, 4 + i, 5 + i]
+    }
+
+    fn _id1() -> Vec<u8> {
+        _id(1)
+    }
+
+    fn _id2() -> Vec<u8> {
+        _id(2)
+    }
+
+    fn _value(i: u8) -> EncryptedValue {
+        EncryptedValue { data: vec![6 + i, 7 + i, 8 + i], key: vec![9 + i, 10 + i, 11 + i] }
+    }
+
+    fn _value1() -> EncryptedValue {
+        _value(1)
+    }
+
+    fn _value2() -> EncryptedValue {
+        _value(2)
+    }
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string())
+        ]
+    }
+
+    fn _new_tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+            Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))
+        ]
+    }
+
+    fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+        v.sort();
+        v
+    }
+}
@@ -1,22 +1,59 @@
 pub mod default;
+pub mod inmem;
 pub mod plugged;
 
 use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
+
 use crate::language;
 use crate::wallet::EncryptedValue;
 
-#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Tag {
     Encrypted(Vec<u8>, Vec<u8>),
     PlainText(Vec<u8>, String)
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum TagName {
     OfEncrypted(Vec<u8>),
     OfPlain(Vec<u8>),
 }
 
+impl TagName {
+    /// Parses the encrypted tag name passed in search options.
+    /// Such names are encoded as base64, names of plaintext tags are prefixed with `~`.
+    pub fn from_search_option(name: &str) -> IndyResult<TagName> {
+        if name.starts_with('~') {
+            Ok(TagName::OfPlain(base64::decode(&name[1..])?))
+        } else {
+            Ok(TagName::OfEncrypted(base64::decode(name)?))
+        }
+    }
+
+    pub fn to_search_option(&self) -> String {
+        match *self {
+            TagName::OfEncrypted(ref name) => base64::encode(name),
+            TagName::OfPlain(ref name) => format!("~{}", base64::encode(name)),
+        }
+    }
+
+    pub fn is_name_of(&self, tag: &Tag) -> bool {
+        match (self, tag) {
+            (&TagName::OfEncrypted(ref name), &Tag::Encrypted(ref tag_name, _)) |
+            (&TagName::OfPlain(ref name), &Tag::PlainText(ref tag_name, _)) => name == tag_name,
+            _ => false
+        }
+    }
+}
+
+/// Leaves only the tags with the given names, used for tags projection in searches.
+pub fn project_tags(tags: Vec<Tag>, tag_names: &[TagName]) -> Vec<Tag> {
+    tags.into_iter()
+        .filter(|tag| tag_names.iter().any(|name| name.is_name_of(tag)))
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct StorageRecord {
     pub id: Vec<u8>,
@@ -36,6 +73,31 @@ impl StorageRecord {
     }
 }
 
+/// Single change of the storage applied as a part of `WalletStorage::batch`.
+/// Arguments are the same as of the corresponding `WalletStorage` methods.
+#[derive(Clone, Debug)]
+pub enum StorageOperation {
+    Add(Vec<u8>, Vec<u8>, EncryptedValue, Vec<Tag>),
+    Update(Vec<u8>, Vec<u8>, EncryptedValue),
+    AddTags(Vec<u8>, Vec<u8>, Vec<Tag>),
+    UpdateTags(Vec<u8>, Vec<u8>, Vec<Tag>),
+    DeleteTags(Vec<u8>, Vec<u8>, Vec<TagName>),
+    Delete(Vec<u8>, Vec<u8>),
+}
+
+impl StorageOperation {
+    pub fn type_and_id(&self) -> (&[u8], &[u8]) {
+        match *self {
+            StorageOperation::Add(ref type_, ref id, _, _) |
+            StorageOperation::Update(ref type_, ref id, _) |
+            StorageOperation::AddTags(ref type_, ref id, _) |
+            StorageOperation::UpdateTags(ref type_, ref id, _) |
+            StorageOperation::DeleteTags(ref type_, ref id, _) |
+            StorageOperation::Delete(ref type_, ref id) => (type_, id)
+        }
+    }
+}
+
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
@@ -49,11 +111,24 @@ pub trait WalletStorage {
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), IndyError>;
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> Result<(), IndyError>;
     fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    /// Applies all operations in the given order or none of them if any fails.
+    fn batch(&self, operations: &[StorageOperation]) -> Result<(), IndyError>;
     fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
+    /// Replaces the metadata with the one `update` builds from the current metadata.
+    /// Storages shared between processes have to read and write it in a single transaction.
+    fn update_storage_metadata(&self, update: &dyn Fn(&[u8]) -> Result<Vec<u8>, IndyError>) -> Result<(), IndyError> {
+        let metadata = update(&self.get_storage_metadata()?)?;
+        self.set_storage_metadata(&metadata)
+    }
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn close(&mut self) -> Result<(), IndyError>;
+    /// Returns the number of tags that don't belong to any record and deletes them if `repair` is set.
+    /// Storages that keep tags together with the records never have such tags.
+    fn check_orphaned_tags(&self, _repair: bool) -> Result<usize, IndyError> {
+        Ok(0)
+    }
 }
 
 pub trait WalletStorageType {
@@ -12,8 +12,8 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::crypto::base64;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
-use super::super::{RecordOptions, SearchOptions};
+use super::{EncryptedValue, StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType, project_tags};
+use super::super::{RecordOptions, SearchOptions, SortOrder};
 
 #[derive(Debug, Deserialize)]
 pub struct PluggedWalletJSONValue {
@@ -211,6 +211,134 @@ impl Drop for PluggedStorageIterator {
     }
 }
 
+// Sorting, paging and tags projection aren't a part of the plugin interface,
+// so they are applied to the records returned by the plugin.
+struct EmulatedSearchIterator {
+    inner: PluggedStorageIterator,
+    sorted: Option<::std::vec::IntoIter<StorageRecord>>,
+    // plugin can count only the records not fetched yet, so it's requested before sorting
+    sorted_total_count: Option<usize>,
+    offset: usize,
+    limit: Option<usize>,
+    retrieve_tags: bool,
+    tag_names: Option<Vec<TagName>>,
+}
+
+impl EmulatedSearchIterator {
+    fn new(mut inner: PluggedStorageIterator, options: &SearchOptions) -> IndyResult<Self> {
+        let mut sorted_total_count = None;
+
+        let sorted = match options.sort_by {
+            Some(ref sort_by) => {
+                let sort_by = match TagName::from_search_option(sort_by)? {
+                    TagName::OfPlain(name) => name,
+                    TagName::OfEncrypted(_) => return Err(err_msg(IndyErrorKind::WalletQueryError, "Records can be sorted only by plaintext tag"))
+                };
+
+                sorted_total_count = inner.get_total_count()?;
+
+                let mut records = Vec::new();
+
+                while let Some(record) = inner.next()? {
+                    records.push(record);
+                }
+
+                // records without the tag go first in ascending order as in the default storage
+                records.sort_by(|a, b| {
+                    let ordering = _plain_tag_value(a, &sort_by).cmp(&_plain_tag_value(b, &sort_by))
+                        .then_with(|| a.id.cmp(&b.id));
+
+                    match options.sort_order {
+                        SortOrder::Asc => ordering,
+                        SortOrder::Desc => ordering.reverse(),
+                    }
+                });
+
+                Some(records.into_iter())
+            }
+            None => None
+        };
+
+        let tag_names = match options.tag_names {
+            Some(ref tag_names) => Some(tag_names.iter()
+                .map(|tag_name| TagName::from_search_option(tag_name))
+                .collect::<IndyResult<Vec<TagName>>>()?),
+            None => None
+        };
+
+        Ok(EmulatedSearchIterator {
+            inner,
+            sorted,
+            sorted_total_count,
+            offset: options.offset.unwrap_or(0),
+            limit: options.limit,
+            retrieve_tags: options.retrieve_tags,
+            tag_names,
+        })
+    }
+
+    fn _next_record(&mut self) -> IndyResult<Option<StorageRecord>> {
+        match self.sorted {
+            Some(ref mut records) => Ok(records.next()),
+            None => self.inner.next()
+        }
+    }
+}
+
+impl StorageIterator for EmulatedSearchIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        while self.offset > 0 {
+            self.offset -= 1;
+
+            if self._next_record()?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        if self.limit == Some(0) {
+            return Ok(None);
+        }
+
+        let mut record = match self._next_record()? {
+            Some(record) => record,
+            None => return Ok(None)
+        };
+
+        if let Some(ref mut limit) = self.limit {
+            *limit -= 1;
+        }
+
+        // tags could be requested from the plugin only for sorting
+        record.tags = if self.retrieve_tags {
+            match self.tag_names {
+                Some(ref tag_names) => record.tags.map(|tags| project_tags(tags, tag_names)),
+                None => record.tags
+            }
+        } else {
+            None
+        };
+
+        Ok(Some(record))
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        match self.sorted {
+            Some(_) => Ok(self.sorted_total_count),
+            None => self.inner.get_total_count()
+        }
+    }
+}
+
+fn _plain_tag_value<'a>(record: &'a StorageRecord, name: &[u8]) -> Option<&'a str> {
+    record.tags.as_ref()?
+        .iter()
+        .filter_map(|tag| match *tag {
+            Tag::PlainText(ref tag_name, ref value) if tag_name.as_slice() == name => Some(value.as_str()),
+            _ => None
+        })
+        .next()
+}
+
 #[derive(PartialEq, Debug)]
 struct PluggedStorage {
     handle: i32,
@@ -518,6 +646,34 @@ impl WalletStorage for PluggedStorage {
         Ok(())
     }
 
+    ///
+    /// Plugged storage API has no transactions, so atomicity is emulated:
+    /// the state of each affected record is saved before its first change and
+    /// restored if any of operations fails.
+    /// Note that changes aren't reverted if the process is terminated in the middle of batch.
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut snapshots: Vec<(Vec<u8>, Vec<u8>, Option<StorageRecord>)> = Vec::new();
+
+        for operation in operations {
+            let (type_, id) = operation.type_and_id();
+
+            let res = if snapshots.iter().any(|&(ref t, ref i, _)| t.as_slice() == type_ && i.as_slice() == id) {
+                Ok(())
+            } else {
+                self._snapshot(type_, id)
+                    .map(|record| snapshots.push((type_.to_vec(), id.to_vec(), record)))
+            };
+
+            if let Err(err) = res.and_then(|_| self._apply(operation)) {
+                self._restore(&snapshots);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
         let mut metadata_ptr: *const c_char = ptr::null_mut();
         let mut metadata_handle = -1;
@@ -572,6 +728,7 @@ impl WalletStorage for PluggedStorage {
                     retrieve_type: true,
                     retrieve_value: true,
                     retrieve_tags: true,
+                    ..SearchOptions::default()
                 },
             )
         ))
@@ -580,11 +737,32 @@ impl WalletStorage for PluggedStorage {
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
         let type_ = CString::new(base64::encode(type_))?;
         let query = CString::new(query.to_string())?;
-        let options_cstr = CString::new(options.unwrap_or("{}"))?;
 
-        let options: SearchOptions = serde_json::from_str(options.unwrap_or("{}"))
+        let options_json = options.unwrap_or("{}");
+
+        let options: SearchOptions = serde_json::from_str(options_json)
             .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?;
 
+        let emulated = options.sort_by.is_some() || options.is_paged() || options.tag_names.is_some();
+
+        // plugin gets only the options it knows about
+        let plugin_options = SearchOptions {
+            retrieve_tags: options.retrieve_tags || options.sort_by.is_some(),
+            sort_by: None,
+            sort_order: SortOrder::default(),
+            offset: None,
+            limit: None,
+            tag_names: None,
+            ..options.clone()
+        };
+
+        let options_cstr = if emulated {
+            CString::new(serde_json::to_string(&plugin_options)
+                .to_indy(IndyErrorKind::InvalidState, "Can't serialize search options")?)?
+        } else {
+            CString::new(options_json)?
+        };
+
         let mut search_handle: i32 = -1;
 
         let err = (self.search_records_handler)(self.handle,
@@ -597,13 +775,13 @@ impl WalletStorage for PluggedStorage {
             return Err(err.into());
         }
 
-        Ok(Box::new(
-            PluggedStorageIterator::new(
-                &self,
-                search_handle,
-                options,
-            )
-        ))
+        let iterator = PluggedStorageIterator::new(&self, search_handle, plugin_options);
+
+        if emulated {
+            Ok(Box::new(EmulatedSearchIterator::new(iterator, &options)?))
+        } else {
+            Ok(Box::new(iterator))
+        }
     }
 
     fn close(&mut self) -> IndyResult<()> {
@@ -620,6 +798,52 @@ impl WalletStorage for PluggedStorage {
     }
 }
 
+impl PluggedStorage {
+    fn _snapshot(&self, type_: &[u8], id: &[u8]) -> IndyResult<Option<StorageRecord>> {
+        match self.get(type_, id, r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#) {
+            Ok(record) => Ok(Some(record)),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => Ok(None),
+            Err(err) => Err(err)
+        }
+    }
+
+    fn _apply(&self, operation: &StorageOperation) -> IndyResult<()> {
+        match *operation {
+            StorageOperation::Add(ref type_, ref id, ref value, ref tags) => self.add(type_, id, value, tags),
+            StorageOperation::Update(ref type_, ref id, ref value) => self.update(type_, id, value),
+            StorageOperation::AddTags(ref type_, ref id, ref tags) => self.add_tags(type_, id, tags),
+            StorageOperation::UpdateTags(ref type_, ref id, ref tags) => self.update_tags(type_, id, tags),
+            StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => self.delete_tags(type_, id, tag_names),
+            StorageOperation::Delete(ref type_, ref id) => self.delete(type_, id),
+        }
+    }
+
+    fn _restore(&self, snapshots: &[(Vec<u8>, Vec<u8>, Option<StorageRecord>)]) {
+        for &(ref type_, ref id, ref record) in snapshots.iter().rev() {
+            let res = match *record {
+                None => match self.delete(type_, id) {
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => Ok(()),
+                    res => res
+                },
+                Some(StorageRecord { value: Some(ref value), ref tags, .. }) => {
+                    let tags = tags.clone().unwrap_or_default();
+
+                    match self.update(type_, id, value) {
+                        Ok(()) => self.update_tags(type_, id, &tags),
+                        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => self.add(type_, id, value, &tags),
+                        Err(err) => Err(err)
+                    }
+                }
+                Some(_) => Err(err_msg(IndyErrorKind::InvalidState, "Record value isn't saved"))
+            };
+
+            if let Err(err) = res {
+                warn!("Unable to restore wallet record after failed batch: {:?}", err);
+            }
+        }
+    }
+}
+
 impl Drop for PluggedStorage {
     fn drop(&mut self) {
         // if storage is not closed, close it before drop.
@@ -2100,6 +2324,54 @@ mod tests {
         assert_eq!(&expected_free_search_call, debug.get(6).unwrap());
     }
 
+    #[test]
+    fn plugged_storage_search_works_for_paging_and_tags_projection() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage = _open_storage();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        let type_ = _random_vector(32);
+
+        let query = language::Operator::And(vec![]);
+        let options = json!({
+            "retrieveTags": true,
+            "offset": 2,
+            "limit": 1,
+            "tagNames": [],
+        }).to_string();
+
+        {
+            let mut storage_iterator = storage.search(&type_, &query, Some(&options)).unwrap();
+
+            let storage_entity = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(RETURN_ID.read().unwrap().1.clone(), storage_entity.id);
+            assert_eq!(Some(Vec::new()), storage_entity.tags);
+
+            assert!(storage_iterator.next().unwrap().is_none());
+        }
+
+        let debug = DEBUG_VEC.read().unwrap();
+
+        let plugin_options: serde_json::Value = match debug.get(0).unwrap() {
+            Call::SearchRecordsHandler(_, _, _, Some(ref options)) => serde_json::from_str(options).unwrap(),
+            call => panic!("Unexpected call {:?}", call)
+        };
+        assert_eq!(json!(true), plugin_options["retrieveTags"]);
+        assert!(plugin_options.get("offset").is_none());
+        assert!(plugin_options.get("limit").is_none());
+        assert!(plugin_options.get("tagNames").is_none());
+
+        let fetch_next_calls = debug.iter()
+            .filter(|call| match call {
+                Call::FetchSearchNextRecordHandler(_, _) => true,
+                _ => false
+            })
+            .count();
+        assert_eq!(3, fetch_next_calls);
+    }
+
     #[test]
     fn plugged_storage_get_all_works() {
         DEBUG_VEC.write().unwrap().clear();
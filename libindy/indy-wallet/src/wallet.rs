@@ -4,6 +4,7 @@ use std::rc::Rc;
 use indy_utils::crypto::{hmacsha256, chacha20poly1305_ietf};
 use indy_utils::wql::Query;
 
+use indy_api_types::domain::wallet::RecordOperation;
 use indy_api_types::errors::prelude::*;
 
 use zeroize::Zeroize;
@@ -11,8 +12,9 @@ use zeroize::Zeroize;
 use super::storage;
 use super::iterator::WalletIterator;
 use super::encryption::*;
+use super::language::Operator;
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::{SearchOptions, WalletRecord};
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -59,7 +61,7 @@ impl Keys {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EncryptedValue {
     pub data: Vec<u8>,
     pub key: Vec<u8>,
@@ -164,6 +166,50 @@ impl Wallet {
         Ok(())
     }
 
+    pub fn batch(&self, operations: &[RecordOperation]) -> IndyResult<()> {
+        let encrypted_operations: Vec<storage::StorageOperation> = operations.iter()
+            .map(|operation| self._encrypt_operation(operation))
+            .collect();
+
+        self.storage.batch(&encrypted_operations)?;
+        Ok(())
+    }
+
+    fn _encrypt_operation(&self, operation: &RecordOperation) -> storage::StorageOperation {
+        let encrypt_type = |type_: &str| encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let encrypt_name = |name: &str| encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+
+        match *operation {
+            RecordOperation::Add { ref type_, ref id, ref value, ref tags } =>
+                storage::StorageOperation::Add(encrypt_type(type_),
+                                               encrypt_name(id),
+                                               EncryptedValue::encrypt(value, &self.keys.value_key),
+                                               tags.as_ref()
+                                                   .map(|tags| encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key))
+                                                   .unwrap_or_default()),
+            RecordOperation::Update { ref type_, ref id, ref value } =>
+                storage::StorageOperation::Update(encrypt_type(type_),
+                                                  encrypt_name(id),
+                                                  EncryptedValue::encrypt(value, &self.keys.value_key)),
+            RecordOperation::AddTags { ref type_, ref id, ref tags } =>
+                storage::StorageOperation::AddTags(encrypt_type(type_),
+                                                   encrypt_name(id),
+                                                   encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)),
+            RecordOperation::UpdateTags { ref type_, ref id, ref tags } =>
+                storage::StorageOperation::UpdateTags(encrypt_type(type_),
+                                                      encrypt_name(id),
+                                                      encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)),
+            RecordOperation::DeleteTags { ref type_, ref id, ref tag_names } => {
+                let tag_names: Vec<&str> = tag_names.iter().map(String::as_str).collect();
+                storage::StorageOperation::DeleteTags(encrypt_type(type_),
+                                                      encrypt_name(id),
+                                                      encrypt_tag_names(&tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key))
+            }
+            RecordOperation::Delete { ref type_, ref id } =>
+                storage::StorageOperation::Delete(encrypt_type(type_), encrypt_name(id)),
+        }
+    }
+
     pub fn get(&self, type_: &str, name: &str, options: &str) -> IndyResult<WalletRecord> {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
@@ -189,16 +235,58 @@ impl Wallet {
     }
 
     pub fn search<'a>(&'a self, type_: &str, query: &str, options: Option<&str>) -> IndyResult<WalletIterator> {
-        let parsed_query: Query = ::serde_json::from_str(query)
-            .map_err(|err| IndyError::from_msg(IndyErrorKind::WalletQueryError, err))?;
-
-        let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
+        let encrypted_query = self.encrypt_query(query)?;
         let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
+        let encrypted_options = options.map(|options| self._encrypt_search_options(options)).transpose()?;
+        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, encrypted_options.as_ref().map(String::as_str))?;
         let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
         Ok(wallet_iterator)
     }
 
+    pub fn encrypt_query(&self, query: &str) -> IndyResult<Operator> {
+        let parsed_query: Query = ::serde_json::from_str(query)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::WalletQueryError, err))?;
+
+        encrypt_query(parsed_query, &self.keys)
+    }
+
+    pub fn encrypt_tags(&self, tags: &HashMap<String, String>) -> Vec<storage::Tag> {
+        encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)
+    }
+
+    // Tag names used for sorting and tags projection are replaced with the encrypted ones the storage operates on
+    fn _encrypt_search_options(&self, options: &str) -> IndyResult<String> {
+        let mut parsed_options: SearchOptions = ::serde_json::from_str(options)
+            .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?;
+
+        if parsed_options.sort_by.is_none() && parsed_options.tag_names.is_none() {
+            return Ok(options.to_string());
+        }
+
+        if let Some(sort_by) = parsed_options.sort_by.take() {
+            if !sort_by.starts_with('~') {
+                return Err(err_msg(IndyErrorKind::WalletQueryError, format!("Records can be sorted only by plaintext tag, but {:?} is given", sort_by)));
+            }
+
+            let tag_name = encrypt_tag_names(&[&sort_by], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+            parsed_options.sort_by = Some(tag_name[0].to_search_option());
+        }
+
+        if let Some(tag_names) = parsed_options.tag_names.take() {
+            let tag_names = tag_names.iter().map(String::as_str).collect::<Vec<&str>>();
+
+            parsed_options.tag_names = Some(
+                encrypt_tag_names(&tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key)
+                    .iter()
+                    .map(storage::TagName::to_search_option)
+                    .collect()
+            );
+        }
+
+        ::serde_json::to_string(&parsed_options)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize search options")
+    }
+
     pub fn close(&mut self) -> IndyResult<()> {
         self.storage.close()
             .map_err(IndyError::from)
@@ -212,6 +300,14 @@ impl Wallet {
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    pub fn get_keys(&self) -> &Keys {
+        &self.keys
+    }
+
+    pub fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.set_storage_metadata(metadata)
+    }
 }
 
 #[cfg(test)]
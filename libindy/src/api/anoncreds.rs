@@ -8,12 +8,14 @@ use crate::commands::anoncreds::verifier::VerifierCommand;
 use crate::domain::anoncreds::schema::{Schema, AttributeNames, Schemas};
 use crate::domain::crypto::did::DidValue;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
+use crate::domain::anoncreds::credential_issuance_record::CredentialIssuanceRecordTags;
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
-use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use crate::domain::anoncreds::credential_request::{CredentialIssuanceRequests, CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialValues};
+use crate::domain::anoncreds::credential::{Credential, RawCredentialValues};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::revocation_registry_pool::RevocationRegistryPoolConfig;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
@@ -555,157 +557,894 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
 ///      "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
 ///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
 ///     }
+///     `encoded` can be omitted, then the value is encoded by libindy (see `indy_encode_credential_attr_values`):
+///     {
+///      "attr1" : "value1",
+///      "attr2" : {"raw": "2000-01-01", "type": "date" }
+///     }
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: Credential json containing signed credential values
+///     {
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_def_id", Optional<string>, - identifier of revocation registry
+///         "values": <see cred_values_json above>, - credential values.
+///         // Fields below can depend on Cred Def type
+///         "signature": <credential signature>,
+///                      (opaque type that contains data structures internal to Ursa.
+///                       It should not be parsed and are likely to change in future versions).
+///         "signature_correctness_proof": credential signature correctness proof
+///                      (opaque type that contains data structures internal to Ursa.
+///                       It should not be parsed and are likely to change in future versions).
+///         "rev_reg" - (Optional) revocation registry accumulator value on the issuing moment.
+///                      (opaque type that contains data structures internal to Ursa.
+///                       It should not be parsed and are likely to change in future versions).
+///         "witness" - (Optional) revocation related data
+///                      (opaque type that contains data structures internal to Ursa.
+///                       It should not be parsed and are likely to change in future versions).
+///     }
+/// cred_revoc_id: local id for revocation info (Can be used for revocation of this credential)
+/// revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            cred_offer_json: *const c_char,
+                                            cred_req_json: *const c_char,
+                                            cred_values_json: *const c_char,
+                                            rev_reg_id: *const c_char,
+                                            blob_storage_reader_handle: IndyHandle,
+                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                 cred_json: *const c_char,
+                                                                 cred_revoc_id: *const c_char,
+                                                                 revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, RawCredentialValues);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam6, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credential: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredential(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    cred_values_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    None,
+                    Box::new(move |result| {
+                        let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
+                        trace!("indy_issuer_create_credential: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
+                        let cred_json = ctypes::string_to_cstring(cred_json);
+                        let revoc_id = revoc_id.map(ctypes::string_to_cstring);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, cred_json.as_ptr(),
+                           revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential: <<< res: {:?}", res);
+
+    res
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request
+/// storing the issuance record with the given tags.
+///
+/// Acts like `indy_issuer_create_credential`. For every credential issued from a revocation registry
+/// libindy stores an issuance record in the wallet (see `indy_issuer_get_credential_record`),
+/// this call allows to attach Issuer's tags to it, e.g. id of the holder in the application database.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_req_json: a credential request created by indy_prover_create_credential_req
+/// cred_values_json: a credential containing attribute values for each of requested attribute names
+///     (see `indy_issuer_create_credential`).
 /// rev_reg_id: id of revocation registry stored in the wallet
 /// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// record_tags_json: (optional) tags of the issuance record used for search:
+///     {
+///         "tagName1": <str>, // string tag (will be stored encrypted)
+///         "~tagName2": <str>, // string tag (will be stored un-encrypted)
+///     }
+///     Tag names "cred_def_id", "rev_reg_id", "cred_rev_id", "prover_did", "revoked" and "~timestamp" are reserved.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: Credential json containing signed credential values (see `indy_issuer_create_credential`)
+/// cred_revoc_id: local id for revocation info (Can be used for revocation of this credential)
+/// revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential_with_tags(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      cred_offer_json: *const c_char,
+                                                      cred_req_json: *const c_char,
+                                                      cred_values_json: *const c_char,
+                                                      rev_reg_id: *const c_char,
+                                                      blob_storage_reader_handle: IndyHandle,
+                                                      record_tags_json: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           cred_json: *const c_char,
+                                                                           cred_revoc_id: *const c_char,
+                                                                           revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential_with_tags: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}, record_tags_json: {:?}", wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle, record_tags_json);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, RawCredentialValues);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam6, RevocationRegistryId);
+    check_useful_opt_validatable_json!(record_tags_json, ErrorCode::CommonInvalidParam8, CredentialIssuanceRecordTags);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credential_with_tags: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}, record_tags_json: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), secret!(&rev_reg_id),
+           blob_storage_reader_handle, record_tags_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredential(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    cred_values_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    record_tags_json,
+                    Box::new(move |result| {
+                        let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
+                        trace!("indy_issuer_create_credential_with_tags: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
+                        let cred_json = ctypes::string_to_cstring(cred_json);
+                        let revoc_id = revoc_id.map(ctypes::string_to_cstring);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, cred_json.as_ptr(),
+                           revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential_with_tags: <<< res: {:?}", res);
+
+    res
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credentials for a batch of Credential Requests.
+///
+/// Acts like `indy_issuer_create_credential` called for every credential request of the batch, but
+/// loads credential definition and revocation registry only once, opens the tails reader only once and
+/// generates CL signatures in parallel on the crypto thread pool (see `crypto_thread_pool_size` of `indy_set_runtime_config`).
+/// Credentials of an ISSUANCE_ON_DEMAND revocation registry are signed sequentially as every issuance updates the accumulator.
+///
+/// Revocation indexes are allocated for the whole batch. The call fails if the revocation registry
+/// doesn't have enough capacity for all credentials; in this case no credential is issued.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_issuance_requests_json: a non-empty list of credential requests with the values to issue for each of them
+///     [
+///         {
+///             "cred_req": <credential request created by indy_prover_create_credential_req>,
+///             "cred_values": <credential values, see `cred_values_json` of indy_issuer_create_credential>,
+///             "record_tags": (optional) <issuance record tags, see `record_tags_json` of indy_issuer_create_credential_with_tags>
+///         },
+///         ...
+///     ]
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// creds_json: issued credentials in the order of requests
+///     [
+///         {
+///             "cred": <credential json, see `cred_json` of indy_issuer_create_credential>,
+///             "cred_rev_id": Optional<string> - local id for revocation info
+///         },
+///         ...
+///     ]
+/// revoc_reg_delta_json: Revocation registry delta json with all newly issued credentials
+///     (returned only for ISSUANCE_ON_DEMAND revocation registries)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cred_offer_json: *const c_char,
+                                             cred_issuance_requests_json: *const c_char,
+                                             rev_reg_id: *const c_char,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  creds_json: *const c_char,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_issuance_requests_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_issuance_requests_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_issuance_requests_json, ErrorCode::CommonInvalidParam4, CredentialIssuanceRequests);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_issuance_requests_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_issuance_requests_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentials(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_issuance_requests_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, creds_json, revoc_reg_delta_json) = prepare_result_2!(result, String::new(), None);
+                        trace!("indy_issuer_create_credentials: creds_json: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(creds_json.as_str()), revoc_reg_delta_json);
+                        let creds_json = ctypes::string_to_cstring(creds_json);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, creds_json.as_ptr(),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
+///
+/// The corresponding credential definition and revocation registry must be already
+/// created an stored into the wallet.
+///
+/// This call returns revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
+/// Note that it is possible to accumulate deltas to reduce ledger load.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// cred_revoc_id: local id for revocation info related to issued credential
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with a revoked credential
+/// {
+///     value: {
+///         prevAccum: string - previous accumulator value.
+///         accum: string - current accumulator value.
+///         revoked: array<number> an array of revoked indices.
+///     },
+///     ver: string - version revocation registry delta json
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            blob_storage_reader_cfg_handle: IndyHandle,
+                                            rev_reg_id: *const c_char,
+                                            cred_revoc_id: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                 revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credential: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_c_str!(cred_revoc_id, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_revoke_credential: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(cred_revoc_id.as_str()));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeCredential(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    rev_reg_id,
+                    cred_revoc_id,
+                    boxed_callback_string!("indy_issuer_revoke_credential", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credential: <<< res: {:?}", res);
+
+    res
+}
+
+/// Revoke a batch of credentials of the same revocation registry identified by cred_revoc_ids
+/// (returned by indy_issuer_create_credential or indy_issuer_create_credentials).
+///
+/// The tails reader is opened only once for the whole batch.
+/// The call fails without revoking anything if any of cred_revoc_ids is unknown or already revoked.
+///
+/// This call returns a single revoc registry delta covering all revoked credentials
+/// intended to be shared as REVOC_REG_ENTRY transaction.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// cred_revoc_ids_json: a non-empty list of local ids for revocation info related to issued credentials
+///     ["1", "2", ...]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with all revoked credentials
+/// {
+///     value: {
+///         prevAccum: string - previous accumulator value.
+///         accum: string - current accumulator value.
+///         revoked: array<number> an array of revoked indices.
+///     },
+///     ver: string - version revocation registry delta json
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             blob_storage_reader_cfg_handle: IndyHandle,
+                                             rev_reg_id: *const c_char,
+                                             cred_revoc_ids_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credentials: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_ids_json);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_json!(cred_revoc_ids_json, ErrorCode::CommonInvalidParam5, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    if cred_revoc_ids_json.is_empty() {
+        return err_msg(IndyErrorKind::InvalidStructure, "Empty list of credential revocation ids has been passed").into();
+    }
+
+    trace!("indy_issuer_revoke_credentials: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(&cred_revoc_ids_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeCredentials(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    rev_reg_id,
+                    cred_revoc_ids_json,
+                    boxed_callback_string!("indy_issuer_revoke_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Create a pool of revocation registries for a credential definition that is used
+/// by indy_issuer_create_credential_from_pool.
+///
+/// The pool consists of the active revocation registry used for issuance
+/// and a number of pre-generated registries kept ready to replace it when it is full.
+/// Once the number of free indexes of the active registry reaches the threshold
+/// missing ready registries are created, and as soon as the active registry is full
+/// the next ready one becomes active. Tails files of all registries are written with
+/// the blob storage of the type and config passed in config_json.
+///
+/// The pool and the issued/revoked indices of its registries are stored in the wallet.
+/// Only one pool can be created for a credential definition.
+///
+/// Note that generation of tails of a registry takes time proportional to max_cred_num
+/// and is performed on the anoncreds command thread, so other anoncreds calls wait for it.
+/// Use the threshold to create ready registries in advance and lower max_cred_num
+/// if issuance latency matters.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issuer_did: a DID of the issuer
+/// cred_def_id: id of stored in ledger credential definition
+/// config_json: type-specific configuration of the pool as json:
+/// {
+///     "rev_reg_type": (optional) type of revocation registries, "CL_ACCUM" by default,
+///     "issuance_type": (optional) type of issuance of revocation registries, "ISSUANCE_ON_DEMAND" by default,
+///     "max_cred_num": (optional) maximum number of credentials of each registry, 100000 by default,
+///     "pregenerated": (optional) number of registries kept ready besides the active one, 1 by default,
+///     "threshold": (optional) number of free indexes of the active registry at which missing ready
+///                  registries are created, 0 by default (must be less than max_cred_num),
+///     "tails_writer_type": (optional) type of blob storage used for tails, "default" by default,
+///     "tails_writer_config": config of blob storage used for tails, e.g. {"base_dir": string, "uri_pattern": string}
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// rev_regs_json: created revocation registries to be published to the ledger, the first one is active:
+/// [{
+///     "rev_reg_id": string - identifier of created revocation registry definition,
+///     "rev_reg_def": object - public part of revocation registry definition,
+///     "rev_reg_entry": object - revocation registry entry that defines initial state of revocation registry
+/// }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_revocation_registry_pool(command_handle: CommandHandle,
+                                                          wallet_handle: WalletHandle,
+                                                          issuer_did: *const c_char,
+                                                          cred_def_id: *const c_char,
+                                                          config_json: *const c_char,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                               rev_regs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_revocation_registry_pool: >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam4, CredentialDefinitionId);
+    check_useful_validatable_json!(config_json, ErrorCode::CommonInvalidParam5, RevocationRegistryPoolConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_create_revocation_registry_pool: entities >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateRevocationRegistryPool(
+                    wallet_handle,
+                    issuer_did,
+                    cred_def_id,
+                    config_json,
+                    boxed_callback_string!("indy_issuer_create_revocation_registry_pool", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_revocation_registry_pool: <<< res: {:?}", res);
+
+    res
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential from the revocation registry pool
+/// of the credential definition (created by indy_issuer_create_revocation_registry_pool).
+///
+/// The active revocation registry of the pool is selected transparently.
+/// If issuance makes the pool create new revocation registries they are returned
+/// in created_rev_regs and must be published to the ledger before credentials of them are used.
+/// When the active registry turns out to be full it is replaced with the next one
+/// and issuance is retried once.
+///
+/// Creation of registries generates their tails on the anoncreds command thread,
+/// so a call that creates registries can take long and delays other anoncreds calls
+/// (see indy_issuer_create_revocation_registry_pool).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_req_json: a credential request created by indy_prover_create_credential_request
+/// cred_values_json: a credential containing attribute values for each of requested attribute names.
+///     Example:
+///     {
+///      "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
+///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// issued_cred_json: Credential issued from the pool
+/// {
+///     "cred": object - credential json as returned by indy_issuer_create_credential,
+///     "cred_rev_id": string - local id for revocation info,
+///     "rev_reg_id": string - id of revocation registry the credential was issued from,
+///     "rev_reg_delta": (optional) object - revocation registry delta to be published (for ISSUANCE_ON_DEMAND registries),
+///     "created_rev_regs": array - revocation registries created by the pool during issuance
+///                                 (in the format of indy_issuer_create_revocation_registry_pool result)
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential_from_pool(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      cred_offer_json: *const c_char,
+                                                      cred_req_json: *const c_char,
+                                                      cred_values_json: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           issued_cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential_from_pool: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}",
+           wallet_handle, cred_offer_json, cred_req_json, cred_values_json);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, RawCredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_create_credential_from_pool: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}",
+           wallet_handle, secret!(&cred_offer_json), secret!(&cred_req_json), secret!(&cred_values_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentialFromPool(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    cred_values_json,
+                    boxed_callback_string!("indy_issuer_create_credential_from_pool", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential_from_pool: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get the state of the revocation registry pool of the credential definition.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_def_id: id of credential definition the pool was created for
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// pool_json: state of the pool
+/// {
+///     "cred_def_id": string - id of credential definition,
+///     "config": object - config of the pool (see indy_issuer_create_revocation_registry_pool),
+///     "registries": [{
+///         "rev_reg_id": string - id of revocation registry,
+///         "state": string - one of "ready", "active", "full",
+///         "max_cred_num": number - maximum number of credentials of the registry,
+///         "issued": number - number of issued indices,
+///         "revoked": array<number> - revoked indices
+///     }] - registries in order of creation
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_revocation_registry_pool(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       cred_def_id: *const c_char,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                            pool_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revocation_registry_pool: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_revocation_registry_pool: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryPool(
+                    wallet_handle,
+                    cred_def_id,
+                    boxed_callback_string!("indy_issuer_get_revocation_registry_pool", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_revocation_registry_pool: <<< res: {:?}", res);
+
+    res
+}
+
+/// Revoke a credential identified by its issuance record (see `indy_issuer_search_credential_records`).
+///
+/// Acts like `indy_issuer_revoke_credential` called for revocation registry and cred_revoc_id of the record.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// record_id: id of issuance record
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with a revoked credential
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credential_by_record(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      blob_storage_reader_cfg_handle: IndyHandle,
+                                                      record_id: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credential_by_record: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, record_id: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, record_id);
+
+    check_useful_c_str!(record_id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_issuer_revoke_credential_by_record: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, record_id: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, record_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeCredentialByRecord(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    record_id,
+                    boxed_callback_string!("indy_issuer_revoke_credential_by_record", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credential_by_record: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get issuance record of a credential issued from a revocation registry.
+///
+/// An issuance record is stored in the wallet for every credential issued from a revocation registry
+/// and is marked as revoked when the credential is revoked.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// record_id: id of issuance record: "<rev_reg_id>:<cred_rev_id>"
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// cred_json: Credential json containing signed credential values
+/// record_json: issuance record
 ///     {
-///         "schema_id": string, - identifier of schema
+///         "record_id": string, - id of issuance record
 ///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_def_id", Optional<string>, - identifier of revocation registry
-///         "values": <see cred_values_json above>, - credential values.
-///         // Fields below can depend on Cred Def type
-///         "signature": <credential signature>,
-///                      (opaque type that contains data structures internal to Ursa.
-///                       It should not be parsed and are likely to change in future versions).
-///         "signature_correctness_proof": credential signature correctness proof
-///                      (opaque type that contains data structures internal to Ursa.
-///                       It should not be parsed and are likely to change in future versions).
-///         "rev_reg" - (Optional) revocation registry accumulator value on the issuing moment.
-///                      (opaque type that contains data structures internal to Ursa.
-///                       It should not be parsed and are likely to change in future versions).
-///         "witness" - (Optional) revocation related data
-///                      (opaque type that contains data structures internal to Ursa.
-///                       It should not be parsed and are likely to change in future versions).
+///         "rev_reg_id": string, - identifier of revocation registry
+///         "cred_rev_id": string, - identifier of credential in the revocation registry
+///         "prover_did": string, - DID of the prover from the credential request
+///         "timestamp": number, - time of issuance (seconds since Unix epoch)
+///         "revoked": bool, - whether credential is revoked
+///         "revoked_timestamp": Optional<number>, - time of revocation
+///         "tags": {"tagName": string} - tags passed to indy_issuer_create_credential_with_tags
 ///     }
-/// cred_revoc_id: local id for revocation info (Can be used for revocation of this credential)
-/// revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
-                                            wallet_handle: WalletHandle,
-                                            cred_offer_json: *const c_char,
-                                            cred_req_json: *const c_char,
-                                            cred_values_json: *const c_char,
-                                            rev_reg_id: *const c_char,
-                                            blob_storage_reader_handle: IndyHandle,
-                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                 cred_json: *const c_char,
-                                                                 cred_revoc_id: *const c_char,
-                                                                 revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_issuer_create_credential: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
-    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle);
-
-    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
-    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
-    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, CredentialValues);
-    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam6, RevocationRegistryId);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+pub extern fn indy_issuer_get_credential_record(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                record_id: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                     record_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_credential_record: >>> wallet_handle: {:?}, record_id: {:?}", wallet_handle, record_id);
 
-    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+    check_useful_c_str!(record_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_issuer_create_credential: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
-    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+    trace!("indy_issuer_get_credential_record: entities >>> wallet_handle: {:?}, record_id: {:?}", wallet_handle, record_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Issuer(
-                IssuerCommand::CreateCredential(
+                IssuerCommand::GetCredentialRecord(
                     wallet_handle,
-                    cred_offer_json,
-                    cred_req_json,
-                    cred_values_json,
-                    rev_reg_id,
-                    blob_storage_reader_handle,
-                    Box::new(move |result| {
-                        let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
-                        trace!("indy_issuer_create_credential: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
-                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
-                        let cred_json = ctypes::string_to_cstring(cred_json);
-                        let revoc_id = revoc_id.map(ctypes::string_to_cstring);
-                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
-                        cb(command_handle, err, cred_json.as_ptr(),
-                           revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
-                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
-                    })
+                    record_id,
+                    boxed_callback_string!("indy_issuer_get_credential_record", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_issuer_create_credential: <<< res: {:?}", res);
+    trace!("indy_issuer_get_credential_record: <<< res: {:?}", res);
 
     res
 }
 
-/// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
-///
-/// The corresponding credential definition and revocation registry must be already
-/// created an stored into the wallet.
+/// Search for issuance records of credentials issued from revocation registries.
 ///
-/// This call returns revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
-/// Note that it is possible to accumulate deltas to reduce ledger load.
+/// Instead of immediately returning of fetched records
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with indy_issuer_fetch_credential_records).
 ///
 /// #Params
-/// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
-/// rev_reg_id: id of revocation registry stored in wallet
-/// cred_revoc_id: local id for revocation info related to issued credential
+/// query_json: Wql query filter for issuance records searching based on tags.
+///     where query: indy-sdk/docs/design/011-wallet-query-language/README.md
+///     The list of allowed tags:
+///     {
+///         "cred_def_id": <credential definition id>,
+///         "rev_reg_id": <revocation registry id>,
+///         "cred_rev_id": <credential revocation id>,
+///         "prover_did": <prover did>,
+///         "revoked": "0" | "1",
+///         "~timestamp": <time of issuance>, - can be compared as number
+///         <tags passed to indy_issuer_create_credential_with_tags>
+///     }
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// revoc_reg_delta_json: Revocation registry delta json with a revoked credential
-/// {
-///     value: {
-///         prevAccum: string - previous accumulator value.
-///         accum: string - current accumulator value.
-///         revoked: array<number> an array of revoked indices.
-///     },
-///     ver: string - version revocation registry delta json
-/// }
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_issuer_fetch_credential_records)
+/// total_count: Total count of records
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
-                                            wallet_handle: WalletHandle,
-                                            blob_storage_reader_cfg_handle: IndyHandle,
-                                            rev_reg_id: *const c_char,
-                                            cred_revoc_id: *const c_char,
-                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                 revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_issuer_revoke_credential: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
-           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_id);
+pub extern fn indy_issuer_search_credential_records(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    query_json: *const c_char,
+                                                    cb: Option<extern fn(
+                                                        command_handle_: CommandHandle, err: ErrorCode,
+                                                        search_handle: SearchHandle,
+                                                        total_count: usize)>) -> ErrorCode {
+    trace!("indy_issuer_search_credential_records: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
 
-    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
-    check_useful_c_str!(cred_revoc_id, ErrorCode::CommonInvalidParam5);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_issuer_revoke_credential: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
-           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(cred_revoc_id.as_str()));
+    trace!("indy_issuer_search_credential_records: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Issuer(
-                IssuerCommand::RevokeCredential(
+                IssuerCommand::SearchCredentialRecords(
                     wallet_handle,
-                    blob_storage_reader_cfg_handle,
-                    rev_reg_id,
-                    cred_revoc_id,
-                    boxed_callback_string!("indy_issuer_revoke_credential", cb, command_handle)
+                    query_json,
+                    Box::new(move |result| {
+                        let (err, handle, total_count) = prepare_result_2!(result, 0, 0);
+                        cb(command_handle, err, handle, total_count)
+                    })
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_issuer_revoke_credential: <<< res: {:?}", res);
+    trace!("indy_issuer_search_credential_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next issuance records for search.
+///
+/// #Params
+/// search_handle: Search handle (created by indy_issuer_search_credential_records)
+/// count: Count of records to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// records_json: List of issuance records (see `indy_issuer_get_credential_record`)
+/// NOTE: The list of length less than the requested count means records search iterator is completed.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_fetch_credential_records(command_handle: CommandHandle,
+                                                   search_handle: SearchHandle,
+                                                   count: usize,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                        records_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_fetch_credential_records: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_fetch_credential_records: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::FetchCredentialRecords(
+                    search_handle,
+                    count,
+                    boxed_callback_string!("indy_issuer_fetch_credential_records", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_fetch_credential_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close issuance records search (make search handle invalid)
+///
+/// #Params
+/// search_handle: Search handle (created by indy_issuer_search_credential_records)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_close_credential_records_search(command_handle: CommandHandle,
+                                                          search_handle: SearchHandle,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_close_credential_records_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_close_credential_records_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CloseCredentialRecordsSearch(
+                    search_handle,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_close_credential_records_search:");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_close_credential_records_search: <<< res: {:?}", res);
 
     res
 }
@@ -1690,6 +2429,8 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 ///         "issuer_did": <credential issuer did>,
 ///         "cred_def_id": <credential definition id>,
 ///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///     Credential tags are encrypted, so $ilike and numeric comparisons are checked in memory
+///     against credentials matching the rest of the query.
 ///
 /// cb: Callback that takes command result as parameter.
 ///
@@ -2238,6 +2979,101 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Verifies a proof (of multiple credential) the same way as `indy_verifier_verify_proof`,
+/// but returns a report explaining the result instead of a bare boolean.
+///
+/// Problems with the proof (missing referents, failed restrictions, missing non-revocation timestamps,
+/// encoded values mismatch, invalid signature, unknown schemas or credential definitions) don't fail the call,
+/// they are listed in the report.
+/// The cryptographic check is done even if some referents aren't satisfied. Sub proofs share
+/// the same challenge, so its failure is reported for every referent the proof provides.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// schemas_json: all schemas participating in the proof (see `indy_verifier_verify_proof`)
+/// credential_defs_json: all credential definitions participating in the proof (see `indy_verifier_verify_proof`)
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof (see `indy_verifier_verify_proof`)
+/// rev_regs_json: all revocation registries participating in the proof (see `indy_verifier_verify_proof`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: proof verification report
+///     {
+///         "valid": bool, // same result as `indy_verifier_verify_proof` returns
+///         "requested_attributes": {
+///             "attr_referent": <referent_report>,
+///         },
+///         "requested_predicates": {
+///             "predicate_referent": <referent_report>,
+///         },
+///         "errors": [string], // all reasons why the proof was rejected,
+///                             // e.g. "referent attr1: restriction validation failed: ..."
+///     }
+/// where
+/// referent_report:
+///     {
+///         "valid": bool,
+///         "revealed": bool,
+///         "self_attested": bool,
+///         "values": Optional<{"attr_name": raw value}>, // revealed or self-attested values
+///         "identifier": Optional<{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}>, // credential used for referent
+///         "restrictions_satisfied": Optional<bool>, // not set if referent has no restrictions
+///         "non_revoked": Optional<<non_revoc_interval>>, // requested non-revocation interval
+///         "errors": [string], // reasons why the referent isn't satisfied
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     schemas_json: *const c_char,
+                                                     credential_defs_json: *const c_char,
+                                                     rev_reg_defs_json: *const c_char,
+                                                     rev_regs_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_report: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_report: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithReport(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            Box::new(move |result| {
+                let (err, report_json) = prepare_result_1!(result, String::new());
+                trace!("indy_verifier_verify_proof_with_report: report_json: {:?}", report_json);
+                let report_json = ctypes::string_to_cstring(report_json);
+                cb(command_handle, err, report_json.as_ptr())
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_report: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential in the particular time moment.
 ///
 /// #Params
@@ -2453,3 +3289,68 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     res
 }
 
+/// Encode raw credential attribute values in the canonical form expected by `indy_issuer_create_credential`.
+///
+/// Each attribute value can be set as a plain raw value (string, number or boolean)
+/// or as an object with an explicit encoding type:
+///     {
+///      "name": "Alex",
+///      "age": 28,
+///      "birthdate": {"raw": "1991-10-18", "type": "date"},
+///      "member": {"raw": true, "type": "boolean"},
+///      "sex": {"raw": "male", "encoded": "5944657099558967239210949258394887428692050081607692519917050011144233115103"}
+///     }
+///
+/// Supported encoding types:
+///     int - 32-bit signed integer, encoded as its decimal form.
+///     string - SHA-256 hash of UTF-8 bytes of the raw value, encoded as decimal big-endian integer.
+///     date - date in `YYYY-MM-DD` format, encoded as `YYYYMMDD` integer (so can be used in predicates).
+///     boolean - `true` or `false`, encoded as `1` or `0`.
+/// If the type isn't set: booleans use `boolean` encoding, integers in canonical form use `int` encoding,
+/// any other value uses `string` encoding. An explicitly set `encoded` value is kept as is,
+/// if the type is set as well the value must match the encoding of this type.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// raw_values_json: raw credential values json (see above).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_values_json: credential values json with encoded values
+///     {
+///      "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
+///      "attr2" : {"raw": "value2", "encoded": "value2_as_int" }
+///     }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_credential_attr_values(command_handle: CommandHandle,
+                                                 raw_values_json: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      err: ErrorCode,
+                                                                      cred_values_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_credential_attr_values: >>> raw_values_json: {:?}", raw_values_json);
+
+    check_useful_validatable_json!(raw_values_json, ErrorCode::CommonInvalidParam2, RawCredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_credential_attr_values: entities >>> raw_values_json: {:?}", secret!(&raw_values_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::EncodeCredentialAttrValues(
+            raw_values_json,
+            Box::new(move |result| {
+                let (err, cred_values_json) = prepare_result_1!(result, String::new());
+                trace!("indy_encode_credential_attr_values: cred_values_json: {:?}", secret!(&cred_values_json));
+                let cred_values_json = ctypes::string_to_cstring(cred_values_json);
+                cb(command_handle, err, cred_values_json.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_credential_attr_values: <<< res: {:?}", res);
+
+    res
+}
@@ -1,5 +1,6 @@
 
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
+use indy_api_types::crypto::*;
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::crypto::CryptoCommand;
 use crate::domain::crypto::pack::JWE;
@@ -11,6 +12,80 @@ use serde_json;
 use libc::c_char;
 
 
+/// Register custom key provider implementation.
+///
+/// Keys created with the registered crypto type (See indy_create_key and indy_create_and_store_my_did)
+/// live in the key provider (For example, in external KMS process) and only verkeys are stored in the wallet.
+/// Signing and decryption with these keys are delegated to the key provider.
+/// Keys are expected to be ed25519 keys, so signature verification and encryption
+/// for these keys are performed by libindy itself.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// crypto_type: Crypto type name. Verkeys of the keys created by the provider have "<verkey>:<crypto_type>" form.
+/// create_key: "create_key" operation handler
+/// sign: "sign" operation handler
+/// crypto_box: "crypto_box" (authenticated encryption) operation handler
+/// crypto_box_open: "crypto_box_open" (authenticated decryption) operation handler
+/// crypto_box_seal_open: "crypto_box_seal_open" (anonymous decryption) operation handler
+/// free_result: frees results returned by the handlers above
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_key_provider(command_handle: CommandHandle,
+                                         crypto_type: *const c_char,
+                                         create_key: Option<KeyProviderCreateKey>,
+                                         sign: Option<KeyProviderSign>,
+                                         crypto_box: Option<KeyProviderCryptoBox>,
+                                         crypto_box_open: Option<KeyProviderCryptoBoxOpen>,
+                                         crypto_box_seal_open: Option<KeyProviderCryptoBoxSealOpen>,
+                                         free_result: Option<KeyProviderFreeResult>,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_key_provider: >>> crypto_type: {:?}", crypto_type);
+
+    check_useful_c_str!(crypto_type, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(crypto_box, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(crypto_box_open, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(crypto_box_seal_open, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(free_result, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_register_key_provider: entities >>> crypto_type: {:?}", crypto_type);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::RegisterKeyProvider(
+            crypto_type,
+            create_key,
+            sign,
+            crypto_box,
+            crypto_box_open,
+            crypto_box_seal_open,
+            free_result,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_register_key_provider: err: {:?}", err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_key_provider: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates keys pair and stores in the wallet.
 ///
 /// #Params
@@ -20,7 +95,8 @@ use libc::c_char;
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value
+///                            // and crypto types of key providers registered with indy_register_key_provider are supported for this field.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
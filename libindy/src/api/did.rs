@@ -33,7 +33,8 @@ use crate::domain::ledger::attrib::Endpoint;
 ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               currently only 'ed25519' value and crypto types of key providers
+///               registered with indy_register_key_provider are supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
 /// }
@@ -98,7 +99,8 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               currently only 'ed25519' value and crypto types of key providers
+///               registered with indy_register_key_provider are supported for this field)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -6,6 +6,7 @@ use crate::domain::anoncreds::credential_definition::{CredentialDefinition, Cred
 use crate::domain::anoncreds::schema::{Schema, SchemaId};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryId};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::cache::GetCacheOptions;
 use crate::domain::crypto::did::DidValue;
 use crate::domain::ledger::author_agreement::{GetTxnAuthorAgreementData, AcceptanceMechanisms};
 use crate::domain::ledger::node::NodeOperationData;
@@ -118,6 +119,69 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
     res
 }
 
+/// Publishes GET request message to validator pool using the persistent read cache of the pool.
+///
+/// If a fresh enough reply on the same request is present inside of the cache, cached reply is returned.
+/// Otherwise the request is sent to the validator pool as is and the reply is stored inside of the cache
+/// if its state proof is verified.
+///
+/// Cached requests: GET_NYM, GET_ATTRIB, GET_SCHEMA, GET_CRED_DEF, GET_REVOC_REG_DEF and GET_TXN.
+/// Other requests are just sent to the pool.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// request_json: Request data json.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional) Return cached data if not older than this many seconds. -1 means do not check age.
+///              By default replies with schemas, credential definitions, revocation registry definitions and transactions
+///              are not checked and other replies are fresh during freshness threshold (see indy_set_runtime_config).
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_submit_cached_request(command_handle: CommandHandle,
+                                         pool_handle: PoolHandle,
+                                         request_json: *const c_char,
+                                         options_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              request_result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_submit_cached_request: >>> pool_handle: {:?}, request_json: {:?}, options_json: {:?}", pool_handle, request_json, options_json);
+
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam4, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_submit_cached_request: entities >>> pool_handle: {:?}, request_json: {:?}, options_json: {:?}", pool_handle, request_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::SubmitCachedRequest(
+            pool_handle,
+            request_json,
+            options_json,
+            boxed_callback_string!("indy_submit_cached_request", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_submit_cached_request: <<< res: {:?}", res);
+
+    res
+}
+
 /// Send action to particular nodes of validator pool.
 ///
 /// The list of requests can be send:
@@ -1036,6 +1100,78 @@ pub extern fn indy_build_get_txn_request(command_handle: CommandHandle,
     res
 }
 
+/// Gets a transaction by its seq_no with the proof of its inclusion into the ledger.
+///
+/// Sends GET_TXN request to the pool and checks the audit path of the reply against the ledger root hash
+/// signed by the pool validators (BLS multi-signature), so a reply of a single node can be trusted.
+/// Note: the pool must support audit proofs (Indy Node 1.10 and greater).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// ledger_type: (Optional) type of the ledger the requested transaction belongs to:
+///     DOMAIN - used default,
+///     POOL,
+///     CONFIG
+///     any number
+/// seq_no: requested transaction sequence number as it's stored on Ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Verified transaction json:
+/// {
+///     "txn": {
+///         "txn": {...}, // transaction as it's stored on Ledger
+///         "txnMetadata": {...},
+///         "ver": string, // (optional)
+///         "reqSignature": {...} // (optional)
+///     },
+///     "seq_no": int,
+///     "root_hash": string, // root hash of the ledger the transaction is included into
+///     "verified": true
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+/// LedgerInvalidTransaction if the inclusion of the transaction can't be proved
+/// LedgerNotFound if there is no transaction with given seq_no
+#[no_mangle]
+pub extern fn indy_get_verified_txn(command_handle: CommandHandle,
+                                    pool_handle: PoolHandle,
+                                    submitter_did: *const c_char,
+                                    ledger_type: *const c_char,
+                                    seq_no: i32,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         txn_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_verified_txn: >>> pool_handle: {:?}, submitter_did: {:?}, ledger_type: {:?}, seq_no: {:?}",
+           pool_handle, submitter_did, ledger_type, seq_no);
+
+    check_useful_validatable_opt_string!(submitter_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_opt_c_str!(ledger_type, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_get_verified_txn: entities >>> pool_handle: {:?}, submitter_did: {:?}, ledger_type: {:?}, seq_no: {:?}",
+           pool_handle, submitter_did, ledger_type, seq_no);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::GetVerifiedTxn(
+            pool_handle,
+            submitter_did,
+            ledger_type,
+            seq_no,
+            boxed_callback_string!("indy_get_verified_txn", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_verified_txn: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds a POOL_CONFIG request. Request to change Pool's configuration.
 ///
 /// #Params
@@ -1,8 +1,8 @@
 
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, SearchHandle};
+use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, SearchHandle, SubscriptionHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::non_secrets::NonSecretsCommand;
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{RecordOperation, Tags};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 
@@ -310,6 +310,56 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     res
 }
 
+/// Apply a list of changes of non-secret wallet records atomically:
+/// either all of them are applied or the wallet is left untouched.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// operations_json: list of operations applied in the given order:
+///   [
+///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": <optional json>},
+///     {"op": "update", "type": <str>, "id": <str>, "value": <str>},
+///     {"op": "add_tags", "type": <str>, "id": <str>, "tags": <json>},
+///     {"op": "update_tags", "type": <str>, "id": <str>, "tags": <json>},
+///     {"op": "delete_tags", "type": <str>, "id": <str>, "tag_names": [<str>, ...]},
+///     {"op": "delete", "type": <str>, "id": <str>},
+///   ]
+///   Tags format is the same as for indy_add_wallet_record.
+///
+/// Note that for plugged storages the atomicity is emulated by restoring the changed records
+/// if any of operations fails.
+#[no_mangle]
+pub extern fn indy_batch_wallet_records(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        operations_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_batch_wallet_records: >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    check_useful_json!(operations_json, ErrorCode::CommonInvalidParam3, Vec<RecordOperation>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_batch_wallet_records: entities >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::BatchRecords(
+                wallet_handle,
+                operations_json,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_batch_wallet_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_batch_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get an wallet record by id
 ///
 /// #Params
@@ -389,6 +439,12 @@ pub  extern fn indy_get_wallet_record(command_handle: CommandHandle,
 ///    retrieveType: (optional, false by default) Retrieve record type,
 ///    retrieveValue: (optional, true by default) Retrieve record value,
 ///    retrieveTags: (optional, false by default) Retrieve record tags,
+///    sortBy: (optional) Name of the plaintext tag ("~" prefixed) to sort records by,
+///            records without this tag go first in ascending order
+///    sortOrder: (optional, "asc" by default) Sort order, "asc" or "desc",
+///    offset: (optional, 0 by default) Number of records to skip,
+///    limit: (optional) Maximum number of records to return,
+///    tagNames: (optional) Names of the tags to retrieve with retrieveTags, all tags by default,
 ///  }
 /// #Returns
 /// search_handle: Wallet search handle that can be used later
@@ -511,3 +567,105 @@ pub  extern fn indy_close_wallet_search(command_handle: CommandHandle,
 
     res
 }
+
+/// Subscribe to changes of wallet records.
+/// The event callback is called after each change of the records of the type which tags
+/// match the query until the subscription is removed or the wallet is closed.
+/// Changes made by operations on whole wallet (import) aren't delivered.
+///
+/// Record values are never delivered, so records of internal libindy types can be watched as well,
+/// e.g. "Indy::Credential" records stored by indy_prover_store_credential.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: type of the records to watch
+/// query_json: WQL query to the record tags, the same as for indy_open_wallet_search.
+///     Tags of the record after the change are matched (before the change for deleted records).
+///     {} matches all records of the type.
+/// event_cb: Callback that takes the subscription handle and the event json:
+///  {
+///    "event": "add" | "update" | "add_tags" | "update_tags" | "delete_tags" | "delete",
+///    "type": "Some type",
+///    "id": "Some id",
+///    "tags": <tags json>, // tags after the change (before it for "delete")
+///  }
+///  Events of indy_batch_wallet_records are delivered after the whole batch is applied.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// subscription_handle: Wallet subscription handle that can be used to remove the subscription
+///   (with indy_unsubscribe_wallet_records)
+#[no_mangle]
+pub  extern fn indy_subscribe_wallet_records(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             type_: *const c_char,
+                                             query_json: *const c_char,
+                                             event_cb: Option<extern fn(subscription_handle: SubscriptionHandle,
+                                                                        event_json: *const c_char)>,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  subscription_handle: SubscriptionHandle)>) -> ErrorCode {
+    trace!("indy_subscribe_wallet_records: >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}", wallet_handle, type_, query_json);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(query_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(event_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_subscribe_wallet_records: entities >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}", wallet_handle, type_, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::SubscribeRecords(
+                wallet_handle,
+                type_,
+                query_json,
+                Box::new(move |subscription_handle, event_json: String| {
+                    trace!("indy_subscribe_wallet_records: event_json: {:?}", event_json);
+                    let event_json = ctypes::string_to_cstring(event_json);
+                    event_cb(subscription_handle, event_json.as_ptr())
+                }),
+                Box::new(move |result| {
+                    let (err, handle) = prepare_result_1!(result, 0);
+                    trace!("indy_subscribe_wallet_records: handle: {:?}", handle);
+                    cb(command_handle, err, handle)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_subscribe_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Remove wallet records subscription (make subscription handle invalid)
+///
+/// #Params
+/// subscription_handle: wallet subscription handle (created by indy_subscribe_wallet_records)
+#[no_mangle]
+pub  extern fn indy_unsubscribe_wallet_records(command_handle: CommandHandle,
+                                               subscription_handle: SubscriptionHandle,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_unsubscribe_wallet_records: >>> subscription_handle: {:?}", subscription_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_unsubscribe_wallet_records: entities >>> subscription_handle: {:?}", subscription_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::UnsubscribeRecords(
+                subscription_handle,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_unsubscribe_wallet_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_unsubscribe_wallet_records: <<< res: {:?}", res);
+
+    res
+}
@@ -2,7 +2,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, PoolHandle, INVALID_POOL_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::pool::PoolCommand;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{PoolConfig, PoolGroupConfig, PoolOpenConfig};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 use indy_api_types::validation::Validatable;
@@ -10,6 +10,13 @@ use indy_api_types::validation::Validatable;
 use serde_json;
 use libc::c_char;
 
+// Native access to opened pools for Rust consumers linking libindy as a library:
+// `AsyncPool::get(pool_handle)?.submit(&request).await` sends the request to the pool worker
+// and receives the reply without passing through the command thread serving the functions below.
+// `indy_submit_request` and `indy_submit_action` submit requests through the same handle
+// and only pass replies back to the command thread to call the callbacks.
+pub use crate::services::pool::{AsyncPool, SubmitFuture};
+
 /// Creates a new local pool ledger configuration that can be used later to connect pool nodes.
 ///
 /// #Params
@@ -74,6 +81,24 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 ///         ["name_of_1st_prior_node",  "name_of_2nd_prior_node", .... ]
 ///         Note: Not specified nodes will be placed in a random way.
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
+///     "refresh_interval": int (optional) - if set, the pool ledger is refreshed in background every `refresh_interval` sec.
+///         Requests sent during the refresh are processed after it. See indy_set_pool_nodes_update_callback
+///         to get notified about changes of the validator set.
+///     "adaptive_ordering": bool (optional) - rank nodes by observed response time and success rate
+///         and send single read requests to the best nodes first (false by default).
+///         Note: nodes without observations go first; `preordered_nodes` order is used for ties.
+///     "simulation": object (optional) - replaces network connections with in-process simulated nodes
+///         built from the pool ledger. Intended for testing only:
+///         {
+///             "nodes": (optional) map of node name to behavior of the node (nodes act honestly by default):
+///                 {"Node1": {"behavior": "faulty"}, "Node2": {"behavior": "slow", "delay": <ms>}, "Node3": {"behavior": "malicious"}}
+///             "pool_txns": array<string> (optional) - pool ledger transactions known to the nodes
+///                 in addition to the genesis ones (the pool will perform catchup for them).
+///                 NODE transactions sent to the simulated pool are appended to its pool ledger as well.
+///         }
+///         Note: GET_NYM, GET_SCHEMA and GET_CRED_DEF replies of simulated nodes contain state proofs.
+///         The nodes sign state proofs with BLS keys derived from their names,
+///         so state proofs are accepted only if the genesis transactions contain the matching keys.
 /// }
 ///
 /// #Returns
@@ -154,6 +179,263 @@ pub extern fn indy_refresh_pool_ledger(command_handle: CommandHandle,
     res
 }
 
+/// Returns health metrics of the nodes of an opened pool ledger collected since it was opened.
+///
+/// #Params
+/// handle: pool handle returned by indy_open_pool_ledger
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+/// stats_json: pool statistics json
+/// {
+///     "connections": <int>, // number of currently opened connections to the pool
+///     "last_catchup": <int>, // (optional) time of the last successful catchup as unix timestamp
+///     "nodes": {
+///         "<node alias>": {
+///             "sent": <int>, // number of messages sent to the node
+///             "replies": <int>, // number of replies (including catchup replies)
+///             "acks": <int>, // number of REQACK
+///             "nacks": <int>, // number of REQNACK
+///             "rejects": <int>, // number of REJECT
+///             "timeouts": <int>, // number of requests the node didn't reply in time
+///             "invalid_state_proofs": <int>, // number of replies with not valid state proof
+///             "last_reply": <int>, // (optional) time of the last reply as unix timestamp
+///             "decayed_latency_ms": <float>, // (optional) exponentially decayed time to reply or to timeout
+///             "success_rate": <float>, // (optional) exponentially decayed share of requests answered in time
+///             "latency": {
+///                 "buckets": [<int>, ...], // count of replies with latency up to 50, 100, 250, 500, 1000, 2500, 5000, 10000 ms and above
+///                 "count": <int>,
+///                 "sum_ms": <int>,
+///                 "max_ms": <int>,
+///                 "avg_ms": <int> // (optional) average latency, not set until the first reply
+///             }
+///         },
+///         ...
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_pool_stats(command_handle: CommandHandle,
+                                  handle: PoolHandle,
+                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                       err: ErrorCode,
+                                                       stats_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_pool_stats: >>> handle: {:?}", handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_pool_stats: entities >>> handle: {:?}", handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::GetStats(handle, boxed_callback_string!("indy_get_pool_stats", cb, command_handle))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_pool_stats: <<< res: {:?}", res);
+
+    res
+}
+
+/// Registers a callback to be called when the set of pool validators changes after a refresh of the pool ledger:
+/// validators are added, removed or their BLS keys are changed.
+/// The refresh is performed by indy_refresh_pool_ledger or in background if `refresh_interval` is set in pool open config.
+///
+/// #Params
+/// handle: pool handle returned by indy_open_pool_ledger
+/// update_cb (optional): Callback that takes the pool handle and the description of the changes. NULL removes registered callback.
+///     nodes_diff_json:
+///     {
+///         "added": {"<node alias>": "<BLS key>"|null, ...}, // new validators
+///         "removed": ["<node alias>", ...], // validators that are not in the pool anymore
+///         "updated": {"<node alias>": "<BLS key>"|null, ...} // validators with changed BLS keys
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_set_pool_nodes_update_callback(command_handle: CommandHandle,
+                                                  handle: PoolHandle,
+                                                  update_cb: Option<extern fn(pool_handle: PoolHandle,
+                                                                              nodes_diff_json: *const c_char)>,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                       err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_pool_nodes_update_callback: >>> handle: {:?}", handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_set_pool_nodes_update_callback: entities >>> handle: {:?}", handle);
+
+    let update_cb = update_cb.map(|update_cb| -> Box<dyn Fn(String) + Send> {
+        Box::new(move |nodes_diff: String| {
+            trace!("indy_set_pool_nodes_update_callback: nodes_diff: {:?}", nodes_diff);
+            let nodes_diff = ctypes::string_to_cstring(nodes_diff);
+            update_cb(handle, nodes_diff.as_ptr())
+        })
+    });
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::SetNodesUpdateCallback(
+            handle,
+            update_cb,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_pool_nodes_update_callback:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_pool_nodes_update_callback: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies BLS multi-signature and state proof of a previously received reply on a GET_* request
+/// without sending anything to the pool. Intended for re-verification of archived ledger replies.
+///
+/// The reply is verified against the validators of an opened pool (as known from the local copy of its ledger)
+/// or against the validators of a genesis transactions file.
+/// Note: the freshness of the reply is not checked, use `last_signed_time` of the verdict for that.
+///
+/// #Params
+/// handle: pool handle returned by indy_open_pool_ledger or 0 if `genesis_txn` is passed.
+/// genesis_txn (optional): path to a genesis transactions file. Must be NULL if `handle` is passed.
+/// request_json: the request the reply was received on.
+/// reply_json: the reply received from the ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+/// verdict_json:
+/// {
+///     "verified": bool, // true if the state proof and the multi-signature are valid
+///     "txn_type": string, // (optional) type of the request
+///     "state_proof_present": bool,
+///     "failure": string, // (optional) reason of rejection, one of:
+///         "no_state_proof", "root_hash_mismatch", "invalid_signature", "invalid_proof", "unsupported_format"
+///     "participants": [string], // aliases of nodes made the multi-signature
+///     "last_signed_time": int, // (optional) time of the multi-signature as unix timestamp
+///     "nodes_count": int, // number of validators the reply was verified against
+///     "f": int // number of validators allowed to be faulty
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_verify_reply_state_proof(command_handle: CommandHandle,
+                                            handle: PoolHandle,
+                                            genesis_txn: *const c_char,
+                                            request_json: *const c_char,
+                                            reply_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 verdict_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verify_reply_state_proof: >>> handle: {:?}, genesis_txn: {:?}, request_json: {:?}, reply_json: {:?}",
+           handle, genesis_txn, request_json, reply_json);
+
+    check_useful_opt_c_str!(genesis_txn, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(reply_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_verify_reply_state_proof: entities >>> handle: {:?}, genesis_txn: {:?}, request_json: {:?}, reply_json: {:?}",
+           handle, genesis_txn, request_json, reply_json);
+
+    let handle = if handle == INVALID_POOL_HANDLE { None } else { Some(handle) };
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::VerifyReplyStateProof(
+            handle,
+            genesis_txn,
+            request_json,
+            reply_json,
+            boxed_callback_string!("indy_verify_reply_state_proof", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verify_reply_state_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Validates a genesis transactions file without connecting to the nodes.
+///
+/// Parses every NODE transaction, checks node keys, addresses and proofs of possession of BLS keys,
+/// detects duplicated aliases and addresses and calculates quorum sizes of the pool.
+/// Intended to be called before indy_create_pool_ledger_config to get a readable explanation of problems.
+///
+/// #Params
+/// genesis_txn: path to a genesis transactions file.
+/// protocol_version: protocol version NODE transactions are checked against (1 or 2),
+///     0 to use the version set by indy_set_protocol_version.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+/// report_json:
+/// {
+///     "valid": bool, // true if there are no errors
+///     "txns_count": int, // number of transactions in the file
+///     "nodes": [{
+///         "alias": string,
+///         "dest": string, // node verkey
+///         "validator": bool,
+///         "client_address": string, // (optional) ip:port
+///         "node_address": string, // (optional) ip:port
+///         "bls_key_valid": bool,
+///         "bls_key_pop_valid": bool, // true if proof of possession of BLS key is verified
+///     }],
+///     "validators_count": int,
+///     "f": int, // number of validators allowed to be faulty
+///     "weak_quorum": int, // number of equal replies to trust a reply (f + 1)
+///     "strong_quorum": int, // number of validators to reach consensus (n - f)
+///     "errors": [string],
+///     "warnings": [string]
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_validate_pool_genesis(command_handle: CommandHandle,
+                                         genesis_txn: *const c_char,
+                                         protocol_version: usize,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_validate_pool_genesis: >>> genesis_txn: {:?}, protocol_version: {:?}", genesis_txn, protocol_version);
+
+    check_useful_c_str!(genesis_txn, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_validate_pool_genesis: entities >>> genesis_txn: {:?}, protocol_version: {:?}", genesis_txn, protocol_version);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::ValidateGenesis(
+            genesis_txn,
+            protocol_version,
+            boxed_callback_string!("indy_validate_pool_genesis", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_validate_pool_genesis: <<< res: {:?}", res);
+
+    res
+}
+
 /// Lists names of created pool ledgers
 ///
 /// #Params
@@ -222,6 +504,101 @@ pub extern fn indy_close_pool_ledger(command_handle: CommandHandle,
     res
 }
 
+/// Creates a group of opened pool ledgers of several networks registered against DID method names.
+///
+/// The returned handle can be passed instead of a pool handle to indy_submit_request, indy_sign_and_submit_request,
+/// indy_submit_action, indy_submit_cached_request, indy_get_verified_txn, indy_get_schema and indy_get_cred_def.
+/// The request is sent to the pool registered for the method of the fully-qualified identifier it references:
+/// - indy_get_schema and indy_get_cred_def route by the requested id;
+/// - other functions route by `identifier`, `endorser` and `operation.dest`, `operation.origin` fields of the request.
+///   Fully-qualified DIDs in these fields are replaced with unqualified ones before sending,
+///   so the request must not be signed in this case. indy_sign_and_submit_request replaces them before signing.
+/// The nodes passed to indy_submit_action must belong to the pool the action is routed to.
+/// Requests referencing unqualified identifiers only are sent to the default pool.
+///
+/// #Params
+/// config: Pool group configuration json:
+/// {
+///     "routes": {"<DID method>": <pool handle>, ...} (optional), pools by DID method names, e.g. "sov" or "sov:staging".
+///         The longest matching method name is used.
+///     "default": <pool handle> (optional), pool for unqualified identifiers and DID methods without route.
+/// }
+///
+/// #Returns
+/// Handle of the pool group.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_create_pool_group(command_handle: CommandHandle,
+                                     config: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          pool_group_handle: PoolHandle)>) -> ErrorCode {
+    trace!("indy_create_pool_group: >>> config: {:?}", config);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, PoolGroupConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_create_pool_group: entities >>> config: {:?}", config);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::CreateGroup(
+            config,
+            Box::new(move |result| {
+                let (err, pool_group_handle) = prepare_result_1!(result, INVALID_POOL_HANDLE);
+                trace!("indy_create_pool_group: pool_group_handle: {:?}", pool_group_handle);
+                cb(command_handle, err, pool_group_handle)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_pool_group: <<< res: {:?}", res);
+
+    res
+}
+
+/// Closes the pool group. Pool ledgers of the group stay opened.
+///
+/// #Params
+/// handle: pool group handle returned by indy_create_pool_group.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_close_pool_group(command_handle: CommandHandle,
+                                    handle: PoolHandle,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_close_pool_group: >>> handle: {:?}", handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_close_pool_group: entities >>> handle: {:?}", handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::CloseGroup(
+            handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_close_pool_group:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_close_pool_group: <<< res: {:?}", res);
+
+    res
+}
+
 /// Deletes created pool ledger configuration.
 ///
 /// #Params
@@ -1,8 +1,10 @@
 
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, INVALID_WALLET_HANDLE};
+use std::sync::Arc;
+
+use indy_api_types::{ErrorCode, CommandHandle, IndyHandle, WalletHandle, INVALID_WALLET_HANDLE};
 use crate::commands::{Command, CommandExecutor};
-use crate::commands::wallet::WalletCommand;
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use crate::commands::wallet::{ExportWriter, ImportReader, WalletCommand};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, ExportStreamConfig, ImportRecordsConfig, ImportRecordsStreamConfig, KeyConfig, RekeyCredentials};
 use indy_api_types::wallet::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -152,6 +154,7 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'in_memory' storage type keeps wallet data in the memory of the process.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -161,6 +164,11 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///             Defaults to $HOME/.indy_client/wallet.
 ///             Wallet will be stored in the file {path}/{id}/sqlite.db
 ///   }
+///   For 'in_memory' storage type configuration is:
+///   {
+///     "snapshot_path": optional<string>, Path to the file the wallet is saved to on close.
+///                      Wallet is restored from this file on open if it isn't in memory yet.
+///   }
 /// }
 /// credentials: Wallet credentials json
 /// {
@@ -233,6 +241,11 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///              "path": optional<string>, Path to the directory with wallet files.
 ///                      Defaults to $HOME/.indy_client/wallet.
 ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
+///              "locking": optional<string>, Locking mode of the wallet file:
+///                         "exclusive" - wallet is used by a single process (default),
+///                         "shared" - wallet can be opened by several processes at the same time.
+///              "busy_timeout": optional<int>, Time in milliseconds to wait for the wallet file locked by another process
+///                              in "shared" mode. Defaults to 5000.
 ///           }
 ///
 ///   }
@@ -311,6 +324,12 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "types": optional<[string]>, Types of records to export. All records are exported by default.
+///     "query": optional<object>, WQL query records of each of the types have to match (requires "types" to be set).
+///     "marker": optional<string>, Name of the export marker. If set, only records added or changed since
+///               the previous export with the same marker are exported, the first export with the marker contains all records.
+///               Records deleted since the previous export are exported as tombstones that delete them on import.
+///               Marker is moved only if the export succeeds.
 ///   }
 ///
 /// #Returns
@@ -348,6 +367,127 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Exports opened wallet passing the exported content to the write callback instead of the file.
+/// The content has the same format as the file produced by indy_export_wallet.
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// export_config: JSON containing settings for input operation.
+///   {
+///     "key": <string>, Key or passphrase used for wallet export key derivation.
+///     "key_derivation_method": optional<string> Algorithm to use for wallet export key derivation:
+///                              ARGON2I_MOD (used by default), ARGON2I_INT or RAW
+///     "types": optional<[string]>, Types of records to export. All records are exported by default.
+///     "query": optional<object>, WQL query records of each of the types have to match (requires "types" to be set).
+///     "marker": optional<string>, Name of the export marker, see indy_export_wallet.
+///   }
+/// stream_handle: caller defined handle passed to the write callback.
+/// write_cb: callback receiving chunks of the exported content in order.
+///           Export fails with CommonIOError if it returns an error code.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_export_wallet_to_stream(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           export_config: *const c_char,
+                                           stream_handle: IndyHandle,
+                                           write_cb: Option<extern fn(stream_handle: IndyHandle,
+                                                                      data_raw: *const u8,
+                                                                      data_len: u32) -> ErrorCode>,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_export_wallet_to_stream: >>> wallet_handle: {:?}, export_config: {:?}, stream_handle: {:?}", wallet_handle, export_config, stream_handle);
+
+    check_useful_json!(export_config, ErrorCode::CommonInvalidParam3, ExportStreamConfig);
+    check_useful_c_callback!(write_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_export_wallet_to_stream: params wallet_handle: {:?}, export_config: {:?}, stream_handle: {:?}", wallet_handle, secret!(&export_config), stream_handle);
+
+    let writer: ExportWriter = Arc::new(move |data: &[u8]| {
+        match write_cb(stream_handle, data.as_ptr(), data.len() as u32) {
+            ErrorCode::Success => Ok(()),
+            err => Err(err_msg(IndyErrorKind::IOError, format!("Export stream write callback returned error: {:?}", err)))
+        }
+    });
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ExportToStream(
+            wallet_handle,
+            export_config,
+            writer,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_export_wallet_to_stream: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_export_wallet_to_stream: <<< res: {:?}", res);
+    res
+}
+
+/// Rotates master key of opened wallet.
+///
+/// Master key protects only the wallet encryption keys, so the wallet records aren't re-encrypted
+/// and the time of the call doesn't depend on the wallet size. New key is applied by the single update
+/// of wallet metadata, so the wallet is left accessible by either the previous or the new key
+/// if the process is interrupted. The wallet stays opened and usable after the call.
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// credentials: JSON containing new wallet credentials.
+///   {
+///     "key": <string>, New key or passphrase used for wallet key derivation.
+///                     Look to key_derivation_method param for information about supported key derivation methods.
+///     "key_derivation_method": optional<string> Algorithm to use for new wallet key derivation:
+///                              ARGON2I_MOD - derive secured wallet master key (used by default)
+///                              ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                              RAW - raw wallet key master provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///   }
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_rekey_wallet(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                credentials: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_rekey_wallet: >>> wallet_handle: {:?}, credentials: {:?}", wallet_handle, credentials);
+
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, RekeyCredentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_rekey_wallet: params wallet_handle: {:?}, credentials: {:?}", wallet_handle, secret!(&credentials));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Rekey(
+            wallet_handle,
+            credentials,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_rekey_wallet: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_rekey_wallet: <<< res: {:?}", res);
+    res
+}
+
 
 /// Creates a new secure wallet and then imports its content
 /// according to fields provided in import_config
@@ -430,6 +570,128 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Imports content of the wallet export file into the opened wallet.
+/// It allows to merge exports into existing wallet, for example to restore incremental exports made with the marker.
+/// Records deleted since the previous export with the marker are deleted from the wallet.
+///
+/// Conflicts are resolved before the wallet is changed and all records are applied at once,
+/// so the wallet is left unchanged if the import fails. Imported records are kept in memory until then.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "path": <string>, path of the file that contains exported wallet content
+///   "key": <string>, key used for export of the wallet
+///   "on_conflict": optional<string>, what to do with records that already exist in the wallet:
+///                  "fail" - fail the import (used by default)
+///                  "skip" - keep the existing record
+///                  "overwrite" - replace value and tags of the existing record
+/// }
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         import_config: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_records: >>> command_handle: {:?}, wallet_handle: {:?}, import_config: {:?}, cb: {:?}",
+           command_handle, wallet_handle, import_config, cb);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, ImportRecordsConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_import_wallet_records: params wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(&import_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportRecords(
+            wallet_handle,
+            import_config,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_records: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_records: <<< res: {:?}", res);
+    res
+}
+
+/// Imports wallet content taken from the read callback into the opened wallet.
+/// The content has the same format as the file produced by indy_export_wallet, see indy_import_wallet_records.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "key": <string>, key used for export of the wallet
+///   "on_conflict": optional<string>, what to do with records that already exist in the wallet,
+///                  see indy_import_wallet_records ("fail" by default)
+/// }
+/// stream_handle: caller defined handle passed to the read callback.
+/// read_cb: callback filling the buffer with the next chunk of the content.
+///          It sets read_len to the size of the chunk, 0 means the end of the content.
+///          Import fails with CommonIOError if it returns an error code.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_records_from_stream(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     import_config: *const c_char,
+                                                     stream_handle: IndyHandle,
+                                                     read_cb: Option<extern fn(stream_handle: IndyHandle,
+                                                                               data_raw: *mut u8,
+                                                                               data_len: u32,
+                                                                               read_len: *mut u32) -> ErrorCode>,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                          err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_records_from_stream: >>> wallet_handle: {:?}, import_config: {:?}, stream_handle: {:?}", wallet_handle, import_config, stream_handle);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, ImportRecordsStreamConfig);
+    check_useful_c_callback!(read_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_wallet_records_from_stream: params wallet_handle: {:?}, import_config: {:?}, stream_handle: {:?}", wallet_handle, secret!(&import_config), stream_handle);
+
+    let reader: ImportReader = Arc::new(move |buf: &mut [u8]| {
+        let mut read_len: u32 = 0;
+        match read_cb(stream_handle, buf.as_mut_ptr(), buf.len() as u32, &mut read_len) {
+            ErrorCode::Success if read_len as usize <= buf.len() => Ok(read_len as usize),
+            ErrorCode::Success => Err(err_msg(IndyErrorKind::IOError, "Import stream read callback returned more data than requested")),
+            err => Err(err_msg(IndyErrorKind::IOError, format!("Import stream read callback returned error: {:?}", err)))
+        }
+    });
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportRecordsFromStream(
+            wallet_handle,
+            import_config,
+            reader,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_records_from_stream: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_records_from_stream: <<< res: {:?}", res);
+    res
+}
+
 
 /// Closes opened wallet and frees allocated resources.
 ///
@@ -539,6 +801,71 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Checks integrity of created wallet.
+/// All records are decrypted with the wallet keys, records that can't be decrypted or found
+/// by their type and id are reported as invalid. Storage types that keep tags separately from records
+/// also report tags that don't belong to any record.
+/// The wallet has to be closed, otherwise WalletAlreadyOpenedError is returned.
+///
+/// #Params
+/// config: Wallet configuration json. The same as for indy_delete_wallet call.
+/// credentials: Wallet credentials json. The same as for indy_delete_wallet call.
+/// options: (optional) check options json.
+/// {
+///   "repair": optional<bool>, Remove invalid records and orphaned tags from the wallet. Defaults to false.
+///   "quarantine_path": optional<string>, Path to the file where removed records are appended before repair,
+///                      one json per line with base64 encoded raw data.
+/// }
+///
+/// #Returns
+/// report: check report json
+/// {
+///   "valid": bool, true if no problems are found,
+///   "metadata_errors": [string], problems of wallet metadata, records aren't checked if there are any,
+///   "checked_records": int, number of checked records,
+///   "invalid_records": [{
+///       "type": optional<string>, record type if it can be decrypted,
+///       "id": optional<string>, record id if it can be decrypted,
+///       "error": string, description of the problem
+///   }],
+///   "orphaned_tags": int, number of tags that don't belong to any record,
+///   "repaired": bool, true if found problems have been removed
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_check_wallet(command_handle: CommandHandle,
+                                config: *const c_char,
+                                credentials: *const c_char,
+                                options: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode,
+                                                     report: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_wallet: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, options: {:?}, cb: {:?}",
+           command_handle, config, credentials, options, cb);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_opt_json!(options, ErrorCode::CommonInvalidParam4, CheckOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_check_wallet: params config: {:?}, credentials: {:?}, options: {:?}", config, secret!(&credentials), options);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Check(
+            config,
+            credentials,
+            options.unwrap_or_default(),
+            boxed_callback_string!("indy_check_wallet", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_check_wallet: <<< res: {:?}", res);
+    res
+}
+
 /// Generate wallet master key.
 /// Returned key is compatible with "RAW" key derivation method.
 /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
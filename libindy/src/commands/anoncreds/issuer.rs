@@ -1,17 +1,22 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use ursa::cl::{
     new_nonce,
+    CredentialSignature,
+    Nonce,
+    RevocationRegistry as CryptoRevocationRegistry,
     RevocationRegistryDelta as CryptoRevocationRegistryDelta,
+    SignatureCorrectnessProof,
     Witness,
 };
 use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey};
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::anoncreds::AnoncredsCommand;
-use crate::domain::anoncreds::credential::{CredentialValues, Credential};
+use crate::domain::anoncreds::credential::{CredentialValues, IssuedCredential, RawCredentialValues, Credential};
 use crate::domain::anoncreds::credential_definition::{
     CredentialDefinition,
     CredentialDefinitionConfig,
@@ -23,8 +28,13 @@ use crate::domain::anoncreds::credential_definition::{
     TemporaryCredentialDefinition,
     CredentialDefinitionId
 };
+use crate::domain::anoncreds::credential_issuance_record::{
+    CredentialIssuanceRecord,
+    CredentialIssuanceRecordInfo,
+    CredentialIssuanceRecordTags,
+};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
-use crate::domain::anoncreds::credential_request::CredentialRequest;
+use crate::domain::anoncreds::credential_request::{CredentialIssuanceRequests, CredentialRequest};
 use crate::domain::anoncreds::revocation_registry::{
     RevocationRegistry,
     RevocationRegistryV1,
@@ -40,6 +50,16 @@ use crate::domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryInfo,
     RevocationRegistryId
 };
+use crate::domain::anoncreds::revocation_registry_pool::{
+    CreatedRevocationRegistry,
+    PoolIssuedCredential,
+    RevocationRegistryPool,
+    RevocationRegistryPoolConfig,
+    RevocationRegistryPoolEntry,
+    RevocationRegistryPoolInfo,
+    RevocationRegistryState,
+    RevocationRegistryStatus,
+};
 use crate::domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
     RevocationRegistryDeltaV1,
@@ -49,15 +69,17 @@ use crate::domain::crypto::did::DidValue;
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
-use crate::services::anoncreds::helpers::parse_cred_rev_id;
+use crate::services::anoncreds::issuer::Issuer;
+use crate::services::anoncreds::helpers::{attr_common_view, parse_cred_rev_id, encode_credential_values};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
-use indy_wallet::{RecordOptions, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
 
-use super::tails::{SDKTailsAccessor, store_tails_from_generator};
+use super::tails::{NoTailsAccessor, SDKTailsAccessor, store_tails_from_generator};
 use indy_api_types::{WalletHandle, CommandHandle};
-use indy_utils::next_command_handle;
+use indy_utils::{next_command_handle, sequence};
+use named_type::NamedType;
 
 pub enum IssuerCommand {
     CreateSchema(
@@ -121,22 +143,75 @@ pub enum IssuerCommand {
         WalletHandle,
         CredentialOffer, // credential offer
         CredentialRequest, // credential request
-        CredentialValues, // credential values
+        RawCredentialValues, // credential values
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
+        Option<CredentialIssuanceRecordTags>, // issuance record tags
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentials(
+        WalletHandle,
+        CredentialOffer, // credential offer
+        CredentialIssuanceRequests, // credential requests with credential values
+        Option<RevocationRegistryId>, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>),
+    CreateCredentialsContinue(
+        IndyResult<Vec<(CredentialSignature, SignatureCorrectnessProof)>>, // signatures in order of requests
+        CommandHandle),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeCredentials(
+        WalletHandle,
+        i32, // blob storage reader config handle
+        RevocationRegistryId, //revocation registry id
+        Vec<String>, //credential revoc ids
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeCredentialByRecord(
+        WalletHandle,
+        i32, // blob storage reader config handle
+        String, // issuance record id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetCredentialRecord(
+        WalletHandle,
+        String, // issuance record id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    SearchCredentialRecords(
+        WalletHandle,
+        Option<String>, // query json
+        Box<dyn Fn(IndyResult<(i32, usize)>) + Send>),
+    FetchCredentialRecords(
+        i32, // search handle
+        usize, // count
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CloseCredentialRecordsSearch(
+        i32, // search handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
             String, //revocation revoc id
             String, //credential revoc id
             Box<dyn Fn(Result<String, IndyError>) + Send>),*/
+    CreateRevocationRegistryPool(
+        WalletHandle,
+        DidValue, // issuer did
+        CredentialDefinitionId, // credential definition id
+        RevocationRegistryPoolConfig, // config
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateCredentialFromPool(
+        WalletHandle,
+        CredentialOffer, // credential offer
+        CredentialRequest, // credential request
+        RawCredentialValues, // credential values
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocationRegistryPool(
+        WalletHandle,
+        CredentialDefinitionId, // credential definition id
+        Box<dyn Fn(IndyResult<String>) + Send>),
     MergeRevocationRegistryDeltas(
         RevocationRegistryDelta, //revocation registry delta
         RevocationRegistryDelta, //other revocation registry delta
@@ -151,6 +226,66 @@ pub struct IssuerCommandExecutor {
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
     pending_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    pending_create_credentials: RefCell<HashMap<CommandHandle, (CredentialsIssuance, Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>)>>,
+    // blob storage config handles opened for revocation registry pools (type and config -> handle)
+    pool_tails_writers: RefCell<HashMap<(String, String), i32>>,
+    pool_tails_readers: RefCell<HashMap<(String, String), i32>>,
+    record_searches: RefCell<HashMap<i32, Box<WalletSearch>>>,
+}
+
+/// Batch issuance state kept on the command thread while credentials are signed on the crypto thread pool.
+struct CredentialsIssuance {
+    wallet_handle: WalletHandle,
+    schema_id: SchemaId,
+    cred_def_id: CredentialDefinitionId,
+    method_name: Option<String>,
+    rev_reg_id: Option<RevocationRegistryId>,
+    signing: Arc<CredentialsSigning>,
+    revocation: Option<CredentialsRevocation>,
+    record_tags: Vec<Option<CredentialIssuanceRecordTags>>,
+}
+
+struct CredentialsRevocation {
+    rev_reg: RevocationRegistryV1,
+    rev_reg_info: RevocationRegistryInfo,
+    tails_accessor: SDKTailsAccessor,
+}
+
+/// Data shared by the jobs signing a batch of credentials.
+struct CredentialsSigning {
+    cred_def: CredentialDefinitionV1,
+    cred_priv_key: CredentialDefinitionPrivateKey,
+    nonce: Nonce,
+    cred_requests: Vec<CredentialRequest>,
+    cred_values: Vec<CredentialValues>,
+    rev_idxs: Vec<Option<u32>>,
+    rev_reg_def: Option<RevocationRegistryDefinitionV1>,
+    rev_reg_json: Option<String>,
+    rev_key_priv: Option<RevocationRegistryDefinitionPrivate>,
+}
+
+impl CredentialsSigning {
+    fn sign(&self, idx: usize) -> IndyResult<(CredentialSignature, SignatureCorrectnessProof)> {
+        // every job works with its own copy of the accumulator
+        let mut rev_reg = self.rev_reg_json.as_ref()
+            .map(|rev_reg_json| serde_json::from_str::<CryptoRevocationRegistry>(rev_reg_json))
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistry")?;
+
+        let (credential_signature, signature_correctness_proof, _) =
+            Issuer::new().new_credential(&self.cred_def,
+                                         &self.cred_priv_key.value,
+                                         &self.nonce,
+                                         &self.cred_requests[idx],
+                                         &self.cred_values[idx],
+                                         self.rev_idxs[idx],
+                                         self.rev_reg_def.as_ref(),
+                                         rev_reg.as_mut(),
+                                         self.rev_key_priv.as_ref().map(|rev_key_priv| &rev_key_priv.value),
+                                         Some(&NoTailsAccessor {}))?;
+
+        Ok((credential_signature, signature_correctness_proof))
+    }
 }
 
 impl IssuerCommandExecutor {
@@ -167,6 +302,10 @@ impl IssuerCommandExecutor {
             crypto_service,
             pending_str_str_callbacks: RefCell::new(HashMap::new()),
             pending_str_callbacks: RefCell::new(HashMap::new()),
+            pending_create_credentials: RefCell::new(HashMap::new()),
+            pool_tails_writers: RefCell::new(HashMap::new()),
+            pool_tails_readers: RefCell::new(HashMap::new()),
+            record_searches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -212,18 +351,62 @@ impl IssuerCommandExecutor {
                 debug!(target: "issuer_command_executor", "CreateCredentialOffer command received");
                 cb(self.create_credential_offer(wallet_handle, &cred_def_id));
             }
-            IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
+            IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, record_tags, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
-                cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
+                cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle, record_tags.as_ref()));
+            }
+            IssuerCommand::CreateCredentials(wallet_handle, cred_offer, cred_issuance_requests, rev_reg_id, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentials command received");
+                self.create_credentials(wallet_handle, cred_offer, cred_issuance_requests, rev_reg_id, blob_storage_reader_handle, cb);
+            }
+            IssuerCommand::CreateCredentialsContinue(result, cb_id) => {
+                debug!(target: "issuer_command_executor", "CreateCredentialsContinue command received");
+                self._create_credentials_continue(cb_id, result);
             }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::RevokeCredentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeCredentials command received");
+                cb(self.revoke_credentials(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_ids));
+            }
+            IssuerCommand::RevokeCredentialByRecord(wallet_handle, blob_storage_reader_handle, record_id, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeCredentialByRecord command received");
+                cb(self.revoke_credential_by_record(wallet_handle, blob_storage_reader_handle, &record_id));
+            }
+            IssuerCommand::GetCredentialRecord(wallet_handle, record_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetCredentialRecord command received");
+                cb(self.get_credential_record(wallet_handle, &record_id));
+            }
+            IssuerCommand::SearchCredentialRecords(wallet_handle, query_json, cb) => {
+                debug!(target: "issuer_command_executor", "SearchCredentialRecords command received");
+                cb(self.search_credential_records(wallet_handle, query_json.as_ref().map(String::as_str)));
+            }
+            IssuerCommand::FetchCredentialRecords(search_handle, count, cb) => {
+                debug!(target: "issuer_command_executor", "FetchCredentialRecords command received");
+                cb(self.fetch_credential_records(search_handle, count));
+            }
+            IssuerCommand::CloseCredentialRecordsSearch(search_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CloseCredentialRecordsSearch command received");
+                cb(self.close_credential_records_search(search_handle));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
                         }*/
+            IssuerCommand::CreateRevocationRegistryPool(wallet_handle, issuer_did, cred_def_id, config, cb) => {
+                debug!(target: "issuer_command_executor", "CreateRevocationRegistryPool command received");
+                cb(self.create_revocation_registry_pool(wallet_handle, &issuer_did, &cred_def_id, config));
+            }
+            IssuerCommand::CreateCredentialFromPool(wallet_handle, cred_offer, cred_req, cred_values, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentialFromPool command received");
+                cb(self.create_credential_from_pool(wallet_handle, &cred_offer, &cred_req, &cred_values));
+            }
+            IssuerCommand::GetRevocationRegistryPool(wallet_handle, cred_def_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryPool command received");
+                cb(self.get_revocation_registry_pool(wallet_handle, &cred_def_id));
+            }
             IssuerCommand::MergeRevocationRegistryDeltas(rev_reg_delta, other_rev_reg_delta, cb) => {
                 debug!(target: "issuer_command_executor", "MergeRevocationRegistryDeltas command received");
                 cb(self.merge_revocation_registry_deltas(&mut RevocationRegistryDeltaV1::from(rev_reg_delta),
@@ -671,11 +854,14 @@ impl IssuerCommandExecutor {
                       wallet_handle: WalletHandle,
                       cred_offer: &CredentialOffer,
                       cred_request: &CredentialRequest,
-                      cred_values: &CredentialValues,
+                      cred_values: &RawCredentialValues,
                       rev_reg_id: Option<&RevocationRegistryId>,
-                      blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>, Option<String>)> {
-        debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
-               wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
+                      blob_storage_reader_handle: Option<i32>,
+                      record_tags: Option<&CredentialIssuanceRecordTags>) -> IndyResult<(String, Option<String>, Option<String>)> {
+        debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}, \
+               record_tags: {:?}", wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle, record_tags);
+
+        let cred_values = encode_credential_values(cred_values)?;
 
         let cred_def_id = match cred_offer.method_name {
             Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
@@ -787,6 +973,8 @@ impl IssuerCommandExecutor {
 
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
+
+            self._add_issuance_record(wallet_handle, &cred_def_id, r_reg_id, r_reg_info.curr_id, &cred_request.prover_did, record_tags)?;
         };
 
         let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
@@ -796,6 +984,343 @@ impl IssuerCommandExecutor {
         Ok((cred_json, cred_rev_id, rev_reg_delta_json))
     }
 
+    fn create_credentials(&self,
+                          wallet_handle: WalletHandle,
+                          cred_offer: CredentialOffer,
+                          cred_issuance_requests: CredentialIssuanceRequests,
+                          rev_reg_id: Option<RevocationRegistryId>,
+                          blob_storage_reader_handle: Option<i32>,
+                          cb: Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>) {
+        debug!("create_credentials >>> wallet_handle: {:?}, cred_offer: {:?}, cred_issuance_requests: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&cred_issuance_requests), rev_reg_id, blob_storage_reader_handle);
+
+        let issuance =
+            try_cb!(self._prepare_credentials_issuance(wallet_handle, cred_offer, cred_issuance_requests, rev_reg_id, blob_storage_reader_handle), cb);
+
+        let issuance_on_demand = issuance.signing.rev_reg_def.as_ref()
+            .map(|rev_reg_def| rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND)
+            .unwrap_or(false);
+
+        if issuance_on_demand {
+            // every issued credential updates the accumulator, so credentials are signed one by one
+            return cb(self._create_credentials_on_demand(issuance));
+        }
+
+        // indexes must be reserved before other commands are processed
+        if let (&Some(ref r_reg_id), &Some(ref revocation)) = (&issuance.rev_reg_id, &issuance.revocation) {
+            try_cb!(self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revocation.rev_reg_info), cb);
+        }
+
+        let signing = issuance.signing.clone();
+
+        let cb_id = next_command_handle();
+        self.pending_create_credentials.borrow_mut().insert(cb_id, (issuance, cb));
+
+        self._sign_credentials(signing, Box::new(move |res| {
+            CommandExecutor::instance().send(
+                Command::Anoncreds(
+                    AnoncredsCommand::Issuer(
+                        IssuerCommand::CreateCredentialsContinue(res, cb_id)
+                    )
+                )).unwrap();
+        }));
+    }
+
+    fn _prepare_credentials_issuance(&self,
+                                     wallet_handle: WalletHandle,
+                                     cred_offer: CredentialOffer,
+                                     cred_issuance_requests: CredentialIssuanceRequests,
+                                     rev_reg_id: Option<RevocationRegistryId>,
+                                     blob_storage_reader_handle: Option<i32>) -> IndyResult<CredentialsIssuance> {
+        let mut cred_requests = Vec::with_capacity(cred_issuance_requests.0.len());
+        let mut cred_values = Vec::with_capacity(cred_issuance_requests.0.len());
+        let mut record_tags = Vec::with_capacity(cred_issuance_requests.0.len());
+
+        for cred_issuance_request in cred_issuance_requests.0 {
+            cred_requests.push(cred_issuance_request.cred_req);
+            cred_values.push(cred_issuance_request.cred_values);
+            record_tags.push(cred_issuance_request.record_tags);
+        }
+
+        let cred_values = cred_values
+            .iter()
+            .map(encode_credential_values)
+            .collect::<IndyResult<Vec<CredentialValues>>>()?;
+
+        let cred_def_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
+            None => cred_offer.cred_def_id.clone()
+        };
+
+        let cred_def: CredentialDefinitionV1 =
+            CredentialDefinitionV1::from(
+                self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
+
+        let cred_priv_key: CredentialDefinitionPrivateKey =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        // signing happens after indexes are reserved, so values must be checked before
+        self._check_cred_values(&cred_def, &cred_values)?;
+
+        let (rev_reg_def, rev_reg_json, rev_key_priv, rev_idxs, revocation) = match rev_reg_id {
+            Some(ref r_reg_id) => {
+                let rev_reg_def: RevocationRegistryDefinitionV1 =
+                    RevocationRegistryDefinitionV1::from(
+                        self._wallet_get_rev_reg_def(wallet_handle, &r_reg_id)?);
+
+                let rev_reg: RevocationRegistryV1 =
+                    RevocationRegistryV1::from(
+                        self._wallet_get_rev_reg(wallet_handle, &r_reg_id)?);
+
+                let rev_key_priv: RevocationRegistryDefinitionPrivate =
+                    self.wallet_service.get_indy_object(wallet_handle, &r_reg_id.0, &RecordOptions::id_value())?;
+
+                let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
+
+                let first_id = rev_reg_info.curr_id + 1;
+                rev_reg_info.curr_id += cred_requests.len() as u32;
+
+                if rev_reg_info.curr_id > rev_reg_def.value.max_cred_num {
+                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
+                }
+
+                if rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+                    rev_reg_info.used_ids.extend(first_id..=rev_reg_info.curr_id);
+                }
+
+                // TODO: FIXME: Review error kind!
+                let blob_storage_reader_handle = blob_storage_reader_handle
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
+
+                let tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                           blob_storage_reader_handle,
+                                                           &rev_reg_def)?;
+
+                let rev_reg_json = serde_json::to_string(&rev_reg.value)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?;
+
+                let rev_idxs = (first_id..=rev_reg_info.curr_id).map(Some).collect();
+
+                (Some(rev_reg_def), Some(rev_reg_json), Some(rev_key_priv), rev_idxs,
+                 Some(CredentialsRevocation { rev_reg, rev_reg_info, tails_accessor }))
+            }
+            None => (None, None, None, vec![None; cred_requests.len()], None)
+        };
+
+        let signing = CredentialsSigning {
+            cred_def,
+            cred_priv_key,
+            nonce: cred_offer.nonce,
+            cred_requests,
+            cred_values,
+            rev_idxs,
+            rev_reg_def,
+            rev_reg_json,
+            rev_key_priv,
+        };
+
+        Ok(CredentialsIssuance {
+            wallet_handle,
+            schema_id: cred_offer.schema_id,
+            cred_def_id: cred_offer.cred_def_id,
+            method_name: cred_offer.method_name,
+            rev_reg_id,
+            signing: Arc::new(signing),
+            revocation,
+            record_tags,
+        })
+    }
+
+    fn _check_cred_values(&self, cred_def: &CredentialDefinitionV1, cred_values: &[CredentialValues]) -> IndyResult<()> {
+        let primary = serde_json::to_value(&cred_def.value.primary)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialPrimaryPublicKey")?;
+
+        let attr_names: HashSet<String> = primary["r"].as_object()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Attributes of CredentialDefinition not found"))?
+            .keys()
+            .filter(|attr| attr.as_str() != "master_secret")
+            .cloned()
+            .collect();
+
+        for (idx, values) in cred_values.iter().enumerate() {
+            let value_names: HashSet<String> = values.0.keys().map(|attr| attr_common_view(attr)).collect();
+
+            if value_names != attr_names {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Credential values of request {} don't match attributes of CredentialDefinition: {:?}", idx, attr_names)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _create_credentials_on_demand(&self, mut issuance: CredentialsIssuance) -> IndyResult<(String, Option<String>)> {
+        let signing = issuance.signing.clone();
+
+        let mut signatures = Vec::with_capacity(signing.cred_requests.len());
+        let mut rev_reg_delta = None;
+
+        {
+            let revocation = issuance.revocation.as_mut()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "RevocationRegistry not found"))?;
+
+            for idx in 0..signing.cred_requests.len() {
+                let (credential_signature, signature_correctness_proof, delta) =
+                    self.anoncreds_service.issuer.new_credential(&signing.cred_def,
+                                                                 &signing.cred_priv_key.value,
+                                                                 &signing.nonce,
+                                                                 &signing.cred_requests[idx],
+                                                                 &signing.cred_values[idx],
+                                                                 signing.rev_idxs[idx],
+                                                                 signing.rev_reg_def.as_ref(),
+                                                                 Some(&mut revocation.rev_reg.value),
+                                                                 signing.rev_key_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
+                                                                 Some(&revocation.tails_accessor))?;
+
+                signatures.push((credential_signature, signature_correctness_proof));
+                rev_reg_delta = _merge_crypto_rev_reg_deltas(rev_reg_delta, delta)?;
+            }
+        }
+
+        let res = self._complete_create_credentials(&issuance, signatures, rev_reg_delta)?;
+
+        if let (Some(r_reg_id), Some(revocation)) = (issuance.rev_reg_id, issuance.revocation) {
+            let rev_reg = RevocationRegistry::RevocationRegistryV1(revocation.rev_reg);
+
+            self.wallet_service.update_indy_object(issuance.wallet_handle, &r_reg_id.0, &rev_reg)?;
+            self.wallet_service.update_indy_object(issuance.wallet_handle, &r_reg_id.0, &revocation.rev_reg_info)?;
+        }
+
+        Ok(res)
+    }
+
+    fn _sign_credentials(&self,
+                         signing: Arc<CredentialsSigning>,
+                         cb: Box<dyn Fn(IndyResult<Vec<(CredentialSignature, SignatureCorrectnessProof)>>) + Send>) {
+        let count = signing.cred_requests.len();
+        let signatures = Arc::new(Mutex::new(Vec::with_capacity(count)));
+        let cb = Arc::new(Mutex::new(cb));
+
+        for idx in 0..count {
+            let signing = signing.clone();
+            let signatures = signatures.clone();
+            let cb = cb.clone();
+
+            crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+                let res = signing.sign(idx);
+
+                let mut signatures = signatures.lock().unwrap();
+                signatures.push((idx, res));
+
+                // the last finished job returns signatures of the whole batch
+                if signatures.len() == count {
+                    signatures.sort_by_key(|&(idx, _)| idx);
+                    let res = signatures.drain(..).map(|(_, res)| res).collect();
+                    (*cb.lock().unwrap())(res);
+                }
+            });
+        }
+    }
+
+    fn _create_credentials_continue(&self,
+                                    cb_id: CommandHandle,
+                                    result: IndyResult<Vec<(CredentialSignature, SignatureCorrectnessProof)>>) {
+        let (issuance, cb) = self.pending_create_credentials.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        cb(result.and_then(|signatures| self._complete_create_credentials(&issuance, signatures, None)))
+    }
+
+    fn _complete_create_credentials(&self,
+                                    issuance: &CredentialsIssuance,
+                                    signatures: Vec<(CredentialSignature, SignatureCorrectnessProof)>,
+                                    rev_reg_delta: Option<CryptoRevocationRegistryDelta>) -> IndyResult<(String, Option<String>)> {
+        let signing = &issuance.signing;
+
+        // all credentials of the batch share the same accumulator value
+        let witness_rev_reg_delta =
+            if let (&Some(ref r_reg_def), &Some(ref revocation)) = (&signing.rev_reg_def, &issuance.revocation) {
+                let (issued, revoked) = match r_reg_def.value.issuance_type {
+                    IssuanceType::ISSUANCE_ON_DEMAND => (revocation.rev_reg_info.used_ids.clone(), HashSet::new()),
+                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), revocation.rev_reg_info.used_ids.clone())
+                };
+
+                Some(CryptoRevocationRegistryDelta::from_parts(None, &revocation.rev_reg.value, &issued, &revoked))
+            } else {
+                None
+            };
+
+        let rev_reg_value = issuance.revocation.as_ref()
+            .map(|revocation| serde_json::to_value(&revocation.rev_reg.value))
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?;
+
+        let cred_rev_reg_id = match (issuance.rev_reg_id.as_ref(), issuance.method_name.as_ref()) {
+            (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
+            (rev_reg_id, _) => rev_reg_id.cloned()
+        };
+
+        let mut issued_credentials = Vec::with_capacity(signatures.len());
+
+        for (idx, (credential_signature, signature_correctness_proof)) in signatures.into_iter().enumerate() {
+            let witness =
+                if let (&Some(ref r_reg_def), &Some(ref revocation), &Some(ref rev_reg_delta), Some(rev_idx)) =
+                (&signing.rev_reg_def, &issuance.revocation, &witness_rev_reg_delta, signing.rev_idxs[idx]) {
+                    Some(Witness::new(rev_idx, r_reg_def.value.max_cred_num,
+                                      r_reg_def.value.issuance_type.to_bool(), rev_reg_delta, &revocation.tails_accessor)?)
+                } else {
+                    None
+                };
+
+            let rev_reg = rev_reg_value.as_ref()
+                .map(|r_reg_value| serde_json::from_value::<CryptoRevocationRegistry>(r_reg_value.clone()))
+                .map_or(Ok(None), |v| v.map(Some))
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistry")?;
+
+            let credential = Credential {
+                schema_id: issuance.schema_id.clone(),
+                cred_def_id: issuance.cred_def_id.clone(),
+                rev_reg_id: cred_rev_reg_id.clone(),
+                values: signing.cred_values[idx].clone(),
+                signature: credential_signature,
+                signature_correctness_proof,
+                rev_reg,
+                witness,
+            };
+
+            issued_credentials.push(IssuedCredential {
+                cred: credential,
+                cred_rev_id: signing.rev_idxs[idx].map(|rev_idx| rev_idx.to_string()),
+            });
+        }
+
+        if let Some(ref r_reg_id) = issuance.rev_reg_id {
+            let cred_def_id = match issuance.method_name {
+                Some(ref method_name) => issuance.cred_def_id.qualify(method_name),
+                None => issuance.cred_def_id.clone()
+            };
+
+            for (idx, rev_idx) in signing.rev_idxs.iter().enumerate() {
+                if let Some(rev_idx) = rev_idx {
+                    self._add_issuance_record(issuance.wallet_handle, &cred_def_id, r_reg_id, *rev_idx,
+                                              &signing.cred_requests[idx].prover_did, issuance.record_tags[idx].as_ref())?;
+                }
+            }
+        }
+
+        let creds_json = serde_json::to_string(&issued_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credentials")?;
+
+        let rev_reg_delta_json = rev_reg_delta
+            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        debug!("create_credentials <<< creds_json: {:?}, rev_reg_delta_json: {:?}", secret!(&creds_json), rev_reg_delta_json);
+
+        Ok((creds_json, rev_reg_delta_json))
+    }
+
     fn revoke_credential(&self,
                          wallet_handle: WalletHandle,
                          blob_storage_reader_handle: i32,
@@ -818,24 +1343,9 @@ impl IssuerCommandExecutor {
                                                        blob_storage_reader_handle,
                                                        &revocation_registry_definition)?;
 
-        if cred_revoc_id > revocation_registry_definition.value.max_cred_num + 1 {
-            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-        }
-
         let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
 
-        match revocation_registry_definition.value.issuance_type {
-            IssuanceType::ISSUANCE_ON_DEMAND => {
-                if !rev_reg_info.used_ids.remove(&cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-                };
-            }
-            IssuanceType::ISSUANCE_BY_DEFAULT => {
-                if !rev_reg_info.used_ids.insert(cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-                }
-            }
-        };
+        self._mark_revoked(&revocation_registry_definition, &mut rev_reg_info, cred_revoc_id)?;
 
         let rev_reg_delta =
             self.anoncreds_service.issuer.revoke(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, cred_revoc_id, &sdk_tails_accessor)?;
@@ -850,11 +1360,259 @@ impl IssuerCommandExecutor {
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
 
+        self._mark_issuance_record_revoked(wallet_handle, rev_reg_id, cred_revoc_id)?;
+
         debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
         Ok(rev_reg_delta_json)
     }
 
+    fn revoke_credentials(&self,
+                          wallet_handle: WalletHandle,
+                          blob_storage_reader_handle: i32,
+                          rev_reg_id: &RevocationRegistryId,
+                          cred_revoc_ids: &[String]) -> IndyResult<String> {
+        debug!("revoke_credentials >>> wallet_handle: {:?}, blob_storage_reader_handle:  {:?}, rev_reg_id: {:?}, cred_revoc_ids: {:?}",
+               wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(cred_revoc_ids));
+
+        let cred_revoc_ids = cred_revoc_ids
+            .iter()
+            .map(|cred_revoc_id| parse_cred_rev_id(cred_revoc_id))
+            .collect::<IndyResult<Vec<u32>>>()?;
+
+        let revocation_registry_definition: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
+
+        let mut rev_reg: RevocationRegistryV1 =
+            RevocationRegistryV1::from(
+                self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
+
+        let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                       blob_storage_reader_handle,
+                                                       &revocation_registry_definition)?;
+
+        let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+
+        let mut rev_reg_delta = None;
+
+        for &cred_revoc_id in cred_revoc_ids.iter() {
+            self._mark_revoked(&revocation_registry_definition, &mut rev_reg_info, cred_revoc_id)?;
+
+            let delta =
+                self.anoncreds_service.issuer.revoke(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, cred_revoc_id, &sdk_tails_accessor)?;
+
+            rev_reg_delta = _merge_crypto_rev_reg_deltas(rev_reg_delta, Some(delta))?;
+        }
+
+        let rev_reg_delta = rev_reg_delta
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Empty list of credential revocation ids has been passed"))?;
+
+        let rev_reg_delta = RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: rev_reg_delta });
+
+        let rev_reg_delta_json = serde_json::to_string(&rev_reg_delta)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
+
+        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
+        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
+
+        for cred_revoc_id in cred_revoc_ids {
+            self._mark_issuance_record_revoked(wallet_handle, rev_reg_id, cred_revoc_id)?;
+        }
+
+        debug!("revoke_credentials <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+
+        Ok(rev_reg_delta_json)
+    }
+
+    fn revoke_credential_by_record(&self,
+                                   wallet_handle: WalletHandle,
+                                   blob_storage_reader_handle: i32,
+                                   record_id: &str) -> IndyResult<String> {
+        debug!("revoke_credential_by_record >>> wallet_handle: {:?}, blob_storage_reader_handle: {:?}, record_id: {:?}",
+               wallet_handle, blob_storage_reader_handle, record_id);
+
+        let record: CredentialIssuanceRecord =
+            self.wallet_service.get_indy_object(wallet_handle, record_id, &RecordOptions::id_value())?;
+
+        if record.revoked {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Credential of issuance record: {} is already revoked", record_id)));
+        }
+
+        let res = self.revoke_credential(wallet_handle, blob_storage_reader_handle, &record.rev_reg_id, &record.cred_rev_id)?;
+
+        debug!("revoke_credential_by_record <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_credential_record(&self,
+                             wallet_handle: WalletHandle,
+                             record_id: &str) -> IndyResult<String> {
+        debug!("get_credential_record >>> wallet_handle: {:?}, record_id: {:?}", wallet_handle, record_id);
+
+        let record: CredentialIssuanceRecord =
+            self.wallet_service.get_indy_object(wallet_handle, record_id, &RecordOptions::id_value())?;
+
+        let record_info = CredentialIssuanceRecordInfo {
+            record_id: record_id.to_string(),
+            record,
+        };
+
+        let record_info_json = serde_json::to_string(&record_info)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialIssuanceRecord")?;
+
+        debug!("get_credential_record <<< record_info_json: {:?}", record_info_json);
+
+        Ok(record_info_json)
+    }
+
+    fn search_credential_records(&self,
+                                 wallet_handle: WalletHandle,
+                                 query_json: Option<&str>) -> IndyResult<(i32, usize)> {
+        debug!("search_credential_records >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+        let records_search =
+            self.wallet_service.search_indy_records::<CredentialIssuanceRecord>(wallet_handle, query_json.unwrap_or("{}"), &SearchOptions::id_value())?;
+
+        let total_count = records_search.get_total_count()?.unwrap_or(0);
+
+        let handle = sequence::get_next_id();
+
+        self.record_searches.borrow_mut().insert(handle, Box::new(records_search));
+
+        let res = (handle, total_count);
+
+        trace!("search_credential_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn fetch_credential_records(&self,
+                                search_handle: i32,
+                                count: usize) -> IndyResult<String> {
+        trace!("fetch_credential_records >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+        let mut searches = self.record_searches.borrow_mut();
+        let search = searches.get_mut(&search_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown CredentialRecordsSearch handle: {}", search_handle)))?;
+
+        let mut records_info: Vec<CredentialIssuanceRecordInfo> = Vec::new();
+
+        for _ in 0..count {
+            match search.fetch_next_record()? {
+                Some(record) => records_info.push(self._get_credential_record_info(&record)?),
+                None => break
+            }
+        }
+
+        let records_info_json = serde_json::to_string(&records_info)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialIssuanceRecord")?;
+
+        trace!("fetch_credential_records <<< records_info_json: {:?}", records_info_json);
+
+        Ok(records_info_json)
+    }
+
+    fn close_credential_records_search(&self, search_handle: i32) -> IndyResult<()> {
+        trace!("close_credential_records_search >>> search_handle: {:?}", search_handle);
+
+        match self.record_searches.borrow_mut().remove(&search_handle) {
+            Some(_) => Ok(()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown CredentialRecordsSearch handle: {}", search_handle)))
+        }?;
+
+        trace!("close_credential_records_search <<< res: ()");
+
+        Ok(())
+    }
+
+    fn _get_credential_record_info(&self, record: &WalletRecord) -> IndyResult<CredentialIssuanceRecordInfo> {
+        let record_id = record.get_id();
+
+        let value = record.get_value()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("CredentialIssuanceRecord not found for id: {}", record_id)))?;
+
+        let record: CredentialIssuanceRecord = serde_json::from_str(value)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize CredentialIssuanceRecord")?;
+
+        Ok(CredentialIssuanceRecordInfo {
+            record_id: record_id.to_string(),
+            record,
+        })
+    }
+
+    fn _add_issuance_record(&self,
+                            wallet_handle: WalletHandle,
+                            cred_def_id: &CredentialDefinitionId,
+                            rev_reg_id: &RevocationRegistryId,
+                            cred_rev_id: u32,
+                            prover_did: &DidValue,
+                            record_tags: Option<&CredentialIssuanceRecordTags>) -> IndyResult<()> {
+        let record = CredentialIssuanceRecord {
+            cred_def_id: cred_def_id.clone(),
+            rev_reg_id: rev_reg_id.clone(),
+            cred_rev_id: cred_rev_id.to_string(),
+            prover_did: prover_did.clone(),
+            timestamp: time::get_time().sec as u64,
+            revoked: false,
+            revoked_timestamp: None,
+            tags: record_tags.cloned().unwrap_or_default(),
+        };
+
+        let record_id = CredentialIssuanceRecord::id(rev_reg_id, &record.cred_rev_id);
+
+        self.wallet_service.add_indy_object(wallet_handle, &record_id, &record, &record.wallet_tags())?;
+
+        Ok(())
+    }
+
+    fn _mark_issuance_record_revoked(&self,
+                                     wallet_handle: WalletHandle,
+                                     rev_reg_id: &RevocationRegistryId,
+                                     cred_rev_id: u32) -> IndyResult<()> {
+        let record_id = CredentialIssuanceRecord::id(rev_reg_id, &cred_rev_id.to_string());
+
+        // credentials issued by previous versions have no issuance records
+        let mut record = match self.wallet_service.get_indy_opt_object::<CredentialIssuanceRecord>(wallet_handle, &record_id, &RecordOptions::id_value())? {
+            Some(record) => record,
+            None => return Ok(())
+        };
+
+        record.revoked = true;
+        record.revoked_timestamp = Some(time::get_time().sec as u64);
+
+        self.wallet_service.update_indy_object(wallet_handle, &record_id, &record)?;
+        self.wallet_service.update_record_tags(wallet_handle,
+                                               &self.wallet_service.add_prefix(CredentialIssuanceRecord::short_type_name()),
+                                               &record_id,
+                                               &record.wallet_tags())?;
+
+        Ok(())
+    }
+
+    fn _mark_revoked(&self,
+                     rev_reg_def: &RevocationRegistryDefinitionV1,
+                     rev_reg_info: &mut RevocationRegistryInfo,
+                     cred_revoc_id: u32) -> IndyResult<()> {
+        if cred_revoc_id > rev_reg_def.value.max_cred_num + 1 {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+        }
+
+        let found = match rev_reg_def.value.issuance_type {
+            IssuanceType::ISSUANCE_ON_DEMAND => rev_reg_info.used_ids.remove(&cred_revoc_id),
+            IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.insert(cred_revoc_id)
+        };
+
+        if !found {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+        }
+
+        Ok(())
+    }
+
     fn _recovery_credential(&self,
                             wallet_handle: WalletHandle,
                             blob_storage_reader_handle: i32,
@@ -914,6 +1672,246 @@ impl IssuerCommandExecutor {
         Ok(rev_reg_delta_json)
     }
 
+    fn create_revocation_registry_pool(&self,
+                                       wallet_handle: WalletHandle,
+                                       issuer_did: &DidValue,
+                                       cred_def_id: &CredentialDefinitionId,
+                                       config: RevocationRegistryPoolConfig) -> IndyResult<String> {
+        debug!("create_revocation_registry_pool >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config: {:?}",
+               wallet_handle, issuer_did, cred_def_id, config);
+
+        if self.wallet_service.record_exists::<RevocationRegistryPool>(wallet_handle, &cred_def_id.0)? {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("RevocationRegistryPool already exists for CredentialDefinition: {}", cred_def_id.0)));
+        }
+
+        let mut pool = RevocationRegistryPool {
+            issuer_did: issuer_did.clone(),
+            cred_def_id: cred_def_id.clone(),
+            config,
+            registries: Vec::new(),
+        };
+
+        let mut created_rev_regs = Vec::new();
+
+        self._pool_rotate(wallet_handle, &mut pool, &mut created_rev_regs)?;
+
+        self.wallet_service.add_indy_object(wallet_handle, &cred_def_id.0, &pool, &HashMap::new())?;
+
+        let created_rev_regs_json = serde_json::to_string(&created_rev_regs)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize created RevocationRegistries")?;
+
+        debug!("create_revocation_registry_pool <<< created_rev_regs_json: {:?}", created_rev_regs_json);
+
+        Ok(created_rev_regs_json)
+    }
+
+    fn create_credential_from_pool(&self,
+                                   wallet_handle: WalletHandle,
+                                   cred_offer: &CredentialOffer,
+                                   cred_request: &CredentialRequest,
+                                   cred_values: &RawCredentialValues) -> IndyResult<String> {
+        debug!("create_credential_from_pool >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values));
+
+        let cred_def_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
+            None => cred_offer.cred_def_id.clone()
+        };
+
+        let mut pool: RevocationRegistryPool =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        let mut created_rev_regs = Vec::new();
+
+        // the active registry can be missed if there was no ready one on rotation
+        if pool.active().is_none() {
+            self._pool_rotate(wallet_handle, &mut pool, &mut created_rev_regs)?;
+            self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &pool)?;
+        }
+
+        let blob_storage_reader_handle = self._pool_tails_reader(&pool.config)?;
+
+        let mut rev_reg_id = self._pool_active_rev_reg_id(&pool)?;
+
+        let res = match self.new_credential(wallet_handle, cred_offer, cred_request, cred_values, Some(&rev_reg_id), Some(blob_storage_reader_handle), None) {
+            // the active registry can be filled bypassing the pool, so rotate and retry once
+            Err(ref err) if err.kind() == IndyErrorKind::RevocationRegistryFull => {
+                self._pool_rotate(wallet_handle, &mut pool, &mut created_rev_regs)?;
+                self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &pool)?;
+
+                rev_reg_id = self._pool_active_rev_reg_id(&pool)?;
+
+                self.new_credential(wallet_handle, cred_offer, cred_request, cred_values, Some(&rev_reg_id), Some(blob_storage_reader_handle), None)
+            }
+            res => res
+        };
+
+        let (cred_json, cred_rev_id, rev_reg_delta_json) = res?;
+
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+        let free = pool.config.max_cred_num().saturating_sub(rev_reg_info.curr_id);
+
+        if free == 0 {
+            self._pool_rotate(wallet_handle, &mut pool, &mut created_rev_regs)?;
+        } else if free <= pool.config.threshold() {
+            self._pool_refill(wallet_handle, &mut pool, &mut created_rev_regs)?;
+        }
+
+        self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &pool)?;
+
+        let rev_reg_delta = match rev_reg_delta_json {
+            Some(ref rev_reg_delta_json) => Some(serde_json::from_str(rev_reg_delta_json)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistryDelta")?),
+            None => None
+        };
+
+        let issued_credential = PoolIssuedCredential {
+            cred: serde_json::from_str(&cred_json)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize Credential")?,
+            cred_rev_id: cred_rev_id
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Credential revocation id not found"))?,
+            rev_reg_id,
+            rev_reg_delta,
+            created_rev_regs,
+        };
+
+        let issued_credential_json = serde_json::to_string(&issued_credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize issued Credential")?;
+
+        debug!("create_credential_from_pool <<< issued_credential_json: {:?}", secret!(&issued_credential_json));
+
+        Ok(issued_credential_json)
+    }
+
+    fn get_revocation_registry_pool(&self,
+                                    wallet_handle: WalletHandle,
+                                    cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
+        debug!("get_revocation_registry_pool >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+        let pool: RevocationRegistryPool =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        let registries = pool.registries
+            .iter()
+            .map(|entry| {
+                let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, &entry.rev_reg_id)?);
+                let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &entry.rev_reg_id)?;
+
+                let mut revoked: Vec<u32> = match rev_reg_def.value.issuance_type {
+                    IssuanceType::ISSUANCE_ON_DEMAND => (1..=rev_reg_info.curr_id).filter(|id| !rev_reg_info.used_ids.contains(id)).collect(),
+                    IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.iter().cloned().collect()
+                };
+                revoked.sort();
+
+                Ok(RevocationRegistryStatus {
+                    rev_reg_id: entry.rev_reg_id.clone(),
+                    state: entry.state,
+                    max_cred_num: rev_reg_def.value.max_cred_num,
+                    issued: rev_reg_info.curr_id,
+                    revoked,
+                })
+            })
+            .collect::<IndyResult<Vec<RevocationRegistryStatus>>>()?;
+
+        let pool_info = RevocationRegistryPoolInfo {
+            cred_def_id: pool.cred_def_id,
+            config: pool.config,
+            registries,
+        };
+
+        let pool_info_json = serde_json::to_string(&pool_info)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryPool")?;
+
+        debug!("get_revocation_registry_pool <<< pool_info_json: {:?}", pool_info_json);
+
+        Ok(pool_info_json)
+    }
+
+    fn _pool_create_rev_reg(&self,
+                            wallet_handle: WalletHandle,
+                            pool: &mut RevocationRegistryPool) -> IndyResult<CreatedRevocationRegistry> {
+        let tails_writer_handle = self._pool_tails_writer(&pool.config)?;
+
+        let (rev_reg_id, rev_reg_def_json, rev_reg_entry_json) =
+            self.create_and_store_revocation_registry(wallet_handle,
+                                                      &pool.issuer_did,
+                                                      pool.config.rev_reg_type.as_ref().map(String::as_str),
+                                                      &pool.next_tag(),
+                                                      &pool.cred_def_id,
+                                                      &pool.config.rev_reg_config(),
+                                                      tails_writer_handle)?;
+
+        let rev_reg_id = RevocationRegistryId(rev_reg_id);
+
+        pool.registries.push(RevocationRegistryPoolEntry {
+            rev_reg_id: rev_reg_id.clone(),
+            state: RevocationRegistryState::Ready,
+        });
+
+        Ok(CreatedRevocationRegistry {
+            rev_reg_id,
+            rev_reg_def: serde_json::from_str(&rev_reg_def_json)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistryDefinition")?,
+            rev_reg_entry: serde_json::from_str(&rev_reg_entry_json)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistry")?,
+        })
+    }
+
+    /// Activates the next registry of the pool (creating it if there is no ready one)
+    /// and creates missing ready registries right away.
+    fn _pool_rotate(&self,
+                    wallet_handle: WalletHandle,
+                    pool: &mut RevocationRegistryPool,
+                    created_rev_regs: &mut Vec<CreatedRevocationRegistry>) -> IndyResult<()> {
+        if !pool.rotate() {
+            created_rev_regs.push(self._pool_create_rev_reg(wallet_handle, pool)?);
+            pool.rotate();
+        }
+
+        self._pool_refill(wallet_handle, pool, created_rev_regs)
+    }
+
+    fn _pool_refill(&self,
+                    wallet_handle: WalletHandle,
+                    pool: &mut RevocationRegistryPool,
+                    created_rev_regs: &mut Vec<CreatedRevocationRegistry>) -> IndyResult<()> {
+        while pool.ready_count() < pool.config.pregenerated() {
+            created_rev_regs.push(self._pool_create_rev_reg(wallet_handle, pool)?);
+        }
+
+        Ok(())
+    }
+
+    fn _pool_active_rev_reg_id(&self, pool: &RevocationRegistryPool) -> IndyResult<RevocationRegistryId> {
+        pool.active()
+            .map(|entry| entry.rev_reg_id.clone())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Active RevocationRegistry not found"))
+    }
+
+    fn _pool_tails_writer(&self, config: &RevocationRegistryPoolConfig) -> IndyResult<i32> {
+        let key = (config.tails_writer_type().to_string(), config.tails_writer_config.to_string());
+
+        if let Some(handle) = self.pool_tails_writers.borrow().get(&key) {
+            return Ok(*handle);
+        }
+
+        let handle = self.blob_storage_service.open_writer(&key.0, &key.1)?;
+        self.pool_tails_writers.borrow_mut().insert(key, handle);
+        Ok(handle)
+    }
+
+    fn _pool_tails_reader(&self, config: &RevocationRegistryPoolConfig) -> IndyResult<i32> {
+        let key = (config.tails_writer_type().to_string(), config.tails_writer_config.to_string());
+
+        if let Some(handle) = self.pool_tails_readers.borrow().get(&key) {
+            return Ok(*handle);
+        }
+
+        let handle = self.blob_storage_service.open_reader(&key.0, &key.1)?;
+        self.pool_tails_readers.borrow_mut().insert(key, handle);
+        Ok(handle)
+    }
+
     fn merge_revocation_registry_deltas(&self,
                                         rev_reg_delta: &mut RevocationRegistryDeltaV1,
                                         other_rev_reg_delta: &RevocationRegistryDeltaV1) -> IndyResult<String> {
@@ -957,3 +1955,15 @@ impl IssuerCommandExecutor {
         self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())
     }
 }
+
+fn _merge_crypto_rev_reg_deltas(rev_reg_delta: Option<CryptoRevocationRegistryDelta>,
+                                other_rev_reg_delta: Option<CryptoRevocationRegistryDelta>) -> IndyResult<Option<CryptoRevocationRegistryDelta>> {
+    match (rev_reg_delta, other_rev_reg_delta) {
+        (Some(mut rev_reg_delta), Some(other_rev_reg_delta)) => {
+            rev_reg_delta.merge(&other_rev_reg_delta)?;
+            Ok(Some(rev_reg_delta))
+        }
+        (rev_reg_delta, None) => Ok(rev_reg_delta),
+        (None, other_rev_reg_delta) => Ok(other_rev_reg_delta)
+    }
+}
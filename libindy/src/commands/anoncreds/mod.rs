@@ -12,7 +12,8 @@ use crate::services::blob_storage::BlobStorageService;
 use crate::services::pool::PoolService;
 use indy_wallet::WalletService;
 use crate::services::crypto::CryptoService;
-use crate::services::anoncreds::helpers::to_unqualified;
+use crate::services::anoncreds::helpers::{to_unqualified, encode_credential_values};
+use crate::domain::anoncreds::credential::RawCredentialValues;
 
 use indy_api_types::errors::prelude::*;
 
@@ -24,6 +25,9 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeCredentialAttrValues(
+        RawCredentialValues, // raw credential values
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -68,6 +72,10 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            AnoncredsCommand::EncodeCredentialAttrValues(raw_values, cb) => {
+                debug!("EncodeCredentialAttrValues command received");
+                cb(encode_credential_values(&raw_values).map(|values| json!(values).to_string()));
+            }
         };
     }
 }
@@ -133,16 +133,19 @@ struct SearchForProofRequest {
     search: WalletSearch,
     interval: Option<NonRevocedInterval>,
     predicate_info: Option<PredicateInfo>,
+    in_memory_query: Option<Query>,
 }
 
 impl SearchForProofRequest {
     fn new(search: WalletSearch,
            interval: Option<NonRevocedInterval>,
-           predicate_info: Option<PredicateInfo>, ) -> Self {
+           predicate_info: Option<PredicateInfo>,
+           in_memory_query: Option<Query>) -> Self {
         Self {
             search,
             interval,
             predicate_info,
+            in_memory_query,
         }
     }
 }
@@ -588,14 +591,13 @@ impl ProverCommandExecutor {
                                                                                         &requested_attr.restrictions,
                                                                                         &extra_query)?;
 
-            let credentials_search =
-                self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query.to_string(), &SearchOptions::id_value())?;
+            let (credentials_search, in_memory_query) = self._search_credentials(wallet_handle, query)?;
 
             let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_attr.non_revoked);
 
             credentials_for_proof_request_search.insert(attr_id.to_string(),
                                                         SearchForProofRequest::new(
-                                                            credentials_search, interval, None));
+                                                            credentials_search, interval, None, in_memory_query));
         }
 
         for (predicate_id, requested_predicate) in proof_req.requested_predicates.iter() {
@@ -606,14 +608,13 @@ impl ProverCommandExecutor {
                                                                                         &requested_predicate.restrictions,
                                                                                         &extra_query)?;
 
-            let credentials_search =
-                self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query.to_string(), &SearchOptions::id_value())?;
+            let (credentials_search, in_memory_query) = self._search_credentials(wallet_handle, query)?;
 
             let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_predicate.non_revoked);
 
             credentials_for_proof_request_search.insert(predicate_id.to_string(),
                                                         SearchForProofRequest::new(
-                                                            credentials_search, interval, Some(requested_predicate.clone())));
+                                                            credentials_search, interval, Some(requested_predicate.clone()), in_memory_query));
         }
 
         let search_handle = sequence::get_next_id();
@@ -634,7 +635,7 @@ impl ProverCommandExecutor {
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown item referent {} for CredentialsSearch handle: {}", item_referent, search_handle)))?;
 
         let requested_credentials: Vec<RequestedCredential> =
-            self._get_requested_credentials(&mut search.search, search.predicate_info.as_ref(), &search.interval, Some(count))?;
+            self._get_requested_credentials(&mut search.search, search.predicate_info.as_ref(), search.in_memory_query.as_ref(), &search.interval, Some(count))?;
 
         let requested_credentials_json = serde_json::to_string(&requested_credentials)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of RequestedCredential")?;
@@ -828,19 +829,33 @@ impl ProverCommandExecutor {
         debug!("_query_requested_credentials >>> wallet_handle: {:?}, query_json: {:?}, predicate_info: {:?}",
                wallet_handle, query_json, predicate_info);
 
-        let mut credentials_search =
-            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json.to_string(), &SearchOptions::id_value())?;
+        let (mut credentials_search, in_memory_query) = self._search_credentials(wallet_handle, query_json.clone())?;
 
-        let credentials = self._get_requested_credentials(&mut credentials_search, predicate_info, interval, None)?;
+        let credentials = self._get_requested_credentials(&mut credentials_search, predicate_info, in_memory_query.as_ref(), interval, None)?;
 
         debug!("_query_requested_credentials <<< credentials: {:?}", credentials);
 
         Ok(credentials)
     }
 
+    // Operators the wallet can't evaluate on encrypted credential tags are checked against fetched tags
+    fn _search_credentials(&self,
+                           wallet_handle: WalletHandle,
+                           query: Query) -> IndyResult<(WalletSearch, Option<Query>)> {
+        let (wallet_query, in_memory_query) = self.anoncreds_service.prover.split_credentials_query(query);
+
+        let options = if in_memory_query.is_some() { SearchOptions::full() } else { SearchOptions::id_value() };
+
+        let credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &wallet_query.to_string(), &options)?;
+
+        Ok((credentials_search, in_memory_query))
+    }
+
     fn _get_requested_credentials(&self,
                                   credentials_search: &mut WalletSearch,
                                   predicate_info: Option<&PredicateInfo>,
+                                  in_memory_query: Option<&Query>,
                                   interval: &Option<NonRevocedInterval>,
                                   max_count: Option<usize>) -> IndyResult<Vec<RequestedCredential>> {
         let mut credentials: Vec<RequestedCredential> = Vec::new();
@@ -850,6 +865,13 @@ impl ProverCommandExecutor {
         }
 
         while let Some(credential_record) = credentials_search.fetch_next_record()? {
+            if let Some(query) = in_memory_query {
+                let tags = credential_record.get_tags()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Credential tags not found"))?;
+
+                if !self.anoncreds_service.prover.credential_tags_match_query(tags, query) { continue; }
+            }
+
             let (referent, credential) = self._get_credential(&credential_record)?;
 
             if let Some(predicate) = predicate_info {
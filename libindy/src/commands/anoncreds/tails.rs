@@ -64,6 +64,17 @@ impl RevocationTailsAccessor for SDKTailsAccessor {
     }
 }
 
+/// Tails accessor for credentials signed outside of the command thread.
+/// Issuance into an `ISSUANCE_BY_DEFAULT` registry doesn't update the accumulator, so tails are never read.
+pub struct NoTailsAccessor {}
+
+impl RevocationTailsAccessor for NoTailsAccessor {
+    fn access_tail(&self, tail_id: u32, _accessor: &mut dyn FnMut(&Tail)) -> Result<(), UrsaCryptoError> {
+        debug!("access_tail >>> tail_id: {:?}", tail_id);
+        Err(UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidState, "Tails are not available for this operation"))
+    }
+}
+
 pub fn store_tails_from_generator(service: Rc<BlobStorageService>,
                                   writer_handle: i32,
                                   rtg: &mut RevocationTailsGenerator) -> IndyResult<(String, String)> {
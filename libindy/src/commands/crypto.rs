@@ -12,6 +12,7 @@ use crate::utils::crypto::base64;
 use crate::utils::crypto::chacha20poly1305_ietf;
 use crate::domain::crypto::combo_box::ComboBox;
 use indy_api_types::WalletHandle;
+use indy_api_types::crypto::*;
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
@@ -19,6 +20,16 @@ pub const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
 pub const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
 
 pub enum CryptoCommand {
+    RegisterKeyProvider(
+        String, // crypto type
+        KeyProviderCreateKey, // create key
+        KeyProviderSign, // sign
+        KeyProviderCryptoBox, // crypto box
+        KeyProviderCryptoBoxOpen, // crypto box open
+        KeyProviderCryptoBoxSealOpen, // crypto box seal open
+        KeyProviderFreeResult, // free result
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
     CreateKey(
         WalletHandle,
         KeyInfo, // key info
@@ -103,6 +114,10 @@ impl CryptoCommandExecutor {
 
     pub fn execute(&self, command: CryptoCommand) {
         match command {
+            CryptoCommand::RegisterKeyProvider(crypto_type, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, free_result, cb) => {
+                debug!("RegisterKeyProvider command received");
+                cb(self.register_key_provider(&crypto_type, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, free_result));
+            }
             CryptoCommand::CreateKey(wallet_handle, key_info, cb) => {
                 debug!("CreateKey command received");
                 cb(self.create_key(wallet_handle, &key_info));
@@ -150,6 +165,23 @@ impl CryptoCommandExecutor {
         };
     }
 
+    fn register_key_provider(&self,
+                             crypto_type: &str,
+                             create_key: KeyProviderCreateKey,
+                             sign: KeyProviderSign,
+                             crypto_box: KeyProviderCryptoBox,
+                             crypto_box_open: KeyProviderCryptoBoxOpen,
+                             crypto_box_seal_open: KeyProviderCryptoBoxSealOpen,
+                             free_result: KeyProviderFreeResult) -> IndyResult<()> {
+        debug!("register_key_provider >>> crypto_type: {:?}", crypto_type);
+
+        let res = self.crypto_service
+            .register_key_provider(crypto_type, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, free_result)?;
+
+        debug!("register_key_provider <<< res: {:?}", res);
+        Ok(res)
+    }
+
     fn create_key(&self, wallet_handle: WalletHandle, key_info: &KeyInfo) -> IndyResult<String> {
         debug!(
             "create_key >>> wallet_handle: {:?}, key_info: {:?}",
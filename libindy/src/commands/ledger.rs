@@ -10,6 +10,7 @@ use crate::domain::anoncreds::credential_definition::{CredentialDefinition, Cred
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::schema::{Schema, SchemaV1, SchemaId};
+use crate::domain::cache::GetCacheOptions;
 use crate::domain::crypto::did::{Did, DidValue};
 use crate::domain::crypto::key::Key;
 use crate::domain::ledger::node::NodeOperationData;
@@ -21,6 +22,7 @@ use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
 use crate::services::pool::{
+    AsyncPool,
     PoolService,
     parse_response_metadata
 };
@@ -43,6 +45,17 @@ pub enum LedgerCommand {
         PoolHandle, // pool handle
         String, // request json
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SubmitCachedRequest(
+        PoolHandle, // pool handle
+        String, // request json
+        GetCacheOptions, // options
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    SubmitCachedRequestContinue(
+        PoolHandle, // pool handle
+        String, // request json
+        GetCacheOptions, // options
+        IndyResult<String>, // response json
+        CommandHandle),
     SubmitAck(
         CommandHandle,
         IndyResult<String>, // result json or error
@@ -130,6 +143,17 @@ pub enum LedgerCommand {
         Option<String>, // ledger type
         i32, // data
         Box<dyn Fn(IndyResult<String>) + Send>),
+    GetVerifiedTxn(
+        PoolHandle, // pool handle
+        Option<DidValue>, // submitter did
+        Option<String>, // ledger type
+        i32, // seq no
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetVerifiedTxnContinue(
+        PoolHandle, // pool handle
+        String, // request json
+        IndyResult<String>, // response json
+        CommandHandle),
     BuildPoolConfigRequest(
         DidValue, // submitter did
         bool, // writes
@@ -280,6 +304,8 @@ pub struct LedgerCommandExecutor {
 
     send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    verified_txn_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    cached_request_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
 }
 
 impl LedgerCommandExecutor {
@@ -294,6 +320,8 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            verified_txn_callbacks: RefCell::new(HashMap::new()),
+            cached_request_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -307,6 +335,14 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "SubmitRequest command received");
                 self.submit_request(handle, &request_json, cb);
             }
+            LedgerCommand::SubmitCachedRequest(handle, request_json, options, cb) => {
+                debug!(target: "ledger_command_executor", "SubmitCachedRequest command received");
+                self.submit_cached_request(handle, &request_json, options, cb);
+            }
+            LedgerCommand::SubmitCachedRequestContinue(handle, request_json, options, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "SubmitCachedRequestContinue command received");
+                self._submit_cached_request_continue(handle, &request_json, options, pool_response, cb_id);
+            }
             LedgerCommand::SubmitAck(handle, result) => {
                 debug!(target: "ledger_command_executor", "SubmitAck command received");
                 match self.send_callbacks.borrow_mut().remove(&handle) {
@@ -402,6 +438,14 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "BuildGetTxnRequest command received");
                 cb(self.build_get_txn_request(submitter_did.as_ref(), ledger_type.as_ref().map(String::as_str), seq_no));
             }
+            LedgerCommand::GetVerifiedTxn(pool_handle, submitter_did, ledger_type, seq_no, cb) => {
+                debug!(target: "ledger_command_executor", "GetVerifiedTxn command received");
+                self.get_verified_txn(pool_handle, submitter_did.as_ref(), ledger_type.as_ref().map(String::as_str), seq_no, cb);
+            }
+            LedgerCommand::GetVerifiedTxnContinue(pool_handle, request_json, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetVerifiedTxnContinue command received");
+                self._get_verified_txn_continue(pool_handle, &request_json, pool_response, cb_id);
+            }
             LedgerCommand::BuildPoolConfigRequest(submitter_did, writes, force, cb) => {
                 debug!(target: "ledger_command_executor", "BuildPoolConfigRequest command received");
                 cb(self.build_pool_config_request(&submitter_did, writes, force));
@@ -539,7 +583,10 @@ impl LedgerCommandExecutor {
         debug!("sign_and_submit_request >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}",
                pool_handle, wallet_handle, submitter_did, request_json);
 
-        match self._sign_request(wallet_handle, submitter_did, request_json, SignatureType::Single) {
+        // signed requests can't be changed, so fully-qualified DIDs are replaced before signing
+        let (pool_handle, request_json) = try_cb!(self.pool_service.route_request(pool_handle, request_json), cb);
+
+        match self._sign_request(wallet_handle, submitter_did, &request_json, SignatureType::Single) {
             Ok(signed_request) => self.submit_request(pool_handle, signed_request.as_str(), cb),
             Err(err) => cb(Err(err))
         }
@@ -603,11 +650,90 @@ impl LedgerCommandExecutor {
             return cb(Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Request is invalid json: {:?}", err))));
         }
 
-        let x: IndyResult<CommandHandle> = self.pool_service.send_tx(handle, request_json);
-        match x {
-            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, cb); }
-            Err(err) => { cb(Err(err)); }
-        };
+        let (handle, request_json) = try_cb!(self.pool_service.route_request(handle, request_json), cb);
+
+        self._submit(handle, &request_json, None, None, cb);
+    }
+
+    /// Submits the request through the async handle of the pool, the reply is passed back
+    /// to the command thread to call the callback.
+    fn _submit(&self,
+               handle: PoolHandle,
+               request_json: &str,
+               nodes: Option<&str>,
+               timeout: Option<i32>,
+               cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        let pool = try_cb!(AsyncPool::get(handle), cb);
+
+        let cb_id = next_command_handle();
+        self.send_callbacks.borrow_mut().insert(cb_id, cb);
+
+        pool.submit_action_with_cb(request_json, nodes, timeout, Box::new(move |res| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::SubmitAck(cb_id, res)
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn submit_cached_request(&self,
+                             handle: PoolHandle,
+                             request_json: &str,
+                             options: GetCacheOptions,
+                             cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("submit_cached_request >>> handle: {:?}, request_json: {:?}, options: {:?}", handle, request_json, options);
+
+        let (handle, request_json) = try_cb!(self.pool_service.route_request(handle, request_json), cb);
+        let request_json = request_json.as_str();
+
+        if let Some(reply) = try_cb!(self.pool_service.get_cached_reply(handle, request_json, &options), cb) {
+            debug!("submit_cached_request <<< reply taken from the read cache");
+            return cb(Ok(reply));
+        }
+
+        if options.no_update.unwrap_or(false) {
+            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
+        }
+
+        let cb_id = next_command_handle();
+        self.cached_request_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let request = request_json.to_string();
+
+        self.submit_request(handle, request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::SubmitCachedRequestContinue(
+                        handle,
+                        request.clone(),
+                        options.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+
+        debug!("submit_cached_request <<<");
+    }
+
+    fn _submit_cached_request_continue(&self,
+                                       handle: PoolHandle,
+                                       request_json: &str,
+                                       options: GetCacheOptions,
+                                       pool_response: IndyResult<String>,
+                                       cb_id: CommandHandle) {
+        let cb = self.cached_request_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+
+        if !options.no_store.unwrap_or(false) {
+            if let Err(err) = self.pool_service.cache_reply(handle, request_json, &pool_response) {
+                warn!("Can't store the reply in the pool read cache: {:?}", err);
+            }
+        }
+
+        cb(Ok(pool_response))
     }
 
     fn submit_action(&self,
@@ -622,11 +748,9 @@ impl LedgerCommandExecutor {
             return cb(Err(err));
         }
 
-        let x: IndyResult<CommandHandle> = self.pool_service.send_action(handle, request_json, nodes, timeout);
-        match x {
-            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, cb); }
-            Err(err) => { cb(Err(err)); }
-        };
+        let (handle, request_json) = try_cb!(self.pool_service.route_request(handle, request_json), cb);
+
+        self._submit(handle, &request_json, nodes, timeout, cb);
     }
 
     fn sign_request(&self,
@@ -889,6 +1013,67 @@ impl LedgerCommandExecutor {
         Ok(res)
     }
 
+    fn get_verified_txn(&self,
+                        pool_handle: PoolHandle,
+                        submitter_did: Option<&DidValue>,
+                        ledger_type: Option<&str>,
+                        seq_no: i32,
+                        cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("get_verified_txn >>> pool_handle: {:?}, submitter_did: {:?}, ledger_type: {:?}, seq_no: {:?}",
+               pool_handle, submitter_did, ledger_type, seq_no);
+
+        let request_json = try_cb!(self.build_get_txn_request(submitter_did, ledger_type, seq_no), cb);
+        let (pool_handle, request_json) = try_cb!(self.pool_service.route_request(pool_handle, &request_json), cb);
+
+        let cb_id = next_command_handle();
+        self.verified_txn_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let request = request_json.clone();
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetVerifiedTxnContinue(
+                        pool_handle,
+                        request.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+
+        debug!("get_verified_txn <<<");
+    }
+
+    fn _get_verified_txn_continue(&self, pool_handle: PoolHandle, request_json: &str, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.verified_txn_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self._verify_get_txn_response(pool_handle, request_json, &pool_response))
+    }
+
+    fn _verify_get_txn_response(&self, pool_handle: PoolHandle, request_json: &str, response: &str) -> IndyResult<String> {
+        debug!("_verify_get_txn_response >>> pool_handle: {:?}, request_json: {:?}, response: {:?}", pool_handle, request_json, response);
+
+        let mut txn = self.ledger_service.parse_get_txn_response(response)?;
+
+        let verdict = self.pool_service.verify_reply_state_proof(Some(pool_handle), None, request_json, response)?;
+
+        if !verdict.verified {
+            return Err(err_msg(IndyErrorKind::InvalidTransaction,
+                               format!("Inclusion of the transaction into the ledger is not proved: {:?}", verdict.failure)));
+        }
+
+        txn.verified = true;
+
+        let res = serde_json::to_string(&txn)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize verified transaction")?;
+
+        debug!("_verify_get_txn_response <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn build_pool_config_request(&self,
                                  submitter_did: &DidValue,
                                  writes: bool,
@@ -1233,6 +1418,7 @@ impl LedgerCommandExecutor {
     }
 
     fn get_schema(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &SchemaId, cb: BoxedCallbackStringStringSend) {
+        let pool_handle = try_cb!(self.pool_service.route_id(pool_handle, &id.0), cb);
         let request_json = try_cb!(self.build_get_schema_request(submitter_did, id), cb);
 
         let cb_id = next_command_handle();
@@ -1259,6 +1445,7 @@ impl LedgerCommandExecutor {
     }
 
     fn get_cred_def(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &CredentialDefinitionId, cb: BoxedCallbackStringStringSend) {
+        let pool_handle = try_cb!(self.pool_service.route_id(pool_handle, &id.0), cb);
         let request_json = try_cb!(self.build_get_cred_def_request(submitter_did, id), cb);
 
         let cb_id = next_command_handle();
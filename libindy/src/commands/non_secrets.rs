@@ -2,11 +2,11 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{RecordOperation, Tags};
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletEvent, WalletRecord, WalletSearch, WalletService};
 use indy_utils::sequence;
-use indy_api_types::WalletHandle;
+use indy_api_types::{SubscriptionHandle, WalletHandle};
 
 
 pub enum NonSecretsCommand {
@@ -40,6 +40,9 @@ pub enum NonSecretsCommand {
                  String, // type
                  String, // id
                  Box<dyn Fn(IndyResult<()>) + Send>),
+    BatchRecords(WalletHandle,
+                 Vec<RecordOperation>, // operations
+                 Box<dyn Fn(IndyResult<()>) + Send>),
     GetRecord(WalletHandle,
               String, // type
               String, // id
@@ -56,6 +59,13 @@ pub enum NonSecretsCommand {
                            Box<dyn Fn(IndyResult<String>) + Send>),
     CloseSearch(i32, // wallet search handle
                 Box<dyn Fn(IndyResult<()>) + Send>),
+    SubscribeRecords(WalletHandle,
+                     String, // type
+                     String, // query json
+                     Box<dyn Fn(SubscriptionHandle, String) + Send>, // event callback
+                     Box<dyn Fn(IndyResult<SubscriptionHandle>) + Send>),
+    UnsubscribeRecords(SubscriptionHandle,
+                       Box<dyn Fn(IndyResult<()>) + Send>),
 }
 
 pub struct NonSecretsCommandExecutor {
@@ -97,6 +107,10 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "DeleteRecord command received");
                 cb(self.delete_record(handle, &type_, &id));
             }
+            NonSecretsCommand::BatchRecords(handle, operations, cb) => {
+                debug!(target: "non_secrets_command_executor", "BatchRecords command received");
+                cb(self.batch_records(handle, &operations));
+            }
             NonSecretsCommand::GetRecord(handle, type_, id, options_json, cb) => {
                 debug!(target: "non_secrets_command_executor", "GetRecord command received");
                 cb(self.get_record(handle, &type_, &id, &options_json));
@@ -113,6 +127,14 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "CloseSearch command received");
                 cb(self.close_search(wallet_search_handle));
             }
+            NonSecretsCommand::SubscribeRecords(wallet_handle, type_, query_json, event_cb, cb) => {
+                debug!(target: "non_secrets_command_executor", "SubscribeRecords command received");
+                cb(self.subscribe_records(wallet_handle, &type_, &query_json, event_cb));
+            }
+            NonSecretsCommand::UnsubscribeRecords(subscription_handle, cb) => {
+                debug!(target: "non_secrets_command_executor", "UnsubscribeRecords command received");
+                cb(self.unsubscribe_records(subscription_handle));
+            }
         };
     }
 
@@ -215,6 +237,22 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn batch_records(&self,
+                     wallet_handle: WalletHandle,
+                     operations: &[RecordOperation]) -> IndyResult<()> {
+        trace!("batch_records >>> wallet_handle: {:?}, operations: {:?}", wallet_handle, operations);
+
+        for operation in operations {
+            self._check_type(operation.type_())?;
+        }
+
+        self.wallet_service.batch_records(wallet_handle, operations)?;
+
+        trace!("batch_records <<< res: ()");
+
+        Ok(())
+    }
+
     fn get_record(&self,
                   wallet_handle: WalletHandle,
                   type_: &str,
@@ -304,6 +342,42 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    // Events carry only ids and tags, so records of internal types can be watched as well,
+    // e.g. "Indy::Credential" ones stored by indy_prover_store_credential
+    fn subscribe_records(&self,
+                         wallet_handle: WalletHandle,
+                         type_: &str,
+                         query_json: &str,
+                         event_cb: Box<dyn Fn(SubscriptionHandle, String) + Send>) -> IndyResult<SubscriptionHandle> {
+        trace!("subscribe_records >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}", wallet_handle, type_, query_json);
+
+        let res = self.wallet_service.subscribe_records(
+            wallet_handle,
+            type_,
+            query_json,
+            Rc::new(move |subscription_handle, event: &WalletEvent| {
+                match serde_json::to_string(event) {
+                    Ok(event_json) => event_cb(subscription_handle, event_json),
+                    Err(err) => warn!("Cannot serialize WalletEvent: {:?}", err)
+                }
+            }))?;
+
+        trace!("subscribe_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn unsubscribe_records(&self,
+                           subscription_handle: SubscriptionHandle) -> IndyResult<()> {
+        trace!("unsubscribe_records >>> subscription_handle: {:?}", subscription_handle);
+
+        self.wallet_service.unsubscribe_records(subscription_handle)?;
+
+        trace!("unsubscribe_records <<< res: ()");
+
+        Ok(())
+    }
+
     fn _check_type(&self, type_: &str) -> IndyResult<()> {
         if type_.starts_with(WalletService::PREFIX) {
             return Err(err_msg(IndyErrorKind::WalletAccessFailed, format!("Record of type \"{}\" is not available for fetching", type_)));
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{PoolConfig, PoolGroupConfig, PoolOpenConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::PoolService;
 use indy_api_types::{PoolHandle, CommandHandle};
@@ -35,6 +35,34 @@ pub enum PoolCommand {
         Box<dyn Fn(IndyResult<()>) + Send>),
     RefreshAck(CommandHandle,
                IndyResult<()>),
+    GetStats(
+        PoolHandle, // pool handle
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetStatsAck(CommandHandle,
+                IndyResult<String>),
+    SetNodesUpdateCallback(
+        PoolHandle, // pool handle
+        Option<Box<dyn Fn(String) + Send>>, // nodes update callback
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    NodesUpdated(
+        PoolHandle, // pool handle
+        String), // nodes diff json
+    CreateGroup(
+        PoolGroupConfig, // config
+        Box<dyn Fn(IndyResult<PoolHandle>) + Send>),
+    CloseGroup(
+        PoolHandle, // pool group handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    VerifyReplyStateProof(
+        Option<PoolHandle>, // pool handle
+        Option<String>, // genesis txn file
+        String, // request json
+        String, // reply json
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ValidateGenesis(
+        String, // genesis txn file
+        usize, // protocol version
+        Box<dyn Fn(IndyResult<String>) + Send>),
     SetProtocolVersion(
         usize, // protocol version
         Box<dyn Fn(IndyResult<()>) + Send>),
@@ -44,6 +72,8 @@ pub struct PoolCommandExecutor {
     pool_service: Rc<PoolService>,
     close_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<()>)>>>,
     refresh_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<()>)>>>,
+    stats_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    nodes_update_callbacks: RefCell<HashMap<PoolHandle, Box<dyn Fn(String)>>>,
     open_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<PoolHandle>)>>>,
 }
 
@@ -53,6 +83,8 @@ impl PoolCommandExecutor {
             pool_service,
             close_callbacks: RefCell::new(HashMap::new()),
             refresh_callbacks: RefCell::new(HashMap::new()),
+            stats_callbacks: RefCell::new(HashMap::new()),
+            nodes_update_callbacks: RefCell::new(HashMap::new()),
             open_callbacks: RefCell::new(HashMap::new()),
         }
     }
@@ -128,6 +160,56 @@ impl PoolCommandExecutor {
                     Err(err) => { error!("{:?}", err); }
                 }
             }
+            PoolCommand::GetStats(handle, cb) => {
+                debug!(target: "pool_command_executor", "GetStats command received");
+                self.get_stats(handle, cb);
+            }
+            PoolCommand::GetStatsAck(handle, result) => {
+                debug!(target: "pool_command_executor", "GetStatsAck command received");
+                match self.stats_callbacks.try_borrow_mut() {
+                    Ok(mut cbs) => {
+                        match cbs.remove(&handle) {
+                            Some(cb) => cb(result),
+                            None => {
+                                error!("Can't process PoolCommand::GetStatsAck for handle {:?} with result {:?} - appropriate callback not found!",
+                                       handle, result);
+                            }
+                        }
+                    }
+                    Err(err) => { error!("{:?}", err); }
+                }
+            }
+            PoolCommand::SetNodesUpdateCallback(handle, update_cb, cb) => {
+                debug!(target: "pool_command_executor", "SetNodesUpdateCallback command received");
+                cb(self.set_nodes_update_callback(handle, update_cb));
+            }
+            PoolCommand::NodesUpdated(handle, nodes_diff) => {
+                debug!(target: "pool_command_executor", "NodesUpdated command received");
+                match self.nodes_update_callbacks.try_borrow() {
+                    Ok(cbs) => {
+                        if let Some(cb) = cbs.get(&handle) {
+                            cb(nodes_diff)
+                        }
+                    }
+                    Err(err) => { error!("{:?}", err); }
+                }
+            }
+            PoolCommand::CreateGroup(config, cb) => {
+                debug!(target: "pool_command_executor", "CreateGroup command received");
+                cb(self.create_group(config));
+            }
+            PoolCommand::CloseGroup(handle, cb) => {
+                debug!(target: "pool_command_executor", "CloseGroup command received");
+                cb(self.close_group(handle));
+            }
+            PoolCommand::VerifyReplyStateProof(handle, genesis_txn, request_json, reply_json, cb) => {
+                debug!(target: "pool_command_executor", "VerifyReplyStateProof command received");
+                cb(self.verify_reply_state_proof(handle, genesis_txn.as_ref().map(String::as_str), &request_json, &reply_json));
+            }
+            PoolCommand::ValidateGenesis(genesis_txn, protocol_version, cb) => {
+                debug!(target: "pool_command_executor", "ValidateGenesis command received");
+                cb(self.validate_genesis(&genesis_txn, protocol_version));
+            }
             PoolCommand::SetProtocolVersion(protocol_version, cb) => {
                 debug!(target: "pool_command_executor", "SetProtocolVersion command received");
                 cb(self.set_protocol_version(protocol_version));
@@ -189,6 +271,10 @@ impl PoolCommandExecutor {
         debug!("close >>> handle: {:?}", pool_handle);
 
         let result = self.pool_service.close(pool_handle)
+            .and_then(|cmd_id| {
+                self.nodes_update_callbacks.try_borrow_mut()?.remove(&pool_handle);
+                Ok(cmd_id)
+            })
             .and_then(|cmd_id| {
                 match self.close_callbacks.try_borrow_mut() {
                     Ok(cbs) => Ok((cbs, cmd_id)),
@@ -221,6 +307,94 @@ impl PoolCommandExecutor {
         debug!("refresh <<<");
     }
 
+    fn get_stats(&self, handle: PoolHandle, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("get_stats >>> handle: {:?}", handle);
+
+        let result = self.pool_service.get_stats(handle)
+            .and_then(|handle| {
+                match self.stats_callbacks.try_borrow_mut() {
+                    Ok(cbs) => Ok((cbs, handle)),
+                    Err(err) => Err(err.into())
+                }
+            });
+        match result {
+            Err(err) => { cb(Err(err)); }
+            Ok((mut cbs, handle)) => { cbs.insert(handle, cb); }
+        };
+
+        debug!("get_stats <<<");
+    }
+
+    fn set_nodes_update_callback(&self, handle: PoolHandle, update_cb: Option<Box<dyn Fn(String) + Send>>) -> IndyResult<()> {
+        debug!("set_nodes_update_callback >>> handle: {:?}", handle);
+
+        self.pool_service.check_pool_handle(handle)?;
+
+        let mut cbs = self.nodes_update_callbacks.try_borrow_mut()?;
+        match update_cb {
+            Some(update_cb) => { cbs.insert(handle, update_cb); }
+            None => { cbs.remove(&handle); }
+        }
+
+        debug!("set_nodes_update_callback <<<");
+
+        Ok(())
+    }
+
+    fn create_group(&self, config: PoolGroupConfig) -> IndyResult<PoolHandle> {
+        debug!("create_group >>> config: {:?}", config);
+
+        let res = self.pool_service.create_group(config)?;
+
+        debug!("create_group << res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn close_group(&self, handle: PoolHandle) -> IndyResult<()> {
+        debug!("close_group >>> handle: {:?}", handle);
+
+        self.pool_service.close_group(handle)?;
+
+        debug!("close_group << res: ()");
+
+        Ok(())
+    }
+
+    fn verify_reply_state_proof(&self, handle: Option<PoolHandle>, genesis_txn: Option<&str>, request_json: &str, reply_json: &str) -> IndyResult<String> {
+        debug!("verify_reply_state_proof >>> handle: {:?}, genesis_txn: {:?}, request_json: {:?}, reply_json: {:?}",
+               handle, genesis_txn, request_json, reply_json);
+
+        let verdict = self.pool_service.verify_reply_state_proof(handle, genesis_txn, request_json, reply_json)?;
+
+        let res = ::serde_json::to_string(&verdict)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize state proof verdict")?;
+
+        debug!("verify_reply_state_proof << res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn validate_genesis(&self, genesis_txn: &str, protocol_version: usize) -> IndyResult<String> {
+        debug!("validate_genesis >>> genesis_txn: {:?}, protocol_version: {:?}", genesis_txn, protocol_version);
+
+        // 0 means the version set by indy_set_protocol_version
+        let protocol_version = match protocol_version {
+            0 => ProtocolVersion::get(),
+            1 | 2 => protocol_version,
+            _ => return Err(err_msg(IndyErrorKind::PoolIncompatibleProtocolVersion, format!("Unsupported Protocol version: {}", protocol_version)))
+        };
+
+        let report = self.pool_service.validate_genesis(genesis_txn, protocol_version)?;
+
+        let res = ::serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize genesis report")?;
+
+        debug!("validate_genesis << res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn set_protocol_version(&self, version: usize) -> IndyResult<()> {
         debug!("set_protocol_version >>> version: {:?}", version);
 
@@ -1,20 +1,30 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::io;
+use std::io::{Read, Write};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use indy_api_types::wallet::*;
 use crate::commands::{Command, CommandExecutor};
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, ExportStreamConfig, ImportConflictPolicy, ImportRecordsConfig, ImportRecordsStreamConfig, KeyConfig, RekeyCredentials};
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
-use indy_wallet::{KeyDerivationData, WalletService, Metadata};
+use indy_wallet::{CheckPreparation, KeyDerivationData, PreparedImport, WalletService, Metadata};
 use crate::utils::crypto::{chacha20poly1305_ietf, randombytes};
 use crate::utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 use indy_api_types::{WalletHandle, CallbackHandle};
 use rust_base58::ToBase58;
+use serde_json;
 
 type DeriveKeyResult<T> = IndyResult<T>;
 
+// Receives chunks of exported wallet data
+pub type ExportWriter = Arc<dyn Fn(&[u8]) -> IndyResult<()> + Send + Sync>;
+
+// Fills the buffer with the next chunk of wallet data to import, returns the chunk size (0 at the end of data)
+pub type ImportReader = Arc<dyn Fn(&mut [u8]) -> IndyResult<usize> + Send + Sync>;
+
 pub enum WalletCommand {
     RegisterWalletType(String, // type_
                        WalletCreate, // create
@@ -66,6 +76,16 @@ pub enum WalletCommand {
                    Metadata, // credentials
                    DeriveKeyResult<MasterKey>,
                    CallbackHandle),
+    Check(Config, // config
+          Credentials, // credentials
+          CheckOptions, // check options
+          Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckContinue(Config, // config
+                  Credentials, // credentials
+                  CheckOptions, // check options
+                  Metadata, // metadata
+                  DeriveKeyResult<MasterKey>,
+                  CallbackHandle),
     Export(WalletHandle,
            ExportConfig, // export config
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -74,6 +94,23 @@ pub enum WalletCommand {
                    KeyDerivationData,
                    DeriveKeyResult<MasterKey>,
                    CallbackHandle),
+    ExportToStream(WalletHandle,
+                   ExportStreamConfig, // export config
+                   ExportWriter,
+                   Box<dyn Fn(IndyResult<()>) + Send>),
+    ExportToStreamContinue(WalletHandle,
+                           ExportStreamConfig, // export config
+                           ExportWriter,
+                           KeyDerivationData,
+                           DeriveKeyResult<MasterKey>,
+                           CallbackHandle),
+    Rekey(WalletHandle,
+          RekeyCredentials, // new credentials
+          Box<dyn Fn(IndyResult<()>) + Send>),
+    RekeyContinue(WalletHandle,
+                  KeyDerivationData,
+                  DeriveKeyResult<MasterKey>,
+                  CallbackHandle),
     Import(Config, // config
            Credentials, // credentials
            ExportConfig, // import config
@@ -84,6 +121,16 @@ pub enum WalletCommand {
                    WalletHandle,
                    CallbackHandle
     ),
+    ImportRecords(WalletHandle,
+                  ImportRecordsConfig, // import config
+                  Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportRecordsFromStream(WalletHandle,
+                            ImportRecordsStreamConfig, // import config
+                            ImportReader,
+                            Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportRecordsContinue(WalletHandle,
+                          DeriveKeyResult<MasterKey>,
+                          CallbackHandle),
     GenerateKey(Option<KeyConfig>, // config
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
@@ -101,7 +148,10 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    pending_check_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    // Imports waiting for the key derivation as the import data can't be read again
+    pending_imports: RefCell<HashMap<CallbackHandle, (PreparedImport<Box<dyn Read>>, ImportConflictPolicy)>>,
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +160,9 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            pending_check_callbacks: RefCell::new(HashMap::new()),
+            pending_imports: RefCell::new(HashMap::new()),
         }
     }
 
@@ -158,6 +210,14 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeleteContinue command received");
                 self._delete_continue(cb_id, &config, &credentials, &metadata, key_result)
             }
+            WalletCommand::Check(config, credentials, options, cb) => {
+                debug!(target: "wallet_command_executor", "Check command received");
+                self._check(&config, &credentials, &options, cb)
+            }
+            WalletCommand::CheckContinue(config, credentials, options, metadata, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "CheckContinue command received");
+                self._check_continue(cb_id, &config, &credentials, &options, &metadata, key_result)
+            }
             WalletCommand::Export(wallet_handle, export_config, cb) => {
                 debug!(target: "wallet_command_executor", "Export command received");
                 self._export(wallet_handle, &export_config, cb)
@@ -166,6 +226,22 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ExportContinue command received");
                 self._export_continue(cb_id, wallet_handle, &export_config, key_data, key_result)
             }
+            WalletCommand::ExportToStream(wallet_handle, export_config, writer, cb) => {
+                debug!(target: "wallet_command_executor", "ExportToStream command received");
+                self._export_to_stream(wallet_handle, &export_config, writer, cb)
+            }
+            WalletCommand::ExportToStreamContinue(wallet_handle, export_config, writer, key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "ExportToStreamContinue command received");
+                self._export_to_stream_continue(cb_id, wallet_handle, &export_config, writer, key_data, key_result)
+            }
+            WalletCommand::Rekey(wallet_handle, credentials, cb) => {
+                debug!(target: "wallet_command_executor", "Rekey command received");
+                self._rekey(wallet_handle, &credentials, cb)
+            }
+            WalletCommand::RekeyContinue(wallet_handle, key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "RekeyContinue command received");
+                self._rekey_continue(cb_id, wallet_handle, key_data, key_result)
+            }
             WalletCommand::Import(config, credentials, import_config, cb) => {
                 debug!(target: "wallet_command_executor", "Import command received");
                 self._import(&config, &credentials, &import_config, cb);
@@ -174,6 +250,18 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
                 self._import_continue(cb_id, wallet_handle, &config, &credential, key_result);
             }
+            WalletCommand::ImportRecords(wallet_handle, import_config, cb) => {
+                debug!(target: "wallet_command_executor", "ImportRecords command received");
+                self._import_records(wallet_handle, &import_config, cb);
+            }
+            WalletCommand::ImportRecordsFromStream(wallet_handle, import_config, reader, cb) => {
+                debug!(target: "wallet_command_executor", "ImportRecordsFromStream command received");
+                self._import_records_from_stream(wallet_handle, &import_config, reader, cb);
+            }
+            WalletCommand::ImportRecordsContinue(wallet_handle, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "ImportRecordsContinue command received");
+                self._import_records_continue(cb_id, wallet_handle, key_result);
+            }
             WalletCommand::GenerateKey(config, cb) => {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 cb(self._generate_key(config.as_ref()));
@@ -387,6 +475,65 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.delete_wallet_continue(config, credentials, metadata, &key)))
     }
 
+    fn _check(&self,
+              config: &Config,
+              credentials: &Credentials,
+              options: &CheckOptions,
+              cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("_check >>> config: {:?}, credentials: {:?}, options: {:?}", config, secret!(credentials), options);
+
+        let (metadata, key_derivation_data) = match try_cb!(self.wallet_service.check_wallet_prepare(&config, &credentials), cb) {
+            CheckPreparation::DeriveKey(metadata, key_derivation_data) => (metadata, key_derivation_data),
+            CheckPreparation::Report(report) => return cb(serde_json::to_string(&report)
+                .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet check report"))
+        };
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_check_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let config = config.clone();
+        let credentials = credentials.clone();
+        let options = options.clone();
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_derivation_data,
+                Box::new(move |key_result| {
+                    let key_result = key_result.clone();
+                    CommandExecutor::instance().send(
+                        Command::Wallet(WalletCommand::CheckContinue(
+                            config.clone(),
+                            credentials.clone(),
+                            options.clone(),
+                            metadata.clone(),
+                            key_result,
+                            cb_id)
+                        )).unwrap()
+                }),
+            ))
+        ).unwrap();
+
+        trace!("_check <<<");
+    }
+
+    fn _check_continue(&self,
+                       cb_id: CallbackHandle,
+                       config: &Config,
+                       credentials: &Credentials,
+                       options: &CheckOptions,
+                       metadata: &Metadata,
+                       key_result: DeriveKeyResult<MasterKey>) {
+        let cb = match self.pending_check_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        cb(key_result
+            .and_then(|key| self.wallet_service.check_wallet_continue(config, credentials, options, metadata, &key))
+            .and_then(|report| serde_json::to_string(&report)
+                .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet check report")))
+    }
+
     fn _export(&self,
                wallet_handle: WalletHandle,
                export_config: &ExportConfig,
@@ -429,6 +576,89 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
     }
 
+    fn _export_to_stream(&self,
+                         wallet_handle: WalletHandle,
+                         export_config: &ExportStreamConfig,
+                         writer: ExportWriter,
+                         cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_export_to_stream >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+
+        let cb_id = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let export_config = export_config.clone();
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data.clone(),
+                Box::new(move |master_key_res| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::ExportToStreamContinue(
+                        wallet_handle,
+                        export_config.clone(),
+                        writer.clone(),
+                        key_data.clone(),
+                        master_key_res,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+
+        trace!("_export_to_stream <<<");
+    }
+
+    fn _export_to_stream_continue(&self,
+                                  cb_id: CallbackHandle,
+                                  wallet_handle: WalletHandle,
+                                  export_config: &ExportStreamConfig,
+                                  writer: ExportWriter,
+                                  key_data: KeyDerivationData,
+                                  key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
+        cb(key_result
+            .and_then(|key| self.wallet_service.export_wallet_to_writer(wallet_handle, &mut StreamWriter(writer), &export_config.options, 0, (&key_data, &key))))
+    }
+
+    fn _rekey(&self,
+              wallet_handle: WalletHandle,
+              credentials: &RekeyCredentials,
+              cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_rekey >>> handle: {:?}, credentials: {:?}", wallet_handle, secret!(credentials));
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+
+        let cb_id = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data.clone(),
+                Box::new(move |master_key_res| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::RekeyContinue(
+                        wallet_handle,
+                        key_data.clone(),
+                        master_key_res,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+
+        trace!("_rekey <<<");
+    }
+
+    fn _rekey_continue(&self,
+                       cb_id: CallbackHandle,
+                       wallet_handle: WalletHandle,
+                       key_data: KeyDerivationData,
+                       key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
+        cb(key_result
+            .and_then(|key| self.wallet_service.rekey_wallet(wallet_handle, (&key_data, &key))))
+    }
+
     fn _import(&self,
                config: &Config,
                credentials: &Credentials,
@@ -485,6 +715,75 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
     }
 
+    fn _import_records(&self,
+                       wallet_handle: WalletHandle,
+                       import_config: &ImportRecordsConfig,
+                       cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_import_records >>> handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        let import = try_cb!(self.wallet_service.import_records_prepare(wallet_handle, import_config), cb);
+        let import = import.into_boxed();
+
+        self._import_records_derive_key(wallet_handle, import, import_config.on_conflict.clone(), cb);
+
+        trace!("_import_records <<<");
+    }
+
+    fn _import_records_from_stream(&self,
+                                   wallet_handle: WalletHandle,
+                                   import_config: &ImportRecordsStreamConfig,
+                                   reader: ImportReader,
+                                   cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_import_records_from_stream >>> handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        let import = try_cb!(self.wallet_service.import_records_prepare_from_reader(wallet_handle, StreamReader(reader), &import_config.key), cb);
+        let import = import.into_boxed();
+
+        self._import_records_derive_key(wallet_handle, import, import_config.on_conflict.clone(), cb);
+
+        trace!("_import_records_from_stream <<<");
+    }
+
+    fn _import_records_derive_key(&self,
+                                  wallet_handle: WalletHandle,
+                                  import: PreparedImport<Box<dyn Read>>,
+                                  on_conflict: ImportConflictPolicy,
+                                  cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        let import_key_data = import.key_derivation_data().clone();
+
+        let cb_id = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+        self.pending_imports.borrow_mut().insert(cb_id, (import, on_conflict));
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                import_key_data,
+                Box::new(move |import_key_result| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::ImportRecordsContinue(
+                        wallet_handle,
+                        import_key_result,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+    }
+
+    fn _import_records_continue(&self,
+                                cb_id: CallbackHandle,
+                                wallet_handle: WalletHandle,
+                                key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
+
+        let (import, on_conflict) = match self.pending_imports.borrow_mut().remove(&cb_id) {
+            Some(import) => import,
+            None => return cb(Err(err_msg(IndyErrorKind::InvalidState, "Pending import not found")))
+        };
+
+        cb(key_result
+            .and_then(|key| self.wallet_service.import_records_continue(wallet_handle, import, &key, &on_conflict)))
+    }
+
     fn _generate_key(&self,
                      config: Option<&KeyConfig>) -> IndyResult<String> {
         trace!("_generate_key >>>config: {:?}", secret!(config));
@@ -506,3 +805,28 @@ impl WalletCommandExecutor {
         crate::commands::THREADPOOL.lock().unwrap().execute(move || cb(key_data.calc_master_key()));
     }
 }
+
+// Passes exported data to the writer provided by the caller
+struct StreamWriter(ExportWriter);
+
+impl Write for StreamWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (self.0)(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Takes wallet data to import from the reader provided by the caller
+struct StreamReader(ImportReader);
+
+impl Read for StreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (self.0)(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))
+    }
+}
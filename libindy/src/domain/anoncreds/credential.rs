@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use named_type::NamedType;
+use serde_json::Value;
 use ursa::cl::{
     CredentialSignature,
     RevocationRegistry,
@@ -35,6 +36,13 @@ impl Credential {
     }
 }
 
+/// Credential issued as part of a batch together with its local revocation id.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssuedCredential {
+    pub cred: Credential,
+    pub cred_rev_id: Option<String>
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub struct CredentialInfo {
     pub referent: String,
@@ -56,6 +64,34 @@ pub struct AttributeValues {
     pub encoded: String
 }
 
+/// Attribute values as passed by the Issuer before encoding.
+/// Each value is either a plain raw value or an object with `raw`, optional `encoded` and optional `type`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RawCredentialValues(pub HashMap<String, RawAttributeValue>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum RawAttributeValue {
+    Typed {
+        raw: Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        encoded: Option<String>,
+        #[serde(rename = "type")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        type_: Option<AttributeEncoding>,
+    },
+    Plain(Value),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeEncoding {
+    Int,
+    String,
+    Date,
+    Boolean,
+}
+
 impl Validatable for CredentialValues {
     fn validate(&self) -> Result<(), String> {
         if self.0.is_empty() {
@@ -66,6 +102,16 @@ impl Validatable for CredentialValues {
     }
 }
 
+impl Validatable for RawCredentialValues {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialValues validation failed: empty list has been passed"));
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable for Credential {
     fn validate(&self) -> Result<(), String> {
         self.schema_id.validate()?;
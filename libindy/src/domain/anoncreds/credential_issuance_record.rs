@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use named_type::NamedType;
+
+use super::DELIMITER;
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::super::crypto::did::DidValue;
+
+use indy_api_types::validation::Validatable;
+
+/// Wallet tags of issuance records set by libindy.
+/// `~timestamp` is un-encrypted to allow numeric comparison in search queries.
+pub const RESERVED_TAGS: [&str; 6] = ["cred_def_id", "rev_reg_id", "cred_rev_id", "prover_did", "revoked", "~timestamp"];
+
+/// Tags supplied by the Issuer for an issued credential.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CredentialIssuanceRecordTags(pub HashMap<String, String>);
+
+impl Validatable for CredentialIssuanceRecordTags {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(name) = self.0.keys().find(|name| RESERVED_TAGS.contains(&name.as_str())) {
+            return Err(format!("CredentialIssuanceRecordTags validation failed: tag name `{}` is reserved", name));
+        }
+
+        Ok(())
+    }
+}
+
+/// Issuer-side record of a credential issued from a revocation registry.
+#[derive(Debug, Clone, Deserialize, Serialize, NamedType)]
+pub struct CredentialIssuanceRecord {
+    pub cred_def_id: CredentialDefinitionId,
+    pub rev_reg_id: RevocationRegistryId,
+    pub cred_rev_id: String,
+    pub prover_did: DidValue,
+    pub timestamp: u64,
+    pub revoked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_timestamp: Option<u64>,
+    #[serde(default)]
+    pub tags: CredentialIssuanceRecordTags,
+}
+
+impl CredentialIssuanceRecord {
+    pub fn id(rev_reg_id: &RevocationRegistryId, cred_rev_id: &str) -> String {
+        format!("{}{}{}", rev_reg_id.0, DELIMITER, cred_rev_id)
+    }
+
+    pub fn wallet_tags(&self) -> HashMap<String, String> {
+        let mut tags = self.tags.0.clone();
+
+        tags.insert("cred_def_id".to_string(), self.cred_def_id.0.clone());
+        tags.insert("rev_reg_id".to_string(), self.rev_reg_id.0.clone());
+        tags.insert("cred_rev_id".to_string(), self.cred_rev_id.clone());
+        tags.insert("prover_did".to_string(), self.prover_did.0.clone());
+        tags.insert("revoked".to_string(), if self.revoked { "1" } else { "0" }.to_string());
+        tags.insert("~timestamp".to_string(), self.timestamp.to_string());
+
+        tags
+    }
+}
+
+/// Issuance record returned to the Issuer.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialIssuanceRecordInfo {
+    pub record_id: String,
+    #[serde(flatten)]
+    pub record: CredentialIssuanceRecord,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _tags(name: &str, value: &str) -> CredentialIssuanceRecordTags {
+        let mut tags = HashMap::new();
+        tags.insert(name.to_string(), value.to_string());
+        CredentialIssuanceRecordTags(tags)
+    }
+
+    fn _record() -> CredentialIssuanceRecord {
+        CredentialIssuanceRecord {
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            rev_reg_id: RevocationRegistryId("NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:TAG_1".to_string()),
+            cred_rev_id: "1".to_string(),
+            prover_did: DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string()),
+            timestamp: 1600000000,
+            revoked: false,
+            revoked_timestamp: None,
+            tags: _tags("holder", "alice"),
+        }
+    }
+
+    #[test]
+    fn wallet_tags_works() {
+        let tags = _record().wallet_tags();
+
+        assert_eq!(7, tags.len());
+        assert_eq!("alice", tags["holder"]);
+        assert_eq!("1", tags["cred_rev_id"]);
+        assert_eq!("0", tags["revoked"]);
+        assert_eq!("1600000000", tags["~timestamp"]);
+    }
+
+    #[test]
+    fn tags_validate_works_for_reserved_name() {
+        let tags = _tags("revoked", "0");
+        assert!(tags.validate().is_err());
+    }
+
+    #[test]
+    fn record_info_serialization_works() {
+        let info = CredentialIssuanceRecordInfo {
+            record_id: "id".to_string(),
+            record: _record(),
+        };
+
+        let info = serde_json::to_value(&info).unwrap();
+        assert_eq!(json!("id"), info["record_id"]);
+        assert_eq!(json!("1"), info["cred_rev_id"]);
+        assert_eq!(json!({"holder": "alice"}), info["tags"]);
+    }
+}
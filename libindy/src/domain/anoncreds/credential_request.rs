@@ -6,7 +6,9 @@ use ursa::cl::{
 };
 use super::super::crypto::did::DidValue;
 
+use super::credential::RawCredentialValues;
 use super::credential_definition::CredentialDefinitionId;
+use super::credential_issuance_record::CredentialIssuanceRecordTags;
 
 use indy_api_types::validation::Validatable;
 
@@ -19,6 +21,19 @@ pub struct CredentialRequest {
     pub nonce: Nonce,
 }
 
+/// Credential request together with the values to issue for it (item of a batch issuance).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_req: CredentialRequest,
+    pub cred_values: RawCredentialValues,
+    /// tags of issuance record (stored for credentials issued from revocation registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record_tags: Option<CredentialIssuanceRecordTags>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequests(pub Vec<CredentialIssuanceRequest>);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CredentialRequestMetadata {
     pub master_secret_blinding_data: CredentialSecretsBlindingFactors,
@@ -46,4 +61,23 @@ impl Validatable for CredentialRequest {
     }
 }
 
+impl Validatable for CredentialIssuanceRequests {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialIssuanceRequests validation failed: empty list has been passed"));
+        }
+
+        for request in self.0.iter() {
+            request.cred_req.validate()?;
+            request.cred_values.validate()?;
+
+            if let Some(ref record_tags) = request.record_tags {
+                record_tags.validate()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable for CredentialRequestMetadata {}
\ No newline at end of file
@@ -42,7 +42,7 @@ pub struct SubProofReferent {
     pub sub_proof_index: u32,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RevealedAttributeInfo {
     pub sub_proof_index: u32,
     pub raw: String,
@@ -284,9 +284,15 @@ fn _process_operator(restriction_op: &Query, version: &ProofRequestsVersion) ->
         Query::Gte(ref tag_name, ref tag_value) |
         Query::Lt(ref tag_name, ref tag_value) |
         Query::Lte(ref tag_name, ref tag_value) |
-        Query::Like(ref tag_name, ref tag_value) => {
+        Query::Like(ref tag_name, ref tag_value) |
+        Query::ILike(ref tag_name, ref tag_value) => {
             _check_restriction(tag_name, tag_value, version)
         }
+        Query::Exist(_) |
+        Query::NumGt(_, _) |
+        Query::NumGte(_, _) |
+        Query::NumLt(_, _) |
+        Query::NumLte(_, _) => Ok(()),
         Query::In(ref tag_name, ref tag_values) => {
             tag_values
                 .iter()
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use super::proof::Identifier;
+use super::proof_request::NonRevocedInterval;
+
+/// Detailed result of a proof verification.
+/// `valid` is the same result `indy_verifier_verify_proof` would return,
+/// `errors` lists every reason why the proof was rejected.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct ProofVerificationReport {
+    pub valid: bool,
+    pub requested_attributes: HashMap<String, ReferentVerificationReport>,
+    pub requested_predicates: HashMap<String, ReferentVerificationReport>,
+    pub errors: Vec<String>
+}
+
+/// Verification details of a single referent of the proof request.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct ReferentVerificationReport {
+    pub valid: bool,
+    pub revealed: bool,
+    pub self_attested: bool,
+    /// raw values revealed or self-attested for the referent (attribute name -> value)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<HashMap<String, String>>,
+    /// schema, credential definition, revocation registry and non-revocation timestamp of the credential used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Identifier>,
+    /// `None` if the proof request doesn't set restrictions for the referent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions_satisfied: Option<bool>,
+    /// non-revocation interval requested for the referent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevocedInterval>,
+    pub errors: Vec<String>
+}
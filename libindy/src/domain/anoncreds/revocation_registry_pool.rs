@@ -0,0 +1,214 @@
+use named_type::NamedType;
+use serde_json::Value;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::{IssuanceType, RevocationRegistryConfig, RevocationRegistryId};
+use super::super::crypto::did::DidValue;
+
+use indy_api_types::validation::Validatable;
+
+pub const DEFAULT_MAX_CRED_NUM: u32 = 100000;
+pub const DEFAULT_PREGENERATED: u32 = 1;
+pub const DEFAULT_TAILS_WRITER_TYPE: &str = "default";
+pub const TAG_PREFIX: &str = "pool";
+
+/// Configuration of the revocation registries an Issuer uses for a credential definition.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevocationRegistryPoolConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuance_type: Option<IssuanceType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cred_num: Option<u32>,
+    /// number of registries kept ready besides the active one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pregenerated: Option<u32>,
+    /// number of free indexes of the active registry at which missing ready registries are created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tails_writer_type: Option<String>,
+    /// config of tails writer, also used to open tails reader of the same type
+    pub tails_writer_config: Value,
+}
+
+impl RevocationRegistryPoolConfig {
+    pub fn max_cred_num(&self) -> u32 {
+        self.max_cred_num.unwrap_or(DEFAULT_MAX_CRED_NUM)
+    }
+
+    pub fn pregenerated(&self) -> u32 {
+        self.pregenerated.unwrap_or(DEFAULT_PREGENERATED)
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold.unwrap_or(0)
+    }
+
+    pub fn tails_writer_type(&self) -> &str {
+        self.tails_writer_type.as_ref().map(String::as_str).unwrap_or(DEFAULT_TAILS_WRITER_TYPE)
+    }
+
+    pub fn rev_reg_config(&self) -> RevocationRegistryConfig {
+        RevocationRegistryConfig {
+            issuance_type: self.issuance_type.clone(),
+            max_cred_num: Some(self.max_cred_num()),
+        }
+    }
+}
+
+impl Validatable for RevocationRegistryPoolConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_cred_num() == 0 {
+            return Err(String::from("RevocationRegistryPoolConfig validation failed: `max_cred_num` must be greater than 0"));
+        }
+
+        if self.threshold() >= self.max_cred_num() {
+            return Err(String::from("RevocationRegistryPoolConfig validation failed: `threshold` must be less than `max_cred_num`"));
+        }
+
+        if !self.tails_writer_config.is_object() {
+            return Err(String::from("RevocationRegistryPoolConfig validation failed: `tails_writer_config` must be an object"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RevocationRegistryState {
+    /// created in advance, becomes active when the current active registry is full
+    Ready,
+    /// used for issuance
+    Active,
+    /// all indexes are issued
+    Full,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevocationRegistryPoolEntry {
+    pub rev_reg_id: RevocationRegistryId,
+    pub state: RevocationRegistryState,
+}
+
+/// Revocation registries of a credential definition stored in the wallet in order of creation.
+#[derive(Debug, Clone, Deserialize, Serialize, NamedType)]
+pub struct RevocationRegistryPool {
+    pub issuer_did: DidValue,
+    pub cred_def_id: CredentialDefinitionId,
+    pub config: RevocationRegistryPoolConfig,
+    pub registries: Vec<RevocationRegistryPoolEntry>,
+}
+
+impl RevocationRegistryPool {
+    pub fn active(&self) -> Option<&RevocationRegistryPoolEntry> {
+        self.registries.iter().find(|entry| entry.state == RevocationRegistryState::Active)
+    }
+
+    pub fn ready_count(&self) -> u32 {
+        self.registries.iter().filter(|entry| entry.state == RevocationRegistryState::Ready).count() as u32
+    }
+
+    /// Marks the active registry as full and activates the oldest ready one.
+    /// Returns `false` if there is no ready registry.
+    pub fn rotate(&mut self) -> bool {
+        for entry in self.registries.iter_mut().filter(|entry| entry.state == RevocationRegistryState::Active) {
+            entry.state = RevocationRegistryState::Full;
+        }
+
+        match self.registries.iter_mut().find(|entry| entry.state == RevocationRegistryState::Ready) {
+            Some(entry) => {
+                entry.state = RevocationRegistryState::Active;
+                true
+            }
+            None => false
+        }
+    }
+
+    pub fn next_tag(&self) -> String {
+        format!("{}{}", TAG_PREFIX, self.registries.len() + 1)
+    }
+}
+
+/// Revocation registry created by the pool that must be published to the ledger.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatedRevocationRegistry {
+    pub rev_reg_id: RevocationRegistryId,
+    pub rev_reg_def: Value,
+    pub rev_reg_entry: Value,
+}
+
+/// Result of issuance from the pool.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PoolIssuedCredential {
+    pub cred: Value,
+    pub cred_rev_id: String,
+    pub rev_reg_id: RevocationRegistryId,
+    pub rev_reg_delta: Option<Value>,
+    pub created_rev_regs: Vec<CreatedRevocationRegistry>,
+}
+
+/// Pool state returned to the Issuer.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevocationRegistryPoolInfo {
+    pub cred_def_id: CredentialDefinitionId,
+    pub config: RevocationRegistryPoolConfig,
+    pub registries: Vec<RevocationRegistryStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevocationRegistryStatus {
+    pub rev_reg_id: RevocationRegistryId,
+    pub state: RevocationRegistryState,
+    pub max_cred_num: u32,
+    /// number of issued indexes
+    pub issued: u32,
+    pub revoked: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _pool(states: &[RevocationRegistryState]) -> RevocationRegistryPool {
+        RevocationRegistryPool {
+            issuer_did: DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string()),
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            config: serde_json::from_value(json!({"tails_writer_config": {}})).unwrap(),
+            registries: states.iter().enumerate().map(|(i, state)|
+                RevocationRegistryPoolEntry {
+                    rev_reg_id: RevocationRegistryId(format!("rev_reg_{}", i)),
+                    state: *state,
+                }).collect(),
+        }
+    }
+
+    #[test]
+    fn rotate_works() {
+        let mut pool = _pool(&[RevocationRegistryState::Full, RevocationRegistryState::Active, RevocationRegistryState::Ready, RevocationRegistryState::Ready]);
+
+        assert!(pool.rotate());
+        assert_eq!(vec![RevocationRegistryState::Full, RevocationRegistryState::Full, RevocationRegistryState::Active, RevocationRegistryState::Ready],
+                   pool.registries.iter().map(|entry| entry.state).collect::<Vec<_>>());
+        assert_eq!("rev_reg_2", pool.active().unwrap().rev_reg_id.0);
+        assert_eq!(1, pool.ready_count());
+        assert_eq!("pool5", pool.next_tag());
+    }
+
+    #[test]
+    fn rotate_works_for_no_ready_registry() {
+        let mut pool = _pool(&[RevocationRegistryState::Active]);
+
+        assert!(!pool.rotate());
+        assert!(pool.active().is_none());
+    }
+
+    #[test]
+    fn config_validate_works_for_threshold_not_less_than_max_cred_num() {
+        let config: RevocationRegistryPoolConfig =
+            serde_json::from_value(json!({"max_cred_num": 5, "threshold": 5, "tails_writer_config": {}})).unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use super::constants::GET_TXN;
+use super::response::ReplyType;
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct GetTxnOperation {
@@ -35,3 +36,45 @@ impl LedgerType {
         }
     }
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTxnReplyResult {
+    pub seq_no: Option<u64>,
+    pub data: Option<::serde_json::Value>,
+}
+
+impl ReplyType for GetTxnReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTxnResultData {
+    pub txn: ::serde_json::Value,
+    pub txn_metadata: ::serde_json::Value,
+    pub ver: Option<String>,
+    pub req_signature: Option<::serde_json::Value>,
+    pub root_hash: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerTxn {
+    pub txn: ::serde_json::Value,
+    pub txn_metadata: ::serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub req_signature: Option<::serde_json::Value>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct VerifiedTxn {
+    pub txn: LedgerTxn,
+    pub seq_no: u64,
+    pub root_hash: String,
+    pub verified: bool,
+}
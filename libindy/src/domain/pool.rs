@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use indy_api_types::PoolHandle;
 use indy_api_types::validation::Validatable;
 
 pub const POOL_CON_ACTIVE_TO: i64 = 5;
@@ -33,6 +36,64 @@ pub struct PoolOpenConfig {
     pub preordered_nodes: Vec<String>,
     #[serde(default = "PoolOpenConfig::default_number_read_nodes")]
     pub number_read_nodes: u8,
+    #[serde(default)]
+    pub adaptive_ordering: bool,
+    #[serde(default)]
+    pub refresh_interval: Option<i64>,
+    #[serde(default)]
+    pub simulation: Option<PoolSimulationConfig>,
+}
+
+/// Replaces the network transport of an opened pool with in-process simulated nodes.
+/// Node names and keys are taken from the genesis transactions of the pool.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolSimulationConfig {
+    /// Behavior of the particular nodes. Nodes that are not listed act honestly.
+    #[serde(default)]
+    pub nodes: HashMap<String, SimulatedNodeBehavior>,
+    /// Pool ledger transactions known to the simulated nodes in addition to the genesis ones.
+    #[serde(default)]
+    pub pool_txns: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "behavior", rename_all = "snake_case")]
+pub enum SimulatedNodeBehavior {
+    Honest,
+    /// Never replies.
+    Faulty,
+    /// Replies after `delay` milliseconds.
+    Slow { delay: u64 },
+    /// Replies with tampered data.
+    Malicious,
+}
+
+/// Routes ledger requests of several networks to the opened pools by DID method
+/// of the fully-qualified identifiers the requests reference.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PoolGroupConfig {
+    /// Pool handles by DID method names, e.g. `sov` or `sov:staging`.
+    #[serde(default)]
+    pub routes: HashMap<String, PoolHandle>,
+    /// Pool for unqualified identifiers and DID methods without route.
+    #[serde(default)]
+    pub default: Option<PoolHandle>,
+}
+
+impl Validatable for PoolGroupConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.routes.is_empty() && self.default.is_none() {
+            return Err(String::from("Either `routes` or `default` must be specified"));
+        }
+        for method in self.routes.keys() {
+            let valid = method.split(':')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+            if !valid {
+                return Err(format!("Invalid DID method name `{}`", method));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Validatable for PoolOpenConfig {
@@ -52,6 +113,9 @@ impl Validatable for PoolOpenConfig {
         if self.number_read_nodes == 0 {
             return Err(String::from("`number_read_nodes` must be greater than 0"));
         }
+        if self.refresh_interval.map(|interval| interval <= 0).unwrap_or(false) {
+            return Err(String::from("`refresh_interval` must be greater than 0"));
+        }
         Ok(())
     }
 }
@@ -65,6 +129,9 @@ impl Default for PoolOpenConfig {
             conn_active_timeout: PoolOpenConfig::default_conn_active_timeout(),
             preordered_nodes: PoolOpenConfig::default_preordered_nodes(),
             number_read_nodes: PoolOpenConfig::default_number_read_nodes(),
+            adaptive_ordering: false,
+            refresh_interval: None,
+            simulation: None,
         }
     }
 }
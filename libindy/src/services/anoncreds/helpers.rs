@@ -1,6 +1,7 @@
 use indy_api_types::errors::prelude::*;
 
-use crate::domain::anoncreds::credential::AttributeValues;
+use crate::domain::anoncreds::credential;
+use crate::domain::anoncreds::credential::{AttributeValues, AttributeEncoding, RawAttributeValue, RawCredentialValues};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval};
 use ursa::cl::{issuer, verifier, CredentialSchema, NonCredentialSchema, MasterSecret, CredentialValues, SubProofRequest};
 
@@ -15,6 +16,10 @@ use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequest;
 use crate::domain::anoncreds::proof_request::ProofRequest;
 
+use indy_utils::crypto::hash::hash;
+use serde_json::Value;
+use ursa::bn::BigNumber;
+
 use std::collections::{HashSet, HashMap};
 
 pub fn attr_common_view(attr: &str) -> String {
@@ -154,6 +159,123 @@ pub fn to_unqualified(entity: &str) -> IndyResult<String> {
     Ok(entity.to_string())
 }
 
+pub fn encode_credential_values(raw_values: &RawCredentialValues) -> IndyResult<credential::CredentialValues> {
+    trace!("encode_credential_values >>> raw_values: {:?}", secret!(raw_values));
+
+    let mut values = HashMap::new();
+
+    for (attr, raw_value) in raw_values.0.iter() {
+        let value = match raw_value {
+            RawAttributeValue::Typed { raw, encoded: Some(encoded), type_: Some(type_) } => {
+                let value = encode_attribute_value(attr, raw, Some(*type_))?;
+
+                if &value.encoded != encoded {
+                    return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                       format!("Encoded value for attribute \"{}\" doesn't match {:?} encoding of raw value", attr, type_)));
+                }
+
+                value
+            }
+            RawAttributeValue::Typed { raw, encoded: Some(encoded), type_: None } =>
+                AttributeValues { raw: raw_value_to_string(attr, raw)?, encoded: encoded.to_string() },
+            RawAttributeValue::Typed { raw, encoded: None, type_ } =>
+                encode_attribute_value(attr, raw, *type_)?,
+            RawAttributeValue::Plain(raw) =>
+                encode_attribute_value(attr, raw, None)?,
+        };
+        values.insert(attr.to_string(), value);
+    }
+
+    let res = credential::CredentialValues(values);
+
+    trace!("encode_credential_values <<< res: {:?}", secret!(&res));
+
+    Ok(res)
+}
+
+/// Encodes a single raw attribute value.
+///
+/// If the encoding isn't specified it is chosen by the raw value:
+/// booleans use `boolean` encoding, 32-bit integers (JSON numbers or strings in canonical form) stay as is,
+/// everything else is treated as `string`.
+pub fn encode_attribute_value(attr: &str, raw: &Value, encoding: Option<AttributeEncoding>) -> IndyResult<AttributeValues> {
+    let raw_str = raw_value_to_string(attr, raw)?;
+
+    let encoding = match encoding {
+        Some(encoding) => encoding,
+        None if raw.is_boolean() => AttributeEncoding::Boolean,
+        None if raw_str.parse::<i32>().map(|val| val.to_string() == raw_str).unwrap_or(false) => AttributeEncoding::Int,
+        None => AttributeEncoding::String,
+    };
+
+    let encoded = match encoding {
+        AttributeEncoding::Int => _encode_int(attr, &raw_str)?,
+        AttributeEncoding::String => _encode_string(&raw_str)?,
+        AttributeEncoding::Date => _encode_date(attr, &raw_str)?,
+        AttributeEncoding::Boolean => _encode_boolean(attr, &raw_str)?,
+    };
+
+    Ok(AttributeValues { raw: raw_str, encoded })
+}
+
+fn raw_value_to_string(attr: &str, raw: &Value) -> IndyResult<String> {
+    match raw {
+        Value::String(val) => Ok(val.to_string()),
+        Value::Number(val) => Ok(val.to_string()),
+        Value::Bool(val) => Ok(val.to_string()),
+        _ => Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid raw value for attribute \"{}\": string, number or boolean expected", attr)))
+    }
+}
+
+fn _encode_int(attr: &str, raw: &str) -> IndyResult<String> {
+    raw.parse::<i32>()
+        .map(|val| val.to_string())
+        .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid raw value for attribute \"{}\": 32-bit integer expected", attr))
+}
+
+fn _encode_string(raw: &str) -> IndyResult<String> {
+    let hash = hash(raw.as_bytes())?;
+    let res = BigNumber::from_bytes(&hash)?.to_dec()?;
+    Ok(res)
+}
+
+fn _encode_date(attr: &str, raw: &str) -> IndyResult<String> {
+    let invalid_date = || err_msg(IndyErrorKind::InvalidStructure, format!("Invalid raw value for attribute \"{}\": date in YYYY-MM-DD format expected", attr));
+
+    let parts: Vec<&str> = raw.split('-').collect();
+
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2
+        || !parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())) {
+        return Err(invalid_date());
+    }
+
+    let year: u32 = parts[0].parse().map_err(|_| invalid_date())?;
+    let month: u32 = parts[1].parse().map_err(|_| invalid_date())?;
+    let day: u32 = parts[2].parse().map_err(|_| invalid_date())?;
+
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return Err(invalid_date())
+    };
+
+    if day == 0 || day > days_in_month {
+        return Err(invalid_date());
+    }
+
+    Ok((year * 10000 + month * 100 + day).to_string())
+}
+
+fn _encode_boolean(attr: &str, raw: &str) -> IndyResult<String> {
+    match raw {
+        "true" => Ok("1".to_string()),
+        "false" => Ok("0".to_string()),
+        _ => Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid raw value for attribute \"{}\": boolean expected", attr)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +337,96 @@ mod tests {
             assert_eq!(REV_REG_ID_UNQUALIFIED, to_unqualified(REV_REG_ID_UNQUALIFIED).unwrap());
         }
     }
-}
\ No newline at end of file
+
+    mod encode_credential_values {
+        use super::*;
+
+        const STRING_ENCODED: &str = "99262857098057710338306967609588410025648622308394250666849665532448612202874";
+
+        fn _encode(raw: Value, encoding: Option<AttributeEncoding>) -> IndyResult<AttributeValues> {
+            encode_attribute_value("attr", &raw, encoding)
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_auto_encoding() {
+            assert_eq!(AttributeValues { raw: "28".to_string(), encoded: "28".to_string() }, _encode(json!("28"), None).unwrap());
+            assert_eq!(AttributeValues { raw: "-28".to_string(), encoded: "-28".to_string() }, _encode(json!(-28), None).unwrap());
+            assert_eq!(AttributeValues { raw: "true".to_string(), encoded: "1".to_string() }, _encode(json!(true), None).unwrap());
+            assert_eq!(AttributeValues { raw: "Alex".to_string(), encoded: STRING_ENCODED.to_string() }, _encode(json!("Alex"), None).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_non_canonical_integers() {
+            assert_ne!("007", _encode(json!("007"), None).unwrap().encoded);
+            assert_ne!("2147483648", _encode(json!("2147483648"), None).unwrap().encoded);
+            assert_ne!("1.5", _encode(json!(1.5), None).unwrap().encoded);
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_int_encoding() {
+            assert_eq!("7", _encode(json!("007"), Some(AttributeEncoding::Int)).unwrap().encoded);
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!("Alex"), Some(AttributeEncoding::Int)).unwrap_err().kind());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_string_encoding() {
+            assert_eq!(STRING_ENCODED, _encode(json!("Alex"), Some(AttributeEncoding::String)).unwrap().encoded);
+            assert_ne!("28", _encode(json!("28"), Some(AttributeEncoding::String)).unwrap().encoded);
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_date_encoding() {
+            assert_eq!("20000229", _encode(json!("2000-02-29"), Some(AttributeEncoding::Date)).unwrap().encoded);
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!("1900-02-29"), Some(AttributeEncoding::Date)).unwrap_err().kind());
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!("2000-13-01"), Some(AttributeEncoding::Date)).unwrap_err().kind());
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!("2000-1-1"), Some(AttributeEncoding::Date)).unwrap_err().kind());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_boolean_encoding() {
+            assert_eq!("0", _encode(json!("false"), Some(AttributeEncoding::Boolean)).unwrap().encoded);
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!("yes"), Some(AttributeEncoding::Boolean)).unwrap_err().kind());
+        }
+
+        #[test]
+        fn encode_attribute_value_fails_for_invalid_raw_value() {
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!(null), None).unwrap_err().kind());
+            assert_eq!(IndyErrorKind::InvalidStructure, _encode(json!({"a": "b"}), None).unwrap_err().kind());
+        }
+
+        #[test]
+        fn encode_credential_values_works() {
+            let raw_values: RawCredentialValues = serde_json::from_value(json!({
+                "name": "Alex",
+                "age": {"raw": "28", "type": "string"},
+                "sex": {"raw": "male", "encoded": "5944657099558967239210949258394887428692050081607692519917050011144233115103"},
+                "height": 175,
+            })).unwrap();
+
+            let values = encode_credential_values(&raw_values).unwrap();
+
+            assert_eq!(4, values.0.len());
+            assert_eq!(STRING_ENCODED, values.0["name"].encoded);
+            assert_eq!(_encode(json!("28"), Some(AttributeEncoding::String)).unwrap(), values.0["age"]);
+            assert_eq!("5944657099558967239210949258394887428692050081607692519917050011144233115103", values.0["sex"].encoded);
+            assert_eq!(AttributeValues { raw: "175".to_string(), encoded: "175".to_string() }, values.0["height"]);
+        }
+
+        #[test]
+        fn encode_credential_values_works_for_encoded_value_and_type() {
+            let raw_values: RawCredentialValues = serde_json::from_value(json!({
+                "age": {"raw": "28", "encoded": "28", "type": "int"},
+            })).unwrap();
+
+            let values = encode_credential_values(&raw_values).unwrap();
+            assert_eq!(AttributeValues { raw: "28".to_string(), encoded: "28".to_string() }, values.0["age"]);
+
+            let raw_values: RawCredentialValues = serde_json::from_value(json!({
+                "age": {"raw": "28", "encoded": "28", "type": "string"},
+            })).unwrap();
+
+            let res = encode_credential_values(&raw_values);
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+    }
+}
@@ -27,7 +27,7 @@ use crate::domain::anoncreds::revocation_state::RevocationState;
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
-use crate::utils::wql::Query;
+use crate::utils::wql::{Query, like_matches};
 
 const ATTRIBUTE_EXISTENCE_MARKER: &str = "1";
 
@@ -495,6 +495,26 @@ impl Prover {
                     Query::In(tag_name, tag_values)
                 }
             }
+            Query::Exist(tag_name) => {
+                if Credential::QUALIFIABLE_TAGS.contains(&tag_name.as_str()) {
+                    Query::Or(vec![Query::Exist(tag_name.clone()),
+                                   Query::Exist(Credential::add_extra_tag_suffix(&tag_name))])
+                } else {
+                    Query::Exist(tag_name)
+                }
+            }
+            Query::ILike(tag_name, tag_value) => {
+                if Credential::QUALIFIABLE_TAGS.contains(&tag_name.as_str()) {
+                    Query::Or(vec![Query::ILike(tag_name.clone(), tag_value.clone()),
+                                   Query::ILike(Credential::add_extra_tag_suffix(&tag_name), tag_value)])
+                } else {
+                    Query::ILike(tag_name, tag_value)
+                }
+            }
+            query @ Query::NumGt(_, _) |
+            query @ Query::NumGte(_, _) |
+            query @ Query::NumLt(_, _) |
+            query @ Query::NumLte(_, _) => query,
             Query::And(operators) => {
                 Query::And(
                     operators
@@ -517,6 +537,87 @@ impl Prover {
             _ => return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "unsupported operator"))
         })
     }
+
+    // Credential tags are encrypted, so $ilike and numeric comparisons can't be evaluated by the wallet.
+    // Splits the query into the wallet query pre-selecting credentials and the query
+    // the pre-selected credential tags must be checked against in memory.
+    pub fn split_credentials_query(&self, query: Query) -> (Query, Option<Query>) {
+        if !Prover::_has_in_memory_operator(&query) {
+            return (query, None);
+        }
+
+        let wallet_query = Prover::_wallet_prefilter(&query).unwrap_or_else(|| Query::And(vec![]));
+
+        (wallet_query, Some(query))
+    }
+
+    pub fn credential_tags_match_query(&self, tags: &HashMap<String, String>, query: &Query) -> bool {
+        match *query {
+            Query::Eq(ref tag_name, ref tag_value) => tags.get(tag_name) == Some(tag_value),
+            Query::Neq(ref tag_name, ref tag_value) => tags.get(tag_name).map(|value| value != tag_value).unwrap_or(false),
+            Query::Gt(ref tag_name, ref tag_value) => tags.get(tag_name).map(|value| value > tag_value).unwrap_or(false),
+            Query::Gte(ref tag_name, ref tag_value) => tags.get(tag_name).map(|value| value >= tag_value).unwrap_or(false),
+            Query::Lt(ref tag_name, ref tag_value) => tags.get(tag_name).map(|value| value < tag_value).unwrap_or(false),
+            Query::Lte(ref tag_name, ref tag_value) => tags.get(tag_name).map(|value| value <= tag_value).unwrap_or(false),
+            Query::Like(ref tag_name, ref pattern) => tags.get(tag_name).map(|value| like_matches(value, pattern)).unwrap_or(false),
+            Query::ILike(ref tag_name, ref pattern) =>
+                tags.get(tag_name).map(|value| like_matches(&value.to_lowercase(), &pattern.to_lowercase())).unwrap_or(false),
+            Query::In(ref tag_name, ref tag_values) => tags.get(tag_name).map(|value| tag_values.contains(value)).unwrap_or(false),
+            Query::Exist(ref tag_name) => tags.contains_key(tag_name),
+            Query::NumGt(ref tag_name, tag_value) => Prover::_tag_numeric_value(tags, tag_name).map(|value| value > tag_value).unwrap_or(false),
+            Query::NumGte(ref tag_name, tag_value) => Prover::_tag_numeric_value(tags, tag_name).map(|value| value >= tag_value).unwrap_or(false),
+            Query::NumLt(ref tag_name, tag_value) => Prover::_tag_numeric_value(tags, tag_name).map(|value| value < tag_value).unwrap_or(false),
+            Query::NumLte(ref tag_name, tag_value) => Prover::_tag_numeric_value(tags, tag_name).map(|value| value <= tag_value).unwrap_or(false),
+            Query::And(ref operators) => operators.iter().all(|operator| self.credential_tags_match_query(tags, operator)),
+            Query::Or(ref operators) => operators.iter().any(|operator| self.credential_tags_match_query(tags, operator)),
+            Query::Not(ref operator) => !self.credential_tags_match_query(tags, operator),
+        }
+    }
+
+    fn _has_in_memory_operator(query: &Query) -> bool {
+        match *query {
+            Query::ILike(_, _) |
+            Query::NumGt(_, _) |
+            Query::NumGte(_, _) |
+            Query::NumLt(_, _) |
+            Query::NumLte(_, _) => true,
+            Query::And(ref operators) | Query::Or(ref operators) => operators.iter().any(Prover::_has_in_memory_operator),
+            Query::Not(ref operator) => Prover::_has_in_memory_operator(operator),
+            _ => false
+        }
+    }
+
+    // Returns the wallet query matching all credentials the query matches or None if any credential can match
+    fn _wallet_prefilter(query: &Query) -> Option<Query> {
+        match *query {
+            Query::ILike(ref tag_name, _) |
+            Query::NumGt(ref tag_name, _) |
+            Query::NumGte(ref tag_name, _) |
+            Query::NumLt(ref tag_name, _) |
+            Query::NumLte(ref tag_name, _) => Some(Query::Exist(tag_name.clone())),
+            Query::And(ref operators) => {
+                let operators: Vec<Query> = operators.iter().filter_map(Prover::_wallet_prefilter).collect();
+                if operators.is_empty() { None } else { Some(Query::And(operators)) }
+            }
+            Query::Or(ref operators) => {
+                operators.iter().map(Prover::_wallet_prefilter).collect::<Option<Vec<Query>>>().map(Query::Or)
+            }
+            Query::Not(ref operator) if Prover::_has_in_memory_operator(operator) => None,
+            _ => Some(query.clone())
+        }
+    }
+
+    // Only values that are integers in decimal notation are compared, as the wallet does for plaintext tags
+    fn _tag_numeric_value(tags: &HashMap<String, String>, tag_name: &str) -> Option<i64> {
+        let value = tags.get(tag_name)?;
+        let digits = if value.starts_with('-') { &value[1..] } else { value.as_str() };
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        value.parse::<i64>().ok()
+    }
 }
 
 #[cfg(test)]
@@ -935,6 +1036,31 @@ mod tests {
             assert_eq!(expected_query, query);
         }
 
+        #[test]
+        fn extend_operator_works_for_exist_for_qualifiable_tag() {
+            let ps = Prover::new();
+
+            let query = Query::Exist(QUALIFIABLE_TAG.to_string());
+            let query = ps.double_restrictions(query).unwrap();
+
+            let expected_query = Query::Or(vec![
+                Query::Exist(QUALIFIABLE_TAG.to_string()),
+                Query::Exist(Credential::add_extra_tag_suffix(QUALIFIABLE_TAG)),
+            ]);
+
+            assert_eq!(expected_query, query);
+        }
+
+        #[test]
+        fn extend_operator_works_for_numeric_comparison() {
+            let ps = Prover::new();
+
+            let query = Query::NumGte(NOT_QUALIFIABLE_TAG.to_string(), 18);
+            let query = ps.double_restrictions(query).unwrap();
+
+            assert_eq!(Query::NumGte(NOT_QUALIFIABLE_TAG.to_string(), 18), query);
+        }
+
         #[test]
         fn extend_operator_works_for_qualifiable_tag_for_combination() {
             let ps = Prover::new();
@@ -957,6 +1083,103 @@ mod tests {
         }
     }
 
+    mod split_credentials_query {
+        use super::*;
+
+        const AGE_TAG: &str = "attr::age::value";
+
+        fn _tags(age: &str) -> HashMap<String, String> {
+            hashmap!(
+                "attr::name::value".to_string() => "Alex".to_string(),
+                AGE_TAG.to_string() => age.to_string()
+            )
+        }
+
+        #[test]
+        fn split_credentials_query_works_for_wallet_operators() {
+            let ps = Prover::new();
+
+            let query = Query::Eq(AGE_TAG.to_string(), "28".to_string());
+            let (wallet_query, in_memory_query) = ps.split_credentials_query(query.clone());
+
+            assert_eq!(query, wallet_query);
+            assert_eq!(None, in_memory_query);
+        }
+
+        #[test]
+        fn split_credentials_query_works_for_numeric_comparison() {
+            let ps = Prover::new();
+
+            let query = Query::And(vec![
+                Query::Eq("attr::age::marker".to_string(), ATTRIBUTE_EXISTENCE_MARKER.to_string()),
+                Query::NumGte(AGE_TAG.to_string(), 18),
+            ]);
+            let (wallet_query, in_memory_query) = ps.split_credentials_query(query.clone());
+
+            let expected_wallet_query = Query::And(vec![
+                Query::Eq("attr::age::marker".to_string(), ATTRIBUTE_EXISTENCE_MARKER.to_string()),
+                Query::Exist(AGE_TAG.to_string()),
+            ]);
+
+            assert_eq!(expected_wallet_query, wallet_query);
+            assert_eq!(Some(query), in_memory_query);
+        }
+
+        #[test]
+        fn split_credentials_query_works_for_negated_numeric_comparison() {
+            let ps = Prover::new();
+
+            let query = Query::And(vec![
+                Query::Eq("attr::age::marker".to_string(), ATTRIBUTE_EXISTENCE_MARKER.to_string()),
+                Query::Not(Box::new(Query::NumGte(AGE_TAG.to_string(), 18))),
+            ]);
+            let (wallet_query, _) = ps.split_credentials_query(query);
+
+            let expected_wallet_query = Query::And(vec![
+                Query::Eq("attr::age::marker".to_string(), ATTRIBUTE_EXISTENCE_MARKER.to_string()),
+            ]);
+
+            assert_eq!(expected_wallet_query, wallet_query);
+        }
+
+        #[test]
+        fn credential_tags_match_query_works_for_numeric_comparison() {
+            let ps = Prover::new();
+
+            let query = Query::NumGte(AGE_TAG.to_string(), 18);
+
+            assert!(ps.credential_tags_match_query(&_tags("28"), &query));
+            assert!(ps.credential_tags_match_query(&_tags("18"), &query));
+            assert!(!ps.credential_tags_match_query(&_tags("17"), &query));
+            assert!(!ps.credential_tags_match_query(&_tags("+28"), &query));
+            assert!(!ps.credential_tags_match_query(&_tags("abc"), &query));
+        }
+
+        #[test]
+        fn credential_tags_match_query_works_for_ilike() {
+            let ps = Prover::new();
+
+            let query = Query::ILike("attr::name::value".to_string(), "al%".to_string());
+            assert!(ps.credential_tags_match_query(&_tags("28"), &query));
+
+            let query = Query::ILike("attr::name::value".to_string(), "bo%".to_string());
+            assert!(!ps.credential_tags_match_query(&_tags("28"), &query));
+        }
+
+        #[test]
+        fn credential_tags_match_query_works_for_combination() {
+            let ps = Prover::new();
+
+            let query = Query::And(vec![
+                Query::Eq("attr::name::value".to_string(), "Alex".to_string()),
+                Query::Not(Box::new(Query::NumLt(AGE_TAG.to_string(), 18))),
+            ]);
+
+            assert!(ps.credential_tags_match_query(&_tags("28"), &query));
+            assert!(!ps.credential_tags_match_query(&_tags("12"), &query));
+        }
+    }
+
     mod extend_proof_request_restrictions {
         use super::*;
 
@@ -1,8 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
+use failure::Fail;
+
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
 use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
+use crate::domain::anoncreds::proof_verification_report::{ProofVerificationReport, ReferentVerificationReport};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
@@ -10,9 +13,9 @@ use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
 
 
-use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
-use ursa::cl::verifier::Verifier as CryptoVerifier;
-use crate::utils::wql::Query;
+use ursa::cl::{CredentialPublicKey, NonCredentialSchema, new_nonce, Nonce};
+use ursa::cl::verifier::{ProofVerifier, Verifier as CryptoVerifier};
+use crate::utils::wql::{Query, like_matches};
 use regex::Regex;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -36,16 +39,38 @@ impl Verifier {
         Verifier {}
     }
 
+    /// Verifies the proof against the proof request.
+    /// If `report` is passed, all referents are checked instead of failing on the first problem
+    /// and the details of each referent and all the reasons the proof was rejected for are collected there.
     pub fn verify(&self,
                   full_proof: &Proof,
                   proof_req: &ProofRequestPayload,
                   schemas: &HashMap<SchemaId, SchemaV1>,
                   cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
                   rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
-                  rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<bool> {
+                  rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                  mut report: Option<&mut ProofVerificationReport>) -> IndyResult<bool> {
         trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
                full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
 
+        match report {
+            Some(ref mut report) => Verifier::_report_requested_proof(full_proof, proof_req, report),
+            None => Verifier::_check_requested_proof(full_proof, proof_req)?
+        }
+
+        let mut valid = Verifier::_verify_sub_proofs(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, report.as_mut().map(|report| &mut **report))?;
+
+        if let Some(report) = report {
+            report.valid = report.errors.is_empty();
+            valid = report.valid;
+        }
+
+        trace!("verify <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    fn _check_requested_proof(full_proof: &Proof, proof_req: &ProofRequestPayload) -> IndyResult<()> {
         let received_revealed_attrs: HashMap<String, Identifier> = Verifier::_received_revealed_attrs(&full_proof)?;
         let received_unrevealed_attrs: HashMap<String, Identifier> = Verifier::_received_unrevealed_attrs(&full_proof)?;
         let received_predicates: HashMap<String, Identifier> = Verifier::_received_predicates(&full_proof)?;
@@ -72,60 +97,211 @@ impl Verifier {
                                                              &received_self_attested_attrs,
                                                              &received_predicates)?;
 
+        Ok(())
+    }
+
+    fn _report_requested_proof(full_proof: &Proof, proof_req: &ProofRequestPayload, report: &mut ProofVerificationReport) {
+        let mut attr_referents: Vec<&String> = proof_req.requested_attributes.keys().collect();
+        attr_referents.sort();
+
+        for referent in attr_referents {
+            let referent_report = Verifier::_verify_requested_attribute(full_proof, proof_req, referent, &proof_req.requested_attributes[referent]);
+            report.errors.extend(referent_report.errors.iter().map(|err| format!("referent {}: {}", referent, err)));
+            report.requested_attributes.insert(referent.to_string(), referent_report);
+        }
+
+        let mut predicate_referents: Vec<&String> = proof_req.requested_predicates.keys().collect();
+        predicate_referents.sort();
+
+        for referent in predicate_referents {
+            let referent_report = Verifier::_verify_requested_predicate(full_proof, proof_req, referent, &proof_req.requested_predicates[referent]);
+            report.errors.extend(referent_report.errors.iter().map(|err| format!("referent {}: {}", referent, err)));
+            report.requested_predicates.insert(referent.to_string(), referent_report);
+        }
+
+        let requested_proof = &full_proof.requested_proof;
+
+        let mut received_attr_referents: Vec<&String> = requested_proof.revealed_attrs.keys()
+            .chain(requested_proof.revealed_attr_groups.keys())
+            .chain(requested_proof.unrevealed_attrs.keys())
+            .chain(requested_proof.self_attested_attrs.keys())
+            .filter(|referent| !proof_req.requested_attributes.contains_key(*referent))
+            .collect();
+        received_attr_referents.sort();
+
+        report.errors.extend(received_attr_referents.iter()
+            .map(|referent| format!("referent {}: attribute isn't requested in proof request", referent)));
+
+        let mut received_predicate_referents: Vec<&String> = requested_proof.predicates.keys()
+            .filter(|referent| !proof_req.requested_predicates.contains_key(*referent))
+            .collect();
+        received_predicate_referents.sort();
+
+        report.errors.extend(received_predicate_referents.iter()
+            .map(|referent| format!("referent {}: predicate isn't requested in proof request", referent)));
+    }
+
+    // Without the report the first problem is returned as error,
+    // with it problems are attributed to the referents of the sub proof they are found for
+    fn _verify_sub_proofs(full_proof: &Proof,
+                          proof_req: &ProofRequestPayload,
+                          schemas: &HashMap<SchemaId, SchemaV1>,
+                          cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                          rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                          rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                          mut report: Option<&mut ProofVerificationReport>) -> IndyResult<bool> {
         let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
         let non_credential_schema = build_non_credential_schema()?;
 
+        let mut sub_proofs_added = true;
+
         for sub_proof_index in 0..full_proof.identifiers.len() {
-            let identifier = full_proof.identifiers[sub_proof_index].clone();
+            let res = Verifier::_add_sub_proof_request(&mut proof_verifier, &non_credential_schema, sub_proof_index,
+                                                       full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+            match (res, report.as_mut()) {
+                (Ok(()), _) => {}
+                (Err(err), Some(report)) => {
+                    Verifier::_report_sub_proof_error(report, full_proof, sub_proof_index, &Verifier::_error_reason(&err));
+                    sub_proofs_added = false;
+                }
+                (Err(err), None) => return Err(err)
+            }
+        }
 
-            let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
+        // proof can't be checked without all its sub proofs, the reasons are reported already
+        if !sub_proofs_added {
+            return Ok(false);
+        }
+
+        let res = proof_verifier.verify(&full_proof.proof, &proof_req.nonce).map_err(IndyError::from);
+
+        let report = match report {
+            Some(report) => report,
+            None => return res
+        };
+
+        let reason = match res {
+            Ok(true) => return Ok(true),
+            Ok(false) => "proof cryptographic verification failed".to_string(),
+            Err(err) => Verifier::_error_reason(&err)
+        };
+
+        // sub proofs are bound by the common challenge, so the failure can't be narrowed down to one of them
+        report.errors.push(reason.clone());
+
+        for sub_proof_index in 0..full_proof.identifiers.len() {
+            for (_, referent_report) in Verifier::_sub_proof_referents(report, full_proof, sub_proof_index) {
+                referent_report.errors.push(reason.clone());
+                referent_report.valid = false;
+            }
+        }
+
+        Ok(false)
+    }
 
-            let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+    fn _add_sub_proof_request(proof_verifier: &mut ProofVerifier,
+                              non_credential_schema: &NonCredentialSchema,
+                              sub_proof_index: usize,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<()> {
+        let identifier = full_proof.identifiers[sub_proof_index].clone();
 
-            let (rev_reg_def, rev_reg) =
-                if let Some(timestamp) = identifier.timestamp {
-                    let rev_reg_id = identifier.rev_reg_id
-                        .clone()
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+        let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
 
-                    let rev_reg_def = Some(rev_reg_defs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+        let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
 
-                    let rev_regs_for_cred = rev_regs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+        let (rev_reg_def, rev_reg) =
+            if let Some(timestamp) = identifier.timestamp {
+                let rev_reg_id = identifier.rev_reg_id
+                    .clone()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
 
-                    let rev_reg = Some(rev_regs_for_cred
-                        .get(&timestamp)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+                let rev_reg_def = Some(rev_reg_defs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
 
-                    (rev_reg_def, rev_reg)
-                } else { (None, None) };
+                let rev_regs_for_cred = rev_regs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
 
-            let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
-            let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
+                let rev_reg = Some(rev_regs_for_cred
+                    .get(&timestamp)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
 
-            let credential_schema = build_credential_schema(&schema.attr_names.0)?;
-            let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
+                (rev_reg_def, rev_reg)
+            } else { (None, None) };
 
-            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+        let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
+        let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
 
-            proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                                 &credential_schema,
-                                                 &non_credential_schema,
-                                                 &credential_pub_key,
-                                                 rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
-                                                 rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+        let credential_schema = build_credential_schema(&schema.attr_names.0)?;
+        let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
+
+        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             non_credential_schema,
+                                             &credential_pub_key,
+                                             rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
+                                             rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+
+        Ok(())
+    }
+
+    fn _report_sub_proof_error(report: &mut ProofVerificationReport, full_proof: &Proof, sub_proof_index: usize, reason: &str) {
+        let mut referents: Vec<String> = Vec::new();
+
+        for (referent, referent_report) in Verifier::_sub_proof_referents(report, full_proof, sub_proof_index) {
+            referent_report.errors.push(reason.to_string());
+            referent_report.valid = false;
+            referents.push(referent);
         }
 
-        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+        if referents.is_empty() {
+            report.errors.push(reason.to_string());
+        } else {
+            referents.sort();
+            report.errors.extend(referents.iter().map(|referent| format!("referent {}: {}", referent, reason)));
+        }
+    }
 
-        trace!("verify <<< valid: {:?}", valid);
+    // Reports of the requested referents the sub proof is used for
+    fn _sub_proof_referents<'a>(report: &'a mut ProofVerificationReport,
+                                full_proof: &Proof,
+                                sub_proof_index: usize) -> Vec<(String, &'a mut ReferentVerificationReport)> {
+        let requested_proof = &full_proof.requested_proof;
+        let sub_proof_index = sub_proof_index as u32;
+
+        let attr_referents: HashSet<&String> = requested_proof.revealed_attrs.iter()
+            .filter(|&(_, info)| info.sub_proof_index == sub_proof_index)
+            .map(|(referent, _)| referent)
+            .chain(requested_proof.revealed_attr_groups.iter()
+                .filter(|&(_, info)| info.sub_proof_index == sub_proof_index)
+                .map(|(referent, _)| referent))
+            .chain(requested_proof.unrevealed_attrs.iter()
+                .filter(|&(_, info)| info.sub_proof_index == sub_proof_index)
+                .map(|(referent, _)| referent))
+            .collect();
 
-        Ok(valid)
+        let predicate_referents: HashSet<&String> = requested_proof.predicates.iter()
+            .filter(|&(_, info)| info.sub_proof_index == sub_proof_index)
+            .map(|(referent, _)| referent)
+            .collect();
+
+        report.requested_attributes.iter_mut()
+            .filter(|&(ref referent, _)| attr_referents.contains(referent))
+            .chain(report.requested_predicates.iter_mut()
+                .filter(|&(ref referent, _)| predicate_referents.contains(referent)))
+            .map(|(referent, referent_report)| (referent.to_string(), referent_report))
+            .collect()
     }
 
     pub fn generate_nonce(&self) -> IndyResult<Nonce> {
@@ -138,6 +314,147 @@ impl Verifier {
         Ok(nonce)
     }
 
+    fn _verify_requested_attribute(proof: &Proof,
+                                   proof_req: &ProofRequestPayload,
+                                   referent: &str,
+                                   info: &AttributeInfo) -> ReferentVerificationReport {
+        let requested_proof = &proof.requested_proof;
+
+        let mut report = ReferentVerificationReport {
+            non_revoked: get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked),
+            ..ReferentVerificationReport::default()
+        };
+
+        let mut revealed_attrs: Vec<(String, RevealedAttributeInfo)> = Vec::new();
+
+        let sub_proof_index = if let Some(attr_info) = requested_proof.revealed_attrs.get(referent) {
+            report.revealed = true;
+            match info.name {
+                Some(ref name) => revealed_attrs.push((name.to_string(), attr_info.clone())),
+                None => report.errors.push("revealed attribute doesn't correspond to requested attribute names".to_string())
+            }
+            Some(attr_info.sub_proof_index)
+        } else if let Some(attr_infos) = requested_proof.revealed_attr_groups.get(referent) {
+            report.revealed = true;
+            let requested_names: HashSet<&String> = info.names.iter().flatten().collect();
+            let received_names: HashSet<&String> = attr_infos.values.keys().collect();
+            if requested_names != received_names {
+                report.errors.push(format!("revealed attribute group {:?} doesn't correspond to requested attribute names {:?}", received_names, requested_names));
+            }
+            for (name, value) in attr_infos.values.iter() {
+                revealed_attrs.push((name.to_string(), RevealedAttributeInfo {
+                    sub_proof_index: attr_infos.sub_proof_index,
+                    raw: value.raw.clone(),
+                    encoded: value.encoded.clone()
+                }));
+            }
+            Some(attr_infos.sub_proof_index)
+        } else if let Some(attr_info) = requested_proof.unrevealed_attrs.get(referent) {
+            Some(attr_info.sub_proof_index)
+        } else if let Some(value) = requested_proof.self_attested_attrs.get(referent) {
+            report.self_attested = true;
+            report.values = Some(info.name.iter().map(|name| (name.to_string(), value.to_string())).collect());
+            None
+        } else {
+            report.errors.push("attribute not found in proof".to_string());
+            return report;
+        };
+
+        if report.revealed {
+            report.values = Some(revealed_attrs.iter().map(|(name, attr_info)| (name.to_string(), attr_info.raw.to_string())).collect());
+        }
+
+        for (name, attr_info) in revealed_attrs.iter() {
+            if let Err(err) = Verifier::_verify_revealed_attribute_value(name, proof, attr_info) {
+                report.errors.push(Verifier::_error_reason(&err));
+            }
+        }
+
+        if let Some(sub_proof_index) = sub_proof_index {
+            Verifier::_report_identifier(&mut report, proof, sub_proof_index);
+        }
+
+        if report.self_attested {
+            if !Verifier::_is_self_attested(referent, info, &Verifier::_received_self_attested_attrs(proof)) {
+                report.restrictions_satisfied = Some(false);
+                report.errors.push("self-attested attribute can't satisfy restrictions".to_string());
+            }
+        } else if let Some(ref query) = info.restrictions {
+            let name_value_map: HashMap<String, Option<&str>> = info.name.iter()
+                .chain(info.names.iter().flatten())
+                .map(|name| (name.to_string(),
+                             revealed_attrs.iter().find(|(revealed_name, _)| revealed_name == name).map(|(_, attr_info)| attr_info.raw.as_str())))
+                .collect();
+
+            Verifier::_report_restrictions(&mut report, |filter| Verifier::_do_process_operator(&name_value_map, query, filter));
+        }
+
+        report.valid = report.errors.is_empty();
+        report
+    }
+
+    fn _verify_requested_predicate(proof: &Proof,
+                                   proof_req: &ProofRequestPayload,
+                                   referent: &str,
+                                   info: &PredicateInfo) -> ReferentVerificationReport {
+        let mut report = ReferentVerificationReport {
+            non_revoked: get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked),
+            ..ReferentVerificationReport::default()
+        };
+
+        match proof.requested_proof.predicates.get(referent) {
+            Some(predicate) => Verifier::_report_identifier(&mut report, proof, predicate.sub_proof_index),
+            None => {
+                report.errors.push("predicate not found in proof".to_string());
+                return report;
+            }
+        }
+
+        if let Some(ref query) = info.restrictions {
+            Verifier::_report_restrictions(&mut report, |filter| Verifier::_process_operator(&info.name, query, filter, None));
+        }
+
+        report.valid = report.errors.is_empty();
+        report
+    }
+
+    fn _report_identifier(report: &mut ReferentVerificationReport, proof: &Proof, sub_proof_index: u32) {
+        match Verifier::_get_proof_identifier(proof, sub_proof_index) {
+            Ok(identifier) => {
+                if report.non_revoked.is_some() && identifier.timestamp.is_none() {
+                    report.errors.push("non-revocation timestamp is missing".to_string());
+                }
+                report.identifier = Some(identifier);
+            }
+            Err(err) => report.errors.push(Verifier::_error_reason(&err))
+        }
+    }
+
+    fn _report_restrictions<F>(report: &mut ReferentVerificationReport, check: F) where F: Fn(&Filter) -> IndyResult<()> {
+        let res = match report.identifier {
+            Some(ref identifier) => Verifier::_filter_from_identifier(identifier).and_then(|filter| check(&filter)),
+            None => return
+        };
+
+        report.restrictions_satisfied = Some(res.is_ok());
+
+        if let Err(err) = res {
+            report.errors.push(format!("restriction validation failed: {}", Verifier::_error_reason(&err)));
+        }
+    }
+
+    fn _error_reason(err: &IndyError) -> String {
+        let reasons: Vec<String> = Fail::iter_causes(err)
+            .map(|cause| cause.to_string())
+            .collect();
+
+        if reasons.is_empty() {
+            err.kind().to_string()
+        } else {
+            reasons.join(": ")
+        }
+    }
+
     fn _get_revealed_attributes_for_credential(sub_proof_index: usize,
                                                requested_proof: &RequestedProof,
                                                proof_req: &ProofRequestPayload) -> IndyResult<Vec<AttributeInfo>> {
@@ -454,6 +771,10 @@ impl Verifier {
                 format!("Identifier not found for referent: {}", referent))
             )?;
 
+        Verifier::_filter_from_identifier(identifier)
+    }
+
+    fn _filter_from_identifier(identifier: &Identifier) -> IndyResult<Filter> {
         let (schema_issuer_did, schema_name, schema_version) = identifier.schema_id.parts()
             .ok_or(IndyError::from_msg(IndyErrorKind::InvalidState, format!("Invalid Schema ID `{}`: wrong number of parts", identifier.schema_id.0)))?;
 
@@ -531,10 +852,70 @@ impl Verifier {
                     Err(IndyError::from_msg(IndyErrorKind::ProofRejected, "$not operator validation failed. All conditions were passed."))
                 }
             }
+            Query::Exist(ref tag_name) => {
+                if Verifier::_filter_value(attr_value_map, &tag_name, filter).is_ok() {
+                    Ok(())
+                } else {
+                    Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                            format!("$exist operator validation failed for tag: \"{}\"", tag_name)))
+                }
+            }
+            Query::ILike(ref tag_name, ref tag_value) => {
+                match Verifier::_filter_value(attr_value_map, &tag_name, filter)? {
+                    Some(value) if !like_matches(&value.to_lowercase(), &tag_value.to_lowercase()) => {
+                        Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                                format!("$ilike operator validation failed for tag: \"{}\", value: \"{}\"", tag_name, tag_value)))
+                    }
+                    _ => Ok(())
+                }
+            }
+            Query::NumGt(ref tag_name, tag_value) => Verifier::_process_numeric(attr_value_map, &tag_name, filter, "$gt", |value| value > *tag_value),
+            Query::NumGte(ref tag_name, tag_value) => Verifier::_process_numeric(attr_value_map, &tag_name, filter, "$gte", |value| value >= *tag_value),
+            Query::NumLt(ref tag_name, tag_value) => Verifier::_process_numeric(attr_value_map, &tag_name, filter, "$lt", |value| value < *tag_value),
+            Query::NumLte(ref tag_name, tag_value) => Verifier::_process_numeric(attr_value_map, &tag_name, filter, "$lte", |value| value <= *tag_value),
             _ => Err(IndyError::from_msg(IndyErrorKind::ProofRejected, "unsupported operator"))
         }
     }
 
+    fn _process_numeric<F>(attr_value_map: &HashMap<String, Option<&str>>,
+                           tag: &str,
+                           filter: &Filter,
+                           operator: &str,
+                           check: F) -> IndyResult<()> where F: Fn(i64) -> bool {
+        match Verifier::_filter_value(attr_value_map, tag, filter)? {
+            Some(value) => match value.parse::<i64>() {
+                Ok(number) if check(number) => Ok(()),
+                _ => Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                             format!("{} operator validation failed for tag: \"{}\", value: \"{}\"", operator, tag, value)))
+            },
+            None => Ok(())
+        }
+    }
+
+    // Returns None if the value can't be checked as the attribute isn't revealed
+    fn _filter_value<'a>(attr_value_map: &HashMap<String, Option<&'a str>>,
+                         tag: &str,
+                         filter: &'a Filter) -> IndyResult<Option<&'a str>> {
+        match tag {
+            "schema_id" => Ok(Some(&filter.schema_id)),
+            "schema_issuer_did" => Ok(Some(&filter.schema_issuer_did)),
+            "schema_name" => Ok(Some(&filter.schema_name)),
+            "schema_version" => Ok(Some(&filter.schema_version)),
+            "cred_def_id" => Ok(Some(&filter.cred_def_id)),
+            "issuer_did" => Ok(Some(&filter.issuer_did)),
+            x if Verifier::_is_attr_internal_tag(x, attr_value_map) && x.ends_with("::value") => {
+                let attr_name = INTERNAL_TAG_MATCHER.captures(x)
+                    .and_then(|caps| caps.get(1))
+                    .map(|attr_name| attr_name.as_str())
+                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidState, "Attribute name became unparseable"))?;
+
+                Ok(attr_value_map.get(attr_name).cloned().unwrap_or(None))
+            }
+            x if Verifier::_is_attr_internal_tag(x, attr_value_map) || Verifier::_is_attr_operator(x) => Ok(None),
+            _ => Err(err_msg(IndyErrorKind::InvalidStructure, "Unknown Filter Type"))
+        }
+    }
+
     fn _process_filter(attr_value_map: &HashMap<String, Option<&str>>,
                        tag: &str,
                        tag_value: &str,
@@ -861,6 +1242,53 @@ mod tests {
         assert!(Verifier::_process_operator("zip", &op, &filter, Some("NOT HERE")).is_err());
     }
 
+    #[test]
+    fn test_process_op_exist() {
+        let filter = filter();
+
+        let op = Query::Exist(schema_name_tag());
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        let op = Query::Exist(attr_tag_value());
+        Verifier::_process_operator("zip", &op, &filter, Some("value")).unwrap();
+
+        let op = Query::Exist("unknown".to_string());
+        assert!(Verifier::_process_operator("zip", &op, &filter, None).is_err());
+    }
+
+    #[test]
+    fn test_process_op_ilike() {
+        let filter = filter();
+
+        let op = Query::ILike(schema_name_tag(), "schema%".to_string());
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        let op = Query::ILike(schema_name_tag(), "_CHEMA NAM_".to_string());
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        let op = Query::ILike(schema_name_tag(), "%other%".to_string());
+        assert!(Verifier::_process_operator("zip", &op, &filter, None).is_err());
+    }
+
+    #[test]
+    fn test_process_op_numeric() {
+        let filter = filter();
+
+        let op = Query::NumGt(attr_tag_value(), 100);
+        Verifier::_process_operator("zip", &op, &filter, Some("101")).unwrap();
+        assert!(Verifier::_process_operator("zip", &op, &filter, Some("100")).is_err());
+        assert!(Verifier::_process_operator("zip", &op, &filter, Some("abc")).is_err());
+
+        // value of unrevealed attribute can't be checked
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        let op = Query::NumLte(attr_tag_value(), 100);
+        Verifier::_process_operator("zip", &op, &filter, Some("100")).unwrap();
+
+        let op = Query::NumGte(schema_version_tag(), 1);
+        assert!(Verifier::_process_operator("zip", &op, &filter, None).is_err());
+    }
+
     fn _received() -> HashMap<String, Identifier> {
         let mut res: HashMap<String, Identifier> = HashMap::new();
         res.insert("referent_1".to_string(), Identifier { timestamp: Some(1234), schema_id: SchemaId(String::new()), cred_def_id: CredentialDefinitionId(String::new()), rev_reg_id: Some(RevocationRegistryId(String::new())) });
@@ -885,4 +1313,57 @@ mod tests {
         Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval())).unwrap_err();
         Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval())).unwrap_err();
     }
+
+    #[test]
+    fn error_reason_works() {
+        let op = Query::Eq(issuer_did_tag(), "NOT HERE".to_string());
+        let err = Verifier::_process_operator("zip", &op, &filter(), None).unwrap_err();
+        assert_eq!(format!("$eq operator validation failed for tag: \"issuer_did\", value: \"NOT HERE\": \"issuer_did\" values are different: expected: \"NOT HERE\", actual: \"{}\"", ISSUER_DID),
+                   Verifier::_error_reason(&err));
+
+        assert_eq!("Invalid structure", Verifier::_error_reason(&IndyError::from(IndyErrorKind::InvalidStructure)));
+    }
+
+    fn _referent_report(timestamp: Option<u64>) -> ReferentVerificationReport {
+        ReferentVerificationReport {
+            identifier: Some(Identifier {
+                timestamp,
+                schema_id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+                cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+                rev_reg_id: None,
+            }),
+            ..ReferentVerificationReport::default()
+        }
+    }
+
+    #[test]
+    fn report_restrictions_works() {
+        let mut report = _referent_report(None);
+        let op = Query::Eq(issuer_did_tag(), "NcYxiDXkpYi6ov5FcYDi1e".to_string());
+        Verifier::_report_restrictions(&mut report, |filter| Verifier::_process_operator("zip", &op, filter, None));
+
+        assert_eq!(Some(true), report.restrictions_satisfied);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn report_restrictions_works_for_mismatch() {
+        let mut report = _referent_report(None);
+        let op = Query::Eq(issuer_did_tag(), ISSUER_DID.to_string());
+        Verifier::_report_restrictions(&mut report, |filter| Verifier::_process_operator("zip", &op, filter, None));
+
+        assert_eq!(Some(false), report.restrictions_satisfied);
+        assert_eq!(1, report.errors.len());
+        assert!(report.errors[0].starts_with("restriction validation failed: $eq operator validation failed for tag: \"issuer_did\""));
+    }
+
+    #[test]
+    fn report_restrictions_skipped_without_identifier() {
+        let mut report = ReferentVerificationReport::default();
+        let op = Query::Eq(issuer_did_tag(), ISSUER_DID.to_string());
+        Verifier::_report_restrictions(&mut report, |filter| Verifier::_process_operator("zip", &op, filter, None));
+
+        assert_eq!(None, report.restrictions_satisfied);
+        assert!(report.errors.is_empty());
+    }
 }
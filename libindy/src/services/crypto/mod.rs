@@ -1,11 +1,14 @@
 extern crate hex;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::str;
 
 use crate::domain::crypto::combo_box::ComboBox;
 use crate::domain::crypto::did::{Did, DidValue, MyDidInfo, TheirDid, TheirDidInfo};
 use crate::domain::crypto::key::{Key, KeyInfo};
+use indy_api_types::crypto::*;
 use indy_api_types::errors::prelude::*;
 use crate::utils::crypto::base64;
 use crate::utils::crypto::ed25519_box;
@@ -15,10 +18,12 @@ use crate::utils::crypto::ed25519_sign;
 use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
 
 use self::ed25519::ED25519CryptoType;
+use self::plugged::PluggedKeyProvider;
 use self::hex::FromHex;
 use rust_base58::{FromBase58, ToBase58};
 
 mod ed25519;
+mod plugged;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
 
@@ -36,8 +41,19 @@ trait CryptoType {
     fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
 }
 
+// Keeps secret keys outside of the wallet (For example, in external KMS process).
+// Keys are ed25519 keys, so only operations that need secret key are delegated to the provider.
+trait KeyProvider {
+    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<ed25519_sign::PublicKey>;
+    fn sign(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<ed25519_sign::Signature>;
+    fn crypto_box(&self, vk: &ed25519_sign::PublicKey, their_vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box_open(&self, vk: &ed25519_sign::PublicKey, their_vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
+}
+
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    key_providers: RefCell<HashMap<String, Rc<dyn KeyProvider>>>,
 }
 
 impl CryptoService {
@@ -46,10 +62,35 @@ impl CryptoService {
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            key_providers: RefCell::new(HashMap::new()),
         }
     }
 
+    pub fn register_key_provider(&self,
+                                 crypto_type: &str,
+                                 create_key: KeyProviderCreateKey,
+                                 sign: KeyProviderSign,
+                                 crypto_box: KeyProviderCryptoBox,
+                                 crypto_box_open: KeyProviderCryptoBoxOpen,
+                                 crypto_box_seal_open: KeyProviderCryptoBoxSealOpen,
+                                 free_result: KeyProviderFreeResult) -> IndyResult<()> {
+        trace!("register_key_provider >>> crypto_type: {:?}", crypto_type);
+
+        let mut key_providers = self.key_providers.borrow_mut();
+
+        if self.crypto_types.contains_key(crypto_type) || key_providers.contains_key(crypto_type) {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Crypto type is already registered: {}", crypto_type)));
+        }
+
+        key_providers.insert(crypto_type.to_string(),
+                             Rc::new(PluggedKeyProvider::new(create_key, sign, crypto_box, crypto_box_open,
+                                                             crypto_box_seal_open, free_result)));
+
+        trace!("register_key_provider <<<");
+        Ok(())
+    }
+
     pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
         trace!("create_key >>> key_info: {:?}", secret!(key_info));
 
@@ -58,16 +99,13 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
-
         let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
+        let (vk, sk) = self._create_key(crypto_type_name, seed.as_ref())?;
         let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
             vk = format!("{}:{}", vk, crypto_type_name);
@@ -88,14 +126,12 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("MyDidInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
-
         let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
+        let (vk, sk) = self._create_key(crypto_type_name, seed.as_ref())?;
         let did = match my_did_info.did {
             Some(ref did) => did.clone(),
             _ if my_did_info.cid == Some(true) =>
@@ -105,7 +141,6 @@ impl CryptoService {
         };
 
         let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
 
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
@@ -142,14 +177,19 @@ impl CryptoService {
 
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to sign message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let signature = match self._key_provider(crypto_type_name) {
+            Some(key_provider) => key_provider.sign(&self._my_vk(my_key)?, doc)?,
+            None => {
+                let crypto_type = self._crypto_type(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let signature = crypto_type.sign(&my_sk, doc)?[..].to_vec();
+                let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
+                crypto_type.sign(&my_sk, doc)?
+            }
+        }[..].to_vec();
 
         trace!("sign <<< signature: {:?}", signature);
 
@@ -161,11 +201,11 @@ impl CryptoService {
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to verify message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = self._crypto_type(crypto_type_name).unwrap();
 
         let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk.from_base58()?)?;
         let signature = ed25519_sign::Signature::from_slice(&signature)?;
@@ -200,11 +240,11 @@ impl CryptoService {
 
         let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to crypto_box message with unknown crypto: {}", crypto_type_name)));
         }
 
-        if !crypto_type_name.eq(their_crypto_type_name) {
+        if !self._base_crypto_type_name(crypto_type_name).eq(self._base_crypto_type_name(their_crypto_type_name)) {
             // TODO: FIXME: Use dedicated error code
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("My key crypto type is incompatible with their key crypto type: {} {}",
@@ -212,13 +252,18 @@ impl CryptoService {
                                        their_crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
+        let crypto_type = self._crypto_type(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
         let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
         let nonce = crypto_type.gen_nonce();
 
-        let encrypted_doc = crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?;
+        let encrypted_doc = match self._key_provider(crypto_type_name) {
+            Some(key_provider) => key_provider.crypto_box(&self._my_vk(my_key)?, &their_vk, doc, &nonce)?,
+            None => {
+                let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
+                crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?
+            }
+        };
         let nonce = nonce[..].to_vec();
 
         trace!("crypto_box <<< encrypted_doc: {:?}, nonce: {:?}", encrypted_doc, nonce);
@@ -233,12 +278,12 @@ impl CryptoService {
 
         let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("Trying to crypto_box_open message with unknown crypto: {}", crypto_type_name)));
         }
 
-        if !crypto_type_name.eq(their_crypto_type_name) {
+        if !self._base_crypto_type_name(crypto_type_name).eq(self._base_crypto_type_name(their_crypto_type_name)) {
             // TODO: FIXME: Use dedicated error code
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("My key crypto type is incompatible with their key crypto type: {} {}",
@@ -246,13 +291,18 @@ impl CryptoService {
                                        their_crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
-
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
         let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
         let nonce = ed25519_box::Nonce::from_slice(&nonce)?;
 
-        let decrypted_doc = crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?;
+        let decrypted_doc = match self._key_provider(crypto_type_name) {
+            Some(key_provider) => key_provider.crypto_box_open(&self._my_vk(my_key)?, &their_vk, &doc, &nonce)?,
+            None => {
+                let crypto_type = self._crypto_type(crypto_type_name).unwrap();
+
+                let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
+                crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?
+            }
+        };
 
         trace!("crypto_box_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -264,11 +314,11 @@ impl CryptoService {
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to encrypt sealed message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = self._crypto_type(crypto_type_name).unwrap();
 
         let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
 
@@ -284,17 +334,22 @@ impl CryptoService {
 
         let (my_vk, crypto_type_name) = split_verkey(&my_key.verkey);
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("Trying to crypto_box_open sealed message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
-
         let my_vk = ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())?;
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
 
-        let decrypted_doc = crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?;
+        let decrypted_doc = match self._key_provider(crypto_type_name) {
+            Some(key_provider) => key_provider.crypto_box_seal_open(&my_vk, doc)?,
+            None => {
+                let crypto_type = self._crypto_type(crypto_type_name).unwrap();
+
+                let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
+                crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?
+            }
+        };
 
         trace!("crypto_box_seal_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -347,11 +402,11 @@ impl CryptoService {
 
         let (vk, crypto_type_name) = split_verkey(vk);
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        if self._crypto_type(crypto_type_name).is_none() {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to use key with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = self._crypto_type(crypto_type_name).unwrap();
 
         if vk.starts_with('~') {
             let _ = vk[1..].from_base58()?; // TODO: proper validate abbreviated verkey
@@ -440,6 +495,45 @@ impl CryptoService {
             err_msg(IndyErrorKind::InvalidStructure, format!("Failed to convert message to UTF-8 {}", err))
         })
     }
+
+    // Keys of external key providers are ed25519 keys, so they are compatible with the default crypto type
+    // and operations that don't need secret key are performed locally.
+    fn _base_crypto_type_name<'a>(&self, crypto_type_name: &'a str) -> &'a str {
+        if self.key_providers.borrow().contains_key(crypto_type_name) {
+            DEFAULT_CRYPTO_TYPE
+        } else {
+            crypto_type_name
+        }
+    }
+
+    fn _crypto_type(&self, crypto_type_name: &str) -> Option<&dyn CryptoType> {
+        self.crypto_types
+            .get(self._base_crypto_type_name(crypto_type_name))
+            .map(Box::as_ref)
+    }
+
+    fn _key_provider(&self, crypto_type_name: &str) -> Option<Rc<dyn KeyProvider>> {
+        self.key_providers.borrow().get(crypto_type_name).cloned()
+    }
+
+    // Returns verkey and signkey. Signkey is empty for keys of external key providers
+    // as secret key never leaves the provider.
+    fn _create_key(&self, crypto_type_name: &str, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(ed25519_sign::PublicKey, String)> {
+        match self._key_provider(crypto_type_name) {
+            Some(key_provider) => Ok((key_provider.create_key(seed)?, String::new())),
+            None => {
+                let crypto_type = self._crypto_type(crypto_type_name).unwrap();
+
+                let (vk, sk) = crypto_type.create_key(seed)?;
+                Ok((vk, sk[..].to_base58()))
+            }
+        }
+    }
+
+    fn _my_vk(&self, my_key: &Key) -> IndyResult<ed25519_sign::PublicKey> {
+        let (my_vk, _) = split_verkey(&my_key.verkey);
+        ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())
+    }
 }
 
 
@@ -447,9 +541,26 @@ impl CryptoService {
 mod tests {
     use crate::domain::crypto::did::MyDidInfo;
     use crate::utils::crypto::chacha20poly1305_ietf::gen_key;
+    use indy_utils::inmem_key_provider::InmemKeyProvider;
 
     use super::*;
 
+    const KEY_PROVIDER_CRYPTO_TYPE: &str = "inmem_key_provider";
+
+    fn _register_key_provider(service: &CryptoService) {
+        _register_key_provider_for(service, KEY_PROVIDER_CRYPTO_TYPE).unwrap();
+    }
+
+    fn _register_key_provider_for(service: &CryptoService, crypto_type: &str) -> IndyResult<()> {
+        service.register_key_provider(crypto_type,
+                                      InmemKeyProvider::create_key,
+                                      InmemKeyProvider::sign,
+                                      InmemKeyProvider::crypto_box,
+                                      InmemKeyProvider::crypto_box_open,
+                                      InmemKeyProvider::crypto_box_seal_open,
+                                      InmemKeyProvider::free_result)
+    }
+
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
@@ -690,6 +801,102 @@ mod tests {
         assert_eq!(msg, decrypted_message.as_slice());
     }
 
+    #[test]
+    fn register_key_provider_works_for_already_registered_crypto_type() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        assert_eq!(IndyErrorKind::InvalidState, _register_key_provider_for(&service, KEY_PROVIDER_CRYPTO_TYPE).unwrap_err().kind());
+        assert_eq!(IndyErrorKind::InvalidState, _register_key_provider_for(&service, DEFAULT_CRYPTO_TYPE).unwrap_err().kind());
+    }
+
+    #[test]
+    fn create_key_works_for_key_provider() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let key_info = KeyInfo { seed: None, crypto_type: Some(KEY_PROVIDER_CRYPTO_TYPE.to_string()) };
+        let key = service.create_key(&key_info).unwrap();
+
+        assert!(key.verkey.ends_with(&format!(":{}", KEY_PROVIDER_CRYPTO_TYPE)));
+        assert!(key.signkey.is_empty());
+        service.validate_key(&key.verkey).unwrap();
+    }
+
+    #[test]
+    fn create_my_did_works_for_key_provider_and_seed() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, method_name: None };
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+
+        let did_info = MyDidInfo { crypto_type: Some(KEY_PROVIDER_CRYPTO_TYPE.to_string()), ..did_info };
+        let (kms_did, kms_key) = service.create_my_did(&did_info).unwrap();
+
+        assert_eq!(my_did.did, kms_did.did);
+        assert_eq!(format!("{}:{}", my_did.verkey, KEY_PROVIDER_CRYPTO_TYPE), kms_key.verkey);
+        assert!(kms_key.signkey.is_empty());
+    }
+
+    #[test]
+    fn sign_verify_works_for_key_provider() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let message = r#"message"#;
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(KEY_PROVIDER_CRYPTO_TYPE.to_string()) }).unwrap();
+
+        let signature = service.sign(&key, message.as_bytes()).unwrap();
+
+        assert!(service.verify(&key.verkey, message.as_bytes(), &signature).unwrap());
+
+        let (verkey, _) = split_verkey(&key.verkey);
+        assert!(service.verify(verkey, message.as_bytes(), &signature).unwrap());
+    }
+
+    #[test]
+    fn sign_works_for_key_provider_unknown_key() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let key = Key::new(format!("5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp:{}", KEY_PROVIDER_CRYPTO_TYPE), String::new());
+
+        assert_eq!(IndyErrorKind::WalletItemNotFound, service.sign(&key, "message".as_bytes()).unwrap_err().kind());
+    }
+
+    #[test]
+    fn crypto_box_and_crypto_box_open_works_for_key_provider() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let msg = "some message";
+
+        let kms_key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(KEY_PROVIDER_CRYPTO_TYPE.to_string()) }).unwrap();
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&kms_key, &key.verkey, msg.as_bytes()).unwrap();
+        let decrypted_message = service.crypto_box_open(&key, &kms_key.verkey, &encrypted_message, &nonce).unwrap();
+        assert_eq!(msg.as_bytes().to_vec(), decrypted_message);
+
+        let (encrypted_message, nonce) = service.crypto_box(&key, &kms_key.verkey, msg.as_bytes()).unwrap();
+        let decrypted_message = service.crypto_box_open(&kms_key, &key.verkey, &encrypted_message, &nonce).unwrap();
+        assert_eq!(msg.as_bytes().to_vec(), decrypted_message);
+    }
+
+    #[test]
+    fn crypto_box_seal_and_crypto_box_seal_open_works_for_key_provider() {
+        let service = CryptoService::new();
+        _register_key_provider(&service);
+
+        let msg = "some message".as_bytes();
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(KEY_PROVIDER_CRYPTO_TYPE.to_string()) }).unwrap();
+
+        let encrypted_message = service.crypto_box_seal(&key.verkey, msg).unwrap();
+        let decrypted_message = service.crypto_box_seal_open(&key, &encrypted_message).unwrap();
+        assert_eq!(msg, decrypted_message.as_slice());
+    }
+
     #[test]
     pub fn test_encrypt_plaintext_and_decrypt_ciphertext_works() {
         let service: CryptoService = CryptoService::new();
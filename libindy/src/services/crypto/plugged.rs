@@ -0,0 +1,176 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::slice;
+
+use libc::c_char;
+use rust_base58::{FromBase58, ToBase58};
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::crypto::*;
+use indy_api_types::errors::prelude::*;
+use crate::utils::crypto::ed25519_box;
+use crate::utils::crypto::ed25519_sign;
+
+use super::KeyProvider;
+
+// Frees the result returned by key provider even in case of error.
+struct ResultGuard {
+    result_handle: IndyHandle,
+    free_handler: KeyProviderFreeResult,
+}
+
+impl Drop for ResultGuard {
+    fn drop(&mut self) {
+        (self.free_handler)(self.result_handle);
+    }
+}
+
+pub struct PluggedKeyProvider {
+    create_key_handler: KeyProviderCreateKey,
+    sign_handler: KeyProviderSign,
+    crypto_box_handler: KeyProviderCryptoBox,
+    crypto_box_open_handler: KeyProviderCryptoBoxOpen,
+    crypto_box_seal_open_handler: KeyProviderCryptoBoxSealOpen,
+    free_result_handler: KeyProviderFreeResult,
+}
+
+impl PluggedKeyProvider {
+    pub fn new(create_key_handler: KeyProviderCreateKey,
+               sign_handler: KeyProviderSign,
+               crypto_box_handler: KeyProviderCryptoBox,
+               crypto_box_open_handler: KeyProviderCryptoBoxOpen,
+               crypto_box_seal_open_handler: KeyProviderCryptoBoxSealOpen,
+               free_result_handler: KeyProviderFreeResult) -> PluggedKeyProvider {
+        PluggedKeyProvider {
+            create_key_handler,
+            sign_handler,
+            crypto_box_handler,
+            crypto_box_open_handler,
+            crypto_box_seal_open_handler,
+            free_result_handler,
+        }
+    }
+
+    fn _result(&self, err: ErrorCode, result_handle: IndyHandle, result: *const u8, result_len: u32) -> IndyResult<Vec<u8>> {
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _result_guard = ResultGuard { result_handle, free_handler: self.free_result_handler };
+
+        if result.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key provider returned empty result"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(result, result_len as usize) }.to_vec())
+    }
+}
+
+impl KeyProvider for PluggedKeyProvider {
+    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<ed25519_sign::PublicKey> {
+        let (seed_ptr, seed_len) = match seed {
+            Some(seed) => (seed[..].as_ptr(), seed[..].len() as u32),
+            None => (ptr::null(), 0)
+        };
+
+        let mut verkey_ptr: *const c_char = ptr::null();
+        let mut result_handle: IndyHandle = -1;
+
+        let err = (self.create_key_handler)(seed_ptr, seed_len, &mut verkey_ptr, &mut result_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _result_guard = ResultGuard { result_handle, free_handler: self.free_result_handler };
+
+        if verkey_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key provider returned empty verkey"));
+        }
+
+        let verkey = unsafe { CStr::from_ptr(verkey_ptr) }
+            .to_str()
+            .to_indy(IndyErrorKind::InvalidState, "Key provider returned verkey that contains non-utf8 symbol")?
+            .from_base58()?;
+
+        ed25519_sign::PublicKey::from_slice(&verkey)
+    }
+
+    fn sign(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<ed25519_sign::Signature> {
+        let verkey = CString::new(vk[..].to_base58())?;
+
+        let mut signature_ptr: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+        let mut result_handle: IndyHandle = -1;
+
+        let err = (self.sign_handler)(verkey.as_ptr(),
+                                      doc.as_ptr(),
+                                      doc.len() as u32,
+                                      &mut signature_ptr,
+                                      &mut signature_len,
+                                      &mut result_handle);
+
+        let signature = self._result(err, result_handle, signature_ptr, signature_len)?;
+
+        ed25519_sign::Signature::from_slice(&signature)
+    }
+
+    fn crypto_box(&self, vk: &ed25519_sign::PublicKey, their_vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(vk[..].to_base58())?;
+        let their_vk = CString::new(their_vk[..].to_base58())?;
+
+        let mut encrypted_ptr: *const u8 = ptr::null();
+        let mut encrypted_len: u32 = 0;
+        let mut result_handle: IndyHandle = -1;
+
+        let err = (self.crypto_box_handler)(verkey.as_ptr(),
+                                            their_vk.as_ptr(),
+                                            doc.as_ptr(),
+                                            doc.len() as u32,
+                                            nonce[..].as_ptr(),
+                                            nonce[..].len() as u32,
+                                            &mut encrypted_ptr,
+                                            &mut encrypted_len,
+                                            &mut result_handle);
+
+        self._result(err, result_handle, encrypted_ptr, encrypted_len)
+    }
+
+    fn crypto_box_open(&self, vk: &ed25519_sign::PublicKey, their_vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(vk[..].to_base58())?;
+        let their_vk = CString::new(their_vk[..].to_base58())?;
+
+        let mut decrypted_ptr: *const u8 = ptr::null();
+        let mut decrypted_len: u32 = 0;
+        let mut result_handle: IndyHandle = -1;
+
+        let err = (self.crypto_box_open_handler)(verkey.as_ptr(),
+                                                 their_vk.as_ptr(),
+                                                 doc.as_ptr(),
+                                                 doc.len() as u32,
+                                                 nonce[..].as_ptr(),
+                                                 nonce[..].len() as u32,
+                                                 &mut decrypted_ptr,
+                                                 &mut decrypted_len,
+                                                 &mut result_handle);
+
+        self._result(err, result_handle, decrypted_ptr, decrypted_len)
+    }
+
+    fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(vk[..].to_base58())?;
+
+        let mut decrypted_ptr: *const u8 = ptr::null();
+        let mut decrypted_len: u32 = 0;
+        let mut result_handle: IndyHandle = -1;
+
+        let err = (self.crypto_box_seal_open_handler)(verkey.as_ptr(),
+                                                      doc.as_ptr(),
+                                                      doc.len() as u32,
+                                                      &mut decrypted_ptr,
+                                                      &mut decrypted_len,
+                                                      &mut result_handle);
+
+        self._result(err, result_handle, decrypted_ptr, decrypted_len)
+    }
+}
@@ -23,7 +23,7 @@ use crate::domain::ledger::response::{Message, Reply, ReplyType};
 use crate::domain::ledger::rev_reg::{GetRevocRegDeltaReplyResult, GetRevocRegReplyResult, GetRevRegDeltaOperation, GetRevRegOperation, RevRegEntryOperation};
 use crate::domain::ledger::rev_reg_def::{GetRevocRegDefReplyResult, GetRevRegDefOperation, RevRegDefOperation};
 use crate::domain::ledger::schema::{GetSchemaOperation, GetSchemaOperationData, GetSchemaReplyResult, SchemaOperation, SchemaOperationData};
-use crate::domain::ledger::txn::{GetTxnOperation, LedgerType};
+use crate::domain::ledger::txn::{GetTxnOperation, GetTxnReplyResult, GetTxnResultData, LedgerTxn, LedgerType, VerifiedTxn};
 use crate::domain::ledger::validator_info::GetValidatorInfoOperation;
 use crate::domain::ledger::auth_rule::*;
 use crate::domain::ledger::author_agreement::*;
@@ -375,6 +375,43 @@ impl LedgerService {
         Ok(res)
     }
 
+    /// Parses GET_TXN reply into the transaction with the ledger root hash it is included into.
+    /// Note: it doesn't verify the audit proof, so `verified` is always false.
+    #[logfn(Info)]
+    pub fn parse_get_txn_response(&self, get_txn_response: &str) -> IndyResult<VerifiedTxn> {
+        let reply: Reply<GetTxnReplyResult> = LedgerService::parse_response(get_txn_response)?;
+        let reply = reply.result();
+
+        let data = match reply.data {
+            Some(Value::String(data)) => serde_json::from_str::<Value>(&data)
+                .to_indy(IndyErrorKind::InvalidTransaction, "Cannot parse GET_TXN response data")?,
+            Some(Value::Null) | None => return Err(err_msg(IndyErrorKind::LedgerItemNotFound, "Transaction not found")),
+            Some(data) => data
+        };
+
+        let data: GetTxnResultData = serde_json::from_value(data)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Cannot parse GET_TXN response data")?;
+
+        let seq_no = data.txn_metadata["seqNo"].as_u64()
+            .or(reply.seq_no)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidTransaction, "No seqNo in GET_TXN response"))?;
+
+        let root_hash = data.root_hash
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidTransaction, "No rootHash in GET_TXN response. Most probably the pool doesn't support audit proofs"))?;
+
+        Ok(VerifiedTxn {
+            txn: LedgerTxn {
+                txn: data.txn,
+                txn_metadata: data.txn_metadata,
+                ver: data.ver,
+                req_signature: data.req_signature,
+            },
+            seq_no,
+            root_hash,
+            verified: false,
+        })
+    }
+
     #[logfn(Info)]
     pub fn build_auth_rule_request(&self, submitter_did: &DidValue, txn_type: &str, action: &str, field: &str,
                                    old_value: Option<&str>, new_value: Option<&str>, constraint: Constraint) -> IndyResult<String> {
@@ -841,6 +878,44 @@ mod tests {
         assert_kind!(IndyErrorKind::InvalidStructure, res);
     }
 
+    #[test]
+    fn parse_get_txn_response_works() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({
+            "op": "REPLY",
+            "result": {
+                "type": GET_TXN,
+                "seqNo": 2,
+                "data": {
+                    "txn": {"type": "1", "data": {"dest": DEST}},
+                    "txnMetadata": {"seqNo": 2},
+                    "ver": "1",
+                    "reqSignature": {},
+                    "rootHash": "root",
+                    "ledgerSize": 2,
+                    "auditPath": ["path"]
+                }
+            }
+        });
+
+        let txn = ledger_service.parse_get_txn_response(&response.to_string()).unwrap();
+        assert_eq!(2, txn.seq_no);
+        assert_eq!("root", txn.root_hash);
+        assert_eq!(json!({"type": "1", "data": {"dest": DEST}}), txn.txn.txn);
+        assert!(!txn.verified);
+    }
+
+    #[test]
+    fn parse_get_txn_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({"op": "REPLY", "result": {"type": GET_TXN, "seqNo": 2, "data": null}});
+
+        let res = ledger_service.parse_get_txn_response(&response.to_string());
+        assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+    }
+
     #[test]
     fn validate_action_works_for_pool_restart() {
         let ledger_service = LedgerService::new();
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use zmq;
+
+use indy_api_types::errors::prelude::*;
+use indy_api_types::{CommandHandle, PoolHandle};
+use indy_utils::next_command_handle;
+
+use super::_send_msg;
+
+lazy_static! {
+    static ref OPEN_POOLS: Mutex<HashMap<PoolHandle, AsyncPool>> = Mutex::new(HashMap::new());
+    static ref PENDING_REPLIES: Mutex<HashMap<CommandHandle, (PoolHandle, Arc<Mutex<ReplyState>>)>> = Mutex::new(HashMap::new());
+}
+
+pub type ReplyCallback = Box<dyn FnOnce(IndyResult<String>) + Send>;
+
+/// Thread-safe handle of an opened pool ledger for Rust consumers.
+///
+/// Requests are passed to the pool worker thread and replies are delivered to the returned futures
+/// directly from it, so neither of them goes through the command thread serving the C API.
+/// The C API submits requests through the same handle.
+#[derive(Clone)]
+pub struct AsyncPool {
+    id: PoolHandle,
+    name: String,
+    cmd_socket: Arc<Mutex<zmq::Socket>>,
+}
+
+impl AsyncPool {
+    /// Returns the handle of the pool opened by `indy_open_pool_ledger`.
+    /// It stays usable until the pool is closed.
+    pub fn get(handle: PoolHandle) -> IndyResult<AsyncPool> {
+        OPEN_POOLS.lock().unwrap()
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
+    }
+
+    pub fn id(&self) -> PoolHandle {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sends the request to the pool, the future is resolved with the reply
+    /// the same way as `indy_submit_request` callback is called.
+    /// If the pool is closed or its worker stops before the reply, the future is resolved
+    /// with `PoolTerminated` error.
+    pub fn submit(&self, request: &str) -> SubmitFuture {
+        self.submit_action(request, None, None)
+    }
+
+    /// Sends the action request to the specified nodes, see `indy_submit_action`.
+    pub fn submit_action(&self, request: &str, nodes: Option<&str>, timeout: Option<i32>) -> SubmitFuture {
+        let state = Arc::new(Mutex::new(ReplyState::default()));
+
+        self._send(request, nodes, timeout, state.clone());
+
+        SubmitFuture { state }
+    }
+
+    /// Sends the action request to the specified nodes and calls `cb` with the reply
+    /// on the pool worker thread instead of resolving a future.
+    pub(crate) fn submit_action_with_cb(&self, request: &str, nodes: Option<&str>, timeout: Option<i32>, cb: ReplyCallback) {
+        let state = Arc::new(Mutex::new(ReplyState { cb: Some(cb), ..ReplyState::default() }));
+
+        self._send(request, nodes, timeout, state);
+    }
+
+    fn _send(&self, request: &str, nodes: Option<&str>, timeout: Option<i32>, state: Arc<Mutex<ReplyState>>) {
+        trace!("AsyncPool::_send >>> id: {:?}, request: {:?}, nodes: {:?}, timeout: {:?}", self.id, request, nodes, timeout);
+
+        let cmd_id: CommandHandle = next_command_handle();
+
+        // must be registered before sending as the reply can come before the send returns
+        PENDING_REPLIES.lock().unwrap().insert(cmd_id, (self.id, state.clone()));
+
+        let res = _send_msg(cmd_id, request, &self.cmd_socket.lock().unwrap(), nodes, timeout);
+
+        if let Err(err) = res {
+            PENDING_REPLIES.lock().unwrap().remove(&cmd_id);
+            _resolve(&state, Err(err));
+        }
+
+        trace!("AsyncPool::_send <<< cmd_id: {:?}", cmd_id);
+    }
+}
+
+#[derive(Default)]
+struct ReplyState {
+    result: Option<IndyResult<String>>,
+    waker: Option<Waker>,
+    cb: Option<ReplyCallback>,
+}
+
+/// Reply on a request submitted through `AsyncPool`.
+pub struct SubmitFuture {
+    state: Arc<Mutex<ReplyState>>,
+}
+
+impl Future for SubmitFuture {
+    type Output = IndyResult<String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub(super) fn register(id: PoolHandle, name: &str, cmd_socket: Arc<Mutex<zmq::Socket>>) {
+    OPEN_POOLS.lock().unwrap()
+        .insert(id, AsyncPool { id, name: name.to_string(), cmd_socket });
+}
+
+/// Forgets the closed pool and terminates requests still waiting for replies from it.
+pub(super) fn unregister(id: PoolHandle) {
+    OPEN_POOLS.lock().unwrap().remove(&id);
+    terminate(id);
+}
+
+/// Resolves all requests of the pool still waiting for replies with `PoolTerminated` error.
+/// Called when the pool worker stops as its replies can't come anymore.
+pub(super) fn terminate(id: PoolHandle) {
+    let states: Vec<Arc<Mutex<ReplyState>>> = {
+        let mut pending = PENDING_REPLIES.lock().unwrap();
+
+        let cmd_ids: Vec<CommandHandle> = pending.iter()
+            .filter(|&(_, &(pool_id, _))| pool_id == id)
+            .map(|(cmd_id, _)| *cmd_id)
+            .collect();
+
+        cmd_ids.iter()
+            .filter_map(|cmd_id| pending.remove(cmd_id))
+            .map(|(_, state)| state)
+            .collect()
+    };
+
+    for state in states {
+        _resolve(&state, Err(err_msg(IndyErrorKind::PoolTerminated, "Pool is terminated")));
+    }
+}
+
+/// Delivers the reply on the command to the future or callback waiting for it.
+pub(super) fn complete(cmd_id: CommandHandle, result: IndyResult<String>) {
+    // the pending entry is removed if the pool was closed before the reply came
+    let state = match PENDING_REPLIES.lock().unwrap().remove(&cmd_id) {
+        Some((_, state)) => state,
+        None => {
+            warn!("Reply on command {:?} isn't awaited: {:?}", cmd_id, result);
+            return;
+        }
+    };
+
+    _resolve(&state, result);
+}
+
+fn _resolve(state: &Mutex<ReplyState>, result: IndyResult<String>) {
+    let mut state = state.lock().unwrap();
+
+    if let Some(cb) = state.cb.take() {
+        drop(state);
+        return cb(result);
+    }
+
+    state.result = Some(result);
+
+    if let Some(waker) = state.waker.take() {
+        waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+    use std::sync::mpsc::channel;
+    use std::task::{RawWaker, RawWakerVTable};
+
+    use byteorder::{ByteOrder, LittleEndian};
+
+    use super::*;
+    use crate::services::pool::pool_create_pair_of_sockets;
+    use indy_utils::next_pool_handle;
+
+    fn _noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker { RawWaker::new(ptr::null(), &VTABLE) }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    fn _poll(future: &mut SubmitFuture) -> Poll<IndyResult<String>> {
+        let waker = _noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        Pin::new(future).poll(&mut cx)
+    }
+
+    #[test]
+    fn async_pool_submit_works() {
+        let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("async_pool_submit_works");
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_submit_works", Arc::new(Mutex::new(send_cmd_sock)));
+
+        let pool = AsyncPool::get(pool_id).unwrap();
+        assert_eq!("async_pool_submit_works", pool.name());
+
+        let mut future = pool.submit("request");
+
+        let parts = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+        assert_eq!(b"request".to_vec(), parts[0]);
+        let cmd_id = LittleEndian::read_i32(&parts[1]);
+
+        assert!(_poll(&mut future).is_pending());
+
+        complete(cmd_id, Ok("reply".to_string()));
+
+        match _poll(&mut future) {
+            Poll::Ready(res) => assert_eq!("reply", res.unwrap()),
+            Poll::Pending => panic!("reply isn't delivered")
+        }
+
+        unregister(pool_id);
+    }
+
+    #[test]
+    fn async_pool_submit_works_for_closed_socket() {
+        let zmq_ctx = zmq::Context::new();
+        let send_cmd_sock = zmq_ctx.socket(zmq::SocketType::PAIR).unwrap();
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_submit_works_for_closed_socket", Arc::new(Mutex::new(send_cmd_sock)));
+
+        let mut future = AsyncPool::get(pool_id).unwrap().submit("request");
+
+        match _poll(&mut future) {
+            Poll::Ready(res) => assert_kind!(IndyErrorKind::IOError, res),
+            Poll::Pending => panic!("error isn't delivered")
+        }
+
+        unregister(pool_id);
+    }
+
+    #[test]
+    fn async_pool_get_works_for_unknown_handle() {
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_get_works_for_unknown_handle", Arc::new(Mutex::new(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap())));
+        unregister(pool_id);
+
+        let res = AsyncPool::get(pool_id);
+        assert_kind!(IndyErrorKind::InvalidPoolHandle, res);
+    }
+
+    #[test]
+    fn async_pool_submit_action_with_cb_works() {
+        let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("async_pool_submit_action_with_cb_works");
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_submit_action_with_cb_works", Arc::new(Mutex::new(send_cmd_sock)));
+
+        let (sender, receiver) = channel();
+        AsyncPool::get(pool_id).unwrap()
+            .submit_action_with_cb("request", None, None, Box::new(move |res| sender.send(res).unwrap()));
+
+        let parts = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+        let cmd_id = LittleEndian::read_i32(&parts[1]);
+
+        complete(cmd_id, Ok("reply".to_string()));
+
+        assert_eq!("reply", receiver.try_recv().unwrap().unwrap());
+
+        unregister(pool_id);
+    }
+
+    #[test]
+    fn async_pool_unregister_terminates_pending_replies() {
+        let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("async_pool_unregister_terminates_pending_replies");
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_unregister_terminates_pending_replies", Arc::new(Mutex::new(send_cmd_sock)));
+
+        let pool = AsyncPool::get(pool_id).unwrap();
+        let mut future = pool.submit("request");
+
+        let (sender, receiver) = channel();
+        pool.submit_action_with_cb("request", None, None, Box::new(move |res| sender.send(res).unwrap()));
+
+        assert!(_poll(&mut future).is_pending());
+
+        unregister(pool_id);
+
+        match _poll(&mut future) {
+            Poll::Ready(res) => assert_kind!(IndyErrorKind::PoolTerminated, res),
+            Poll::Pending => panic!("pending reply isn't terminated")
+        }
+        assert_kind!(IndyErrorKind::PoolTerminated, receiver.try_recv().unwrap());
+    }
+
+    #[test]
+    fn async_pool_terminate_works_for_other_pool_replies() {
+        let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("async_pool_terminate_works_for_other_pool_replies");
+        let pool_id = next_pool_handle();
+        register(pool_id, "async_pool_terminate_works_for_other_pool_replies", Arc::new(Mutex::new(send_cmd_sock)));
+
+        let mut future = AsyncPool::get(pool_id).unwrap().submit("request");
+
+        terminate(next_pool_handle());
+        assert!(_poll(&mut future).is_pending());
+
+        let parts = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+        complete(LittleEndian::read_i32(&parts[1]), Ok("reply".to_string()));
+        assert!(_poll(&mut future).is_ready());
+
+        unregister(pool_id);
+    }
+}
@@ -72,6 +72,7 @@ pub enum NetworkerEvent {
 pub const COMMAND_EXIT : &str = "exit";
 pub const COMMAND_CONNECT : &str = "connect";
 pub const COMMAND_REFRESH : &str = "refresh";
+pub const COMMAND_STATS : &str = "stats";
 
 #[derive(Clone, Debug)]
 pub enum PoolEvent {
@@ -86,6 +87,9 @@ pub enum PoolEvent {
     Refresh(
         CommandHandle
     ),
+    GetStats(
+        CommandHandle
+    ),
     CatchupTargetFound(
         Vec<u8>, //target_mt_root
         usize, //target_mt_size
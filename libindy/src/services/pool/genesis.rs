@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, BufRead};
+use std::net::IpAddr;
+
+use rust_base58::FromBase58;
+use serde_json;
+use ursa::bls::{Bls, Generator, ProofOfPossession, VerKey};
+
+use crate::domain::ledger::constants;
+use indy_api_types::errors::prelude::*;
+use crate::services::pool::pool::_get_f;
+use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+use crate::services::pool::types::{GenesisNodeReport, GenesisReport, NodeData, NodeTransaction, NodeTransactionV0, NodeTransactionV1};
+use crate::utils::crypto::ed25519_sign;
+
+/// Checks the genesis transactions file without connecting to the nodes:
+/// parses every NODE transaction, checks node keys, addresses and BLS keys proofs of possession,
+/// detects duplicated aliases and addresses and calculates quorums of the pool.
+/// NODE transactions are checked against the format of the given protocol version.
+pub fn validate(genesis_txn: &str, protocol_version: usize) -> IndyResult<GenesisReport> {
+    trace!("genesis::validate >>> genesis_txn: {:?}, protocol_version: {:?}", genesis_txn, protocol_version);
+
+    let f = fs::File::open(genesis_txn)
+        .to_indy(IndyErrorKind::IOError, "Can't open genesis txn file")?;
+
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut txns_count = 0;
+
+    // node transactions merged by dest in order of appearance
+    let mut nodes: Vec<NodeTransactionV1> = Vec::new();
+
+    for (idx, line) in io::BufReader::new(&f).lines().enumerate() {
+        let line = line
+            .to_indy(IndyErrorKind::IOError, "Can't read from genesis txn file")?;
+
+        if line.trim().is_empty() { continue; }
+
+        txns_count += 1;
+
+        let txn = match serde_json::from_str::<NodeTransaction>(&line) {
+            Ok(txn) => txn,
+            Err(err) => {
+                errors.push(format!("Line {}: not a valid NODE transaction: {}", idx + 1, err));
+                continue;
+            }
+        };
+
+        let mut txn = match txn {
+            NodeTransaction::NodeTransactionV0(txn) => {
+                if protocol_version != 1 {
+                    errors.push(format!("Line {}: transaction is of version {} that requires PROTOCOL_VERSION 1 but it is {}",
+                                        idx + 1, NodeTransactionV0::VERSION, protocol_version));
+                }
+                NodeTransactionV1::from(txn)
+            }
+            NodeTransaction::NodeTransactionV1(txn) => {
+                if protocol_version != 2 {
+                    errors.push(format!("Line {}: transaction is of version {} that requires PROTOCOL_VERSION 2 but it is {}",
+                                        idx + 1, NodeTransactionV1::VERSION, protocol_version));
+                }
+                txn
+            }
+        };
+
+        if txn.txn.txn_type != constants::NODE {
+            errors.push(format!("Line {}: transaction type is {} but NODE is expected", idx + 1, txn.txn.txn_type));
+            continue;
+        }
+
+        match nodes.iter_mut().find(|node| node.txn.data.dest == txn.txn.data.dest) {
+            Some(node) => {
+                if node.txn.data.data.alias != txn.txn.data.data.alias {
+                    errors.push(format!("Line {}: node {} is renamed to {}", idx + 1, node.txn.data.data.alias, txn.txn.data.data.alias));
+                    continue;
+                }
+                node.update(&mut txn)?;
+            }
+            None => nodes.push(txn)
+        }
+    }
+
+    if txns_count == 0 {
+        errors.push("Genesis txn file is empty".to_string());
+    }
+
+    let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut addresses: BTreeMap<String, String> = BTreeMap::new();
+
+    let nodes: Vec<GenesisNodeReport> = nodes.iter()
+        .map(|txn| {
+            let data = &txn.txn.data.data;
+            let dest = &txn.txn.data.dest;
+
+            if let Some(other_dest) = aliases.insert(data.alias.clone(), dest.clone()) {
+                errors.push(format!("Node {}: alias is used by nodes {} and {}", data.alias, other_dest, dest));
+            }
+
+            let dest_valid = dest.from_base58().ok()
+                .and_then(|dest| ed25519_sign::PublicKey::from_slice(&dest).ok())
+                .is_some();
+
+            if !dest_valid {
+                errors.push(format!("Node {}: dest {} is not a valid node verkey", data.alias, dest));
+            }
+
+            let validator = data.services.as_ref()
+                .map(|services| services.iter().any(|service| service == "VALIDATOR"))
+                .unwrap_or(false);
+
+            if !validator {
+                warnings.push(format!("Node {}: node is not a validator", data.alias));
+            }
+
+            let client_address = _check_address(&data.alias, "client", &data.client_ip, &data.client_port, validator, &mut errors);
+            let node_address = _check_address(&data.alias, "node", &data.node_ip, &data.node_port, validator, &mut errors);
+
+            if validator {
+                for address in client_address.iter().chain(node_address.iter()) {
+                    if let Some(other_alias) = addresses.insert(address.clone(), data.alias.clone()) {
+                        errors.push(format!("Node {}: address {} is used by node {} too", data.alias, address, other_alias));
+                    }
+                }
+            }
+
+            let (bls_key_valid, bls_key_pop_valid) = _check_bls_key(data, &gen, &mut errors, &mut warnings);
+
+            GenesisNodeReport {
+                alias: data.alias.clone(),
+                dest: dest.clone(),
+                validator,
+                client_address,
+                node_address,
+                bls_key_valid,
+                bls_key_pop_valid,
+            }
+        })
+        .collect();
+
+    let validators_count = nodes.iter().filter(|node| node.validator).count();
+
+    if txns_count > 0 && validators_count == 0 {
+        errors.push("There are no validator nodes".to_string());
+    } else if validators_count > 0 && validators_count < 4 {
+        warnings.push(format!("Pool of {} validators can't tolerate any faulty node", validators_count));
+    }
+
+    let f = _get_f(validators_count);
+
+    let report = GenesisReport {
+        valid: errors.is_empty(),
+        txns_count,
+        nodes,
+        validators_count,
+        f,
+        weak_quorum: f + 1,
+        strong_quorum: validators_count - f,
+        errors,
+        warnings,
+    };
+
+    trace!("genesis::validate <<< report: {:?}", report);
+
+    Ok(report)
+}
+
+fn _check_address(alias: &str, kind: &str, ip: &Option<String>, port: &Option<u64>, required: bool, errors: &mut Vec<String>) -> Option<String> {
+    let (ip, port) = match (ip, port) {
+        (Some(ip), Some(port)) => (ip, *port),
+        _ => {
+            if required {
+                errors.push(format!("Node {}: {} address is not specified", alias, kind));
+            }
+            return None;
+        }
+    };
+
+    match ip.parse::<IpAddr>() {
+        Ok(addr) if addr.is_unspecified() || addr.is_multicast() =>
+            errors.push(format!("Node {}: {} ip {} is not reachable", alias, kind, ip)),
+        Ok(_) => {}
+        Err(_) => errors.push(format!("Node {}: {} ip {} is not a valid IP address", alias, kind, ip))
+    }
+
+    if port == 0 || port > 65535 {
+        errors.push(format!("Node {}: {} port {} is out of range", alias, kind, port));
+    }
+
+    Some(format!("{}:{}", ip, port))
+}
+
+fn _check_bls_key(data: &NodeData, gen: &Generator, errors: &mut Vec<String>, warnings: &mut Vec<String>) -> (bool, bool) {
+    let blskey = match data.blskey {
+        Some(ref blskey) => blskey,
+        None => {
+            warnings.push(format!("Node {}: BLS key is not specified, state proofs of the node can't be verified", data.alias));
+            return (false, false);
+        }
+    };
+
+    let blskey = match blskey.from_base58().ok().and_then(|key| VerKey::from_bytes(&key).ok()) {
+        Some(blskey) => blskey,
+        None => {
+            errors.push(format!("Node {}: BLS key is malformed", data.alias));
+            return (false, false);
+        }
+    };
+
+    let pop = match data.blskey_pop {
+        Some(ref pop) => pop,
+        None => {
+            warnings.push(format!("Node {}: proof of possession of BLS key is not specified", data.alias));
+            return (true, false);
+        }
+    };
+
+    let pop_valid = pop.from_base58().ok()
+        .and_then(|pop| ProofOfPossession::from_bytes(&pop).ok())
+        .and_then(|pop| Bls::verify_proof_of_posession(&pop, &blskey, gen).ok())
+        .unwrap_or(false);
+
+    if !pop_valid {
+        errors.push(format!("Node {}: proof of possession of BLS key is not valid", data.alias));
+    }
+
+    (true, pop_valid)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    use crate::utils::{environment, test};
+
+    fn _write_genesis_txns(name: &str, txns: &[String]) -> String {
+        let mut path = environment::tmp_path();
+        fs::create_dir_all(path.as_path()).unwrap();
+        path.push(name);
+        path.set_extension("txn");
+
+        let mut f = fs::File::create(path.as_path()).unwrap();
+        f.write_all(txns.join("\n").as_bytes()).unwrap();
+        f.flush().unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn genesis_validate_works() {
+        test::cleanup_storage("genesis_validate_works");
+
+        let path = _write_genesis_txns("genesis_validate_works", &test::gen_txns());
+
+        let report = validate(&path, 2).unwrap();
+
+        assert!(report.valid, "{:?}", report.errors);
+        assert_eq!(4, report.txns_count);
+        assert_eq!(4, report.validators_count);
+        assert_eq!(1, report.f);
+        assert_eq!(2, report.weak_quorum);
+        assert_eq!(3, report.strong_quorum);
+        assert!(report.nodes.iter().all(|node| node.bls_key_valid && node.bls_key_pop_valid));
+
+        test::cleanup_storage("genesis_validate_works");
+    }
+
+    #[test]
+    fn genesis_validate_works_for_other_protocol_version() {
+        test::cleanup_storage("genesis_validate_works_for_other_protocol_version");
+
+        let path = _write_genesis_txns("genesis_validate_works_for_other_protocol_version", &test::gen_txns());
+
+        let report = validate(&path, 1).unwrap();
+
+        assert!(!report.valid);
+        assert_eq!(4, report.errors.iter().filter(|err| err.contains("requires PROTOCOL_VERSION 2")).count());
+
+        test::cleanup_storage("genesis_validate_works_for_other_protocol_version");
+    }
+
+    #[test]
+    fn genesis_validate_works_for_invalid_bls_key_pop() {
+        test::cleanup_storage("genesis_validate_works_for_invalid_bls_key_pop");
+
+        let mut txns = test::gen_txns();
+        let pop_of_node2 = "Qr658mWZ2YC8JXGXwMDQTzuZCWF7NK9EwxphGmcBvCh6ybUuLxbG65nsX4JvD4SPNtkJ2w9ug1yLTj6fgmuDg41TgECXjLCij3RMsV8CwewBVgVN67wsA45DFWvqvLtu4rjNnE9JbdFTc1Z4WCPA3Xan44K1HoHAq9EVeaRYs8zoF5";
+        let pop_of_node1 = "RahHYiCvoNCtPTrVtP7nMC5eTYrsUA8WjXbdhNc8debh1agE9bGiJxWBXYNFbnJXoXhWFMvyqhqhRoq737YQemH5ik9oL7R4NTTCz2LEZhkgLJzB3QRQqJyBNyv7acbdHrAT8nQ9UkLbaVL9NBpnWXBTw4LEMePaSHEw66RzPNdAX1";
+        txns[0] = txns[0].replace(pop_of_node1, pop_of_node2);
+
+        let path = _write_genesis_txns("genesis_validate_works_for_invalid_bls_key_pop", &txns);
+
+        let report = validate(&path, 2).unwrap();
+
+        assert!(!report.valid);
+        assert!(report.nodes[0].bls_key_valid);
+        assert!(!report.nodes[0].bls_key_pop_valid);
+        assert_eq!(1, report.errors.len());
+
+        test::cleanup_storage("genesis_validate_works_for_invalid_bls_key_pop");
+    }
+
+    #[test]
+    fn genesis_validate_works_for_duplicated_alias_and_address() {
+        test::cleanup_storage("genesis_validate_works_for_duplicated_alias_and_address");
+
+        let mut txns = test::gen_txns();
+        txns[1] = txns[1].replace("\"Node2\"", "\"Node1\"").replace("9704", "9702");
+
+        let path = _write_genesis_txns("genesis_validate_works_for_duplicated_alias_and_address", &txns);
+
+        let report = validate(&path, 2).unwrap();
+
+        assert!(!report.valid);
+        assert!(report.errors.iter().any(|err| err.contains("alias is used")));
+        assert!(report.errors.iter().any(|err| err.contains("address")));
+
+        test::cleanup_storage("genesis_validate_works_for_duplicated_alias_and_address");
+    }
+
+    #[test]
+    fn genesis_validate_works_for_malformed_txn() {
+        test::cleanup_storage("genesis_validate_works_for_malformed_txn");
+
+        let mut txns = test::gen_txns();
+        txns.push("{\"txn\": 1}".to_string());
+        txns[2] = txns[2].replace(&environment::test_pool_ip(), "0.0.0.0");
+
+        let path = _write_genesis_txns("genesis_validate_works_for_malformed_txn", &txns);
+
+        let report = validate(&path, 2).unwrap();
+
+        assert!(!report.valid);
+        assert_eq!(5, report.txns_count);
+        assert_eq!(4, report.validators_count);
+        assert!(report.errors.iter().any(|err| err.starts_with("Line 5")));
+        assert!(report.errors.iter().any(|err| err.contains("is not reachable")));
+
+        test::cleanup_storage("genesis_validate_works_for_malformed_txn");
+    }
+
+    #[test]
+    fn genesis_validate_works_for_missed_file() {
+        let res = validate("not_existing_genesis.txn", 2);
+        assert_kind!(IndyErrorKind::IOError, res);
+    }
+}
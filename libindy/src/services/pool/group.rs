@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use serde_json;
+use serde_json::Value as SJsonValue;
+
+use crate::domain::pool::PoolGroupConfig;
+use crate::utils::qualifier;
+use indy_api_types::errors::prelude::*;
+use indy_api_types::PoolHandle;
+
+/// Request fields which can contain fully-qualified DIDs.
+const REQUEST_DID_FIELDS: [&str; 2] = ["identifier", "endorser"];
+
+/// Operation fields which can contain fully-qualified DIDs.
+const OPERATION_DID_FIELDS: [&str; 2] = ["dest", "origin"];
+
+pub struct PoolGroup {
+    routes: HashMap<String, PoolHandle>,
+    default: Option<PoolHandle>,
+}
+
+impl PoolGroup {
+    pub fn new(config: PoolGroupConfig) -> PoolGroup {
+        PoolGroup {
+            routes: config.routes,
+            default: config.default,
+        }
+    }
+
+    pub fn pools(&self) -> Vec<PoolHandle> {
+        self.routes.values().cloned().chain(self.default).collect()
+    }
+
+    /// Returns the pool the identifier belongs to.
+    /// Unqualified identifiers are routed to the default pool.
+    pub fn route_id(&self, id: Option<&str>) -> IndyResult<PoolHandle> {
+        match id.and_then(|id| self._route_qualified(id)) {
+            Some((handle, _)) => Ok(handle),
+            None => self._default(id.filter(|id| qualifier::is_fully_qualified(id)))
+        }
+    }
+
+    /// Returns the pool the request must be sent to and the request with unqualified DIDs.
+    ///
+    /// Signed requests can't be changed, so they must not contain fully-qualified DIDs.
+    pub fn route_request(&self, request: &str) -> IndyResult<(PoolHandle, String)> {
+        let mut request_json: SJsonValue = serde_json::from_str(request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is invalid json")?;
+
+        let mut routed: Option<(String, PoolHandle)> = None;
+        let mut changed = false;
+
+        {
+            let mut fields: Vec<&mut SJsonValue> = Vec::new();
+
+            if let SJsonValue::Object(ref mut request_map) = request_json {
+                let mut operation = None;
+
+                for (key, value) in request_map.iter_mut() {
+                    if REQUEST_DID_FIELDS.contains(&key.as_str()) {
+                        fields.push(value);
+                    } else if key == "operation" {
+                        operation = Some(value);
+                    }
+                }
+
+                if let Some(SJsonValue::Object(operation_map)) = operation {
+                    for (key, value) in operation_map.iter_mut() {
+                        if OPERATION_DID_FIELDS.contains(&key.as_str()) {
+                            fields.push(value);
+                        }
+                    }
+                }
+            }
+
+            for field in fields {
+                let (did, handle, unqualified) = match field.as_str() {
+                    Some(did) => match self._route_qualified(did) {
+                        Some((handle, unqualified)) => (did.to_string(), handle, unqualified),
+                        None if qualifier::is_fully_qualified(did) => (did.to_string(), self._default(Some(did))?, qualifier::to_unqualified(did)),
+                        None => continue
+                    },
+                    None => continue
+                };
+
+                if let Some((ref routed_did, routed_handle)) = routed {
+                    if routed_handle != handle {
+                        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Request references DIDs of different ledgers: {} and {}", routed_did, did)));
+                    }
+                }
+
+                if unqualified != did {
+                    *field = SJsonValue::String(unqualified);
+                    changed = true;
+                }
+
+                routed = Some((did, handle));
+            }
+        }
+
+        let handle = match routed {
+            Some((_, handle)) => handle,
+            None => return Ok((self._default(None)?, request.to_string()))
+        };
+
+        if !changed {
+            return Ok((handle, request.to_string()));
+        }
+
+        if !request_json["signature"].is_null() || !request_json["signatures"].is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Signed request can't contain fully-qualified DIDs"));
+        }
+
+        let request = serde_json::to_string(&request_json)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize request")?;
+
+        Ok((handle, request))
+    }
+
+    /// Finds the route with the longest DID method name matching the fully-qualified identifier.
+    fn _route_qualified(&self, id: &str) -> Option<(PoolHandle, String)> {
+        if !qualifier::is_fully_qualified(id) {
+            return None;
+        }
+
+        let (_, method_and_id) = id.split_at(id.find(':')? + 1);
+
+        self.routes.iter()
+            .filter(|(method, _)| method_and_id.starts_with(&format!("{}:", method)))
+            .max_by_key(|(method, _)| method.len())
+            .map(|(method, handle)| (*handle, method_and_id[method.len() + 1..].to_string()))
+    }
+
+    fn _default(&self, id: Option<&str>) -> IndyResult<PoolHandle> {
+        self.default
+            .ok_or_else(|| match id {
+                Some(id) => err_msg(IndyErrorKind::InvalidStructure, format!("No pool is registered for DID method of {}", id)),
+                None => err_msg(IndyErrorKind::InvalidStructure, "No pool is registered for unqualified identifiers")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOV_POOL: PoolHandle = 1;
+    const STAGING_POOL: PoolHandle = 2;
+    const DEFAULT_POOL: PoolHandle = 3;
+
+    fn _group(default: Option<PoolHandle>) -> PoolGroup {
+        let mut routes = HashMap::new();
+        routes.insert("sov".to_string(), SOV_POOL);
+        routes.insert("sov:staging".to_string(), STAGING_POOL);
+        PoolGroup::new(PoolGroupConfig { routes, default })
+    }
+
+    #[test]
+    fn route_id_works() {
+        let group = _group(Some(DEFAULT_POOL));
+
+        assert_eq!(SOV_POOL, group.route_id(Some("did:sov:NcYxiDXkpYi6ov5FcYDi1e")).unwrap());
+        assert_eq!(STAGING_POOL, group.route_id(Some("did:sov:staging:NcYxiDXkpYi6ov5FcYDi1e")).unwrap());
+        assert_eq!(SOV_POOL, group.route_id(Some("schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0")).unwrap());
+        assert_eq!(DEFAULT_POOL, group.route_id(Some("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0")).unwrap());
+        assert_eq!(DEFAULT_POOL, group.route_id(Some("did:peer:NcYxiDXkpYi6ov5FcYDi1e")).unwrap());
+        assert_eq!(DEFAULT_POOL, group.route_id(None).unwrap());
+    }
+
+    #[test]
+    fn route_id_works_for_no_default() {
+        let group = _group(None);
+
+        let res = group.route_id(Some("did:peer:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let res = group.route_id(Some("NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn route_request_works() {
+        let group = _group(None);
+
+        let request = json!({
+            "reqId": 1,
+            "identifier": "did:sov:staging:NcYxiDXkpYi6ov5FcYDi1e",
+            "operation": {"type": "105", "dest": "did:sov:staging:VsKV7grR1BUE29mG2Fm2kX"}
+        }).to_string();
+
+        let (handle, request) = group.route_request(&request).unwrap();
+        assert_eq!(STAGING_POOL, handle);
+
+        let request: SJsonValue = serde_json::from_str(&request).unwrap();
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", request["identifier"]);
+        assert_eq!("VsKV7grR1BUE29mG2Fm2kX", request["operation"]["dest"]);
+    }
+
+    #[test]
+    fn route_request_works_for_unqualified_request() {
+        let group = _group(Some(DEFAULT_POOL));
+
+        let request = json!({
+            "reqId": 1,
+            "identifier": "NcYxiDXkpYi6ov5FcYDi1e",
+            "operation": {"type": "105", "dest": "VsKV7grR1BUE29mG2Fm2kX"},
+            "signature": "signature"
+        }).to_string();
+
+        assert_eq!((DEFAULT_POOL, request.clone()), group.route_request(&request).unwrap());
+    }
+
+    #[test]
+    fn route_request_works_for_different_ledgers() {
+        let group = _group(None);
+
+        let request = json!({
+            "reqId": 1,
+            "identifier": "did:sov:NcYxiDXkpYi6ov5FcYDi1e",
+            "operation": {"type": "105", "dest": "did:sov:staging:VsKV7grR1BUE29mG2Fm2kX"}
+        }).to_string();
+
+        let res = group.route_request(&request);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn route_request_works_for_signed_qualified_request() {
+        let group = _group(None);
+
+        let request = json!({
+            "reqId": 1,
+            "identifier": "did:sov:NcYxiDXkpYi6ov5FcYDi1e",
+            "operation": {"type": "105", "dest": "did:sov:VsKV7grR1BUE29mG2Fm2kX"},
+            "signature": "signature"
+        }).to_string();
+
+        let res = group.route_request(&request);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+}
@@ -19,7 +19,8 @@ use serde::de::DeserializeOwned;
 
 use crate::api::ledger::{CustomFree, CustomTransactionParser};
 use crate::domain::{
-    pool::{PoolConfig, PoolOpenConfig},
+    cache::GetCacheOptions,
+    pool::{PoolConfig, PoolGroupConfig, PoolOpenConfig},
     ledger::response::{
         Message,
         Reply,
@@ -27,21 +28,33 @@ use crate::domain::{
     }
 };
 use indy_api_types::errors::*;
+use crate::services::pool::group::PoolGroup;
 use crate::services::pool::pool::{Pool, ZMQPool};
+use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+use crate::services::pool::types::{GenesisReport, StateProofVerdict};
 use crate::utils::environment;
-use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH};
+use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH, COMMAND_STATS};
 use indy_api_types::{CommandHandle, PoolHandle};
 use indy_utils::{next_command_handle, next_pool_handle};
-use ursa::bls::VerKey;
+use ursa::bls::{Generator, VerKey};
+use rust_base58::FromBase58;
 
+pub use self::async_pool::{AsyncPool, SubmitFuture};
+
+mod async_pool;
 mod catchup;
 mod commander;
 mod events;
+mod genesis;
+mod group;
 mod merkle_tree_factory;
 mod networker;
 mod pool;
+mod read_cache;
 mod request_handler;
+mod simulator;
 mod state_proof;
+mod stats;
 mod types;
 
 lazy_static! {
@@ -53,6 +66,7 @@ type Nodes = HashMap<String, Option<VerKey>>;
 pub struct PoolService {
     open_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
     pending_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
+    pool_groups: RefCell<HashMap<PoolHandle, PoolGroup>>,
 }
 
 impl PoolService {
@@ -60,6 +74,7 @@ impl PoolService {
         PoolService {
             open_pools: RefCell::new(HashMap::new()),
             pending_pools: RefCell::new(HashMap::new()),
+            pool_groups: RefCell::new(HashMap::new()),
         }
     }
 
@@ -159,7 +174,7 @@ impl PoolService {
         let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets(&format!("pool_{}", name));
 
         new_pool.work(recv_cmd_sock);
-        self._send_msg(pool_handle, COMMAND_CONNECT, &send_cmd_sock, None, None)?;
+        _send_msg(pool_handle, COMMAND_CONNECT, &send_cmd_sock, None, None)?;
 
         self.pending_pools.try_borrow_mut()?
             .insert(new_pool.get_id(), ZMQPool::new(new_pool, send_cmd_sock));
@@ -171,14 +186,20 @@ impl PoolService {
             .remove(&pool_id)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", pool_id)))?;
 
+        async_pool::register(pool_id, pool.pool.get_name(), pool.cmd_socket.clone());
+
         self.open_pools.try_borrow_mut()?.insert(pool_id, pool);
 
         Ok(pool_id)
     }
 
 
-    pub fn send_tx(&self, handle: PoolHandle, msg: &str) -> IndyResult<CommandHandle> {
-        self.send_action(handle, msg, None, None)
+    pub fn check_pool_handle(&self, handle: PoolHandle) -> IndyResult<()> {
+        if self.open_pools.try_borrow()?.contains_key(&handle) {
+            Ok(())
+        } else {
+            Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
+        }
     }
 
     pub fn send_action(&self, handle: PoolHandle, msg: &str, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<CommandHandle> {
@@ -186,7 +207,7 @@ impl PoolService {
 
         if let Some(ref pool) = pools.get(&handle) {
             let cmd_id: CommandHandle = next_command_handle();
-            self._send_msg(cmd_id, msg, &pool.cmd_socket, nodes, timeout)?;
+            _send_msg(cmd_id, msg, &*pool.cmd_socket.lock().unwrap(), nodes, timeout)?;
             Ok(cmd_id)
         } else {
             Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
@@ -219,8 +240,10 @@ impl PoolService {
 
         let mut pools = self.open_pools.try_borrow_mut()?;
 
+        async_pool::unregister(handle);
+
         match pools.remove(&handle) {
-            Some(ref pool) => self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None)?,
+            Some(ref pool) => _send_msg(cmd_id, COMMAND_EXIT, &*pool.cmd_socket.lock().unwrap(), None, None)?,
             None => return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))
         }
 
@@ -231,17 +254,117 @@ impl PoolService {
         self.send_action(handle, COMMAND_REFRESH, None, None)
     }
 
-    fn _send_msg(&self, cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<()> {
-        let mut buf = [0u8; 4];
-        let mut buf_to = [0u8; 4];
-        LittleEndian::write_i32(&mut buf, cmd_id);
-        let timeout = timeout.unwrap_or(-1);
-        LittleEndian::write_i32(&mut buf_to, timeout);
-        if let Some(nodes) = nodes {
-            Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to, nodes.as_bytes()], zmq::DONTWAIT)?)
-        } else {
-            Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to], zmq::DONTWAIT)?)
+    pub fn get_stats(&self, handle: PoolHandle) -> IndyResult<i32> {
+        self.send_action(handle, COMMAND_STATS, None, None)
+    }
+
+    /// Registers opened pools against DID methods. The returned handle can be used instead of
+    /// a pool handle to submit requests to the pool owning the identifiers they reference.
+    pub fn create_group(&self, config: PoolGroupConfig) -> IndyResult<PoolHandle> {
+        let group = PoolGroup::new(config);
+
+        for handle in group.pools() {
+            self.check_pool_handle(handle)?;
+        }
+
+        let group_handle: PoolHandle = next_pool_handle();
+
+        self.pool_groups.try_borrow_mut()?.insert(group_handle, group);
+
+        Ok(group_handle)
+    }
+
+    /// Forgets the pool group. Pools of the group stay opened.
+    pub fn close_group(&self, handle: PoolHandle) -> IndyResult<()> {
+        self.pool_groups.try_borrow_mut()?
+            .remove(&handle)
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool group with requested handle {:?}", handle)))
+    }
+
+    /// Resolves the pool the request must be sent to if `handle` is a pool group handle.
+    /// Returns the pool handle and the request to send.
+    pub fn route_request(&self, handle: PoolHandle, request: &str) -> IndyResult<(PoolHandle, String)> {
+        match self.pool_groups.try_borrow()?.get(&handle) {
+            Some(group) => group.route_request(request),
+            None => Ok((handle, request.to_string()))
+        }
+    }
+
+    /// Resolves the pool owning the identifier if `handle` is a pool group handle.
+    pub fn route_id(&self, handle: PoolHandle, id: &str) -> IndyResult<PoolHandle> {
+        match self.pool_groups.try_borrow()?.get(&handle) {
+            Some(group) => group.route_id(Some(id)),
+            None => Ok(handle)
+        }
+    }
+
+    /// Validates the genesis transactions file without opening connections to the nodes.
+    pub fn validate_genesis(&self, genesis_txn: &str, protocol_version: usize) -> IndyResult<GenesisReport> {
+        genesis::validate(genesis_txn, protocol_version)
+    }
+
+    /// Verifies state proof of a previously received reply against the nodes of the opened pool
+    /// (as known from the local copy of its ledger) or the nodes of the genesis transactions file.
+    pub fn verify_reply_state_proof(&self, handle: Option<PoolHandle>, genesis_txn: Option<&str>, request: &str, reply: &str) -> IndyResult<StateProofVerdict> {
+        let merkle = match (handle, genesis_txn) {
+            (Some(handle), None) => merkle_tree_factory::create(&self._get_pool_name(handle)?)?,
+            (None, Some(genesis_txn)) => merkle_tree_factory::from_file(genesis_txn)?,
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, "Either pool handle or genesis transactions file must be specified"))
+        };
+
+        let (nodes, _) = pool::_get_nodes_and_remotes(&merkle)?;
+
+        let request = serde_json::from_str::<serde_json::Value>(request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is malformed json")?;
+
+        let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+        state_proof::verify_reply(&request, reply, &nodes, pool::_get_f(nodes.len()), &gen)
+    }
+
+    /// Returns the reply on the request stored in the persistent read cache of the pool
+    /// if it is fresh enough according to `options`.
+    pub fn get_cached_reply(&self, handle: PoolHandle, request: &str, options: &GetCacheOptions) -> IndyResult<Option<String>> {
+        if options.no_cache.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let name = self._get_pool_name(handle)?;
+
+        let request = serde_json::from_str::<serde_json::Value>(request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is malformed json")?;
+
+        read_cache::get(&name, &request, options.min_fresh, _get_freshness_threshold())
+    }
+
+    /// Stores the reply in the persistent read cache of the pool.
+    /// Only replies on supported requests with verified state proof are stored.
+    pub fn cache_reply(&self, handle: PoolHandle, request: &str, reply: &str) -> IndyResult<bool> {
+        let request_json = serde_json::from_str::<serde_json::Value>(request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is malformed json")?;
+
+        if !read_cache::is_cacheable(&request_json) {
+            return Ok(false);
         }
+
+        let verdict = self.verify_reply_state_proof(Some(handle), None, request, reply)?;
+
+        if !verdict.verified {
+            debug!("Reply isn't stored in the read cache: state proof isn't verified {:?}", verdict.failure);
+            return Ok(false);
+        }
+
+        read_cache::put(&self._get_pool_name(handle)?, &request_json, reply, verdict.last_signed_time)?;
+
+        Ok(true)
+    }
+
+    fn _get_pool_name(&self, handle: PoolHandle) -> IndyResult<String> {
+        self.open_pools.try_borrow()?
+            .get(&handle)
+            .map(|pool| pool.pool.get_name().to_string())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
     }
 
     pub fn list(&self) -> IndyResult<Vec<serde_json::Value>> {
@@ -271,6 +394,10 @@ pub fn set_freshness_threshold(threshold: u64) {
     *th = ::std::cmp::max(threshold, 300);
 }
 
+fn _get_freshness_threshold() -> u64 {
+    *THRESHOLD.lock().unwrap()
+}
+
 
 pub fn parse_response_metadata(response: &str) -> IndyResult<ResponseMetadata> {
     trace!("indy::services::pool::parse_response_metadata << response: {}", response);
@@ -325,6 +452,19 @@ fn _parse_transaction_metadata_v1(message: &serde_json::Value) -> ResponseMetada
     }
 }
 
+fn _send_msg(cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<()> {
+    let mut buf = [0u8; 4];
+    let mut buf_to = [0u8; 4];
+    LittleEndian::write_i32(&mut buf, cmd_id);
+    let timeout = timeout.unwrap_or(-1);
+    LittleEndian::write_i32(&mut buf_to, timeout);
+    if let Some(nodes) = nodes {
+        Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to, nodes.as_bytes()], zmq::DONTWAIT)?)
+    } else {
+        Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to], zmq::DONTWAIT)?)
+    }
+}
+
 pub fn pool_create_pair_of_sockets(addr: &str) -> (zmq::Socket, zmq::Socket) {
     let zmq_ctx = zmq::Context::new();
     let send_cmd_sock = zmq_ctx.socket(zmq::SocketType::PAIR).unwrap();
@@ -407,6 +547,21 @@ mod tests {
             assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
         }
 
+        #[test]
+        fn pool_service_get_stats_works() {
+            test::cleanup_storage("pool_service_get_stats_works");
+
+            let ps = PoolService::new();
+            let pool_id = next_pool_handle();
+            let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_service_get_stats_works");
+            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
+            let cmd_id = ps.get_stats(pool_id).unwrap();
+            let recv = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+            assert_eq!(recv.len(), 3);
+            assert_eq!(COMMAND_STATS, String::from_utf8(recv[0].clone()).unwrap());
+            assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
+        }
+
         #[test]
         fn pool_service_delete_works() {
             test::cleanup_storage("pool_service_delete_works");
@@ -445,23 +600,8 @@ mod tests {
         }
 
         #[test]
-        fn pool_send_tx_works() {
-            test::cleanup_storage("pool_send_tx_works");
-
-            let name = "test";
-            let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_send_tx_works");
-            let pool_id = next_pool_handle();
-            let pool = Pool::new(name, pool_id, PoolOpenConfig::default());
-            let ps = PoolService::new();
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
-            let test_data = "str_instead_of_tx_json";
-            ps.send_tx(pool_id, test_data).unwrap();
-            assert_eq!(recv_cmd_sock.recv_string(zmq::DONTWAIT).unwrap().unwrap(), test_data);
-        }
-
-        #[test]
-        fn pool_send_tx_works_for_closed_socket() {
-            test::cleanup_storage("pool_send_tx_works_for_closed_socket");
+        fn pool_send_action_works_for_closed_socket() {
+            test::cleanup_storage("pool_send_action_works_for_closed_socket");
 
             let name = "test";
             let zmq_ctx = zmq::Context::new();
@@ -471,15 +611,15 @@ mod tests {
             let pool = Pool::new(name, pool_id, PoolOpenConfig::default());
             let ps = PoolService::new();
             ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
-            let res = ps.send_tx(pool_id, "test_data");
+            let res = ps.send_action(pool_id, "test_data", None, None);
             assert_eq!(IndyErrorKind::IOError, res.unwrap_err().kind());
         }
 
         #[test]
-        fn pool_send_tx_works_for_invalid_handle() {
-            test::cleanup_storage("pool_send_tx_works_for_invalid_handle");
+        fn pool_send_action_works_for_invalid_handle() {
+            test::cleanup_storage("pool_send_action_works_for_invalid_handle");
             let ps = PoolService::new();
-            let res = ps.send_tx(INVALID_POOL_HANDLE, "txn");
+            let res = ps.send_action(INVALID_POOL_HANDLE, "txn", None, None);
             assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
         }
 
@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::rc::Rc;
 
 use rand::thread_rng;
 use rand::prelude::SliceRandom;
@@ -7,6 +8,7 @@ use time::Tm;
 
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::events::*;
+use crate::services::pool::stats::PoolStats;
 use crate::services::pool::types::*;
 use indy_utils::sequence;
 
@@ -16,11 +18,12 @@ use super::zmq::PollItem;
 use super::zmq::Socket as ZSocket;
 
 pub trait Networker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self;
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, adaptive_ordering: bool) -> Self;
     fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent>;
     fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent>;
     fn get_timeout(&self) -> ((String, String), i64);
     fn get_poll_items(&self) -> Vec<PollItem>;
+    fn stats(&self) -> &RefCell<PoolStats>;
 }
 
 pub struct ZMQNetworker {
@@ -30,10 +33,12 @@ pub struct ZMQNetworker {
     active_timeout: i64,
     conn_limit: usize,
     preordered_nodes: Vec<String>,
+    adaptive_ordering: bool,
+    stats: Rc<RefCell<PoolStats>>,
 }
 
 impl Networker for ZMQNetworker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self {
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, adaptive_ordering: bool) -> Self {
         ZMQNetworker {
             req_id_mappings: HashMap::new(),
             pool_connections: BTreeMap::new(),
@@ -41,6 +46,8 @@ impl Networker for ZMQNetworker {
             active_timeout,
             conn_limit,
             preordered_nodes,
+            adaptive_ordering,
+            stats: Rc::new(RefCell::new(PoolStats::new())),
         }
     }
 
@@ -83,10 +90,11 @@ impl Networker for ZMQNetworker {
                     None => {
                         trace!("send request in new conn");
                         let pc_id = sequence::get_next_id();
-                        let mut pc = PoolConnection::new(self.nodes.clone(), self.active_timeout, self.preordered_nodes.clone());
+                        let mut pc = PoolConnection::new(self.nodes.clone(), self.active_timeout, self.preordered_nodes.clone(), self.stats.clone(), self.adaptive_ordering);
                         pc.send_request(pe).expect("FIXME");
                         self.pool_connections.insert(pc_id, pc);
                         self.req_id_mappings.insert(req_id.clone(), pc_id);
+                        self._update_connections_stats();
                     }
                 }
                 None
@@ -129,9 +137,11 @@ impl Networker for ZMQNetworker {
                         self.pool_connections.remove(idx);
                     }
                 }
+                self._update_connections_stats();
 
                 if node_alias.is_none() {
                     self.req_id_mappings.remove(&req_id);
+                    self.stats.borrow_mut().request_finished(&req_id);
                 }
 
                 None
@@ -145,6 +155,7 @@ impl Networker for ZMQNetworker {
                     trace!("removing pool connection {}", idx);
                     self.pool_connections.remove(idx);
                 });
+                self._update_connections_stats();
                 None
             }
             _ => None
@@ -162,6 +173,16 @@ impl Networker for ZMQNetworker {
         self.pool_connections.iter()
             .flat_map(|(_, pool)| pool.get_poll_items()).collect()
     }
+
+    fn stats(&self) -> &RefCell<PoolStats> {
+        &self.stats
+    }
+}
+
+impl ZMQNetworker {
+    fn _update_connections_stats(&self) {
+        self.stats.borrow_mut().connections = self.pool_connections.len();
+    }
 }
 
 pub struct PoolConnection {
@@ -174,10 +195,11 @@ pub struct PoolConnection {
     time_created: time::Tm,
     req_cnt: usize,
     active_timeout: i64,
+    stats: Rc<RefCell<PoolStats>>,
 }
 
 impl PoolConnection {
-    fn new(mut nodes: Vec<RemoteNode>, active_timeout: i64, preordered_nodes: Vec<String>) -> Self {
+    fn new(mut nodes: Vec<RemoteNode>, active_timeout: i64, preordered_nodes: Vec<String>, stats: Rc<RefCell<PoolStats>>, adaptive_ordering: bool) -> Self {
         trace!("PoolConnection::new: from nodes {:?}", nodes);
 
         nodes.shuffle(&mut thread_rng());
@@ -190,6 +212,10 @@ impl PoolConnection {
             });
         }
 
+        if adaptive_ordering {
+            stats.borrow().rank_nodes(&mut nodes);
+        }
+
         let mut sockets: Vec<Option<ZSocket>> = Vec::with_capacity(nodes.len());
 
         for _ in 0..nodes.len() { sockets.push(None); }
@@ -204,6 +230,7 @@ impl PoolConnection {
             timeouts: RefCell::new(HashMap::new()),
             req_cnt: 0,
             active_timeout,
+            stats,
         }
     }
 
@@ -322,6 +349,7 @@ impl PoolConnection {
             let s = self._get_socket(idx)?;
             s.send(&req, zmq::DONTWAIT)?;
         }
+        self.stats.borrow_mut().request_sent(&req_id, &self.nodes[idx].name);
         self.timeouts.borrow_mut().insert((req_id, self.nodes[idx].name.clone()), time::now() + Duration::seconds(timeout));
         trace!("_send_msg_to_one_node <<");
         Ok(())
@@ -355,13 +383,15 @@ impl RemoteNode {
 #[cfg(test)]
 pub struct MockNetworker {
     pub events: Vec<Option<NetworkerEvent>>,
+    pub stats: RefCell<PoolStats>,
 }
 
 #[cfg(test)]
 impl Networker for MockNetworker {
-    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>) -> Self {
+    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>, _adaptive_ordering: bool) -> Self {
         MockNetworker {
             events: Vec::new(),
+            stats: RefCell::new(PoolStats::new()),
         }
     }
 
@@ -381,6 +411,10 @@ impl Networker for MockNetworker {
     fn get_poll_items(&self) -> Vec<PollItem> {
         unimplemented!()
     }
+
+    fn stats(&self) -> &RefCell<PoolStats> {
+        &self.stats
+    }
 }
 
 
@@ -400,6 +434,10 @@ pub mod networker_tests {
     const MESSAGE: &str = "msg";
     const NODE_NAME: &str = "n1";
 
+    fn _stats() -> Rc<RefCell<PoolStats>> {
+        Rc::new(RefCell::new(PoolStats::new()))
+    }
+
     pub fn _remote_node(txn: &NodeTransactionV1) -> RemoteNode {
         RemoteNode {
             public_key: ed25519_sign::vk_to_curve25519(&ed25519_sign::PublicKey::from_slice(&txn.txn.data.dest.as_str().from_base58().unwrap()).unwrap()).unwrap()[..].to_vec(),
@@ -417,12 +455,12 @@ pub mod networker_tests {
 
         #[test]
         pub fn networker_new_works() {
-            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
         }
 
         #[test]
         pub fn networker_process_event_works() {
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(None);
         }
 
@@ -431,7 +469,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             assert_eq!(0, networker.nodes.len());
 
@@ -446,7 +484,7 @@ pub mod networker_tests {
             let handle = nodes_emulator::start(&mut txn);
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             assert!(networker.pool_connections.is_empty());
@@ -462,6 +500,25 @@ pub mod networker_tests {
             assert!(nodes_emulator::next(&handle).is_none());
         }
 
+        #[test]
+        fn networker_process_send_request_event_updates_stats() {
+            let mut txn = nodes_emulator::node();
+            let handle = nodes_emulator::start(&mut txn);
+            let rn = _remote_node(&txn);
+
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
+            networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
+            networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
+
+            {
+                let stats = networker.stats().borrow();
+                assert_eq!(1, stats.connections);
+                assert_eq!(1, stats.nodes[NODE_NAME].sent);
+            }
+
+            assert_eq!(MESSAGE.to_string(), nodes_emulator::next(&handle).unwrap());
+        }
+
         #[test]
         fn networker_process_send_all_request_event_works() {
             let mut txn_1 = nodes_emulator::node();
@@ -472,7 +529,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, None)));
@@ -495,7 +552,7 @@ pub mod networker_tests {
 
             let send_cnt = 2;
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
 
@@ -525,7 +582,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, Some(vec![NODE_NAME.to_string()]))));
@@ -541,7 +598,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -564,7 +621,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -587,7 +644,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -614,9 +671,9 @@ pub mod networker_tests {
         fn networker_process_timeout_event_works() {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
-            let conn = PoolConnection::new(vec![rn.clone()], POOL_CON_ACTIVE_TO, vec![]);
+            let conn = PoolConnection::new(vec![rn.clone()], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.pool_connections.insert(1, conn);
@@ -633,7 +690,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
 
@@ -649,7 +706,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -667,7 +724,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -686,7 +743,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], false);
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -736,7 +793,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
         }
 
         #[test]
@@ -752,7 +809,7 @@ pub mod networker_tests {
                 nodes.push(_remote_node(&txn));
             }
 
-            let pc = PoolConnection::new(nodes, POOL_CON_ACTIVE_TO, vec![]);
+            let pc = PoolConnection::new(nodes, POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             let act_names: Vec<String> = pc.nodes.iter().map(|n| n.name.to_string()).collect();
 
@@ -780,19 +837,44 @@ pub mod networker_tests {
 
             let pc = PoolConnection::new(vec![rn_1.clone(), rn_2.clone(), rn_3.clone(), rn_4.clone(), rn_5.clone()],
                                          POOL_CON_ACTIVE_TO,
-                                         vec![rn_2.name.clone(), rn_1.name.clone(), rn_5.name.clone()]);
+                                         vec![rn_2.name.clone(), rn_1.name.clone(), rn_5.name.clone()], _stats(), false);
 
             assert_eq!(rn_2.name, pc.nodes[0].name);
             assert_eq!(rn_1.name, pc.nodes[1].name);
             assert_eq!(rn_5.name, pc.nodes[2].name);
         }
 
+        #[test]
+        fn pool_connection_new_works_for_adaptive_ordering() {
+            let mut txn = nodes_emulator::node();
+
+            txn.txn.data.data.alias = "Node1".to_string();
+            let rn_1 = _remote_node(&txn);
+
+            txn.txn.data.data.alias = "Node2".to_string();
+            let rn_2 = _remote_node(&txn);
+
+            let stats = _stats();
+            stats.borrow_mut().request_sent(REQ_ID, &rn_1.name);
+            stats.borrow_mut().request_sent(REQ_ID, &rn_2.name);
+            thread::sleep(std::time::Duration::from_millis(10));
+            stats.borrow_mut().event_received(&RequestEvent::Timeout(REQ_ID.to_string(), rn_1.name.clone()));
+            stats.borrow_mut().event_received(&RequestEvent::ReqNACK(Response::ResponseV0(ResponseV0 { req_id: 1 }),
+                                                                     String::new(), rn_2.name.clone(), REQ_ID.to_string()));
+
+            let pc = PoolConnection::new(vec![rn_1.clone(), rn_2.clone()], POOL_CON_ACTIVE_TO,
+                                         vec![rn_1.name.clone(), rn_2.name.clone()], stats.clone(), true);
+
+            assert_eq!(rn_2.name, pc.nodes[0].name);
+            assert_eq!(rn_1.name, pc.nodes[1].name);
+        }
+
         #[test]
         fn pool_connection_is_active_works() {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             assert!(conn.is_active());
 
@@ -806,7 +888,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             assert!(!conn.has_active_requests());
 
@@ -820,7 +902,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             let ((req_id, node_alias), timeout) = conn.get_timeout();
             assert_eq!(req_id, "".to_string());
@@ -841,7 +923,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
 
@@ -861,7 +943,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
 
@@ -877,7 +959,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             let _socket = conn._get_socket(0).unwrap();
         }
@@ -888,7 +970,7 @@ pub mod networker_tests {
             let mut rn = _remote_node(&txn);
             rn.zaddr = "invalid_address".to_string();
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             let res = conn._get_socket(0);
             assert_kind!(IndyErrorKind::IOError, res);
@@ -900,7 +982,7 @@ pub mod networker_tests {
             let handle = nodes_emulator::start(&mut txn);
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
             conn.send_request(Some(NetworkerEvent::SendOneRequest("msg2".to_string(), "12".to_string(), POOL_ACK_TIMEOUT))).unwrap();
@@ -920,7 +1002,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec!["n1".to_string(), "n2".to_string()]);
+            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec!["n1".to_string(), "n2".to_string()], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
 
@@ -940,7 +1022,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, None))).unwrap();
 
@@ -956,7 +1038,7 @@ pub mod networker_tests {
             let handle = nodes_emulator::start(&mut txn);
             let rn = _remote_node(&txn);
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
 
@@ -977,7 +1059,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn_1, rn_2], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT))).unwrap();
 
@@ -995,7 +1077,7 @@ pub mod networker_tests {
             let mut rn = _remote_node(&txn);
             rn.zaddr = "invalid_address".to_string();
 
-            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![]);
+            let mut conn = PoolConnection::new(vec![rn], POOL_CON_ACTIVE_TO, vec![], _stats(), false);
 
             let res = conn.send_request(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
             assert_kind!(IndyErrorKind::IOError, res);
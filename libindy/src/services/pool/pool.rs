@@ -3,31 +3,35 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::JoinHandle;
 
 use failure::Context;
+use serde_json::Value as SJsonValue;
+use time::Tm;
 
 use crate::commands::Command;
 use crate::commands::CommandExecutor;
-use crate::commands::ledger::LedgerCommand;
 use crate::commands::pool::PoolCommand;
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::PoolOpenConfig;
+use crate::domain::pool::{PoolOpenConfig, PoolSimulationConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::ledger::merkletree::merkletree::MerkleTree;
 use crate::services::pool::commander::Commander;
 use crate::services::pool::events::*;
-use crate::services::pool::{merkle_tree_factory, Nodes};
+use crate::services::pool::{async_pool, merkle_tree_factory, Nodes};
 use crate::services::pool::networker::{Networker, ZMQNetworker};
 use crate::services::pool::request_handler::{RequestHandler, RequestHandlerImpl};
+use crate::services::pool::simulator::SimulatedNetworker;
 use rust_base58::{FromBase58, ToBase58};
 use crate::services::pool::types::{LedgerStatus, RemoteNode};
 use crate::utils::crypto::ed25519_sign;
 
+use super::time::Duration;
 use super::ursa::bls::VerKey;
 use super::zmq;
-use indy_api_types::{PoolHandle, CommandHandle};
+use indy_api_types::{PoolHandle, CommandHandle, INVALID_COMMAND_HANDLE};
 
 struct PoolSM<T: Networker, R: RequestHandler<T>> {
     pool_name: String,
@@ -63,6 +67,8 @@ struct GettingCatchupTargetState<T: Networker, R: RequestHandler<T>> {
     request_handler: R,
     cmd_id: CommandHandle,
     refresh: bool,
+    // nodes of the active pool to fall back to if a background refresh fails
+    fallback_nodes: Option<Nodes>,
 }
 
 struct ActiveState<T: Networker, R: RequestHandler<T>> {
@@ -76,6 +82,7 @@ struct SyncCatchupState<T: Networker, R: RequestHandler<T>> {
     request_handler: R,
     cmd_id: CommandHandle,
     refresh: bool,
+    fallback_nodes: Option<Nodes>,
 }
 
 struct TerminatedState<T: Networker> {
@@ -114,6 +121,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(R, CommandHandle, InitializationS
             request_handler,
             cmd_id,
             refresh: false,
+            fallback_nodes: None,
         }
     }
 }
@@ -153,6 +161,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(R, GettingCatchupTargetState<T, R
             request_handler,
             cmd_id: state.cmd_id,
             refresh: state.refresh,
+            fallback_nodes: state.fallback_nodes,
         }
     }
 }
@@ -190,11 +199,13 @@ impl<T: Networker, R: RequestHandler<T>> From<(ActiveState<T, R>, R, CommandHand
     fn from((state, request_handler, cmd_id): (ActiveState<T, R>, R, CommandHandle)) -> Self {
         trace!("PoolSM: from active to getting catchup target");
         //TODO: close connections!
+        let fallback_nodes = if cmd_id == INVALID_COMMAND_HANDLE { Some(state.nodes) } else { None };
         GettingCatchupTargetState {
             networker: state.networker,
             cmd_id,
             request_handler,
             refresh: true,
+            fallback_nodes,
         }
     }
 }
@@ -245,6 +256,32 @@ impl<T: Networker, R: RequestHandler<T>> From<SyncCatchupState<T, R>> for Closed
     }
 }
 
+// failed background refresh
+
+impl<T: Networker, R: RequestHandler<T>> GettingCatchupTargetState<T, R> {
+    fn into_fallback(mut self) -> PoolState<T, R> {
+        match self.fallback_nodes.take() {
+            Some(nodes) => {
+                warn!("PoolSM: background refresh failed, keep previous nodes");
+                PoolState::Active((self, nodes).into())
+            }
+            None => PoolState::Terminated(self.into())
+        }
+    }
+}
+
+impl<T: Networker, R: RequestHandler<T>> SyncCatchupState<T, R> {
+    fn into_fallback(mut self) -> PoolState<T, R> {
+        match self.fallback_nodes.take() {
+            Some(nodes) => {
+                warn!("PoolSM: background refresh failed, keep previous nodes");
+                PoolState::Active((self, nodes).into())
+            }
+            None => PoolState::Terminated(self.into())
+        }
+    }
+}
+
 // transitions from Terminated
 
 impl<T: Networker, R: RequestHandler<T>> From<(TerminatedState<T>, R, CommandHandle)> for GettingCatchupTargetState<T, R> {
@@ -255,6 +292,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(TerminatedState<T>, R, CommandHan
             cmd_id,
             request_handler,
             refresh: true,
+            fallback_nodes: None,
         }
     }
 }
@@ -304,8 +342,8 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         PoolState::Closed(state.into())
                     }
                     PoolEvent::CatchupTargetNotFound(err) => {
-                        _send_open_refresh_ack(state.cmd_id, id, state.refresh,Err(err));
-                        PoolState::Terminated(state.into())
+                        _send_open_refresh_ack(state.cmd_id, id, state.refresh, Err(err));
+                        state.into_fallback()
                     }
                     PoolEvent::CatchupRestart(merkle_tree) => {
                         if let Ok((nodes, remotes)) = _get_nodes_and_remotes(&merkle_tree) {
@@ -315,7 +353,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                             state.request_handler.process_event(Some(RequestEvent::LedgerStatus(ls, None, Some(merkle_tree))));
                             PoolState::GettingCatchupTarget(state)
                         } else {
-                            PoolState::Terminated(state.into())
+                            state.into_fallback()
                         }
                     }
                     PoolEvent::CatchupTargetFound(target_mt_root, target_mt_size, merkle_tree) => {
@@ -325,16 +363,17 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                             request_handler.process_event(Some(RequestEvent::CatchupReq(merkle_tree, target_mt_size, target_mt_root)));
                             PoolState::SyncCatchup((request_handler, state).into())
                         } else {
-                            PoolState::Terminated(state.into())
+                            state.into_fallback()
                         }
                     }
                     PoolEvent::Synced(merkle) => {
                         if let Ok((nodes, remotes)) = _get_nodes_and_remotes(&merkle) {
                             state.networker.borrow_mut().process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
+                            state.networker.borrow().stats().borrow_mut().catchup_finished();
                             _send_open_refresh_ack(state.cmd_id, id, state.refresh, Ok(()));
                             PoolState::Active((state, nodes).into())
                         } else {
-                            PoolState::Terminated(state.into())
+                            state.into_fallback()
                         }
                     }
                     _ => PoolState::GettingCatchupTarget(state)
@@ -347,12 +386,18 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         PoolState::Closed(state.into())
                     }
                     PoolEvent::Refresh(cmd_id) => {
-                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
-                            PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into())
-                        } else {
-                            PoolState::Terminated(state)
+                        match _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
+                            Ok(request_handler) => PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into()),
+                            Err(err) => {
+                                _send_open_refresh_ack(cmd_id, id, true, Err(err));
+                                PoolState::Terminated(state)
+                            }
                         }
                     }
+                    PoolEvent::SendRequest(cmd_id, _, _, _) => {
+                        _send_submit_ack(cmd_id, Err(err_msg(IndyErrorKind::PoolTerminated, "Pool is terminated")));
+                        PoolState::Terminated(state)
+                    }
                     PoolEvent::Timeout(req_id, node_alias) => {
                         if "".eq(&req_id) {
                             state.networker.borrow_mut().process_event(Some(NetworkerEvent::Timeout));
@@ -364,7 +409,12 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                     _ => PoolState::Terminated(state)
                 }
             }
-            PoolState::Closed(state) => PoolState::Closed(state),
+            PoolState::Closed(state) => {
+                if let PoolEvent::SendRequest(cmd_id, _, _, _) = pe {
+                    _send_submit_ack(cmd_id, Err(err_msg(IndyErrorKind::InvalidPoolHandle, "Pool is closed")));
+                }
+                PoolState::Closed(state)
+            }
             PoolState::Active(mut state) => {
                 match pe.clone() {
                     PoolEvent::PoolOutdated => PoolState::Terminated(state.into()),
@@ -373,10 +423,16 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         PoolState::Closed(state.into())
                     }
                     PoolEvent::Refresh(cmd_id) => {
-                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
-                            PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into())
-                        } else {
-                            PoolState::Terminated(state.into())
+                        match _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
+                            Ok(request_handler) => PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into()),
+                            Err(_) if cmd_id == INVALID_COMMAND_HANDLE => {
+                                warn!("PoolSM: can't start background refresh, keep previous nodes");
+                                PoolState::Active(state)
+                            }
+                            Err(err) => {
+                                _send_open_refresh_ack(cmd_id, id, true, Err(err));
+                                PoolState::Terminated(state.into())
+                            }
                         }
                     }
                     PoolEvent::SendRequest(cmd_id, _, _, _) => {
@@ -435,14 +491,15 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         _close_pool_ack(cmd_id);
                         PoolState::Closed(state.into())
                     }
-                    PoolEvent::NodesBlacklisted => PoolState::Terminated(state.into()),
+                    PoolEvent::NodesBlacklisted => state.into_fallback(),
                     PoolEvent::Synced(merkle) => {
                         if let Ok((nodes, remotes)) = _get_nodes_and_remotes(&merkle).map_err(map_err_err!()) {
                             state.networker.borrow_mut().process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
+                            state.networker.borrow().stats().borrow_mut().catchup_finished();
                             _send_open_refresh_ack(state.cmd_id, id, state.refresh, Ok(()));
                             PoolState::Active((state, nodes).into())
                         } else {
-                            PoolState::Terminated(state.into())
+                            state.into_fallback()
                         }
                     }
                     _ => PoolState::SyncCatchup(state)
@@ -452,6 +509,29 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
         PoolSM::step(pool_name, id, timeout, extended_timeout, number_read_nodes, state)
     }
 
+    pub fn nodes(&self) -> Option<&Nodes> {
+        match self.state {
+            PoolState::Active(ref state) => Some(&state.nodes),
+            _ => None
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        match self.state {
+            PoolState::Active(ref state) => state.request_handlers.is_empty(),
+            PoolState::Terminated(_) => true,
+            _ => false
+        }
+    }
+
+    pub fn is_refreshing(&self) -> bool {
+        match self.state {
+            PoolState::GettingCatchupTarget(ref state) => state.refresh,
+            PoolState::SyncCatchup(ref state) => state.refresh,
+            _ => false
+        }
+    }
+
     pub fn is_terminal(&self) -> bool {
         match self.state {
             PoolState::Initialization(_) |
@@ -475,6 +555,9 @@ pub struct Pool<S: Networker, R: RequestHandler<S>> {
     conn_limit: usize,
     preordered_nodes: Vec<String>,
     number_read_nodes: u8,
+    adaptive_ordering: bool,
+    refresh_interval: Option<i64>,
+    simulation: Option<PoolSimulationConfig>,
 }
 
 impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
@@ -491,6 +574,9 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
             conn_limit: config.conn_limit,
             preordered_nodes: config.preordered_nodes,
             number_read_nodes: config.number_read_nodes,
+            adaptive_ordering: config.adaptive_ordering,
+            refresh_interval: config.refresh_interval,
+            simulation: config.simulation,
         }
     }
 
@@ -503,13 +589,28 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
         let conn_limit = self.conn_limit;
         let preordered_nodes = self.preordered_nodes.clone();
         let number_read_nodes = self.number_read_nodes;
+        let adaptive_ordering = self.adaptive_ordering;
+        let refresh_interval = self.refresh_interval;
+        let simulation = self.simulation.clone();
         self.worker = Some(thread::spawn(move || {
-            let mut pool_thread: PoolThread<S, R> = PoolThread::new(cmd_socket, name, id,
-                                                                    timeout, extended_timeout,
-                                                                    active_timeout, conn_limit,
-                                                                    preordered_nodes,
-                                                                    number_read_nodes);
-            pool_thread.work();
+            match simulation {
+                Some(simulation) => {
+                    let networker = SimulatedNetworker::new_for_pool(&name, simulation, preordered_nodes, adaptive_ordering);
+                    let mut pool_thread: PoolThread<SimulatedNetworker, RequestHandlerImpl<SimulatedNetworker>> =
+                        PoolThread::with_networker(networker, cmd_socket, name, id,
+                                                   timeout, extended_timeout,
+                                                   number_read_nodes, refresh_interval);
+                    pool_thread.work();
+                }
+                None => {
+                    let mut pool_thread: PoolThread<S, R> = PoolThread::new(cmd_socket, name, id,
+                                                                            timeout, extended_timeout,
+                                                                            active_timeout, conn_limit,
+                                                                            preordered_nodes, adaptive_ordering,
+                                                                            number_read_nodes, refresh_interval);
+                    pool_thread.work();
+                }
+            }
         }));
     }
 
@@ -523,20 +624,34 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
 }
 
 struct PoolThread<S: Networker, R: RequestHandler<S>> {
+    id: PoolHandle,
     pool_sm: Option<PoolSM<S, R>>,
     events: VecDeque<PoolEvent>,
+    deferred: VecDeque<PoolEvent>,
     commander: Commander,
     networker: Rc<RefCell<S>>,
+    nodes: Option<Nodes>,
+    refresh_interval: Option<i64>,
+    next_refresh: Option<Tm>,
 }
 
 impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
-    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, number_read_nodes: u8) -> Self {
-        let networker = Rc::new(RefCell::new(S::new(active_timeout, conn_limit, preordered_nodes)));
+    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, adaptive_ordering: bool, number_read_nodes: u8, refresh_interval: Option<i64>) -> Self {
+        PoolThread::with_networker(S::new(active_timeout, conn_limit, preordered_nodes, adaptive_ordering), cmd_socket, name, id, timeout, extended_timeout, number_read_nodes, refresh_interval)
+    }
+
+    pub fn with_networker(networker: S, cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, number_read_nodes: u8, refresh_interval: Option<i64>) -> Self {
+        let networker = Rc::new(RefCell::new(networker));
         PoolThread {
+            id,
             pool_sm: Some(PoolSM::new(networker.clone(), &name, id, timeout, extended_timeout, number_read_nodes)),
             events: VecDeque::new(),
+            deferred: VecDeque::new(),
             commander: Commander::new(cmd_socket),
             networker,
+            nodes: None,
+            refresh_interval,
+            next_refresh: _next_refresh(refresh_interval),
         }
     }
 
@@ -548,6 +663,9 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
                 break;
             }
         }
+
+        // nobody replies on requests still waiting after the worker stops
+        async_pool::terminate(self.id);
     }
 
     fn _loop(&mut self) -> bool {
@@ -555,8 +673,27 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
             let pe = self.events.pop_front();
             trace!("received pool event: {:?}", pe);
             match pe {
+                Some(PoolEvent::GetStats(cmd_id)) => {
+                    _send_stats_ack(cmd_id, &*self.networker.borrow());
+                }
                 Some(pe) => {
+                    if let PoolEvent::SendRequest(..) = pe {
+                        if self._is_refreshing() {
+                            trace!("defer request until pool refresh is finished");
+                            self.deferred.push_back(pe);
+                            continue;
+                        }
+                    }
+
+                    self._record_stats(&pe);
+                    let was_active = self._nodes().is_some();
                     self.pool_sm = self.pool_sm.take().map(|w| w.handle_event(pe));
+                    if !was_active {
+                        self._check_nodes_update();
+                    }
+                    if !self.deferred.is_empty() && !self._is_refreshing() {
+                        self.events.extend(self.deferred.drain(..));
+                    }
                 }
                 _ => ()
             }
@@ -564,7 +701,53 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
         self.pool_sm.as_ref().map(|w| w.is_terminal()).unwrap_or(true)
     }
 
+    fn _nodes(&self) -> Option<&Nodes> {
+        self.pool_sm.as_ref().and_then(PoolSM::nodes)
+    }
+
+    fn _is_refreshing(&self) -> bool {
+        self.pool_sm.as_ref().map(PoolSM::is_refreshing).unwrap_or(false)
+    }
+
+    fn _check_nodes_update(&mut self) {
+        let nodes = match self._nodes() {
+            Some(nodes) => nodes.clone(),
+            None => return
+        };
+
+        if let Some(diff) = self.nodes.as_ref().and_then(|prev| _nodes_diff(prev, &nodes)) {
+            debug!("pool {} nodes updated: {:?}", self.id, diff);
+            let pc = PoolCommand::NodesUpdated(self.id, diff.to_string());
+            CommandExecutor::instance().send(Command::Pool(pc)).unwrap();
+        }
+
+        self.nodes = Some(nodes);
+    }
+
+    /// Time in ms left before the next background refresh, if it may be started when the pool is idle.
+    fn _refresh_timeout(&self) -> Option<i64> {
+        match (self.next_refresh, self.pool_sm.as_ref()) {
+            (Some(next_refresh), Some(pool_sm)) if pool_sm.is_idle() =>
+                Some((next_refresh - time::now()).num_milliseconds()),
+            _ => None
+        }
+    }
+
+    fn _record_stats(&self, pe: &PoolEvent) {
+        match *pe {
+            PoolEvent::NodeReply(_, _) | PoolEvent::Timeout(_, _) => {
+                let re: Option<RequestEvent> = pe.clone().into();
+                if let Some(re) = re {
+                    self.networker.borrow().stats().borrow_mut().event_received(&re);
+                }
+            }
+            _ => ()
+        }
+    }
+
     fn _poll(&mut self) {
+        let refresh_timeout = self._refresh_timeout();
+
         let events = {
             let networker = self.networker.borrow();
 
@@ -575,12 +758,21 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
             let ((req_id, alias), timeout) = networker.get_timeout();
             //            trace!("next timeout: {:?}", timeout);
 
+            let refresh = refresh_timeout.map(|refresh_timeout| refresh_timeout < timeout).unwrap_or(false);
+            let timeout = if refresh { refresh_timeout.unwrap_or(timeout) } else { timeout };
+
             let poll_res = zmq::poll(&mut poll_items, ::std::cmp::max(timeout, 0))
                 .map_err(map_err_err!())
                 .map_err(|_| unimplemented!() /* FIXME */).unwrap();
             //            trace!("poll_res: {:?}", poll_res);
             if poll_res == 0 {
-                self.events.push_back(PoolEvent::Timeout(req_id, alias)); // TODO check duplicate ?
+                if refresh {
+                    trace!("start background refresh");
+                    self.events.push_back(PoolEvent::Refresh(INVALID_COMMAND_HANDLE));
+                    self.next_refresh = _next_refresh(self.refresh_interval);
+                } else {
+                    self.events.push_back(PoolEvent::Timeout(req_id, alias)); // TODO check duplicate ?
+                }
             }
             //            trace!("poll_items: {:?}", poll_items.len());
 
@@ -598,7 +790,39 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
     }
 }
 
-fn _get_f(cnt: usize) -> usize {
+fn _next_refresh(refresh_interval: Option<i64>) -> Option<Tm> {
+    refresh_interval.map(|interval| time::now() + Duration::seconds(interval))
+}
+
+fn _nodes_diff(prev: &Nodes, nodes: &Nodes) -> Option<SJsonValue> {
+    let blskey = |key: &Option<VerKey>| key.as_ref().map(|key| key.as_bytes().to_base58());
+
+    let added: HashMap<&String, Option<String>> = nodes.iter()
+        .filter(|&(alias, _)| !prev.contains_key(alias))
+        .map(|(alias, key)| (alias, blskey(key)))
+        .collect();
+
+    let removed: Vec<&String> = prev.keys()
+        .filter(|alias| !nodes.contains_key(*alias))
+        .collect();
+
+    let updated: HashMap<&String, Option<String>> = nodes.iter()
+        .filter(|&(alias, key)| prev.get(alias).map(|prev_key| blskey(prev_key) != blskey(key)).unwrap_or(false))
+        .map(|(alias, key)| (alias, blskey(key)))
+        .collect();
+
+    if added.is_empty() && removed.is_empty() && updated.is_empty() {
+        return None;
+    }
+
+    Some(json!({
+        "added": added,
+        "removed": removed,
+        "updated": updated,
+    }))
+}
+
+pub(super) fn _get_f(cnt: usize) -> usize {
     if cnt < 4 {
         return 0;
     }
@@ -646,7 +870,7 @@ fn _ledger_status(merkle: &MerkleTree) -> LedgerStatus {
     }
 }
 
-fn _get_nodes_and_remotes(merkle: &MerkleTree) -> IndyResult<(Nodes, Vec<RemoteNode>)> {
+pub(super) fn _get_nodes_and_remotes(merkle: &MerkleTree) -> IndyResult<(Nodes, Vec<RemoteNode>)> {
     let nodes = merkle_tree_factory::build_node_state(merkle)?;
 
     Ok(nodes.iter().map(|(_, txn)| {
@@ -716,12 +940,23 @@ fn _close_pool_ack(cmd_id: CommandHandle) {
 }
 
 fn _send_submit_ack(cmd_id: CommandHandle, res: IndyResult<String>) {
-    let lc = LedgerCommand::SubmitAck(cmd_id, res);
-    CommandExecutor::instance().send(Command::Ledger(lc)).unwrap();
+    async_pool::complete(cmd_id, res);
+}
+
+fn _send_stats_ack<T: Networker>(cmd_id: CommandHandle, networker: &T) {
+    let res = serde_json::to_string(&*networker.stats().borrow())
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize pool stats");
+    let pc = PoolCommand::GetStatsAck(cmd_id, res);
+    CommandExecutor::instance().send(Command::Pool(pc)).unwrap();
 }
 
 fn _send_open_refresh_ack(cmd_id: CommandHandle, id: PoolHandle, is_refresh: bool, res: IndyResult<()>) {
     trace!("PoolSM: from getting catchup target to active");
+    if is_refresh && cmd_id == INVALID_COMMAND_HANDLE {
+        // nobody waits for a background refresh, deferred requests are acked once the pool leaves catchup
+        trace!("background refresh finished: {:?}", res);
+        return;
+    }
     let pc = if is_refresh {
         PoolCommand::RefreshAck(cmd_id, res)
     } else {
@@ -732,14 +967,14 @@ fn _send_open_refresh_ack(cmd_id: CommandHandle, id: PoolHandle, is_refresh: boo
 
 pub struct ZMQPool {
     pub(super) pool: Pool<ZMQNetworker, RequestHandlerImpl<ZMQNetworker>>,
-    pub(super) cmd_socket: zmq::Socket,
+    pub(super) cmd_socket: Arc<Mutex<zmq::Socket>>,
 }
 
 impl ZMQPool {
     pub fn new(pool: Pool<ZMQNetworker, RequestHandlerImpl<ZMQNetworker>>, cmd_socket: zmq::Socket) -> ZMQPool {
         ZMQPool {
             pool,
-            cmd_socket,
+            cmd_socket: Arc::new(Mutex::new(cmd_socket)),
         }
     }
 }
@@ -748,7 +983,7 @@ impl Drop for ZMQPool {
     fn drop(&mut self) {
         info!("Drop started");
 
-        if let Err(err) = self.cmd_socket.send(COMMAND_EXIT.as_bytes(), zmq::DONTWAIT) {
+        if let Err(err) = self.cmd_socket.lock().unwrap().send(COMMAND_EXIT.as_bytes(), zmq::DONTWAIT) {
             warn!("Can't send exit command to pool worker thread (may be already finished) {}", err);
         }
 
@@ -809,7 +1044,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_new_initialization_works() {
-            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
         }
 
         #[test]
@@ -819,7 +1054,7 @@ mod tests {
             ProtocolVersion::set(2);
             _write_genesis_txns("pool_wrapper_check_cache_works");
 
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             assert_match!(PoolState::GettingCatchupTarget(_), p.state);
@@ -830,7 +1065,7 @@ mod tests {
         #[test]
         pub fn pool_wrapper_check_cache_works_for_no_pool_created() {
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))),
                             "pool_wrapper_check_cache_works_for_no_pool_created", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
@@ -839,7 +1074,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_terminated_close_works() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -850,7 +1085,7 @@ mod tests {
         #[test]
         pub fn pool_wrapper_terminated_refresh_works() {
             test::cleanup_pool("pool_wrapper_terminated_refresh_works");
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
 
@@ -869,7 +1104,7 @@ mod tests {
                 pool_name: "pool_wrapper_terminated_timeout_works".to_string(),
                 id: next_pool_handle(),
                 state: PoolState::Terminated(TerminatedState {
-                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
+                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))),
                 }),
                 timeout: 0,
                 extended_timeout: 0,
@@ -890,7 +1125,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_cloe_works_from_initialization() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::Close(cmd_id));
             assert_match!(PoolState::Closed(_), p.state);
@@ -904,7 +1139,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_close_works_from_getting_catchup_target");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -922,7 +1157,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_catchup_target_not_found_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetNotFound(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout")));
@@ -931,6 +1166,88 @@ mod tests {
             test::cleanup_storage("pool_wrapper_catchup_target_not_found_works");
         }
 
+        #[test]
+        pub fn pool_wrapper_catchup_target_not_found_works_for_background_refresh() {
+            test::cleanup_storage("pool_wrapper_catchup_target_not_found_works_for_background_refresh");
+
+            ProtocolVersion::set(2);
+            _write_genesis_txns("pool_wrapper_catchup_target_not_found_works_for_background_refresh");
+
+            let p: PoolSM<MockNetworker, MockRequestHandler> =
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_catchup_target_not_found_works_for_background_refresh", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
+            let p = p.handle_event(PoolEvent::Synced(merkle_tree_factory::create("pool_wrapper_catchup_target_not_found_works_for_background_refresh").unwrap()));
+            let nodes = p.nodes().unwrap().clone();
+
+            let p = p.handle_event(PoolEvent::Refresh(INVALID_COMMAND_HANDLE));
+            assert!(p.is_refreshing());
+            let p = p.handle_event(PoolEvent::CatchupTargetNotFound(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout")));
+            assert_match!(PoolState::Active(_), p.state);
+            assert_eq!(4, nodes.len());
+            assert!(nodes.keys().all(|alias| p.nodes().unwrap().contains_key(alias)));
+
+            test::cleanup_storage("pool_wrapper_catchup_target_not_found_works_for_background_refresh");
+        }
+
+        #[test]
+        pub fn pool_wrapper_catchup_target_not_found_works_for_refresh() {
+            test::cleanup_storage("pool_wrapper_catchup_target_not_found_works_for_refresh");
+
+            ProtocolVersion::set(2);
+            _write_genesis_txns("pool_wrapper_catchup_target_not_found_works_for_refresh");
+
+            let p: PoolSM<MockNetworker, MockRequestHandler> =
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_catchup_target_not_found_works_for_refresh", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
+            let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::Refresh(cmd_id));
+            let p = p.handle_event(PoolEvent::CatchupTargetNotFound(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout")));
+            assert_match!(PoolState::Terminated(_), p.state);
+
+            test::cleanup_storage("pool_wrapper_catchup_target_not_found_works_for_refresh");
+        }
+
+        #[test]
+        pub fn pool_wrapper_active_background_refresh_works_for_no_pool_created() {
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM {
+                pool_name: "pool_wrapper_active_background_refresh_works_for_no_pool_created".to_string(),
+                id: next_pool_handle(),
+                state: PoolState::Active(ActiveState {
+                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))),
+                    request_handlers: HashMap::new(),
+                    nodes: Nodes::new(),
+                }),
+                timeout: 0,
+                extended_timeout: 0,
+                number_read_nodes: NUMBER_READ_NODES,
+            };
+
+            let p = p.handle_event(PoolEvent::Refresh(INVALID_COMMAND_HANDLE));
+            assert_match!(PoolState::Active(_), p.state);
+        }
+
+        #[test]
+        pub fn pool_wrapper_terminated_send_request_works() {
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM {
+                pool_name: "pool_wrapper_terminated_send_request_works".to_string(),
+                id: next_pool_handle(),
+                state: PoolState::Terminated(TerminatedState {
+                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))),
+                }),
+                timeout: 0,
+                extended_timeout: 0,
+                number_read_nodes: NUMBER_READ_NODES,
+            };
+
+            let req = json!({"reqId": 1, "operation": {"type": "1"}}).to_string();
+            let p = p.handle_event(PoolEvent::SendRequest(next_command_handle(), req, None, None));
+            assert_match!(PoolState::Terminated(_), p.state);
+            assert!(p.is_idle());
+        }
+
         #[test]
         pub fn pool_wrapper_getting_catchup_target_synced_works() {
             test::cleanup_storage("pool_wrapper_getting_catchup_target_synced_works");
@@ -939,7 +1256,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_getting_catchup_target_synced_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false))), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -958,7 +1275,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
-                                       0, vec![]))),
+                                       0, vec![], false))),
                 "pool_wrapper_getting_catchup_target_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
@@ -985,7 +1302,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], false))),
                 "pool_wrapper_getting_catchup_target_catchup_target_found_works",
                 next_pool_handle(),
                 0,
@@ -1009,7 +1326,7 @@ mod tests {
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
                 PoolSM::new(Rc::new(RefCell::new(
-                    MockNetworker::new(0, 0, vec![]))),
+                    MockNetworker::new(0, 0, vec![], false))),
                             "pool_wrapper_getting_catchup_target_catchup_target_found_works_for_node_state_error",
                             next_pool_handle(),
                             0,
@@ -1037,7 +1354,7 @@ mod tests {
                     RefCell::new(
                         MockNetworker::new(0,
                                            0,
-                                           vec![]))),
+                                           vec![], false))),
                             "pool_wrapper_sync_catchup_close_works",
                             next_pool_handle(),
                             0,
@@ -1065,7 +1382,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], false))),
                 "pool_wrapper_sync_catchup_synced_works",
                 next_pool_handle(),
                 0,
@@ -1092,7 +1409,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], false))),
                 "pool_wrapper_sync_catchup_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
@@ -1124,7 +1441,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], false))),
                                                                            "pool_wrapper_active_send_request_works",
                                                                            next_pool_handle(),
                                                                            0,
@@ -1164,7 +1481,7 @@ mod tests {
                     MockNetworker::new(
                         0,
                         0,
-                        vec![]))),
+                        vec![], false))),
                             "pool_wrapper_active_send_request_works_for_no_req_id",
                             next_pool_handle(),
                             0,
@@ -1217,7 +1534,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], false))),
                 "pool_wrapper_active_node_reply_works",
                 next_pool_handle(),
                 0,
@@ -1257,7 +1574,7 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], false))),
                             "pool_wrapper_sends_requests_to_two_nodes",
                             next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
@@ -1307,7 +1624,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], false))),
                                                                            "pool_wrapper_active_node_reply_works_for_no_request",
                                                                            next_pool_handle(),
                                                                            0,
@@ -1350,7 +1667,7 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(
                     0,
                     0,
-                    vec![]))),
+                    vec![], false))),
                             "pool_wrapper_active_node_reply_works_for_invalid_reply",
                             next_pool_handle(),
                             0,
@@ -1384,6 +1701,8 @@ mod tests {
 
     mod other {
         use super::*;
+        use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+        use ursa::bls::{Generator, SignKey};
 
         #[test]
         fn get_f_works() {
@@ -1396,5 +1715,40 @@ mod tests {
             assert_eq!(_get_f(6), 1);
             assert_eq!(_get_f(7), 2);
         }
+
+        fn _blskey() -> VerKey {
+            let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+            VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn nodes_diff_works_for_same_nodes() {
+            let mut nodes = Nodes::new();
+            nodes.insert("n1".to_string(), Some(_blskey()));
+            nodes.insert("n2".to_string(), None);
+
+            assert!(_nodes_diff(&nodes, &nodes.clone()).is_none());
+        }
+
+        #[test]
+        fn nodes_diff_works() {
+            let blskey = _blskey();
+
+            let mut prev = Nodes::new();
+            prev.insert("n1".to_string(), Some(_blskey()));
+            prev.insert("n2".to_string(), None);
+
+            let mut nodes = Nodes::new();
+            nodes.insert("n1".to_string(), Some(blskey.clone()));
+            nodes.insert("n3".to_string(), None);
+
+            let expected = json!({
+                "added": {"n3": null},
+                "removed": ["n2"],
+                "updated": {"n1": blskey.as_bytes().to_base58()},
+            });
+
+            assert_eq!(expected, _nodes_diff(&prev, &nodes).unwrap());
+        }
     }
 }
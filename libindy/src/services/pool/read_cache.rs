@@ -0,0 +1,236 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json;
+use serde_json::Value as SJsonValue;
+
+use crate::domain::ledger::constants;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hash::hash as openssl_hash;
+use crate::utils::environment;
+
+const READ_CACHE_DIR: &str = "read_cache";
+
+/// Requests addressing data that is never changed on the ledger once it is written.
+const REQUESTS_FOR_IMMUTABLE_DATA: [&str; 4] = [
+    constants::GET_SCHEMA,
+    constants::GET_CRED_DEF,
+    constants::GET_REVOC_REG_DEF,
+    constants::GET_TXN,
+];
+
+/// Requests addressing data that can be changed, their replies are cached while they are fresh.
+const REQUESTS_FOR_MUTABLE_DATA: [&str; 2] = [
+    constants::GET_NYM,
+    constants::GET_ATTR,
+];
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheEntry {
+    reply: String,
+    stored: u64,
+    last_signed_time: Option<u64>,
+    immutable: bool,
+}
+
+pub fn is_cacheable(request: &SJsonValue) -> bool {
+    request["operation"]["type"].as_str()
+        .map(|type_| REQUESTS_FOR_IMMUTABLE_DATA.contains(&type_) || REQUESTS_FOR_MUTABLE_DATA.contains(&type_))
+        .unwrap_or(false)
+}
+
+/// Returns the cached reply on the request if it is fresh enough.
+///
+/// `min_fresh` is the max age of the reply in seconds, -1 means do not check age.
+/// If it isn't set, replies with immutable data are always fresh
+/// and other replies are fresh during `freshness_threshold` since they were signed by the pool.
+pub fn get(pool_name: &str, request: &SJsonValue, min_fresh: Option<i32>, freshness_threshold: u64) -> IndyResult<Option<String>> {
+    trace!("read_cache::get >>> pool_name: {:?}, request: {:?}, min_fresh: {:?}", pool_name, request, min_fresh);
+
+    if !is_cacheable(request) {
+        return Ok(None);
+    }
+
+    let path = _entry_path(pool_name, request)?;
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let entry = fs::read_to_string(&path)
+        .to_indy(IndyErrorKind::IOError, "Can't read pool read cache entry")?;
+
+    let entry: CacheEntry = match serde_json::from_str(&entry) {
+        Ok(entry) => entry,
+        Err(err) => {
+            warn!("Malformed pool read cache entry {:?} -- dropping it: {:?}", path, err);
+            fs::remove_file(&path)
+                .to_indy(IndyErrorKind::IOError, "Can't drop pool read cache entry")?;
+            return Ok(None);
+        }
+    };
+
+    let max_age = match min_fresh {
+        Some(-1) => None,
+        Some(min_fresh) => Some(::std::cmp::max(min_fresh, 0) as u64),
+        None if entry.immutable => None,
+        None => Some(freshness_threshold)
+    };
+
+    let fresh = max_age
+        .map(|max_age| _now().saturating_sub(entry.last_signed_time.unwrap_or(entry.stored)) <= max_age)
+        .unwrap_or(true);
+
+    trace!("read_cache::get <<< fresh: {:?}", fresh);
+
+    Ok(if fresh { Some(entry.reply) } else { None })
+}
+
+/// Stores the reply on the request. The reply must be verified by state proof before.
+pub fn put(pool_name: &str, request: &SJsonValue, reply: &str, last_signed_time: Option<u64>) -> IndyResult<()> {
+    trace!("read_cache::put >>> pool_name: {:?}, request: {:?}, reply: {:?}", pool_name, request, reply);
+
+    if !is_cacheable(request) {
+        return Ok(());
+    }
+
+    let reply_json: SJsonValue = serde_json::from_str(reply)
+        .to_indy(IndyErrorKind::InvalidStructure, "Reply is malformed json")?;
+
+    // absence of the data can change at any moment
+    let immutable = request["operation"]["type"].as_str()
+        .map(|type_| REQUESTS_FOR_IMMUTABLE_DATA.contains(&type_))
+        .unwrap_or(false)
+        && !reply_json["result"]["data"].is_null();
+
+    let entry = CacheEntry {
+        reply: reply.to_string(),
+        stored: _now(),
+        last_signed_time,
+        immutable,
+    };
+
+    let entry = serde_json::to_string(&entry)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize pool read cache entry")?;
+
+    let path = _entry_path(pool_name, request)?;
+
+    fs::create_dir_all(_cache_dir(pool_name))
+        .to_indy(IndyErrorKind::IOError, "Can't create pool read cache directory")?;
+
+    let mut tmp_path = path.clone();
+    tmp_path.set_extension("tmp");
+
+    fs::write(&tmp_path, entry)
+        .to_indy(IndyErrorKind::IOError, "Can't write pool read cache entry")?;
+
+    fs::rename(&tmp_path, &path)
+        .to_indy(IndyErrorKind::IOError, "Can't write pool read cache entry")?;
+
+    trace!("read_cache::put <<<");
+
+    Ok(())
+}
+
+fn _cache_dir(pool_name: &str) -> PathBuf {
+    let mut path = environment::pool_path(pool_name);
+    path.push(READ_CACHE_DIR);
+    path
+}
+
+fn _entry_path(pool_name: &str, request: &SJsonValue) -> IndyResult<PathBuf> {
+    let key = json!({
+        "operation": request["operation"],
+        "protocolVersion": request["protocolVersion"],
+    });
+
+    let key = openssl_hash(key.to_string().as_bytes())?;
+
+    let mut path = _cache_dir(pool_name);
+    path.push(hex::encode(key));
+    path.set_extension("json");
+    Ok(path)
+}
+
+fn _now() -> u64 {
+    time::get_time().sec as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::test;
+
+    const DEST: &str = "VsKV7grR1BUE29mG2Fm2kX";
+
+    fn _request(type_: &str) -> SJsonValue {
+        json!({"reqId": 1, "protocolVersion": 2, "operation": {"type": type_, "dest": DEST}})
+    }
+
+    fn _reply(data: SJsonValue) -> String {
+        json!({"op": "REPLY", "result": {"data": data}}).to_string()
+    }
+
+    #[test]
+    fn read_cache_put_get_works_for_immutable_data() {
+        let pool_name = "read_cache_put_get_works_for_immutable_data";
+        test::cleanup_storage(pool_name);
+
+        let request = _request(constants::GET_SCHEMA);
+        let reply = _reply(json!({"name": "gvt"}));
+
+        put(pool_name, &request, &reply, Some(0)).unwrap();
+
+        assert_eq!(Some(reply), get(pool_name, &request, None, 600).unwrap());
+        assert_eq!(None, get(pool_name, &request, Some(600), 600).unwrap());
+
+        test::cleanup_storage(pool_name);
+    }
+
+    #[test]
+    fn read_cache_get_works_for_stale_mutable_data() {
+        let pool_name = "read_cache_get_works_for_stale_mutable_data";
+        test::cleanup_storage(pool_name);
+
+        let request = _request(constants::GET_NYM);
+        let reply = _reply(json!({"dest": DEST}));
+
+        put(pool_name, &request, &reply, Some(_now() - 1000)).unwrap();
+
+        assert_eq!(None, get(pool_name, &request, None, 600).unwrap());
+        assert_eq!(Some(reply.clone()), get(pool_name, &request, None, 2000).unwrap());
+        assert_eq!(Some(reply), get(pool_name, &request, Some(-1), 600).unwrap());
+
+        test::cleanup_storage(pool_name);
+    }
+
+    #[test]
+    fn read_cache_get_works_for_absent_immutable_data() {
+        let pool_name = "read_cache_get_works_for_absent_immutable_data";
+        test::cleanup_storage(pool_name);
+
+        let request = _request(constants::GET_CRED_DEF);
+
+        put(pool_name, &request, &_reply(SJsonValue::Null), Some(0)).unwrap();
+
+        assert_eq!(None, get(pool_name, &request, None, 600).unwrap());
+
+        test::cleanup_storage(pool_name);
+    }
+
+    #[test]
+    fn read_cache_works_for_not_cacheable_request() {
+        let pool_name = "read_cache_works_for_not_cacheable_request";
+        test::cleanup_storage(pool_name);
+
+        let request = _request(constants::GET_REVOC_REG);
+
+        put(pool_name, &request, &_reply(json!({})), Some(_now())).unwrap();
+
+        assert!(!is_cacheable(&request));
+        assert_eq!(None, get(pool_name, &request, Some(-1), 600).unwrap());
+
+        test::cleanup_storage(pool_name);
+    }
+}
@@ -11,16 +11,13 @@ use serde_json;
 use serde_json::Value as SJsonValue;
 use self::super::THRESHOLD;
 
-use crate::commands::Command;
-use crate::commands::CommandExecutor;
-use crate::commands::ledger::LedgerCommand;
 use indy_api_types::errors::prelude::*;
 use crate::services::ledger::merkletree::merkletree::MerkleTree;
 use crate::services::pool::catchup::{build_catchup_req, CatchupProgress, check_cons_proofs, check_nodes_responses_on_status};
 use crate::services::pool::events::NetworkerEvent;
 use crate::services::pool::events::PoolEvent;
 use crate::services::pool::events::RequestEvent;
-use crate::services::pool::{get_last_signed_time, Nodes};
+use crate::services::pool::{async_pool, get_last_signed_time, Nodes};
 use crate::services::pool::merkle_tree_factory;
 use crate::services::pool::networker::Networker;
 use crate::services::pool::state_proof;
@@ -402,8 +399,14 @@ impl<T: Networker> RequestSM<T> {
                                 )
                             };
 
-                            if cnt > f
-                                || _check_state_proof(&result, f, &generator, &nodes, &raw_msg, state.sp_key.as_ref().map(Vec::as_slice), state.timestamps, last_write_time) {
+                            let proved = cnt <= f
+                                && _check_state_proof(&result, f, &generator, &nodes, &raw_msg, state.sp_key.as_ref().map(Vec::as_slice), state.timestamps, last_write_time);
+
+                            if cnt <= f && !proved && !result["state_proof"].is_null() {
+                                state.networker.borrow().stats().borrow_mut().state_proof_rejected(&node_alias);
+                            }
+
+                            if cnt > f || proved {
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
                                 _send_ok_replies(&cmd_ids, if cnt > f { &soonest } else { &raw_msg });
                                 (RequestState::finish(), None)
@@ -712,12 +715,7 @@ fn _finish_request(cmd_ids: &[CommandHandle]) {
 }
 
 fn _send_replies(cmd_ids: &[CommandHandle], msg: IndyResult<String>) {
-    cmd_ids.iter().for_each(|id| {
-        CommandExecutor::instance().send(
-            Command::Ledger(
-                LedgerCommand::SubmitAck(*id, msg.clone()))
-        ).unwrap();
-    });
+    cmd_ids.iter().for_each(|id| async_pool::complete(*id, msg.clone()));
 }
 
 fn _get_msg_result_without_state_proof(msg: &str) -> IndyResult<(SJsonValue, SJsonValue)> {
@@ -920,7 +918,7 @@ pub mod tests {
     }
 
     fn _request_handler(pool_name: &str, f: usize, nodes_cnt: usize) -> RequestHandlerImpl<MockNetworker> {
-        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![])));
+        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], false)));
 
         let mut default_nodes: Nodes = HashMap::new();
         default_nodes.insert(NODE.to_string(), None);
@@ -0,0 +1,1038 @@
+//! In-process `Networker` that emulates a pool of validator nodes.
+//!
+//! Every node known from the pool ledger answers client messages from a simulated ledger shared
+//! by all nodes. Replies are delivered through an inproc zmq socket, so `PoolSM` and `RequestSM`
+//! process them exactly as replies of real nodes: catchup, consensus and state proof checks work
+//! without a running pool.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use failure::Context;
+use rust_base58::{FromBase58, ToBase58};
+use serde_json;
+use serde_json::Value as SJsonValue;
+use time::Tm;
+use ursa::bls::{Bls, Generator, MultiSignature, SignKey, VerKey};
+
+use crate::domain::ledger::constants;
+use crate::domain::pool::{PoolSimulationConfig, SimulatedNodeBehavior};
+use indy_api_types::errors::prelude::*;
+use crate::services::pool::events::*;
+use crate::services::pool::merkle_tree_factory;
+use crate::services::pool::networker::Networker;
+use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+use crate::services::pool::state_proof;
+use crate::services::pool::stats::PoolStats;
+use crate::services::pool::types::*;
+use indy_utils::crypto::hash::{EMPTY_HASH_BYTES, Hash};
+use indy_utils::crypto::hash::hash as openssl_hash;
+use indy_utils::sequence;
+
+use super::time::Duration;
+use super::zmq;
+use super::zmq::PollItem;
+
+const READ_REQUESTS: [&str; 12] = [
+    constants::GET_TXN,
+    constants::GET_ATTR,
+    constants::GET_NYM,
+    constants::GET_SCHEMA,
+    constants::GET_CRED_DEF,
+    constants::GET_REVOC_REG_DEF,
+    constants::GET_REVOC_REG,
+    constants::GET_REVOC_REG_DELTA,
+    constants::GET_VALIDATOR_INFO,
+    constants::GET_AUTH_RULE,
+    constants::GET_TXN_AUTHR_AGRMT,
+    constants::GET_TXN_AUTHR_AGRMT_AML,
+];
+
+const POOL_LEDGER_ID: u64 = 0;
+const DOMAIN_LEDGER_ID: u64 = 1;
+
+pub struct SimulatedNetworker {
+    ledger: SimulatedLedger,
+    behaviors: HashMap<String, SimulatedNodeBehavior>,
+    nodes: Vec<RemoteNode>,
+    preordered_nodes: Vec<String>,
+    adaptive_ordering: bool,
+    resend: HashMap<String, (usize, String)>,
+    timeouts: HashMap<(String, String), Tm>,
+    stats: RefCell<PoolStats>,
+    ctx: zmq::Context,
+    endpoint: String,
+    replies: zmq::Socket,
+    sender: zmq::Socket,
+}
+
+impl SimulatedNetworker {
+    pub fn new_for_pool(pool_name: &str, config: PoolSimulationConfig, preordered_nodes: Vec<String>, adaptive_ordering: bool) -> Self {
+        let mut pool_txns: Vec<Vec<u8>> = match merkle_tree_factory::create(pool_name) {
+            Ok(merkle_tree) => merkle_tree.into_iter().collect(),
+            Err(err) => {
+                error!("SimulatedNetworker: can't restore pool ledger for {}: {:?}", pool_name, err);
+                Vec::new()
+            }
+        };
+
+        for txn in config.pool_txns.iter() {
+            match serde_json::from_str::<SJsonValue>(txn)
+                .to_indy(IndyErrorKind::InvalidStructure, "Simulated pool txn is malformed json")
+                .and_then(|txn| rmp_serde::to_vec_named(&txn)
+                    .to_indy(IndyErrorKind::InvalidState, "Can't encode simulated pool txn as message pack")) {
+                Ok(txn) => pool_txns.push(txn),
+                Err(err) => error!("SimulatedNetworker: skip pool txn {}: {:?}", txn, err),
+            }
+        }
+
+        SimulatedNetworker::_new(SimulatedLedger::new(pool_txns), config.nodes, preordered_nodes, adaptive_ordering)
+    }
+
+    fn _new(ledger: SimulatedLedger, behaviors: HashMap<String, SimulatedNodeBehavior>, preordered_nodes: Vec<String>, adaptive_ordering: bool) -> Self {
+        let ctx = zmq::Context::new();
+        let endpoint = format!("inproc://simulated_pool_{}", sequence::get_next_id());
+
+        let replies = ctx.socket(zmq::SocketType::PULL).expect("FIXME");
+        replies.bind(&endpoint).expect("FIXME");
+
+        let sender = ctx.socket(zmq::SocketType::PUSH).expect("FIXME");
+        sender.connect(&endpoint).expect("FIXME");
+
+        SimulatedNetworker {
+            ledger,
+            behaviors,
+            nodes: Vec::new(),
+            preordered_nodes,
+            adaptive_ordering,
+            resend: HashMap::new(),
+            timeouts: HashMap::new(),
+            stats: RefCell::new(PoolStats::new()),
+            ctx,
+            endpoint,
+            replies,
+            sender,
+        }
+    }
+
+    fn _send_to_node(&mut self, idx: usize, req_id: &str, msg: &str, timeout: i64) {
+        let node = match self.nodes.get(idx) {
+            Some(node) => node.name.clone(),
+            None => {
+                warn!("SimulatedNetworker: no node with index {} to send request {}", idx, req_id);
+                return;
+            }
+        };
+
+        self.timeouts.insert((req_id.to_string(), node.clone()), time::now() + Duration::seconds(timeout));
+        self.stats.borrow_mut().request_sent(req_id, &node);
+
+        let behavior = self.behaviors.get(&node).cloned().unwrap_or(SimulatedNodeBehavior::Honest);
+
+        if let SimulatedNodeBehavior::Faulty = behavior {
+            trace!("SimulatedNetworker: faulty node {} ignores request {}", node, req_id);
+            return;
+        }
+
+        let signers = self._signers();
+
+        let replies = match self.ledger.process(msg, &node, &behavior, &signers) {
+            Ok(replies) => replies,
+            Err(err) => {
+                warn!("SimulatedNetworker: node {} can't process request {}: {:?}", node, req_id, err);
+                return;
+            }
+        };
+
+        self._deliver(node, replies, &behavior);
+    }
+
+    fn _signers(&self) -> Vec<String> {
+        self.nodes.iter()
+            .filter(|node| self.behaviors.get(&node.name) != Some(&SimulatedNodeBehavior::Faulty))
+            .map(|node| node.name.clone())
+            .collect()
+    }
+
+    fn _deliver(&self, node: String, replies: Vec<String>, behavior: &SimulatedNodeBehavior) {
+        match *behavior {
+            SimulatedNodeBehavior::Slow { delay } => {
+                let ctx = self.ctx.clone();
+                let endpoint = self.endpoint.clone();
+                thread::spawn(move || {
+                    thread::sleep(StdDuration::from_millis(delay));
+                    match _connect(&ctx, &endpoint) {
+                        Ok(socket) => replies.iter().for_each(|reply| _send(&socket, &node, reply)),
+                        Err(err) => warn!("SimulatedNetworker: slow node {} can't connect: {:?}", node, err),
+                    }
+                });
+            }
+            _ => replies.iter().for_each(|reply| _send(&self.sender, &node, reply))
+        }
+    }
+}
+
+impl Networker for SimulatedNetworker {
+    fn new(_active_timeout: i64, _conn_limit: usize, preordered_nodes: Vec<String>, adaptive_ordering: bool) -> Self {
+        SimulatedNetworker::_new(SimulatedLedger::new(Vec::new()), HashMap::new(), preordered_nodes, adaptive_ordering)
+    }
+
+    fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent> {
+        let mut events = Vec::new();
+
+        if !poll_items.first().map(PollItem::is_readable).unwrap_or(false) {
+            return events;
+        }
+
+        while let Ok(parts) = self.replies.recv_multipart(zmq::DONTWAIT) {
+            match (parts.get(0).map(|alias| String::from_utf8(alias.clone())),
+                   parts.get(1).map(|msg| String::from_utf8(msg.clone()))) {
+                (Some(Ok(alias)), Some(Ok(msg))) => events.push(PoolEvent::NodeReply(msg, alias)),
+                _ => warn!("SimulatedNetworker: malformed reply {:?}", parts),
+            }
+        }
+
+        events
+    }
+
+    fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent> {
+        match pe {
+            Some(NetworkerEvent::SendOneRequest(msg, req_id, timeout)) => {
+                if self.adaptive_ordering {
+                    self.stats.borrow().rank_nodes(&mut self.nodes);
+                }
+                self.resend.insert(req_id.clone(), (0, msg.clone()));
+                self._send_to_node(0, &req_id, &msg, timeout);
+            }
+            Some(NetworkerEvent::SendAllRequest(msg, req_id, timeout, nodes_to_send)) => {
+                let idxs: Vec<usize> = self.nodes.iter().enumerate()
+                    .filter(|&(_, node)| nodes_to_send.as_ref().map(|nodes| nodes.contains(&node.name)).unwrap_or(true))
+                    .map(|(idx, _)| idx)
+                    .collect();
+
+                for idx in idxs {
+                    self._send_to_node(idx, &req_id, &msg, timeout);
+                }
+            }
+            Some(NetworkerEvent::Resend(req_id, timeout)) => {
+                let nodes_cnt = self.nodes.len();
+                let next = self.resend.get_mut(&req_id).and_then(|&mut (ref mut cnt, ref msg)| {
+                    *cnt += 1;
+                    if nodes_cnt == 0 { None } else { Some((*cnt % nodes_cnt, msg.clone())) }
+                });
+
+                if let Some((idx, msg)) = next {
+                    self._send_to_node(idx, &req_id, &msg, timeout);
+                }
+            }
+            Some(NetworkerEvent::NodesStateUpdated(mut nodes)) => {
+                trace!("SimulatedNetworker::process_event: nodes_updated {:?}", nodes);
+                let preordered_nodes = &self.preordered_nodes;
+                nodes.sort_by_key(|node| preordered_nodes.iter().position(|name| name == &node.name).unwrap_or(usize::max_value()));
+                self.nodes = nodes;
+            }
+            Some(NetworkerEvent::ExtendTimeout(req_id, node_alias, timeout)) => {
+                if let Some(to) = self.timeouts.get_mut(&(req_id, node_alias)) {
+                    *to = time::now() + Duration::seconds(timeout);
+                }
+            }
+            Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))) => {
+                self.timeouts.remove(&(req_id, node_alias));
+            }
+            Some(NetworkerEvent::CleanTimeout(req_id, None)) => {
+                self.timeouts.retain(|&(ref id, _), _| id != &req_id);
+                self.resend.remove(&req_id);
+                self.stats.borrow_mut().request_finished(&req_id);
+            }
+            Some(NetworkerEvent::Timeout) | None => {}
+        }
+        None
+    }
+
+    fn get_timeout(&self) -> ((String, String), i64) {
+        self.timeouts.iter()
+            .map(|(key, value)| (key, (*value - time::now()).num_milliseconds()))
+            .min_by(|&(_, val1), &(_, val2)| val1.cmp(&val2))
+            .map(|(&(ref req_id, ref node_alias), timeout)| ((req_id.to_string(), node_alias.to_string()), timeout))
+            .unwrap_or((("".to_string(), "".to_string()), ::std::i64::MAX))
+    }
+
+    fn get_poll_items(&self) -> Vec<PollItem> {
+        vec![self.replies.as_poll_item(zmq::POLLIN)]
+    }
+
+    fn stats(&self) -> &RefCell<PoolStats> {
+        &self.stats
+    }
+}
+
+struct SimulatedLedger {
+    pool_txns: Vec<Vec<u8>>,
+    domain_txns: Vec<SJsonValue>,
+    // (identifier, req_id) -> (ledger_id, seq_no) of the written txn
+    written: HashMap<(String, u64), (u64, usize)>,
+}
+
+impl SimulatedLedger {
+    fn new(pool_txns: Vec<Vec<u8>>) -> Self {
+        SimulatedLedger {
+            pool_txns,
+            domain_txns: Vec::new(),
+            written: HashMap::new(),
+        }
+    }
+
+    fn process(&mut self, msg: &str, node: &str, behavior: &SimulatedNodeBehavior, signers: &[String]) -> IndyResult<Vec<String>> {
+        match Message::from_raw_str(msg) {
+            Ok(Message::LedgerStatus(ls)) => self._ledger_status(ls),
+            Ok(Message::CatchupReq(req)) => self._catchup(req),
+            Ok(Message::Ping) => Ok(vec!["po".to_string()]),
+            Ok(_) => Ok(Vec::new()),
+            Err(_) => self._request(msg, node, behavior, signers),
+        }
+    }
+
+    fn _ledger_status(&self, ls: LedgerStatus) -> IndyResult<Vec<String>> {
+        if ls.ledgerId as u64 != POOL_LEDGER_ID {
+            return Ok(Vec::new());
+        }
+
+        let leaves = _leaf_hashes(&self.pool_txns)?;
+        let root = _subtree_hash(&leaves)?.to_base58();
+
+        let reply = if ls.txnSeqNo > 0 && ls.txnSeqNo < leaves.len() {
+            Message::ConsistencyProof(ConsistencyProof {
+                seqNoEnd: leaves.len(),
+                seqNoStart: ls.txnSeqNo,
+                ledgerId: POOL_LEDGER_ID as usize,
+                hashes: _consistency_proof(&leaves, ls.txnSeqNo)?.iter().map(|hash| hash.to_base58()).collect(),
+                oldMerkleRoot: _subtree_hash(&leaves[..ls.txnSeqNo])?.to_base58(),
+                newMerkleRoot: root,
+            })
+        } else {
+            Message::LedgerStatus(LedgerStatus {
+                txnSeqNo: leaves.len(),
+                merkleRoot: root,
+                ledgerId: POOL_LEDGER_ID as u8,
+                ppSeqNo: None,
+                viewNo: None,
+                protocolVersion: ls.protocolVersion,
+            })
+        };
+
+        Ok(vec![_to_string(&reply)?])
+    }
+
+    fn _catchup(&self, req: CatchupReq) -> IndyResult<Vec<String>> {
+        if req.ledgerId as u64 != POOL_LEDGER_ID || req.seqNoStart == 0 || req.seqNoStart > req.seqNoEnd
+            || req.seqNoEnd > req.catchupTill || req.catchupTill > self.pool_txns.len() {
+            return Ok(Vec::new());
+        }
+
+        let mut txns = HashMap::new();
+
+        for seq_no in req.seqNoStart..=req.seqNoEnd {
+            let txn: SJsonValue = rmp_serde::from_slice(&self.pool_txns[seq_no - 1])
+                .to_indy(IndyErrorKind::InvalidState, "Simulated pool ledger contains invalid txn")?;
+            txns.insert(seq_no.to_string(), txn);
+        }
+
+        let leaves = _leaf_hashes(&self.pool_txns[..req.catchupTill])?;
+
+        let reply = Message::CatchupRep(CatchupRep {
+            ledgerId: POOL_LEDGER_ID as usize,
+            consProof: _consistency_proof(&leaves, req.seqNoEnd)?.iter().map(|hash| hash.to_base58()).collect(),
+            txns,
+        });
+
+        Ok(vec![_to_string(&reply)?])
+    }
+
+    fn _request(&mut self, msg: &str, node: &str, behavior: &SimulatedNodeBehavior, signers: &[String]) -> IndyResult<Vec<String>> {
+        let request: SJsonValue = serde_json::from_str(msg)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is malformed json")?;
+
+        let req_id = match request["reqId"].as_u64() {
+            Some(req_id) => req_id,
+            None => return Ok(Vec::new())
+        };
+        let identifier = request["identifier"].as_str().unwrap_or_default().to_string();
+        let txn_type = request["operation"]["type"].as_str().unwrap_or_default().to_string();
+
+        let mut replies = Vec::new();
+
+        let mut result = if READ_REQUESTS.contains(&txn_type.as_str()) {
+            self._read(&request, &txn_type, node, signers)?
+        } else if txn_type.is_empty() {
+            let nack = json!({"op": "REQNACK", "reqId": req_id, "identifier": identifier, "reason": "Request is not supported by simulated pool"});
+            return Ok(vec![nack.to_string()]);
+        } else {
+            replies.push(json!({"op": "REQACK", "reqId": req_id, "identifier": identifier}).to_string());
+            self._write(&request, &txn_type, req_id, &identifier)?
+        };
+
+        if let SimulatedNodeBehavior::Malicious = *behavior {
+            _tamper(&mut result, node);
+        }
+
+        replies.push(json!({"op": "REPLY", "result": result}).to_string());
+        Ok(replies)
+    }
+
+    fn _write(&mut self, request: &SJsonValue, txn_type: &str, req_id: u64, identifier: &str) -> IndyResult<SJsonValue> {
+        let key = (identifier.to_string(), req_id);
+
+        let (ledger_id, seq_no) = match self.written.get(&key) {
+            Some(written) => *written,
+            None => {
+                let ledger_id = if txn_type == constants::NODE { POOL_LEDGER_ID } else { DOMAIN_LEDGER_ID };
+                let seq_no = if ledger_id == POOL_LEDGER_ID { self.pool_txns.len() + 1 } else { self.domain_txns.len() + 1 };
+
+                let mut data = request["operation"].clone();
+                data.as_object_mut().map(|data| data.remove("type"));
+
+                let mut txn = json!({});
+                txn["txn"] = json!({
+                    "type": txn_type,
+                    "data": data,
+                    "metadata": {"reqId": req_id, "from": identifier},
+                    "protocolVersion": request["protocolVersion"],
+                });
+                txn["txnMetadata"] = json!({"seqNo": seq_no, "txnTime": _now()});
+                txn["ver"] = json!("1");
+                txn["reqSignature"] = json!({});
+
+                if ledger_id == POOL_LEDGER_ID {
+                    // NODE txns extend the pool ledger, so the next catchup picks up the validator set change
+                    let txn = rmp_serde::to_vec_named(&txn)
+                        .to_indy(IndyErrorKind::InvalidState, "Can't encode simulated pool txn as message pack")?;
+                    self.pool_txns.push(txn);
+                } else {
+                    self.domain_txns.push(txn);
+                }
+
+                self.written.insert(key, (ledger_id, seq_no));
+                (ledger_id, seq_no)
+            }
+        };
+
+        match ledger_id {
+            POOL_LEDGER_ID => rmp_serde::from_slice(&self.pool_txns[seq_no - 1])
+                .to_indy(IndyErrorKind::InvalidState, "Simulated pool ledger contains invalid txn"),
+            _ => Ok(self.domain_txns[seq_no - 1].clone())
+        }
+    }
+
+    fn _read(&self, request: &SJsonValue, txn_type: &str, node: &str, signers: &[String]) -> IndyResult<SJsonValue> {
+        let mut result = json!({
+            "type": txn_type,
+            "identifier": request["identifier"],
+            "reqId": request["reqId"],
+            "data": SJsonValue::Null,
+        });
+
+        match txn_type {
+            constants::GET_TXN => self._get_txn(request, &mut result, signers)?,
+            constants::GET_NYM => {
+                let dest = request["operation"]["dest"].as_str().unwrap_or_default();
+                result["dest"] = json!(dest);
+
+                let nym = self.domain_txns.iter().rev()
+                    .find(|txn| txn["txn"]["type"] == constants::NYM && txn["txn"]["data"]["dest"] == dest);
+
+                if let Some(txn) = nym {
+                    result["seqNo"] = txn["txnMetadata"]["seqNo"].clone();
+                    result["txnTime"] = txn["txnMetadata"]["txnTime"].clone();
+                    result["data"] = json!(json!({
+                        "dest": dest,
+                        "identifier": txn["txn"]["metadata"]["from"],
+                        "role": txn["txn"]["data"]["role"],
+                        "seqNo": txn["txnMetadata"]["seqNo"],
+                        "txnTime": txn["txnMetadata"]["txnTime"],
+                        "verkey": txn["txn"]["data"]["verkey"],
+                    }).to_string());
+                }
+            }
+            constants::GET_SCHEMA => {
+                let dest = request["operation"]["dest"].as_str().unwrap_or_default();
+                let (name, version) = (&request["operation"]["data"]["name"], &request["operation"]["data"]["version"]);
+                result["dest"] = json!(dest);
+
+                let schema = self.domain_txns.iter().rev()
+                    .find(|txn| txn["txn"]["type"] == constants::SCHEMA && txn["txn"]["metadata"]["from"] == dest
+                        && txn["txn"]["data"]["data"]["name"] == *name && txn["txn"]["data"]["data"]["version"] == *version);
+
+                if let Some(txn) = schema {
+                    result["seqNo"] = txn["txnMetadata"]["seqNo"].clone();
+                    result["txnTime"] = txn["txnMetadata"]["txnTime"].clone();
+                    result["data"] = txn["txn"]["data"]["data"].clone();
+                }
+            }
+            constants::GET_CRED_DEF => {
+                let operation = &request["operation"];
+                let origin = operation["origin"].as_str().unwrap_or_default();
+                result["origin"] = json!(origin);
+                result["ref"] = operation["ref"].clone();
+                result["signature_type"] = operation["signature_type"].clone();
+                result["tag"] = operation["tag"].clone();
+
+                let cred_def = self.domain_txns.iter().rev()
+                    .find(|txn| txn["txn"]["type"] == constants::CRED_DEF && txn["txn"]["metadata"]["from"] == origin
+                        && txn["txn"]["data"]["ref"] == operation["ref"] && txn["txn"]["data"]["signature_type"] == operation["signature_type"]
+                        && txn["txn"]["data"]["tag"] == operation["tag"]);
+
+                if let Some(txn) = cred_def {
+                    result["seqNo"] = txn["txnMetadata"]["seqNo"].clone();
+                    result["txnTime"] = txn["txnMetadata"]["txnTime"].clone();
+                    result["data"] = txn["txn"]["data"]["data"].clone();
+                }
+            }
+            constants::GET_VALIDATOR_INFO => {
+                result["data"] = json!({"alias": node, "ledgerSize": self.domain_txns.len()});
+            }
+            _ => {}
+        }
+
+        if [constants::GET_NYM, constants::GET_SCHEMA, constants::GET_CRED_DEF].contains(&txn_type) {
+            self._state_proof(request, &mut result, signers)?;
+        }
+
+        Ok(result)
+    }
+
+    // The simulated ledger keeps no state trie, so the proof is a trie holding the only value of the reply
+    fn _state_proof(&self, request: &SJsonValue, result: &mut SJsonValue, signers: &[String]) -> IndyResult<()> {
+        if let Some((root_hash, proof_nodes)) = state_proof::build_single_value_sp(request, result) {
+            let txn_root_hash = _subtree_hash(&_leaf_hashes(&self._domain_txns()?)?)?.to_base58();
+
+            result["state_proof"] = json!({
+                "root_hash": root_hash,
+                "proof_nodes": proof_nodes,
+                "multi_signature": self._multi_signature(DOMAIN_LEDGER_ID, &root_hash, &txn_root_hash, signers)?,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn _domain_txns(&self) -> IndyResult<Vec<Vec<u8>>> {
+        self.domain_txns.iter()
+            .map(|txn| rmp_serde::to_vec(txn)
+                .to_indy(IndyErrorKind::InvalidState, "Can't encode simulated txn as message pack"))
+            .collect()
+    }
+
+    fn _get_txn(&self, request: &SJsonValue, result: &mut SJsonValue, signers: &[String]) -> IndyResult<()> {
+        let ledger_id = request["operation"]["ledgerId"].as_u64().unwrap_or(DOMAIN_LEDGER_ID);
+        let seq_no = request["operation"]["data"].as_u64().unwrap_or(0) as usize;
+
+        let txns: Vec<Vec<u8>> = match ledger_id {
+            POOL_LEDGER_ID => self.pool_txns.clone(),
+            DOMAIN_LEDGER_ID => self._domain_txns()?,
+            _ => return Ok(())
+        };
+
+        if seq_no == 0 || seq_no > txns.len() {
+            return Ok(());
+        }
+
+        let leaves = _leaf_hashes(&txns)?;
+        let root_hash = _subtree_hash(&leaves)?.to_base58();
+
+        let mut data: SJsonValue = rmp_serde::from_slice(&txns[seq_no - 1])
+            .to_indy(IndyErrorKind::InvalidState, "Simulated ledger contains invalid txn")?;
+        data["auditPath"] = json!(_audit_path(&leaves, seq_no - 1)?.iter().map(|hash| hash.to_base58()).collect::<Vec<String>>());
+        data["ledgerSize"] = json!(txns.len());
+        data["rootHash"] = json!(root_hash);
+
+        result["seqNo"] = json!(seq_no);
+        result["data"] = data;
+        let state_root_hash = openssl_hash(root_hash.as_bytes())?.to_base58();
+        result["state_proof"] = json!({"multi_signature": self._multi_signature(ledger_id, &state_root_hash, &root_hash, signers)?});
+
+        Ok(())
+    }
+
+    fn _multi_signature(&self, ledger_id: u64, state_root_hash: &str, txn_root_hash: &str, signers: &[String]) -> IndyResult<SJsonValue> {
+        let value = json!({
+            "ledger_id": ledger_id,
+            "pool_state_root_hash": _subtree_hash(&_leaf_hashes(&self.pool_txns)?)?.to_base58(),
+            "state_root_hash": state_root_hash,
+            "timestamp": _now(),
+            "txn_root_hash": txn_root_hash,
+        });
+
+        let message = rmp_serde::to_vec_named(&value)
+            .to_indy(IndyErrorKind::InvalidState, "Can't encode multi signature value as message pack")?;
+
+        let mut signatures = Vec::with_capacity(signers.len());
+        for alias in signers {
+            let (sign_key, _) = _node_bls_keys(alias)?;
+            signatures.push(Bls::sign(&message, &sign_key)?);
+        }
+
+        let signature = MultiSignature::new(&signatures.iter().collect::<Vec<_>>())?;
+
+        Ok(json!({
+            "signature": signature.as_bytes().to_base58(),
+            "participants": signers,
+            "value": value,
+        }))
+    }
+}
+
+fn _node_bls_keys(alias: &str) -> IndyResult<(SignKey, VerKey)> {
+    let sign_key = SignKey::new(Some(_node_seed(alias)?.as_slice()))?;
+    let ver_key = VerKey::new(&_generator()?, &sign_key)?;
+    Ok((sign_key, ver_key))
+}
+
+fn _node_seed(alias: &str) -> IndyResult<Vec<u8>> {
+    openssl_hash(format!("simulated node {}", alias).as_bytes())
+}
+
+fn _generator() -> IndyResult<Generator> {
+    let generator = DEFAULT_GENERATOR
+        .from_base58()
+        .map_err(Context::new)
+        .to_indy(IndyErrorKind::InvalidState, "Invalid BLS generator")?;
+
+    Ok(Generator::from_bytes(&generator)?)
+}
+
+fn _tamper(result: &mut SJsonValue, node: &str) {
+    let tampered = json!({"tamperedBy": node});
+
+    if result["txnMetadata"].is_object() {
+        result["txnMetadata"]["txnTime"] = json!(0);
+    } else if result["data"]["txn"].is_object() {
+        result["data"]["txn"]["data"] = tampered;
+    } else {
+        result["data"] = json!(tampered.to_string());
+    }
+}
+
+fn _connect(ctx: &zmq::Context, endpoint: &str) -> Result<zmq::Socket, zmq::Error> {
+    let socket = ctx.socket(zmq::SocketType::PUSH)?;
+    socket.set_linger(0)?;
+    socket.connect(endpoint)?;
+    Ok(socket)
+}
+
+fn _send(socket: &zmq::Socket, node: &str, msg: &str) {
+    if let Err(err) = socket.send_multipart(&[node.as_bytes(), msg.as_bytes()], zmq::DONTWAIT) {
+        warn!("SimulatedNetworker: can't deliver reply of node {}: {:?}", node, err);
+    }
+}
+
+fn _to_string(msg: &Message) -> IndyResult<String> {
+    serde_json::to_string(msg)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize simulated node reply")
+}
+
+fn _now() -> u64 {
+    time::get_time().sec as u64
+}
+
+fn _leaf_hashes(txns: &[Vec<u8>]) -> IndyResult<Vec<Vec<u8>>> {
+    txns.iter().map(|txn| Hash::hash_leaf(txn)).collect()
+}
+
+fn _split(size: usize) -> usize {
+    size.next_power_of_two() / 2
+}
+
+fn _subtree_hash(leaves: &[Vec<u8>]) -> IndyResult<Vec<u8>> {
+    match leaves.len() {
+        0 => Ok(EMPTY_HASH_BYTES.to_vec()),
+        1 => Ok(leaves[0].clone()),
+        size => {
+            let k = _split(size);
+            Hash::hash_nodes(&_subtree_hash(&leaves[..k])?, &_subtree_hash(&leaves[k..])?)
+        }
+    }
+}
+
+// RFC 6962, 2.1.1
+fn _audit_path(leaves: &[Vec<u8>], idx: usize) -> IndyResult<Vec<Vec<u8>>> {
+    if leaves.len() <= 1 {
+        return Ok(Vec::new());
+    }
+
+    let k = _split(leaves.len());
+
+    let (mut path, sibling) = if idx < k {
+        (_audit_path(&leaves[..k], idx)?, _subtree_hash(&leaves[k..])?)
+    } else {
+        (_audit_path(&leaves[k..], idx - k)?, _subtree_hash(&leaves[..k])?)
+    };
+
+    path.push(sibling);
+    Ok(path)
+}
+
+// RFC 6962, 2.1.2
+fn _consistency_proof(leaves: &[Vec<u8>], old_size: usize) -> IndyResult<Vec<Vec<u8>>> {
+    _subproof(old_size, leaves, true)
+}
+
+fn _subproof(old_size: usize, leaves: &[Vec<u8>], complete: bool) -> IndyResult<Vec<Vec<u8>>> {
+    if old_size == leaves.len() {
+        return Ok(if complete { Vec::new() } else { vec![_subtree_hash(leaves)?] });
+    }
+
+    let k = _split(leaves.len());
+
+    let (mut proof, sibling) = if old_size <= k {
+        (_subproof(old_size, &leaves[..k], complete)?, _subtree_hash(&leaves[k..])?)
+    } else {
+        (_subproof(old_size - k, &leaves[k..], false)?, _subtree_hash(&leaves[..k])?)
+    };
+
+    proof.push(sibling);
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ursa::bls::ProofOfPossession;
+
+    use crate::utils::crypto::ed25519_sign;
+
+    use crate::services::ledger::merkletree::merkletree::MerkleTree;
+    use crate::services::pool::catchup::check_cons_proofs;
+    use crate::services::pool::Nodes;
+
+    fn _genesis_txns(node_count: usize) -> IndyResult<Vec<String>> {
+        (1..=node_count).map(|idx| {
+            let alias = format!("Node{}", idx);
+
+            let seed = ed25519_sign::Seed::from_slice(&_node_seed(&alias)?)?;
+            let (verkey, _) = ed25519_sign::create_key_pair_for_signature(Some(&seed))?;
+
+            let (sign_key, bls_verkey) = _node_bls_keys(&alias)?;
+            let pop = ProofOfPossession::new(&bls_verkey, &sign_key)?;
+
+            Ok(json!({
+                "reqSignature": {},
+                "txn": {
+                    "data": {
+                        "data": {
+                            "alias": alias,
+                            "blskey": bls_verkey.as_bytes().to_base58(),
+                            "blskey_pop": pop.as_bytes().to_base58(),
+                            "client_ip": "127.0.0.1",
+                            "client_port": 9700 + 2 * idx,
+                            "node_ip": "127.0.0.1",
+                            "node_port": 9699 + 2 * idx,
+                            "services": ["VALIDATOR"],
+                        },
+                        "dest": verkey[..].to_base58(),
+                    },
+                    "metadata": {"from": verkey[0..16].to_base58()},
+                    "type": constants::NODE,
+                },
+                "txnMetadata": {"seqNo": idx},
+                "ver": "1",
+            }).to_string())
+        }).collect()
+    }
+
+    fn _txns(count: usize) -> Vec<Vec<u8>> {
+        (0..count).map(|idx| format!("txn {}", idx).into_bytes()).collect()
+    }
+
+    fn _networker(nodes: &[(&str, SimulatedNodeBehavior)]) -> SimulatedNetworker {
+        let txns = _genesis_txns(nodes.len()).unwrap().iter()
+            .map(|txn| rmp_serde::to_vec_named(&serde_json::from_str::<SJsonValue>(txn).unwrap()).unwrap())
+            .collect();
+
+        let behaviors = nodes.iter().map(|&(name, ref behavior)| (name.to_string(), behavior.clone())).collect();
+
+        let mut networker = SimulatedNetworker::_new(SimulatedLedger::new(txns), behaviors, vec![], false);
+
+        let remotes = nodes.iter().map(|&(name, _)| RemoteNode {
+            name: name.to_string(),
+            public_key: vec![],
+            zaddr: String::new(),
+            is_blacklisted: false,
+        }).collect();
+
+        networker.process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
+        networker
+    }
+
+    fn _turns(mut size: usize, mut idx: usize) -> Vec<bool> {
+        let mut turns = Vec::new();
+        while size != 1 {
+            let k = _split(size);
+            let right = idx < k;
+            turns.push(right);
+            if right { size = k } else { idx -= k; size -= k }
+        }
+        turns.reverse();
+        turns
+    }
+
+    fn _fetch(networker: &SimulatedNetworker) -> Vec<PoolEvent> {
+        let mut poll_items = networker.get_poll_items();
+        zmq::poll(&mut poll_items, 1_000).unwrap();
+        networker.fetch_events(&poll_items)
+    }
+
+    #[test]
+    fn subtree_hash_works_as_merkle_tree() {
+        for size in 1..20 {
+            let txns = _txns(size);
+            let merkle_tree = MerkleTree::from_vec(txns.clone()).unwrap();
+            assert_eq!(merkle_tree.root_hash(), &_subtree_hash(&_leaf_hashes(&txns).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn consistency_proof_works() {
+        for new_size in 1..20 {
+            let txns = _txns(new_size);
+            let leaves = _leaf_hashes(&txns).unwrap();
+            let new_root = _subtree_hash(&leaves).unwrap();
+
+            for old_size in 1..=new_size {
+                let merkle_tree = MerkleTree::from_vec(txns[..old_size].to_vec()).unwrap();
+                let proof = _consistency_proof(&leaves, old_size).unwrap().iter().map(|hash| hash.to_base58()).collect();
+                check_cons_proofs(&merkle_tree, &proof, &new_root, new_size).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn audit_path_works() {
+        for size in 1..20 {
+            let leaves = _leaf_hashes(&_txns(size)).unwrap();
+            let root = _subtree_hash(&leaves).unwrap();
+
+            for idx in 0..size {
+                let mut hash = leaves[idx].clone();
+
+                for (sibling, right) in _audit_path(&leaves, idx).unwrap().iter().zip(_turns(size, idx)) {
+                    hash = if right { Hash::hash_nodes(&hash, sibling) } else { Hash::hash_nodes(sibling, &hash) }.unwrap();
+                }
+
+                assert_eq!(root, hash);
+            }
+        }
+    }
+
+    #[test]
+    fn genesis_txns_works() {
+        let txns = _genesis_txns(4).unwrap();
+        assert_eq!(4, txns.len());
+
+        let txn: SJsonValue = serde_json::from_str(&txns[0]).unwrap();
+        assert_eq!("Node1", txn["txn"]["data"]["data"]["alias"]);
+        assert_eq!(_node_bls_keys("Node1").unwrap().1.as_bytes().to_base58(), txn["txn"]["data"]["data"]["blskey"]);
+    }
+
+    #[test]
+    fn simulated_networker_replies_ledger_status() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Honest)]);
+
+        let ls = LedgerStatus { txnSeqNo: 1, merkleRoot: "root".to_string(), ledgerId: 0, ppSeqNo: None, viewNo: None, protocolVersion: Some(2) };
+        networker.process_event(Some(NetworkerEvent::SendAllRequest(_to_string(&Message::LedgerStatus(ls)).unwrap(), "root".to_string(), 1, None)));
+
+        let events = _fetch(&networker);
+        assert_eq!(1, events.len());
+        match events[0] {
+            PoolEvent::NodeReply(ref msg, ref alias) => {
+                assert_eq!("Node1", alias);
+                assert_match!(Ok(Message::LedgerStatus(_)), Message::from_raw_str(msg));
+            }
+            ref event => panic!("unexpected event {:?}", event)
+        }
+    }
+
+    #[test]
+    fn simulated_networker_replies_consistency_proof_for_outdated_client() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Honest), ("Node2", SimulatedNodeBehavior::Honest)]);
+
+        let ls = LedgerStatus { txnSeqNo: 1, merkleRoot: "root".to_string(), ledgerId: 0, ppSeqNo: None, viewNo: None, protocolVersion: Some(2) };
+        networker.process_event(Some(NetworkerEvent::SendOneRequest(_to_string(&Message::LedgerStatus(ls)).unwrap(), "root".to_string(), 1)));
+
+        let events = _fetch(&networker);
+        match events[0] {
+            PoolEvent::NodeReply(ref msg, _) => {
+                match Message::from_raw_str(msg).unwrap() {
+                    Message::ConsistencyProof(cp) => {
+                        assert_eq!(1, cp.seqNoStart);
+                        assert_eq!(2, cp.seqNoEnd);
+                    }
+                    msg => panic!("unexpected message {:?}", msg)
+                }
+            }
+            ref event => panic!("unexpected event {:?}", event)
+        }
+    }
+
+    #[test]
+    fn simulated_networker_does_not_reply_for_faulty_node() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Faulty)]);
+
+        networker.process_event(Some(NetworkerEvent::SendOneRequest(r#"{"reqId":1,"identifier":"did","operation":{"type":"105","dest":"did"}}"#.to_string(), "1".to_string(), 1)));
+
+        assert!(_fetch(&networker).is_empty());
+        assert_eq!(("1".to_string(), "Node1".to_string()), networker.get_timeout().0);
+    }
+
+    #[test]
+    fn simulated_networker_replies_same_write_result_for_all_nodes() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Honest), ("Node2", SimulatedNodeBehavior::Honest)]);
+
+        networker.process_event(Some(NetworkerEvent::SendAllRequest(r#"{"reqId":1,"identifier":"did","operation":{"type":"1","dest":"did2"}}"#.to_string(), "1".to_string(), 1, None)));
+
+        let replies: Vec<SJsonValue> = _fetch(&networker).into_iter()
+            .filter_map(|event| match event {
+                PoolEvent::NodeReply(msg, _) => Some(serde_json::from_str::<SJsonValue>(&msg).unwrap()),
+                _ => None
+            })
+            .filter(|msg| msg["op"] == "REPLY")
+            .collect();
+
+        assert_eq!(2, replies.len());
+        assert_eq!(replies[0]["result"], replies[1]["result"]);
+        assert_eq!(1, replies[0]["result"]["txnMetadata"]["seqNo"]);
+    }
+
+    #[test]
+    fn simulated_networker_writes_node_txn_to_pool_ledger() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Honest), ("Node2", SimulatedNodeBehavior::Honest)]);
+
+        let request = json!({
+            "reqId": 1,
+            "identifier": "did",
+            "operation": {"type": constants::NODE, "dest": "dest", "data": {"alias": "Node2", "services": []}},
+        });
+        networker.process_event(Some(NetworkerEvent::SendAllRequest(request.to_string(), "1".to_string(), 1, None)));
+
+        let replies: Vec<SJsonValue> = _fetch(&networker).into_iter()
+            .filter_map(|event| match event {
+                PoolEvent::NodeReply(msg, _) => Some(serde_json::from_str::<SJsonValue>(&msg).unwrap()),
+                _ => None
+            })
+            .filter(|msg| msg["op"] == "REPLY")
+            .collect();
+
+        assert_eq!(2, replies.len());
+        assert_eq!(replies[0]["result"], replies[1]["result"]);
+        assert_eq!(3, replies[0]["result"]["txnMetadata"]["seqNo"]);
+        assert_eq!(json!([]), replies[0]["result"]["txn"]["data"]["data"]["services"]);
+
+        assert_eq!(3, networker.ledger.pool_txns.len());
+        assert!(networker.ledger.domain_txns.is_empty());
+    }
+
+    fn _honest_networker() -> SimulatedNetworker {
+        _networker(&[("Node1", SimulatedNodeBehavior::Honest), ("Node2", SimulatedNodeBehavior::Honest),
+            ("Node3", SimulatedNodeBehavior::Honest), ("Node4", SimulatedNodeBehavior::Honest)])
+    }
+
+    fn _submit(networker: &mut SimulatedNetworker, request: &SJsonValue) -> String {
+        let req_id = request["reqId"].to_string();
+        networker.process_event(Some(NetworkerEvent::SendOneRequest(request.to_string(), req_id, 1)));
+
+        _fetch(networker).into_iter()
+            .filter_map(|event| match event {
+                PoolEvent::NodeReply(msg, _) => Some(msg),
+                _ => None
+            })
+            .find(|msg| serde_json::from_str::<SJsonValue>(msg).unwrap()["op"] == "REPLY")
+            .unwrap()
+    }
+
+    fn _verify_reply(request: &SJsonValue, reply: &str) -> StateProofVerdict {
+        let nodes: Nodes = (1..=4)
+            .map(|idx| {
+                let alias = format!("Node{}", idx);
+                let verkey = _node_bls_keys(&alias).unwrap().1;
+                (alias, Some(verkey))
+            })
+            .collect();
+
+        state_proof::verify_reply(request, reply, &nodes, 1, &_generator().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn simulated_networker_replies_state_proof_for_get_nym() {
+        let mut networker = _honest_networker();
+
+        _submit(&mut networker, &json!({"reqId": 1, "identifier": "did", "operation": {"type": constants::NYM, "dest": "did2", "verkey": "verkey"}}));
+
+        let request = json!({"reqId": 2, "identifier": "did", "operation": {"type": constants::GET_NYM, "dest": "did2"}});
+        let reply = _submit(&mut networker, &request);
+
+        let verdict = _verify_reply(&request, &reply);
+        assert!(verdict.state_proof_present);
+        assert!(verdict.verified);
+    }
+
+    #[test]
+    fn simulated_networker_replies_state_proof_for_get_schema() {
+        let mut networker = _honest_networker();
+
+        let data = json!({"name": "gvt", "version": "1.0", "attr_names": ["name", "age"]});
+        _submit(&mut networker, &json!({"reqId": 1, "identifier": "did", "operation": {"type": constants::SCHEMA, "data": data}}));
+
+        let request = json!({"reqId": 2, "identifier": "did", "operation": {"type": constants::GET_SCHEMA, "dest": "did", "data": {"name": "gvt", "version": "1.0"}}});
+        let reply = _submit(&mut networker, &request);
+
+        let reply_json: SJsonValue = serde_json::from_str(&reply).unwrap();
+        assert_eq!(data, reply_json["result"]["data"]);
+        assert!(_verify_reply(&request, &reply).verified);
+    }
+
+    #[test]
+    fn simulated_networker_replies_state_proof_for_get_cred_def() {
+        let mut networker = _honest_networker();
+
+        let data = json!({"primary": {"n": "1"}});
+        _submit(&mut networker, &json!({"reqId": 1, "identifier": "did", "operation": {"type": constants::CRED_DEF, "ref": 1, "signature_type": "CL", "tag": "tag", "data": data}}));
+
+        let request = json!({"reqId": 2, "identifier": "did", "operation": {"type": constants::GET_CRED_DEF, "ref": 1, "signature_type": "CL", "origin": "did", "tag": "tag"}});
+        let reply = _submit(&mut networker, &request);
+
+        let reply_json: SJsonValue = serde_json::from_str(&reply).unwrap();
+        assert_eq!(data, reply_json["result"]["data"]);
+        assert!(_verify_reply(&request, &reply).verified);
+    }
+
+    #[test]
+    fn simulated_networker_replies_without_state_proof_for_unknown_nym() {
+        let mut networker = _honest_networker();
+
+        let request = json!({"reqId": 1, "identifier": "did", "operation": {"type": constants::GET_NYM, "dest": "did2"}});
+        let reply = _submit(&mut networker, &request);
+
+        let reply: SJsonValue = serde_json::from_str(&reply).unwrap();
+        assert!(reply["result"]["data"].is_null());
+        assert!(reply["result"]["state_proof"].is_null());
+    }
+
+    #[test]
+    fn simulated_networker_tampers_replies_for_malicious_node() {
+        let mut networker = _networker(&[("Node1", SimulatedNodeBehavior::Honest), ("Node2", SimulatedNodeBehavior::Malicious)]);
+
+        networker.process_event(Some(NetworkerEvent::SendAllRequest(r#"{"reqId":1,"identifier":"did","operation":{"type":"1","dest":"did2"}}"#.to_string(), "1".to_string(), 1, None)));
+
+        let replies: HashMap<String, SJsonValue> = _fetch(&networker).into_iter()
+            .filter_map(|event| match event {
+                PoolEvent::NodeReply(msg, alias) => Some((alias, serde_json::from_str::<SJsonValue>(&msg).unwrap())),
+                _ => None
+            })
+            .filter(|&(_, ref msg)| msg["op"] == "REPLY")
+            .collect();
+
+        assert_ne!(replies["Node1"]["result"], replies["Node2"]["result"]);
+    }
+}
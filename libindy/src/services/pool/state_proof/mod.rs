@@ -22,9 +22,9 @@ use super::types::*;
 
 use self::log_derive::logfn;
 use ursa::bls::{Bls, Generator, MultiSignature, VerKey};
-use self::node::{Node, TrieDB};
+use self::node::{Leaf, Node, TrieDB};
 use rust_base58::FromBase58;
-use crate::services::pool::Nodes;
+use crate::services::pool::{get_last_signed_time, Nodes};
 
 mod node;
 
@@ -79,38 +79,46 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
                         nodes: &Nodes,
                         f: usize,
                         gen: &Generator) -> bool {
+    check_parsed_sp(parsed_sps, nodes, f, gen).is_ok()
+}
+
+/// Same as `verify_parsed_sp` but tells which part of the state proof is not valid.
+pub fn check_parsed_sp(parsed_sps: Vec<ParsedSP>,
+                       nodes: &Nodes,
+                       f: usize,
+                       gen: &Generator) -> Result<(), SPFailure> {
     for parsed_sp in parsed_sps {
         if parsed_sp.multi_signature["value"]["state_root_hash"].as_str().ne(
             &Some(&parsed_sp.root_hash)) && parsed_sp.multi_signature["value"]["txn_root_hash"].as_str().ne(
             &Some(&parsed_sp.root_hash)) {
             error!("Given signature is not for current root hash, aborting");
-            return false;
+            return Err(SPFailure::RootHashMismatch);
         }
 
         let data_to_check_proof_signature =
             _parse_reply_for_proof_signature_checking(&parsed_sp.multi_signature);
-        let (signature, participants, value) = unwrap_opt_or_return!(data_to_check_proof_signature, false);
+        let (signature, participants, value) = unwrap_opt_or_return!(data_to_check_proof_signature, Err(SPFailure::InvalidSignature));
         if !_verify_proof_signature(signature,
                                     participants.as_slice(),
                                     &value,
                                     nodes, f, gen)
             .map_err(|err| warn!("{:?}", err)).unwrap_or(false) {
-            return false;
+            return Err(SPFailure::InvalidSignature);
         }
 
-        let proof_nodes = unwrap_or_return!(base64::decode(&parsed_sp.proof_nodes), false);
-        let root_hash = unwrap_or_return!(parsed_sp.root_hash.from_base58(), false);
+        let proof_nodes = unwrap_or_return!(base64::decode(&parsed_sp.proof_nodes), Err(SPFailure::InvalidProof));
+        let root_hash = unwrap_or_return!(parsed_sp.root_hash.from_base58(), Err(SPFailure::InvalidProof));
         match parsed_sp.kvs_to_verify {
             KeyValuesInSP::Simple(kvs) => {
                 match kvs.verification_type {
                     KeyValueSimpleDataVerificationType::Simple => {
                         for (k, v) in kvs.kvs {
-                            let key = unwrap_or_return!(base64::decode(&k), false);
+                            let key = unwrap_or_return!(base64::decode(&k), Err(SPFailure::InvalidProof));
                             if !_verify_proof(proof_nodes.as_slice(),
                                               root_hash.as_slice(),
                                               &key,
                                               v.as_ref().map(String::as_str)) {
-                                return false;
+                                return Err(SPFailure::InvalidProof);
                             }
                         }
                     }
@@ -121,7 +129,7 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
                                                 data.from,
                                                 data.next,
                                                 &kvs.kvs) {
-                            return false;
+                            return Err(SPFailure::InvalidProof);
                         }
                     }
                     KeyValueSimpleDataVerificationType::MerkleTree(length) => {
@@ -129,7 +137,7 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
                                                 root_hash.as_slice(),
                                                 &kvs.kvs,
                                                 length){
-                            return false;
+                            return Err(SPFailure::InvalidProof);
                         }
                     }
                 }
@@ -137,12 +145,12 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
             //TODO IS-713 support KeyValuesInSP::SubTrie
             kvs => {
                 warn!("Unsupported parsed state proof format for key-values {:?} ", kvs);
-                return false;
+                return Err(SPFailure::UnsupportedFormat);
             }
         }
     }
 
-    true
+    Ok(())
 }
 
 #[logfn(Trace)]
@@ -312,11 +320,99 @@ pub fn parse_key_from_request_for_builtin_sp(json_msg: &SJsonValue) -> Option<Ve
     Some(key)
 }
 
+/// Verifies state proof of a previously received reply on the request against given set of nodes.
+pub fn verify_reply(request: &SJsonValue, raw_reply: &str, nodes: &Nodes, f: usize, gen: &Generator) -> IndyResult<StateProofVerdict> {
+    trace!("verify_reply >>> request: {:?}, raw_reply: {:?}", request, raw_reply);
+
+    let reply: SJsonValue = serde_json::from_str(raw_reply)
+        .to_indy(IndyErrorKind::InvalidStructure, "Reply is malformed json")?;
+
+    let result = &reply["result"];
+
+    if let (Some(req_id), Some(reply_req_id)) = (request["reqId"].as_u64(), result["reqId"].as_u64()) {
+        if req_id != reply_req_id {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Reply doesn't correspond to the request"));
+        }
+    }
+
+    let txn_type = result["type"].as_str()
+        .or_else(|| request["operation"]["type"].as_str())
+        .map(String::from);
+
+    let sp_key = parse_key_from_request_for_builtin_sp(request);
+    let parsed_sps = parse_generic_reply_for_proof_checking(result, raw_reply, sp_key.as_ref().map(Vec::as_slice));
+
+    let state_proof_present = parsed_sps.is_some();
+
+    let participants = parsed_sps.as_ref()
+        .and_then(|parsed_sps| parsed_sps.first())
+        .and_then(|parsed_sp| parsed_sp.multi_signature["participants"].as_array())
+        .map(|participants| participants.iter().flat_map(SJsonValue::as_str).map(String::from).collect())
+        .unwrap_or_default();
+
+    let failure = match parsed_sps {
+        Some(parsed_sps) => check_parsed_sp(parsed_sps, nodes, f, gen).err(),
+        None => Some(SPFailure::NoStateProof)
+    };
+
+    let res = StateProofVerdict {
+        verified: failure.is_none(),
+        txn_type,
+        state_proof_present,
+        failure,
+        participants,
+        last_signed_time: get_last_signed_time(raw_reply),
+        nodes_count: nodes.len(),
+        f,
+    };
+
+    trace!("verify_reply <<< res: {:?}", res);
+
+    Ok(res)
+}
+
 fn _parse_reply_for_builtin_sp(json_msg: &SJsonValue, type_: &str, key: &[u8]) -> Option<Vec<ParsedSP>> {
     trace!("TransactionHandler::parse_reply_for_builtin_sp: >>> json_msg: {:?}", json_msg);
 
     assert!(REQUESTS_FOR_STATE_PROOFS.contains(&type_));
 
+    let (data, parsed_data) = _parse_reply_data(json_msg)?;
+
+    trace!("TransactionHandler::parse_reply_for_builtin_sp: data: {:?}, parsed_data: {:?}", data, parsed_data);
+
+    let mut state_proofs = vec![];
+
+    match _parse_reply_for_sp(json_msg, data.as_ref().map(String::as_str), &parsed_data, type_, key) {
+        Ok(state_proof) => {
+            trace!("TransactionHandler::_parse_reply_for_sp: proof: {:?}", state_proof);
+            state_proofs.push(state_proof)
+        }
+        Err(err) => {
+            trace!("TransactionHandler::_parse_reply_for_sp: <<<  {:?}", err);
+            return None;
+        }
+    }
+
+    if REQUESTS_FOR_MULTI_STATE_PROOFS.contains(&type_) {
+        match _parse_reply_for_multi_sp(json_msg, data.as_ref().map(String::as_str), &parsed_data, type_, key) {
+            Ok(Some(state_proof)) => {
+                trace!("TransactionHandler::_parse_reply_for_multi_sp: proof: {:?}", state_proof);
+                state_proofs.push(state_proof);
+            }
+            Ok(None) => {
+                trace!("TransactionHandler::_parse_reply_for_multi_sp: <<<  No proof");
+            }
+            Err(err) => {
+                trace!("TransactionHandler::_parse_reply_for_multi_sp: <<<  {:?}", err);
+                return None;
+            }
+        }
+    }
+
+    Some(state_proofs)
+}
+
+fn _parse_reply_data(json_msg: &SJsonValue) -> Option<(Option<String>, SJsonValue)> {
     // TODO: FIXME: It is a workaround for Node's problem. Node returns some transactions as strings and some as objects.
     // If node returns marshaled json it can contain spaces and it can cause invalid hash.
     // So we have to save the original string too.
@@ -349,38 +445,24 @@ fn _parse_reply_for_builtin_sp(json_msg: &SJsonValue, type_: &str, key: &[u8]) -
         }
     };
 
-    trace!("TransactionHandler::parse_reply_for_builtin_sp: data: {:?}, parsed_data: {:?}", data, parsed_data);
+    Some((data, parsed_data))
+}
 
-    let mut state_proofs = vec![];
+/// Builds a state proof for the reply on the built-in read request as a trie that holds the only value of the reply.
+/// Returns base58 encoded root hash and base64 encoded proof nodes, or None if the reply has no value to prove.
+/// Intended for simulated nodes that don't keep the real ledger state.
+pub(super) fn build_single_value_sp(request: &SJsonValue, json_msg: &SJsonValue) -> Option<(String, String)> {
+    let type_ = json_msg["type"].as_str()?;
+    let key = parse_key_from_request_for_builtin_sp(request)?;
+    let (data, parsed_data) = _parse_reply_data(json_msg)?;
 
-    match _parse_reply_for_sp(json_msg, data.as_ref().map(String::as_str), &parsed_data, type_, key) {
-        Ok(state_proof) => {
-            trace!("TransactionHandler::_parse_reply_for_sp: proof: {:?}", state_proof);
-            state_proofs.push(state_proof)
-        }
-        Err(err) => {
-            trace!("TransactionHandler::_parse_reply_for_sp: <<<  {:?}", err);
-            return None;
-        }
-    }
+    let value = _parse_reply_for_proof_value(json_msg, data.as_ref().map(String::as_str), &parsed_data, type_, &key).ok()??;
 
-    if REQUESTS_FOR_MULTI_STATE_PROOFS.contains(&type_) {
-        match _parse_reply_for_multi_sp(json_msg, data.as_ref().map(String::as_str), &parsed_data, type_, key) {
-            Ok(Some(state_proof)) => {
-                trace!("TransactionHandler::_parse_reply_for_multi_sp: proof: {:?}", state_proof);
-                state_proofs.push(state_proof);
-            }
-            Ok(None) => {
-                trace!("TransactionHandler::_parse_reply_for_multi_sp: <<<  No proof");
-            }
-            Err(err) => {
-                trace!("TransactionHandler::_parse_reply_for_multi_sp: <<<  {:?}", err);
-                return None;
-            }
-        }
-    }
+    let leaf = Node::Leaf(Leaf::new(&key, value.as_bytes()));
+    let root_hash = leaf.get_hash().as_slice().to_base58();
+    let proof_nodes = base64::encode(&rlp::encode_list::<Node, _>(&[leaf]).to_vec());
 
-    Some(state_proofs)
+    Some((root_hash, proof_nodes))
 }
 
 fn _parse_reply_for_sp(json_msg: &SJsonValue, data: Option<&str>, parsed_data: &SJsonValue, xtype: &str, sp_key: &[u8]) -> Result<ParsedSP, String> {
@@ -1714,4 +1796,58 @@ mod tests {
                            }),
                    }));
     }
+
+    fn _parsed_sp(root_hash: &str, multi_signature: SJsonValue) -> ParsedSP {
+        ParsedSP {
+            proof_nodes: String::new(),
+            root_hash: root_hash.to_string(),
+            kvs_to_verify: KeyValuesInSP::Simple(KeyValueSimpleData {
+                kvs: Vec::new(),
+                verification_type: KeyValueSimpleDataVerificationType::Simple,
+            }),
+            multi_signature,
+        }
+    }
+
+    fn _gen() -> Generator {
+        Generator::from_bytes(&crate::services::pool::request_handler::DEFAULT_GENERATOR.from_base58().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn check_parsed_sp_works_for_root_hash_mismatch() {
+        let parsed_sp = _parsed_sp("rh", json!({"value": {"state_root_hash": "other"}}));
+
+        let res = check_parsed_sp(vec![parsed_sp], &Nodes::new(), 0, &_gen());
+        assert_eq!(Err(SPFailure::RootHashMismatch), res);
+    }
+
+    #[test]
+    fn check_parsed_sp_works_for_missed_signature() {
+        let parsed_sp = _parsed_sp("rh", json!({"value": {"state_root_hash": "rh"}}));
+
+        let res = check_parsed_sp(vec![parsed_sp], &Nodes::new(), 0, &_gen());
+        assert_eq!(Err(SPFailure::InvalidSignature), res);
+    }
+
+    #[test]
+    fn verify_reply_works_for_reply_without_state_proof() {
+        let request = json!({"reqId": 1, "operation": {"type": "105", "dest": "dest"}});
+        let reply = json!({"op": "REPLY", "result": {"reqId": 1, "type": "105", "dest": "dest", "data": null}});
+
+        let verdict = verify_reply(&request, &reply.to_string(), &Nodes::new(), 0, &_gen()).unwrap();
+
+        assert!(!verdict.verified);
+        assert!(!verdict.state_proof_present);
+        assert_eq!(Some(SPFailure::NoStateProof), verdict.failure);
+        assert_eq!(Some("105".to_string()), verdict.txn_type);
+    }
+
+    #[test]
+    fn verify_reply_works_for_other_request() {
+        let request = json!({"reqId": 1, "operation": {"type": "105", "dest": "dest"}});
+        let reply = json!({"op": "REPLY", "result": {"reqId": 2, "type": "105", "dest": "dest", "data": null}});
+
+        let res = verify_reply(&request, &reply.to_string(), &Nodes::new(), 0, &_gen());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
 }
@@ -38,6 +38,19 @@ pub struct Leaf {
     value: Vec<u8>,
 }
 
+impl Leaf {
+    /// Leaf that keeps the value by the whole key, i.e. the only node of a single value trie.
+    pub fn new(key: &[u8], value: &[u8]) -> Leaf {
+        let mut path = vec![Node::IS_LEAF_MASK];
+        path.extend_from_slice(key);
+
+        let mut stream = RlpStream::new_list(1);
+        stream.append(&value.to_vec());
+
+        Leaf { path, value: stream.out() }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Extension {
     path: Vec<u8>,
@@ -414,6 +427,20 @@ mod tests {
                    vec![Node::Blank]);
     }
 
+    #[test]
+    fn leaf_new_works() {
+        let leaf = Node::Leaf(Leaf::new(b"key", b"value"));
+
+        let nodes = rlp::encode_list::<Node, _>(&[leaf]).to_vec();
+        let nodes: Vec<Node> = UntrustedRlp::new(&nodes).as_list().unwrap();
+
+        let mut db: TrieDB = HashMap::new();
+        db.insert(nodes[0].get_hash(), &nodes[0]);
+
+        assert_eq!(Some("value".to_string()), nodes[0].get_str_value(&db, b"key").unwrap());
+        assert_eq!(None, nodes[0].get_str_value(&db, b"other").unwrap());
+    }
+
     #[test]
     fn node_serialize_works_for_emtpy() {
         assert_eq!(base64::encode(&rlp::encode_list(&vec![Node::Blank])),
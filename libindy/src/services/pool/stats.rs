@@ -0,0 +1,295 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use time::Tm;
+
+use crate::services::pool::events::RequestEvent;
+use crate::services::pool::types::RemoteNode;
+
+/// Upper bounds (in ms) of latency histogram buckets. The last bucket is unbounded.
+pub const LATENCY_BUCKETS: [i64; 8] = [50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// Weight of the latest observation in the decayed latency and success rate of a node.
+const DECAY_WEIGHT: f64 = 0.2;
+
+/// Lower bound of the success rate used for ranking, so never answering nodes still get a finite score.
+const MIN_SUCCESS_RATE: f64 = 0.05;
+
+/// Health metrics of the nodes of one opened pool.
+/// Collected in the pool worker thread and returned by `indy_get_pool_stats`.
+#[derive(Debug, Default, Serialize)]
+pub struct PoolStats {
+    pub connections: usize,
+    pub last_catchup: Option<i64>,
+    pub nodes: BTreeMap<String, NodeStats>,
+    #[serde(skip)]
+    sent: HashMap<(String, String), Tm>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct NodeStats {
+    pub sent: u64,
+    pub replies: u64,
+    pub acks: u64,
+    pub nacks: u64,
+    pub rejects: u64,
+    pub timeouts: u64,
+    pub invalid_state_proofs: u64,
+    pub last_reply: Option<i64>,
+    pub latency: LatencyHistogram,
+    /// Exponentially decayed time to reply (or to timeout) in ms.
+    pub decayed_latency_ms: Option<f64>,
+    /// Exponentially decayed share of requests answered in time with valid data.
+    pub success_rate: Option<f64>,
+}
+
+impl NodeStats {
+    /// Expected cost of sending a single request to the node. Lower is better.
+    pub fn score(&self) -> Option<f64> {
+        let success_rate = self.success_rate.unwrap_or(1.0).max(MIN_SUCCESS_RATE);
+        self.decayed_latency_ms.map(|latency| latency / success_rate)
+    }
+
+    fn _observe(&mut self, latency_ms: Option<i64>, success: bool) {
+        if let Some(latency_ms) = latency_ms {
+            self.decayed_latency_ms = Some(_decay(self.decayed_latency_ms, latency_ms as f64));
+        }
+        self.success_rate = Some(_decay(self.success_rate, if success { 1.0 } else { 0.0 }));
+    }
+}
+
+fn _decay(prev: Option<f64>, value: f64) -> f64 {
+    match prev {
+        Some(prev) => prev + DECAY_WEIGHT * (value - prev),
+        None => value
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LatencyHistogram {
+    /// Count of replies for every bucket of `LATENCY_BUCKETS` plus the unbounded one.
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub sum_ms: i64,
+    pub max_ms: i64,
+    pub avg_ms: Option<i64>,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, latency_ms: i64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS.len() + 1];
+        }
+
+        let idx = LATENCY_BUCKETS.iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or_else(|| LATENCY_BUCKETS.len());
+
+        self.buckets[idx] += 1;
+        self.count += 1;
+        self.sum_ms += latency_ms;
+        self.max_ms = ::std::cmp::max(self.max_ms, latency_ms);
+        self.avg_ms = Some(self.sum_ms / self.count as i64);
+    }
+}
+
+impl PoolStats {
+    pub fn new() -> Self {
+        PoolStats::default()
+    }
+
+    pub fn request_sent(&mut self, req_id: &str, node_alias: &str) {
+        self._node(node_alias).sent += 1;
+        self.sent.insert((req_id.to_string(), node_alias.to_string()), time::now());
+    }
+
+    pub fn request_finished(&mut self, req_id: &str) {
+        self.sent.retain(|&(ref id, _), _| id != req_id);
+    }
+
+    pub fn event_received(&mut self, event: &RequestEvent) {
+        match *event {
+            RequestEvent::Reply(_, _, ref node_alias, ref req_id) => {
+                self._reply_received(req_id, node_alias);
+                self._node(node_alias).replies += 1;
+            }
+            RequestEvent::ReqNACK(_, _, ref node_alias, ref req_id) => {
+                self._reply_received(req_id, node_alias);
+                self._node(node_alias).nacks += 1;
+            }
+            RequestEvent::Reject(_, _, ref node_alias, ref req_id) => {
+                self._reply_received(req_id, node_alias);
+                self._node(node_alias).rejects += 1;
+            }
+            RequestEvent::ReqACK(_, _, ref node_alias, _) => {
+                self._node(node_alias).acks += 1;
+            }
+            RequestEvent::LedgerStatus(ref ls, Some(ref node_alias), _) => {
+                self._reply_received(&ls.merkleRoot, node_alias);
+                self._node(node_alias).replies += 1;
+            }
+            RequestEvent::ConsistencyProof(ref cp, ref node_alias) => {
+                self._reply_received(&cp.oldMerkleRoot, node_alias);
+                self._node(node_alias).replies += 1;
+            }
+            RequestEvent::CatchupRep(_, ref node_alias) => {
+                self._node(node_alias).replies += 1;
+            }
+            RequestEvent::Timeout(ref req_id, ref node_alias) if !node_alias.is_empty() => {
+                let waited = self.sent.remove(&(req_id.to_string(), node_alias.to_string()))
+                    .map(|sent| (time::now() - sent).num_milliseconds());
+                let node = self._node(node_alias);
+                node.timeouts += 1;
+                node._observe(waited, false);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn state_proof_rejected(&mut self, node_alias: &str) {
+        let node = self._node(node_alias);
+        node.invalid_state_proofs += 1;
+        node._observe(None, false);
+    }
+
+    /// Sorts nodes by their score, the best first. Nodes without observations go first
+    /// to get measured; the order of nodes with equal scores is kept.
+    pub fn rank_nodes(&self, nodes: &mut Vec<RemoteNode>) {
+        nodes.sort_by(|a, b| {
+            let a = self.nodes.get(&a.name).and_then(NodeStats::score);
+            let b = self.nodes.get(&b.name).and_then(NodeStats::score);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+    }
+
+    pub fn catchup_finished(&mut self) {
+        self.last_catchup = Some(time::get_time().sec);
+    }
+
+    fn _reply_received(&mut self, req_id: &str, node_alias: &str) {
+        let now = time::now();
+
+        let latency = self.sent.remove(&(req_id.to_string(), node_alias.to_string()))
+            .map(|sent| (now - sent).num_milliseconds());
+
+        let node = self._node(node_alias);
+        if let Some(latency) = latency {
+            node.latency.record(latency);
+        }
+        node._observe(latency, true);
+        node.last_reply = Some(now.to_timespec().sec);
+    }
+
+    fn _node(&mut self, node_alias: &str) -> &mut NodeStats {
+        self.nodes.entry(node_alias.to_string()).or_insert_with(NodeStats::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::pool::types::{Response, ResponseV0};
+
+    const REQ_ID: &str = "1";
+    const NODE: &str = "n1";
+
+    fn _node(name: &str) -> RemoteNode {
+        RemoteNode { name: name.to_string(), public_key: vec![], zaddr: String::new(), is_blacklisted: false }
+    }
+
+    fn _nack() -> RequestEvent {
+        RequestEvent::ReqNACK(Response::ResponseV0(ResponseV0 { req_id: 1 }), String::new(), NODE.to_string(), REQ_ID.to_string())
+    }
+
+    #[test]
+    fn latency_histogram_record_works() {
+        let mut histogram = LatencyHistogram::default();
+
+        histogram.record(10);
+        histogram.record(300);
+        histogram.record(20_000);
+
+        assert_eq!(vec![1, 0, 0, 1, 0, 0, 0, 0, 1], histogram.buckets);
+        assert_eq!(3, histogram.count);
+        assert_eq!(20_000, histogram.max_ms);
+        assert_eq!(Some(6770), histogram.avg_ms);
+    }
+
+    #[test]
+    fn pool_stats_records_reply_latency() {
+        let mut stats = PoolStats::new();
+
+        stats.request_sent(REQ_ID, NODE);
+        stats.event_received(&_nack());
+
+        let node = &stats.nodes[NODE];
+        assert_eq!(1, node.sent);
+        assert_eq!(1, node.nacks);
+        assert_eq!(1, node.latency.count);
+        assert!(node.last_reply.is_some());
+        assert!(stats.sent.is_empty());
+    }
+
+    #[test]
+    fn pool_stats_records_timeout() {
+        let mut stats = PoolStats::new();
+
+        stats.request_sent(REQ_ID, NODE);
+        stats.event_received(&RequestEvent::Timeout(REQ_ID.to_string(), NODE.to_string()));
+
+        let node = &stats.nodes[NODE];
+        assert_eq!(1, node.timeouts);
+        assert_eq!(0, node.latency.count);
+        assert!(stats.sent.is_empty());
+    }
+
+    #[test]
+    fn pool_stats_ignores_pool_connection_timeout() {
+        let mut stats = PoolStats::new();
+
+        stats.event_received(&RequestEvent::Timeout(String::new(), String::new()));
+
+        assert!(stats.nodes.is_empty());
+    }
+
+    #[test]
+    fn node_stats_score_works() {
+        let mut node = NodeStats::default();
+        assert_eq!(None, node.score());
+
+        node._observe(Some(100), true);
+        assert_eq!(Some(100.0), node.score());
+
+        node._observe(Some(200), true);
+        assert_eq!(Some(120.0), node.score());
+
+        node._observe(None, false);
+        assert_eq!(Some(150.0), node.score());
+    }
+
+    #[test]
+    fn pool_stats_rank_nodes_works() {
+        let mut stats = PoolStats::new();
+        stats._node("slow")._observe(Some(5_000), true);
+        stats._node("fast")._observe(Some(50), true);
+        stats._node("flaky")._observe(Some(50), false);
+
+        let mut nodes = vec![_node("slow"), _node("fast"), _node("flaky"), _node("new")];
+        stats.rank_nodes(&mut nodes);
+
+        let names: Vec<&str> = nodes.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(vec!["new", "fast", "flaky", "slow"], names);
+    }
+
+    #[test]
+    fn pool_stats_request_finished_works() {
+        let mut stats = PoolStats::new();
+
+        stats.request_sent(REQ_ID, NODE);
+        stats.request_finished(REQ_ID);
+        stats.event_received(&_nack());
+
+        assert_eq!(0, stats.nodes[NODE].latency.count);
+    }
+}
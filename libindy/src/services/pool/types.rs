@@ -476,6 +476,73 @@ pub struct KeyValuesSubTrieData {
     pub kvs: Vec<(String /* b64-encoded key_suffix */, Option<String /* val */>)>,
 }
 
+/**
+ Reason of rejection of a state proof.
+*/
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SPFailure {
+    /* reply has no state proof or it can't be parsed */
+    NoStateProof,
+    /* multi signature is made for another root hash */
+    RootHashMismatch,
+    /* multi signature is malformed, not valid or made by not enough nodes */
+    InvalidSignature,
+    /* proof nodes don't prove the data of the reply */
+    InvalidProof,
+    /* parsed state proof has not supported format */
+    UnsupportedFormat,
+}
+
+/**
+ Result of verification of a previously received reply against a set of nodes.
+*/
+#[derive(Serialize, Debug)]
+pub struct StateProofVerdict {
+    pub verified: bool,
+    pub txn_type: Option<String>,
+    pub state_proof_present: bool,
+    pub failure: Option<SPFailure>,
+    /// aliases of nodes made the multi signature
+    pub participants: Vec<String>,
+    pub last_signed_time: Option<u64>,
+    pub nodes_count: usize,
+    pub f: usize,
+}
+
+/**
+ Result of offline validation of a genesis transactions file.
+*/
+#[derive(Serialize, Debug)]
+pub struct GenesisReport {
+    /// there are no errors, so the pool can be created from the file
+    pub valid: bool,
+    pub txns_count: usize,
+    pub nodes: Vec<GenesisNodeReport>,
+    /// number of validator nodes
+    pub validators_count: usize,
+    pub f: usize,
+    /// number of replies needed to trust a reply (f + 1)
+    pub weak_quorum: usize,
+    /// number of nodes needed to reach consensus (n - f)
+    pub strong_quorum: usize,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GenesisNodeReport {
+    pub alias: String,
+    pub dest: String,
+    pub validator: bool,
+    pub client_address: Option<String>,
+    pub node_address: Option<String>,
+    /// BLS key is present and well-formed
+    pub bls_key_valid: bool,
+    /// proof of possession of the BLS key is verified
+    pub bls_key_pop_valid: bool,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct RemoteNode {
     pub name: String,
@@ -88,6 +88,104 @@ mod high_cases {
         fn issuer_create_credential_works() {
             anoncreds::init_common_wallet();
         }
+
+        #[test]
+        fn issuer_create_credential_works_for_raw_only_values() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let raw_values_json = json!({
+                "sex": "male",
+                "name": "Alex",
+                "height": 175,
+                "age": {"raw": "28", "type": "int"}
+            }).to_string();
+
+            let (credential_json, _, _) = anoncreds::issuer_create_credential(wallet_handle,
+                                                                              &credential_offer,
+                                                                              &credential_req,
+                                                                              &raw_values_json,
+                                                                              None,
+                                                                              None).unwrap();
+
+            let credential: serde_json::Value = serde_json::from_str(&credential_json).unwrap();
+            let expected_values: serde_json::Value = serde_json::from_str(&anoncreds::encode_credential_attr_values(&raw_values_json).unwrap()).unwrap();
+            assert_eq!(expected_values, credential["values"]);
+            assert_eq!(json!({"raw": "male", "encoded": "5944657099558967239210949258394887428692050081607692519917050011144233115103"}), credential["values"]["sex"]);
+            assert_eq!(json!({"raw": "175", "encoded": "175"}), credential["values"]["height"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_create_credentials {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credentials_works() {
+            let (credential_def_json, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (credential_req, credential_req_metadata) = anoncreds::prover_create_credential_req(wallet_handle,
+                                                                                                    DID_MY1,
+                                                                                                    &credential_offer,
+                                                                                                    &credential_def_json,
+                                                                                                    COMMON_MASTER_SECRET).unwrap();
+
+            let credential_req: serde_json::Value = serde_json::from_str(&credential_req).unwrap();
+            let cred_issuance_requests_json = json!([
+                {"cred_req": credential_req, "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap()},
+                {"cred_req": credential_req, "cred_values": {"sex": "female", "name": "Alexandra", "height": 165, "age": 26}}
+            ]).to_string();
+
+            let (creds_json, revoc_reg_delta_json) = anoncreds::issuer_create_credentials(wallet_handle,
+                                                                                          &credential_offer,
+                                                                                          &cred_issuance_requests_json,
+                                                                                          None,
+                                                                                          None).unwrap();
+            assert!(revoc_reg_delta_json.is_none());
+
+            let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+            assert_eq!(2, creds.len());
+            assert_eq!(json!("Alex"), creds[0]["cred"]["values"]["name"]["raw"]);
+            assert_eq!(json!("Alexandra"), creds[1]["cred"]["values"]["name"]["raw"]);
+            assert_eq!(json!(null), creds[0]["cred_rev_id"]);
+            assert_eq!(json!(null), creds[1]["cred_rev_id"]);
+
+            for (i, cred) in creds.iter().enumerate() {
+                anoncreds::prover_store_credential(wallet_handle,
+                                                   &format!("issuer_create_credentials_works_{}", i),
+                                                   &credential_req_metadata,
+                                                   &cred["cred"].to_string(),
+                                                   &credential_def_json,
+                                                   None).unwrap();
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_search_credential_records {
+        use super::*;
+
+        #[test]
+        fn issuer_search_credential_records_works_for_credentials_without_revocation() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (search_handle, total_count) = anoncreds::issuer_search_credential_records(wallet_handle, "{}").unwrap();
+            assert_eq!(0, total_count);
+
+            let records_json = anoncreds::issuer_fetch_credential_records(search_handle, 10).unwrap();
+            assert_eq!("[]", records_json);
+
+            anoncreds::issuer_close_credential_records_search(search_handle).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
     }
 
     mod prover_store_credential {
@@ -474,6 +572,38 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_get_credentials_for_proof_req_works_for_numeric_restriction() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name",
+                       "restrictions": {
+                            "$not": {"attr::height::value": {"$lt": 171}}
+                       }
+                   })
+               }),
+               "requested_predicates": json!({ }),
+            }).to_string();
+
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(wallet_handle, &proof_req).unwrap();
+
+            let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+
+            let credentials_for_attr_1 = credentials.attrs.get("attr1_referent").unwrap();
+            assert_eq!(credentials_for_attr_1.len(), 1);
+            assert_eq!("175", credentials_for_attr_1[0].cred_info.attrs["height"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_get_credentials_for_proof_req_works_for_revealed_attr_only() {
             anoncreds::init_common_wallet();
@@ -2464,6 +2594,75 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_search_credentials_for_proof_req_works_for_numeric_restriction() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name",
+                       "restrictions": {
+                            "attr::height::value": {"$gte": 171}
+                       }
+                   })
+               }),
+               "requested_predicates": json!({ }),
+            }).to_string();
+
+            let search_handle = anoncreds::prover_search_credentials_for_proof_req(wallet_handle, &proof_req, None).unwrap();
+
+            let credentials_json = anoncreds::prover_fetch_next_credentials_for_proof_req(
+                search_handle, "attr1_referent", 100).unwrap();
+
+            let credentials: Vec<RequestedCredential> = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(credentials.len(), 1);
+            assert_eq!("175", credentials[0].cred_info.attrs["height"]);
+
+            anoncreds::prover_close_credentials_search_for_proof_req(search_handle).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_search_credentials_for_proof_req_works_for_ilike_restriction() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name",
+                       "restrictions": {
+                            "attr::name::value": {"$ilike": "alex%"}
+                       }
+                   })
+               }),
+               "requested_predicates": json!({ }),
+            }).to_string();
+
+            let search_handle = anoncreds::prover_search_credentials_for_proof_req(wallet_handle, &proof_req, None).unwrap();
+
+            let credentials_json = anoncreds::prover_fetch_next_credentials_for_proof_req(
+                search_handle, "attr1_referent", 100).unwrap();
+
+            let credentials: Vec<RequestedCredential> = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(credentials.len(), 2);
+
+            anoncreds::prover_close_credentials_search_for_proof_req(search_handle).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_search_credentials_for_proof_req_works_for_non_significant_predicate() {
             anoncreds::init_common_wallet();
@@ -3253,6 +3452,170 @@ mod high_cases {
         assert!(!valid);
     }
 
+    mod verifier_verify_proof_with_report {
+        use super::*;
+        use crate::utils::domain::anoncreds::proof_verification_report::ProofVerificationReport;
+
+        fn _proof_request(attr_info: serde_json::Value) -> String {
+            json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": {
+                   "attr1_referent": attr_info
+               },
+               "requested_predicates": {},
+            }).to_string()
+        }
+
+        fn _verify_with_report(proof_request_json: &str, proof_json: &str) -> ProofVerificationReport {
+            let report_json = anoncreds::verifier_verify_proof_with_report(proof_request_json,
+                                                                           proof_json,
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            serde_json::from_str(&report_json).unwrap()
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_correct_proof() {
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &anoncreds::proof_json());
+
+            assert!(report.valid);
+            assert!(report.errors.is_empty());
+
+            let attr_report = &report.requested_attributes["attr1_referent"];
+            assert!(attr_report.valid);
+            assert!(attr_report.revealed);
+            assert_eq!("Alex", attr_report.values.as_ref().unwrap()["name"]);
+            assert_eq!(anoncreds::gvt_schema_id(), attr_report.identifier.as_ref().unwrap().schema_id.0);
+            assert_eq!(None, attr_report.restrictions_satisfied);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_satisfied_restrictions() {
+            let proof_req = _proof_request(json!({
+                "name":"name",
+                "restrictions": { "issuer_did": ISSUER_DID }
+            }));
+
+            let report = _verify_with_report(&proof_req, &anoncreds::proof_json());
+
+            assert!(report.valid);
+            assert_eq!(Some(true), report.requested_attributes["attr1_referent"].restrictions_satisfied);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_restriction_mismatch() {
+            let proof_req = _proof_request(json!({
+                "name":"name",
+                "restrictions": { "issuer_did": "NO DID" }
+            }));
+
+            let report = _verify_with_report(&proof_req, &anoncreds::proof_json());
+
+            assert!(!report.valid);
+            assert_eq!(1, report.errors.len());
+            assert!(report.errors[0].starts_with("referent attr1_referent: restriction validation failed"));
+            assert!(report.errors[0].contains("issuer_did"));
+
+            let attr_report = &report.requested_attributes["attr1_referent"];
+            assert!(!attr_report.valid);
+            assert_eq!(Some(false), attr_report.restrictions_satisfied);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missed_referent() {
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": {
+                   "attr1_referent": { "name":"name" },
+                   "attr2_referent": { "name":"sex" }
+               },
+               "requested_predicates": {},
+            }).to_string();
+
+            let report = _verify_with_report(&proof_req, &anoncreds::proof_json());
+
+            assert!(!report.valid);
+            assert_eq!(vec!["referent attr2_referent: attribute not found in proof".to_string()], report.errors);
+            assert!(report.requested_attributes["attr1_referent"].valid);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missed_timestamp() {
+            let proof_req = _proof_request(json!({
+                "name":"name",
+                "non_revoked": { "to": 100 }
+            }));
+
+            let report = _verify_with_report(&proof_req, &anoncreds::proof_json());
+
+            assert!(!report.valid);
+            assert_eq!(vec!["referent attr1_referent: non-revocation timestamp is missing".to_string()], report.errors);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_revealed_attr_value() {
+            let proof_json = anoncreds::proof_json().replace(r#"name":"1139481716457488690172217916278103335"#, r#"name":"1111111111111111111111111111111111111"#);
+
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &proof_json);
+
+            assert!(!report.valid);
+            assert!(report.errors[0].starts_with("referent attr1_referent: Encoded Values for \"name\" are different"));
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_proof() {
+            let proof_json = anoncreds::proof_json().replace("1139481716457488690172217916278103335", "1111111111111111111111111111111111111");
+
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &proof_json);
+
+            assert!(!report.valid);
+            assert_eq!(vec!["proof cryptographic verification failed".to_string()], report.errors);
+
+            let attr_report = &report.requested_attributes["attr1_referent"];
+            assert!(!attr_report.valid);
+            assert_eq!(vec!["proof cryptographic verification failed".to_string()], attr_report.errors);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_proof_and_missed_timestamp() {
+            let proof_req = _proof_request(json!({
+                "name":"name",
+                "non_revoked": { "to": 100 }
+            }));
+            let proof_json = anoncreds::proof_json().replace("1139481716457488690172217916278103335", "1111111111111111111111111111111111111");
+
+            let report = _verify_with_report(&proof_req, &proof_json);
+
+            assert!(!report.valid);
+            assert!(report.errors.contains(&"referent attr1_referent: non-revocation timestamp is missing".to_string()));
+            assert!(report.errors.contains(&"proof cryptographic verification failed".to_string()));
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_unknown_credential_defs() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           "{}",
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: ProofVerificationReport = serde_json::from_str(&report_json).unwrap();
+
+            assert!(!report.valid);
+            assert!(report.errors[0].starts_with("referent attr1_referent: CredentialDefinition not found for id"));
+
+            let attr_report = &report.requested_attributes["attr1_referent"];
+            assert!(!attr_report.valid);
+            assert!(attr_report.errors[0].starts_with("CredentialDefinition not found for id"));
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -3706,6 +4069,51 @@ mod high_cases {
             assert_eq!(anoncreds::local_gvt_cred_def_id(), cred_req.cred_def_id.0);
         }
     }
+
+    mod encode_credential_attr_values {
+        use super::*;
+        use std::collections::HashMap;
+        use crate::utils::domain::anoncreds::credential::AttributeValues;
+
+        #[test]
+        fn encode_credential_attr_values_works() {
+            let raw_values_json = json!({
+                "name": "Alex",
+                "age": 28,
+                "height": "175",
+                "birthdate": {"raw": "1991-10-18", "type": "date"},
+                "member": {"raw": true, "type": "boolean"},
+                "zip": {"raw": "02134", "type": "string"}
+            }).to_string();
+
+            let cred_values_json = anoncreds::encode_credential_attr_values(&raw_values_json).unwrap();
+            let cred_values: HashMap<String, AttributeValues> = serde_json::from_str(&cred_values_json).unwrap();
+
+            assert_eq!(6, cred_values.len());
+            assert_eq!(AttributeValues { raw: "28".to_string(), encoded: "28".to_string() }, cred_values["age"]);
+            assert_eq!(AttributeValues { raw: "175".to_string(), encoded: "175".to_string() }, cred_values["height"]);
+            assert_eq!(AttributeValues { raw: "1991-10-18".to_string(), encoded: "19911018".to_string() }, cred_values["birthdate"]);
+            assert_eq!(AttributeValues { raw: "true".to_string(), encoded: "1".to_string() }, cred_values["member"]);
+            assert_eq!("Alex", cred_values["name"].raw);
+            assert_ne!("02134", cred_values["zip"].encoded);
+        }
+
+        #[test]
+        fn encode_credential_attr_values_works_for_string_encoding() {
+            let cred_values_json = anoncreds::encode_credential_attr_values(r#"{"sex": "male"}"#).unwrap();
+            let cred_values: HashMap<String, AttributeValues> = serde_json::from_str(&cred_values_json).unwrap();
+
+            assert_eq!(anoncreds::gvt_credential_values()["sex"], cred_values["sex"]);
+        }
+
+        #[test]
+        fn encode_credential_attr_values_keeps_encoded_values() {
+            let cred_values_json = anoncreds::encode_credential_attr_values(&anoncreds::gvt_credential_values_json()).unwrap();
+            let cred_values: HashMap<String, AttributeValues> = serde_json::from_str(&cred_values_json).unwrap();
+
+            assert_eq!(anoncreds::gvt_credential_values(), cred_values);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -4132,7 +4540,7 @@ mod medium_cases {
                                            "sex":"male",
                                            "name":"Alex",
                                            "height":"175",
-                                           "age":"28"
+                                           "age":{"raw":"twenty eight","type":"int"}
                                          }"#;
 
             let res = anoncreds::issuer_create_credential(wallet_handle,
@@ -4147,6 +4555,165 @@ mod medium_cases {
         }
     }
 
+    mod issuer_create_credentials {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credentials_works_for_empty_requests() {
+            let (_, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credentials(wallet_handle,
+                                                           &credential_offer,
+                                                           "[]",
+                                                           None,
+                                                           None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_works_for_credential_values_not_corresponding_to_credential_def() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let credential_req: serde_json::Value = serde_json::from_str(credential_req).unwrap();
+            let cred_issuance_requests_json = json!([
+                {"cred_req": credential_req, "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap()},
+                {"cred_req": credential_req, "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::xyz_credential_values_json()).unwrap()}
+            ]).to_string();
+
+            let res = anoncreds::issuer_create_credentials(wallet_handle,
+                                                           &credential_offer,
+                                                           &cred_issuance_requests_json,
+                                                           None,
+                                                           None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_create_revocation_registry_pool {
+        use super::*;
+
+        #[test]
+        fn issuer_create_revocation_registry_pool_works_for_threshold_not_less_than_max_cred_num() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let config_json = json!({
+                "max_cred_num": 5,
+                "threshold": 5,
+                "tails_writer_config": serde_json::from_str::<serde_json::Value>(&anoncreds::tails_writer_config()).unwrap()
+            }).to_string();
+
+            let res = anoncreds::issuer_create_revocation_registry_pool(wallet_handle,
+                                                                        ISSUER_DID,
+                                                                        &anoncreds::issuer_1_gvt_cred_def_id(),
+                                                                        &config_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_create_credential_from_pool {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credential_from_pool_works_for_no_pool() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credential_from_pool(wallet_handle,
+                                                                    &credential_offer,
+                                                                    credential_req,
+                                                                    &anoncreds::gvt_credential_values_json());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_get_revocation_registry_pool {
+        use super::*;
+
+        #[test]
+        fn issuer_get_revocation_registry_pool_works_for_no_pool() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_get_revocation_registry_pool(wallet_handle, &anoncreds::issuer_1_gvt_cred_def_id());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_create_credential_with_tags {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credential_with_tags_works_for_reserved_tag_name() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credential_with_tags(wallet_handle,
+                                                                    &credential_offer,
+                                                                    credential_req,
+                                                                    &anoncreds::gvt_credential_values_json(),
+                                                                    None,
+                                                                    None,
+                                                                    Some(r#"{"revoked": "0"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_get_credential_record {
+        use super::*;
+
+        #[test]
+        fn issuer_get_credential_record_works_for_unknown_record() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_get_credential_record(wallet_handle, "unknown_record_id");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_fetch_credential_records {
+        use super::*;
+
+        #[test]
+        fn issuer_fetch_credential_records_works_for_closed_search() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (search_handle, _) = anoncreds::issuer_search_credential_records(wallet_handle, "{}").unwrap();
+            anoncreds::issuer_close_credential_records_search(search_handle).unwrap();
+
+            let res = anoncreds::issuer_fetch_credential_records(search_handle, 1);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -2005,6 +2005,617 @@ mod demos {
         wallet::close_and_delete_wallet(prover_3_wallet_handle, &prover_3_wallet_config).unwrap();
     }
 
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_on_demand_revocation_strategy_batch_issuance_and_revocation() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_on_demand_revocation_strategy_batch_issuance_and_revocation_issuer").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_on_demand_revocation_strategy_batch_issuance_and_revocation_prover").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (schema_id, schema_json,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer creates Credential Offer
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        //6. Prover creates Credential Requests
+        let (cred_req_1_json, cred_req_1_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                  DID_MY1,
+                                                                                                  &cred_offer_json,
+                                                                                                  &cred_def_json,
+                                                                                                  COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_req_2_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                           DID_MY1,
+                                                                           &cred_offer_json,
+                                                                           &cred_def_json,
+                                                                           COMMON_MASTER_SECRET).unwrap();
+
+        //7. Issuer creates Credentials by one call
+        let cred_issuance_requests_json = json!([
+            {"cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_1_json).unwrap(), "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap()},
+            {"cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_2_json).unwrap(), "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt2_credential_values_json()).unwrap()}
+        ]).to_string();
+
+        let (creds_json, revoc_reg_delta_json) = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                                                      &cred_offer_json,
+                                                                                      &cred_issuance_requests_json,
+                                                                                      Some(&rev_reg_id),
+                                                                                      Some(blob_storage_reader_handle)).unwrap();
+        let revoc_reg_delta_json = revoc_reg_delta_json.unwrap();
+
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(json!("1"), creds[0]["cred_rev_id"]);
+        assert_eq!(json!("2"), creds[1]["cred_rev_id"]);
+
+        let revoc_reg_delta: serde_json::Value = serde_json::from_str(&revoc_reg_delta_json).unwrap();
+        assert_eq!(json!([1, 2]), revoc_reg_delta["value"]["issued"]);
+
+        //8. Prover stores the first Credential
+        anoncreds::prover_store_credential(prover_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &cred_req_1_metadata_json,
+                                           &creds[0]["cred"].to_string(),
+                                           &cred_def_json,
+                                           Some(&revoc_reg_def_json)).unwrap();
+
+        //9. Prover creates RevocationState and Proof
+        let proof_request = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name"
+               })
+           }),
+           "requested_predicates": json!({}),
+           "non_revoked": json!({ "from":80, "to":100 })
+        }).to_string();
+
+        let timestamp = 100;
+        let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                &revoc_reg_def_json,
+                                                                &revoc_reg_delta_json,
+                                                                timestamp,
+                                                                "1").unwrap();
+
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "timestamp":timestamp, "revealed":true })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()
+        }).to_string();
+
+        let rev_states_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationState>(&rev_state_json).unwrap()
+            })
+        }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_request,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //10. Verifier verifies proof
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //11. Issuer revokes both Credentials by one call
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        r#"["1", "2"]"#).unwrap();
+
+        let revoc_reg_delta: serde_json::Value = serde_json::from_str(&revoc_reg_delta_json).unwrap();
+        assert_eq!(json!([1, 2]), revoc_reg_delta["value"]["revoked"]);
+
+        //12. Verifier verifies proof after that was revoked
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(!valid);
+
+        //13. Issuer can't revoke the same Credentials again
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                       blob_storage_reader_handle,
+                                                       &rev_reg_id,
+                                                       r#"["1"]"#);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_default_revocation_strategy_batch_issuance() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_batch_issuance_issuer").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_batch_issuance_prover").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, rev_reg_entry_json,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":3, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer creates Credential Offer
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        //6. Prover creates Credential Request
+        let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                              DID_MY1,
+                                                                                              &cred_offer_json,
+                                                                                              &cred_def_json,
+                                                                                              COMMON_MASTER_SECRET).unwrap();
+
+        let cred_issuance_request = json!({
+            "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap()
+        });
+
+        //7. Issuer creates Credentials by one call
+        let (creds_json, revoc_reg_delta_json) = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                                                      &cred_offer_json,
+                                                                                      &json!([cred_issuance_request, cred_issuance_request]).to_string(),
+                                                                                      Some(&rev_reg_id),
+                                                                                      Some(blob_storage_reader_handle)).unwrap();
+        assert!(revoc_reg_delta_json.is_none());
+
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(json!("1"), creds[0]["cred_rev_id"]);
+        assert_eq!(json!("2"), creds[1]["cred_rev_id"]);
+
+        //8. Prover stores Credentials
+        for (i, cred) in creds.iter().enumerate() {
+            anoncreds::prover_store_credential(prover_wallet_handle,
+                                               &format!("{}_{}", CREDENTIAL1_ID, i),
+                                               &cred_req_metadata_json,
+                                               &cred["cred"].to_string(),
+                                               &cred_def_json,
+                                               Some(&revoc_reg_def_json)).unwrap();
+        }
+
+        //9. Prover creates RevocationState for the second Credential
+        anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                           &revoc_reg_def_json,
+                                           &rev_reg_entry_json,
+                                           100,
+                                           "2").unwrap();
+
+        //10. Issuer can't issue more Credentials than left in Revocation Registry
+        let res = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                       &cred_offer_json,
+                                                       &json!([cred_issuance_request, cred_issuance_request]).to_string(),
+                                                       Some(&rev_reg_id),
+                                                       Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_batch_issuance_with_invalid_credential_values() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_with_invalid_credential_values_issuer").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_with_invalid_credential_values_prover").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, _, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
+
+        //4. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        let cred_req: serde_json::Value = serde_json::from_str(&cred_req_json).unwrap();
+        let valid_request = json!({
+            "cred_req": cred_req,
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap()
+        });
+        let invalid_request = json!({
+            "cred_req": cred_req,
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::xyz_credential_values_json()).unwrap()
+        });
+
+        //5. Issuer can't create Credentials if values of any request don't match Credential Definition
+        let res = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                       &cred_offer_json,
+                                                       &json!([valid_request, invalid_request]).to_string(),
+                                                       Some(&rev_reg_id),
+                                                       Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        //6. Indexes of Revocation Registry are not consumed by the failed call
+        let (creds_json, _) = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                                   &cred_offer_json,
+                                                                   &json!([valid_request, valid_request]).to_string(),
+                                                                   Some(&rev_reg_id),
+                                                                   Some(blob_storage_reader_handle)).unwrap();
+
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(json!("1"), creds[0]["cred_rev_id"]);
+        assert_eq!(json!("2"), creds[1]["cred_rev_id"]);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_pool_rotation() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_rotation_issuer").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_rotation_prover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //4. Issuer creates Revocation Registry Pool with one pre-generated Registry
+        let tails_writer_config: serde_json::Value = serde_json::from_str(&anoncreds::tails_writer_config()).unwrap();
+        let pool_config = json!({
+            "issuance_type": "ISSUANCE_ON_DEMAND",
+            "max_cred_num": 2,
+            "pregenerated": 1,
+            "threshold": 1,
+            "tails_writer_config": tails_writer_config.clone()
+        }).to_string();
+
+        let rev_regs_json = anoncreds::issuer_create_revocation_registry_pool(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              &cred_def_id,
+                                                                              &pool_config).unwrap();
+        let rev_regs: Vec<serde_json::Value> = serde_json::from_str(&rev_regs_json).unwrap();
+        assert_eq!(2, rev_regs.len());
+
+        let rev_reg_1_id = rev_regs[0]["rev_reg_id"].as_str().unwrap().to_string();
+        let rev_reg_2_id = rev_regs[1]["rev_reg_id"].as_str().unwrap().to_string();
+
+        //5. Issuer can't create the second Pool for the same Credential Definition
+        let res = anoncreds::issuer_create_revocation_registry_pool(issuer_wallet_handle,
+                                                                    ISSUER_DID,
+                                                                    &cred_def_id,
+                                                                    &pool_config);
+        assert_code!(ErrorCode::CommonInvalidState, res);
+
+        //6. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                              DID_MY1,
+                                                                                              &cred_offer_json,
+                                                                                              &cred_def_json,
+                                                                                              COMMON_MASTER_SECRET).unwrap();
+
+        //7. Issuer issues two Credentials filling the first Registry, the last one rotates the Pool and creates the next ready Registry
+        let mut created_rev_regs = Vec::new();
+        for cred_rev_id in &["1", "2"] {
+            let issued_cred_json = anoncreds::issuer_create_credential_from_pool(issuer_wallet_handle,
+                                                                                 &cred_offer_json,
+                                                                                 &cred_req_json,
+                                                                                 &anoncreds::gvt_credential_values_json()).unwrap();
+            let issued_cred: serde_json::Value = serde_json::from_str(&issued_cred_json).unwrap();
+            assert_eq!(json!(rev_reg_1_id), issued_cred["rev_reg_id"]);
+            assert_eq!(json!(cred_rev_id), issued_cred["cred_rev_id"]);
+            created_rev_regs.extend(issued_cred["created_rev_regs"].as_array().unwrap().clone());
+        }
+        assert_eq!(1, created_rev_regs.len());
+
+        //8. Issuer issues Credential from the second Registry
+        let issued_cred_json = anoncreds::issuer_create_credential_from_pool(issuer_wallet_handle,
+                                                                             &cred_offer_json,
+                                                                             &cred_req_json,
+                                                                             &anoncreds::gvt_credential_values_json()).unwrap();
+        let issued_cred: serde_json::Value = serde_json::from_str(&issued_cred_json).unwrap();
+        assert_eq!(json!(rev_reg_2_id), issued_cred["rev_reg_id"]);
+        assert_eq!(json!("1"), issued_cred["cred_rev_id"]);
+        assert_eq!(json!([]), issued_cred["created_rev_regs"]);
+
+        //9. Prover stores Credential of the second Registry
+        anoncreds::prover_store_credential(prover_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &cred_req_metadata_json,
+                                           &issued_cred["cred"].to_string(),
+                                           &cred_def_json,
+                                           Some(&rev_regs[1]["rev_reg_def"].to_string())).unwrap();
+
+        //10. Issuer revokes Credential of the first Registry
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config.to_string()).unwrap();
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_1_id, "2").unwrap();
+
+        //11. Issuer gets state of the Pool
+        let pool_json = anoncreds::issuer_get_revocation_registry_pool(issuer_wallet_handle, &cred_def_id).unwrap();
+        let pool: serde_json::Value = serde_json::from_str(&pool_json).unwrap();
+
+        let registries = pool["registries"].as_array().unwrap();
+        assert_eq!(3, registries.len());
+
+        assert_eq!(json!(rev_reg_1_id), registries[0]["rev_reg_id"]);
+        assert_eq!(json!("full"), registries[0]["state"]);
+        assert_eq!(json!(2), registries[0]["issued"]);
+        assert_eq!(json!([2]), registries[0]["revoked"]);
+
+        assert_eq!(json!(rev_reg_2_id), registries[1]["rev_reg_id"]);
+        assert_eq!(json!("active"), registries[1]["state"]);
+        assert_eq!(json!(1), registries[1]["issued"]);
+        assert_eq!(json!([]), registries[1]["revoked"]);
+
+        assert_eq!(created_rev_regs[0]["rev_reg_id"], registries[2]["rev_reg_id"]);
+        assert_eq!(json!("ready"), registries[2]["state"]);
+        assert_eq!(json!(0), registries[2]["issued"]);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_pool_retry_on_full_registry() {
+        Setup::empty();
+
+        //1. Issuer and Prover create wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_retry_on_full_registry_issuer").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_retry_on_full_registry_prover").unwrap();
+
+        //2. Issuer creates Schema and Credential Definition
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //3. Issuer creates Revocation Registry Pool
+        let tails_writer_config: serde_json::Value = serde_json::from_str(&anoncreds::tails_writer_config()).unwrap();
+        let pool_config = json!({
+            "issuance_type": "ISSUANCE_ON_DEMAND",
+            "max_cred_num": 1,
+            "pregenerated": 1,
+            "tails_writer_config": tails_writer_config.clone()
+        }).to_string();
+
+        let rev_regs_json = anoncreds::issuer_create_revocation_registry_pool(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              &cred_def_id,
+                                                                              &pool_config).unwrap();
+        let rev_regs: Vec<serde_json::Value> = serde_json::from_str(&rev_regs_json).unwrap();
+        let rev_reg_1_id = rev_regs[0]["rev_reg_id"].as_str().unwrap().to_string();
+        let rev_reg_2_id = rev_regs[1]["rev_reg_id"].as_str().unwrap().to_string();
+
+        //4. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer fills the active Registry bypassing the Pool
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config.to_string()).unwrap();
+        anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                            &cred_offer_json,
+                                            &cred_req_json,
+                                            &anoncreds::gvt_credential_values_json(),
+                                            Some(&rev_reg_1_id),
+                                            Some(blob_storage_reader_handle)).unwrap();
+
+        //6. Issuance from the Pool rotates the full Registry and is retried with the next one
+        let issued_cred_json = anoncreds::issuer_create_credential_from_pool(issuer_wallet_handle,
+                                                                             &cred_offer_json,
+                                                                             &cred_req_json,
+                                                                             &anoncreds::gvt_credential_values_json()).unwrap();
+        let issued_cred: serde_json::Value = serde_json::from_str(&issued_cred_json).unwrap();
+        assert_eq!(json!(rev_reg_2_id), issued_cred["rev_reg_id"]);
+        assert_eq!(json!("1"), issued_cred["cred_rev_id"]);
+
+        //7. The Pool has no full active Registry and keeps a ready one
+        let pool_json = anoncreds::issuer_get_revocation_registry_pool(issuer_wallet_handle, &cred_def_id).unwrap();
+        let pool: serde_json::Value = serde_json::from_str(&pool_json).unwrap();
+
+        let states = pool["registries"].as_array().unwrap().iter().map(|registry| registry["state"].clone()).collect::<Vec<_>>();
+        assert_eq!(vec![json!("full"), json!("full"), json!("active"), json!("ready")], states);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_credential_issuance_records() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_issuance_records_issuer").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_issuance_records_prover").unwrap();
+
+        //3. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _, cred_def_id, cred_def_json, rev_reg_id, _, _, blob_storage_reader_handle) =
+            anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                 ISSUER_DID,
+                                                                 GVT_SCHEMA_NAME,
+                                                                 GVT_SCHEMA_ATTRIBUTES,
+                                                                 r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues tagged Credential and plain Credential
+        let (_, cred_rev_id, _) = anoncreds::issuer_create_credential_with_tags(issuer_wallet_handle,
+                                                                                &cred_offer_json,
+                                                                                &cred_req_json,
+                                                                                &anoncreds::gvt_credential_values_json(),
+                                                                                Some(&rev_reg_id),
+                                                                                Some(blob_storage_reader_handle),
+                                                                                Some(r#"{"holder": "alice"}"#)).unwrap();
+        let cred_rev_id = cred_rev_id.unwrap();
+
+        anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                            &cred_offer_json,
+                                            &cred_req_json,
+                                            &anoncreds::gvt_credential_values_json(),
+                                            Some(&rev_reg_id),
+                                            Some(blob_storage_reader_handle)).unwrap();
+
+        //6. Issuer searches all issuance records of Credential Definition
+        let (search_handle, total_count) = anoncreds::issuer_search_credential_records(issuer_wallet_handle,
+                                                                                        &json!({"cred_def_id": cred_def_id}).to_string()).unwrap();
+        assert_eq!(2, total_count);
+        anoncreds::issuer_close_credential_records_search(search_handle).unwrap();
+
+        //7. Issuer searches issuance records by custom tag
+        let (search_handle, total_count) = anoncreds::issuer_search_credential_records(issuer_wallet_handle, r#"{"holder": "alice"}"#).unwrap();
+        assert_eq!(1, total_count);
+
+        let records_json = anoncreds::issuer_fetch_credential_records(search_handle, 10).unwrap();
+        let records: Vec<serde_json::Value> = serde_json::from_str(&records_json).unwrap();
+        assert_eq!(1, records.len());
+
+        let record_id = records[0]["record_id"].as_str().unwrap().to_string();
+        assert_eq!(format!("{}:{}", rev_reg_id, cred_rev_id), record_id);
+        assert_eq!(json!(cred_rev_id), records[0]["cred_rev_id"]);
+        assert_eq!(json!(DID_MY1), records[0]["prover_did"]);
+        assert_eq!(json!(false), records[0]["revoked"]);
+        assert_eq!(json!({"holder": "alice"}), records[0]["tags"]);
+
+        anoncreds::issuer_close_credential_records_search(search_handle).unwrap();
+
+        //8. Issuer revokes Credential by issuance record
+        anoncreds::issuer_revoke_credential_by_record(issuer_wallet_handle, blob_storage_reader_handle, &record_id).unwrap();
+
+        let record_json = anoncreds::issuer_get_credential_record(issuer_wallet_handle, &record_id).unwrap();
+        let record: serde_json::Value = serde_json::from_str(&record_json).unwrap();
+        assert_eq!(json!(true), record["revoked"]);
+        assert!(record["revoked_timestamp"].is_u64());
+
+        //9. Issuer can't revoke the same Credential twice
+        let res = anoncreds::issuer_revoke_credential_by_record(issuer_wallet_handle, blob_storage_reader_handle, &record_id);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        //10. Issuer searches revoked issuance records
+        let (search_handle, total_count) = anoncreds::issuer_search_credential_records(issuer_wallet_handle, r#"{"revoked": "1"}"#).unwrap();
+        assert_eq!(1, total_count);
+        anoncreds::issuer_close_credential_records_search(search_handle).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[cfg(feature = "revocation_tests")]
     #[test]
     fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_not_issued_credential_id() {
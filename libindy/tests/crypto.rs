@@ -326,6 +326,63 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod key_provider {
+        use super::*;
+
+        #[test]
+        fn indy_create_key_works_for_key_provider() {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_for_key_provider(setup.wallet_handle, None).unwrap();
+            assert!(verkey.ends_with(&format!(":{}", crypto::KEY_PROVIDER_CRYPTO_TYPE)));
+        }
+
+        #[test]
+        fn indy_crypto_sign_works_for_key_provider() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key_for_key_provider(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            assert_eq!(format!("{}:{}", VERKEY_MY1, crypto::KEY_PROVIDER_CRYPTO_TYPE), my_vk);
+
+            let signature = crypto::sign(setup.wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+
+            assert!(crypto::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_and_auth_decrypt_works_for_key_provider() {
+            let sender_setup = Setup::wallet();
+            let recipient_setup = Setup::key();
+
+            let sender_vk = crypto::create_key_for_key_provider(sender_setup.wallet_handle, None).unwrap();
+
+            let encrypted_msg = crypto::auth_crypt(sender_setup.wallet_handle, &sender_vk, &recipient_setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            let (vk, msg) = crypto::auth_decrypt(recipient_setup.wallet_handle, &recipient_setup.verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+            assert_eq!(sender_vk, vk);
+
+            let encrypted_msg = crypto::auth_crypt(recipient_setup.wallet_handle, &recipient_setup.verkey, &sender_vk, MESSAGE.as_bytes()).unwrap();
+
+            let (vk, msg) = crypto::auth_decrypt(sender_setup.wallet_handle, &sender_vk, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+            assert_eq!(recipient_setup.verkey, vk);
+        }
+
+        #[test]
+        fn indy_crypto_anon_decrypt_works_for_key_provider() {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_for_key_provider(setup.wallet_handle, None).unwrap();
+
+            let encrypted_msg = crypto::anon_crypt(&verkey, MESSAGE.as_bytes()).unwrap();
+
+            let msg = crypto::anon_decrypt(setup.wallet_handle, &verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -343,6 +400,13 @@ mod medium_cases {
             let res = crypto::create_key(INVALID_WALLET_HANDLE, None);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_create_key_works_for_unknown_crypto_type() {
+            let setup = Setup::wallet();
+            let res = crypto::create_key_with_crypto_type(setup.wallet_handle, None, "unknown_key_provider");
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
     }
 
     mod set_key_metadata {
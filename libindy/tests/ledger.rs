@@ -131,6 +131,40 @@ mod high_cases {
         }
     }
 
+    mod submit_cached_request {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_cached_request_works() {
+            let setup = Setup::pool();
+
+            let (schema_id, _, _) = ledger::post_entities();
+
+            let get_schema_request = ledger::build_get_schema_request(Some(DID_MY1), &schema_id).unwrap();
+            let get_schema_response = ledger::submit_cached_request(setup.pool_handle, &get_schema_request, "{}").unwrap();
+            let (_, schema_json) = ledger::parse_get_schema_response(&get_schema_response).unwrap();
+            let _schema: SchemaV1 = serde_json::from_str(&schema_json).unwrap();
+
+            let get_schema_request = ledger::build_get_schema_request(Some(DID_MY1), &schema_id).unwrap();
+            let cached_response = ledger::submit_cached_request(setup.pool_handle, &get_schema_request, r#"{"noUpdate": true}"#).unwrap();
+            assert_eq!(get_schema_response, cached_response);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_cached_request_works_for_no_store() {
+            let setup = Setup::pool();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let get_nym_response = ledger::submit_cached_request(setup.pool_handle, &get_nym_request, r#"{"noStore": true}"#).unwrap();
+            pool::check_response_type(&get_nym_response, ResponseType::REPLY);
+
+            let res = ledger::submit_cached_request(setup.pool_handle, &get_nym_request, r#"{"noUpdate": true}"#);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+    }
+
     mod submit_action {
         use super::*;
 
@@ -888,6 +922,31 @@ mod high_cases {
             let expected_schema_data: SchemaData = serde_json::from_str(r#"{"name":"gvt","version":"1.0","attr_names":["name", "age", "sex", "height"]}"#).unwrap();
             assert_eq!(expected_schema_data, get_txn_schema_data);
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_verified_txn_works() {
+            let setup = Setup::new_identity();
+
+            let schema_request = ledger::build_schema_request(&setup.did, &anoncreds::gvt_schema_json()).unwrap();
+            let schema_response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+            pool::check_response_type(&schema_response, ResponseType::REPLY);
+
+            let seq_no = ledger::extract_seq_no_from_reply(&schema_response).unwrap() as i32;
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let txn = ledger::get_verified_txn(setup.pool_handle, Some(&setup.did), seq_no, None).unwrap();
+            let txn: serde_json::Value = serde_json::from_str(&txn).unwrap();
+
+            assert_eq!(true, txn["verified"]);
+            assert_eq!(seq_no as u64, txn["seq_no"].as_u64().unwrap());
+            assert!(txn["root_hash"].is_string());
+
+            let get_txn_schema_data: SchemaData = serde_json::from_value(txn["txn"]["txn"]["data"]["data"].clone()).unwrap();
+            let expected_schema_data: SchemaData = serde_json::from_str(r#"{"name":"gvt","version":"1.0","attr_names":["name", "age", "sex", "height"]}"#).unwrap();
+            assert_eq!(expected_schema_data, get_txn_schema_data);
+        }
     }
 
     mod pool_config {
@@ -2881,6 +2940,38 @@ mod medium_cases {
         }
     }
 
+    mod submit_cached_request {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_cached_request_works_for_no_update_and_empty_cache() {
+            let setup = Setup::pool();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_MY1).unwrap();
+            let res = ledger::submit_cached_request(setup.pool_handle, &get_nym_request, r#"{"noUpdate": true}"#);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_cached_request_works_for_invalid_options() {
+            let setup = Setup::pool();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_MY1).unwrap();
+            let res = ledger::submit_cached_request(setup.pool_handle, &get_nym_request, r#"{"noUpdate": "yes"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_submit_cached_request_works_for_invalid_pool_handle() {
+            Setup::empty();
+
+            let res = ledger::submit_cached_request(INVALID_POOL_HANDLE, REQUEST, "{}");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
     mod submit_action {
         use super::*;
 
@@ -3611,6 +3702,23 @@ mod medium_cases {
             let get_txn_response: Reply<GetTxnResult> = serde_json::from_str(&get_txn_response).unwrap();
             assert!(get_txn_response.result.data.is_none());
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_verified_txn_works_for_invalid_seq_no() {
+            let setup = Setup::pool();
+
+            let res = ledger::get_verified_txn(setup.pool_handle, None, i32::max_value(), None);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_get_verified_txn_works_for_invalid_pool_handle() {
+            Setup::empty();
+
+            let res = ledger::get_verified_txn(INVALID_POOL_HANDLE, None, 1, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
     }
 
     mod revoc_reg_def_requests {
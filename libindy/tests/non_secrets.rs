@@ -12,6 +12,9 @@ use crate::utils::non_secrets::*;
 use crate::utils::types::{WalletRecord, SearchRecords};
 
 use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::sync::Mutex;
 
 use self::indy::ErrorCode;
 
@@ -20,6 +23,24 @@ pub const FORBIDDEN_TYPE: &'static str = "Indy::Test";
 use crate::utils::test::cleanup_wallet;
 use crate::utils::Setup;
 
+lazy_static! {
+    static ref RECORDS_EVENTS: Mutex<Vec<(i32, serde_json::Value)>> = Default::default();
+}
+
+extern fn records_event_cb(subscription_handle: i32, event_json: *const c_char) {
+    let event_json = unsafe { CStr::from_ptr(event_json) }.to_str().unwrap();
+    let event = serde_json::from_str(event_json).unwrap();
+    RECORDS_EVENTS.lock().unwrap().push((subscription_handle, event));
+}
+
+fn records_events(subscription_handle: i32) -> Vec<serde_json::Value> {
+    RECORDS_EVENTS.lock().unwrap()
+        .iter()
+        .filter(|(handle, _)| *handle == subscription_handle)
+        .map(|(_, event)| event.clone())
+        .collect()
+}
+
 mod high_cases {
     use super::*;
 
@@ -231,6 +252,53 @@ mod high_cases {
         }
     }
 
+    mod batch_records {
+        use super::*;
+
+        #[test]
+        fn indy_batch_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID, "value": VALUE},
+                {"op": "update_tags", "type": TYPE, "id": ID, "tags": tags_1()},
+                {"op": "delete", "type": TYPE, "id": ID_2},
+            ]).to_string();
+
+            batch_wallet_records(setup.wallet_handle, &operations).unwrap();
+
+            let record = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_FULL).unwrap();
+            let record: WalletRecord = serde_json::from_str(&record).unwrap();
+            assert_eq!(record_1(), record);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_batch_wallet_records_works_for_failed_operation() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID, "value": VALUE},
+                {"op": "delete", "type": TYPE, "id": ID_2},
+                {"op": "update", "type": TYPE, "id": ID_3, "value": VALUE_3},
+            ]).to_string();
+
+            let res = batch_wallet_records(setup.wallet_handle, &operations);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY).unwrap();
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -819,6 +887,60 @@ mod high_cases {
                 tear_down(wallet_handle, search_handle);
                 cleanup_wallet("indy_wallet_search_for_retrieve_records_only");
             }
+
+            #[test]
+            fn indy_wallet_search_for_sorting_and_paging() {
+                const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_wallet_search_for_sorting_and_paging"}"#;
+                let wallet_handle = setup("indy_wallet_search_for_sorting_and_paging", SEARCH_WALLET_CONFIG);
+
+                let options = json!({
+                    "retrieveTotalCount": true,
+                    "sortBy": "~tagName3",
+                    "sortOrder": "desc",
+                    "offset": 1,
+                    "limit": 2
+                }).to_string();
+
+                let search_handle = open_wallet_search(wallet_handle, TYPE, QUERY_EMPTY, &options).unwrap();
+
+                let search_records = fetch_wallet_search_next_records(wallet_handle, search_handle, 5).unwrap();
+
+                let search_records: SearchRecords = serde_json::from_str(&search_records).unwrap();
+                assert_eq!(5, search_records.total_count.unwrap());
+
+                let ids = search_records.records.unwrap().into_iter().map(|record| record.id).collect::<Vec<String>>();
+                assert_eq!(vec![ID_5.to_string(), ID_4.to_string()], ids);
+
+                tear_down(wallet_handle, search_handle);
+                cleanup_wallet("indy_wallet_search_for_sorting_and_paging");
+            }
+
+            #[test]
+            fn indy_wallet_search_for_tags_projection() {
+                const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_wallet_search_for_tags_projection"}"#;
+                let wallet_handle = setup("indy_wallet_search_for_tags_projection", SEARCH_WALLET_CONFIG);
+
+                let options = json!({
+                    "retrieveTags": true,
+                    "tagNames": ["tagName1", "~tagName3"],
+                    "sortBy": "~tagName3",
+                    "limit": 1
+                }).to_string();
+
+                let search_handle = open_wallet_search(wallet_handle, TYPE, QUERY_EMPTY, &options).unwrap();
+
+                let records = fetch_wallet_search_next_records(wallet_handle, search_handle, 5).unwrap();
+
+                // record without ~tagName3 goes first
+                let mut tags = HashMap::new();
+                tags.insert("tagName1".to_string(), "str1".to_string());
+
+                check_search_records(&records, vec![
+                    WalletRecord { id: ID_3.to_string(), type_: None, value: Some(VALUE_3.to_string()), tags: Some(tags) }]);
+
+                tear_down(wallet_handle, search_handle);
+                cleanup_wallet("indy_wallet_search_for_tags_projection");
+            }
         }
 
         mod close {
@@ -853,6 +975,60 @@ mod high_cases {
             }
         }
     }
+
+    mod subscribe_records {
+        use super::*;
+
+        #[test]
+        fn indy_subscribe_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            let subscription_handle = subscribe_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, records_event_cb).unwrap();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+            update_wallet_record_value(setup.wallet_handle, TYPE, ID, VALUE_2).unwrap();
+            delete_wallet_record(setup.wallet_handle, TYPE, ID).unwrap();
+
+            let expected_tags: serde_json::Value = serde_json::from_str(TAGS).unwrap();
+            let expected_events = vec![
+                json!({"event": "add", "type": TYPE, "id": ID, "tags": expected_tags}),
+                json!({"event": "update", "type": TYPE, "id": ID, "tags": expected_tags}),
+                json!({"event": "delete", "type": TYPE, "id": ID, "tags": expected_tags}),
+            ];
+            assert_eq!(expected_events, records_events(subscription_handle));
+
+            unsubscribe_wallet_records(subscription_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_subscribe_wallet_records_works_for_query() {
+            let setup = Setup::wallet();
+
+            let subscription_handle = subscribe_wallet_records(setup.wallet_handle, TYPE, r#"{"tagName1":"str2"}"#, records_event_cb).unwrap();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE, Some(TAGS_2)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE_2, ID, VALUE, Some(TAGS_2)).unwrap();
+
+            let events = records_events(subscription_handle);
+            assert_eq!(1, events.len());
+            assert_eq!(ID_2, events[0]["id"].as_str().unwrap());
+
+            unsubscribe_wallet_records(subscription_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_unsubscribe_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            let subscription_handle = subscribe_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, records_event_cb).unwrap();
+            unsubscribe_wallet_records(subscription_handle).unwrap();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            assert!(records_events(subscription_handle).is_empty());
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -1119,6 +1295,44 @@ mod medium_cases {
         }
     }
 
+    mod batch_records {
+        use super::*;
+
+        #[test]
+        fn indy_batch_wallet_records_works_for_invalid_handle() {
+            Setup::empty();
+
+            let operations = json!([{"op": "delete", "type": TYPE, "id": ID}]).to_string();
+
+            let res = batch_wallet_records(INVALID_WALLET_HANDLE, &operations);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_batch_wallet_records_works_for_invalid_operations() {
+            let setup = Setup::wallet();
+
+            let res = batch_wallet_records(setup.wallet_handle, r#"[{"op": "unknown", "type": "TestType", "id": "RecordId"}]"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_batch_wallet_records_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID, "value": VALUE},
+                {"op": "add", "type": FORBIDDEN_TYPE, "id": ID, "value": VALUE},
+            ]).to_string();
+
+            let res = batch_wallet_records(setup.wallet_handle, &operations);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -1193,6 +1407,30 @@ mod medium_cases {
             tear_down(wallet_handle, search_handle);
         }
 
+        #[test]
+        fn indy_wallet_search_for_sorting_by_encrypted_tag() {
+            const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_wallet_search_for_sorting_by_encrypted_tag"}"#;
+            let wallet_handle = setup("indy_wallet_search_for_sorting_by_encrypted_tag", SEARCH_WALLET_CONFIG);
+
+            let res = open_wallet_search(wallet_handle, TYPE, QUERY_EMPTY, r#"{"sortBy": "tagName1"}"#);
+            assert_code!(ErrorCode::WalletQueryError, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            cleanup_wallet("indy_wallet_search_for_sorting_by_encrypted_tag");
+        }
+
+        #[test]
+        fn indy_wallet_search_for_invalid_sort_order() {
+            const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_wallet_search_for_invalid_sort_order"}"#;
+            let wallet_handle = setup("indy_wallet_search_for_invalid_sort_order", SEARCH_WALLET_CONFIG);
+
+            let res = open_wallet_search(wallet_handle, TYPE, QUERY_EMPTY, r#"{"sortBy": "~tagName2", "sortOrder": "random"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            cleanup_wallet("indy_wallet_search_for_invalid_sort_order");
+        }
+
         #[test]
         fn indy_wallet_search_for_invalid_wallet_handle() {
             const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_wallet_search_for_invalid_wallet_handle"}"#;
@@ -1247,6 +1485,70 @@ mod medium_cases {
         }
     }
 
+    mod subscribe_records {
+        use super::*;
+
+        #[test]
+        fn indy_subscribe_wallet_records_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = subscribe_wallet_records(INVALID_WALLET_HANDLE, TYPE, QUERY_EMPTY, records_event_cb);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_subscribe_wallet_records_works_for_invalid_query() {
+            let setup = Setup::wallet();
+
+            let res = subscribe_wallet_records(setup.wallet_handle, TYPE, r#"{"tagName1": {"$unknown": "str1"}}"#, records_event_cb);
+            assert_code!(ErrorCode::WalletQueryError, res);
+        }
+
+        #[test]
+        fn indy_subscribe_wallet_records_works_for_tags_changes() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let subscription_handle = subscribe_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, records_event_cb).unwrap();
+
+            add_wallet_record_tags(setup.wallet_handle, TYPE, ID, TAGS).unwrap();
+            delete_wallet_record_tags(setup.wallet_handle, TYPE, ID, r#"["tagName1"]"#).unwrap();
+
+            let events = records_events(subscription_handle);
+            assert_eq!(vec!["add_tags", "delete_tags"], events.iter().map(|event| event["event"].as_str().unwrap()).collect::<Vec<&str>>());
+            assert_eq!(json!({"~tagName2": "5", "~tagName3": "8"}), events[1]["tags"]);
+
+            unsubscribe_wallet_records(subscription_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_unsubscribe_wallet_records_works_for_twice() {
+            let setup = Setup::wallet();
+
+            let subscription_handle = subscribe_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, records_event_cb).unwrap();
+            unsubscribe_wallet_records(subscription_handle).unwrap();
+
+            let res = unsubscribe_wallet_records(subscription_handle);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_unsubscribe_wallet_records_works_for_closed_wallet() {
+            let setup = Setup::empty();
+
+            let (wallet_handle, config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let subscription_handle = subscribe_wallet_records(wallet_handle, TYPE, QUERY_EMPTY, records_event_cb).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let res = unsubscribe_wallet_records(subscription_handle);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+    }
+
     mod rusqlite_transaction_fix {
         use super::*;
 
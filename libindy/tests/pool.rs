@@ -6,12 +6,44 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, INVALID_POOL_HANDLE};
 
-use crate::utils::{environment, pool};
+use crate::utils::{did, environment, ledger, pool};
 use crate::utils::constants::*;
+use crate::utils::types::ResponseType;
 use crate::utils::Setup;
 
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+lazy_static! {
+    static ref NODES_UPDATES: Mutex<Vec<(i32, serde_json::Value)>> = Default::default();
+}
+
+extern fn nodes_update_cb(pool_handle: i32, nodes_diff_json: *const c_char) {
+    let nodes_diff_json = unsafe { CStr::from_ptr(nodes_diff_json) }.to_str().unwrap();
+    let nodes_diff = serde_json::from_str(nodes_diff_json).unwrap();
+    NODES_UPDATES.lock().unwrap().push((pool_handle, nodes_diff));
+}
+
+fn wait_nodes_update(pool_handle: i32, timeout: Duration) -> Option<serde_json::Value> {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let update = NODES_UPDATES.lock().unwrap()
+            .iter()
+            .find(|&(handle, _)| *handle == pool_handle)
+            .map(|(_, diff)| diff.clone());
+        if update.is_some() {
+            return update;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    None
+}
+
 mod high_cases {
     use super::*;
 
@@ -72,6 +104,25 @@ mod high_cases {
             pool::open_pool_ledger(&setup.name, Some(config)).unwrap();
         }
 
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn open_pool_ledger_works_for_refresh_interval() {
+            let setup = Setup::empty();
+
+            let config = r#"{"refresh_interval": 1}"#;
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(config)).unwrap();
+
+            ::std::thread::sleep(::std::time::Duration::from_secs(3));
+
+            pool::refresh(pool_handle).unwrap();
+            pool::close(pool_handle).unwrap();
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn open_pool_ledger_works_for_two_nodes() {
@@ -119,6 +170,287 @@ mod high_cases {
             let setup = Setup::pool();
             pool::refresh(setup.pool_handle).unwrap();
         }
+
+        #[test]
+        fn indy_refresh_pool_ledger_works_for_requests_during_background_refresh() {
+            let setup = Setup::empty();
+
+            let slow = json!({"behavior": "slow", "delay": 200});
+            let config = json!({
+                "refresh_interval": 1,
+                "simulation": {"nodes": {"Node1": slow, "Node2": slow, "Node3": slow, "Node4": slow}},
+            });
+            let pool_handle = pool::create_and_open_simulated_pool_ledger(&setup.name, config).unwrap();
+
+            // requests sent while a background refresh is in progress are deferred until it finishes
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_secs(4) {
+                let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+                let response = ledger::submit_request(pool_handle, &request).unwrap();
+                pool::check_response_type(&response, ResponseType::REPLY);
+                thread::sleep(Duration::from_millis(150));
+            }
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod set_nodes_update_callback {
+        use super::*;
+
+        const NODE4_DEST: &str = "4PS3EDQ3dW1tci1Bp6543CfuuebjFrg36kLAUcskGfaA";
+
+        #[test]
+        fn indy_set_pool_nodes_update_callback_works_for_demoted_node() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::create_and_open_simulated_pool_ledger(&setup.name, json!({"refresh_interval": 1})).unwrap();
+            pool::set_nodes_update_callback(pool_handle, Some(nodes_update_cb)).unwrap();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(STEWARD_SEED)).unwrap();
+            let request = ledger::build_node_request(&did, NODE4_DEST, r#"{"alias":"Node4","services":[]}"#).unwrap();
+            let response = ledger::sign_and_submit_request(pool_handle, setup.wallet_handle, &did, &request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+
+            let diff = wait_nodes_update(pool_handle, Duration::from_secs(10)).unwrap();
+            assert_eq!(json!(["Node4"]), diff["removed"]);
+            assert_eq!(json!({}), diff["added"]);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod get_stats {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_pool_stats_works() {
+            let setup = Setup::pool();
+
+            let stats = pool::get_stats(setup.pool_handle).unwrap();
+            let stats: serde_json::Value = serde_json::from_str(&stats).unwrap();
+
+            assert!(stats["last_catchup"].is_i64());
+            assert_eq!(4, stats["nodes"].as_object().unwrap().len());
+            assert!(stats["nodes"]["Node1"]["sent"].as_u64().unwrap() > 0);
+        }
+    }
+
+    mod verify_reply_state_proof {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_verify_reply_state_proof_works() {
+            let setup = Setup::pool();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let reply = ledger::submit_request(setup.pool_handle, &request).unwrap();
+
+            let verdict = pool::verify_reply_state_proof(setup.pool_handle, None, &request, &reply).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+
+            assert_eq!(true, verdict["verified"]);
+            assert_eq!("105", verdict["txn_type"]);
+            assert_eq!(4, verdict["nodes_count"]);
+            assert!(verdict["last_signed_time"].is_u64());
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_verify_reply_state_proof_works_for_genesis_txn() {
+            let setup = Setup::pool();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let reply = ledger::submit_request(setup.pool_handle, &request).unwrap();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&format!("{}_genesis", setup.name), None, None);
+
+            let verdict = pool::verify_reply_state_proof(INVALID_POOL_HANDLE, Some(txn_file_path.to_str().unwrap()), &request, &reply).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+
+            assert_eq!(true, verdict["verified"]);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_verify_reply_state_proof_works_for_tampered_reply() {
+            let setup = Setup::pool();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let reply = ledger::submit_request(setup.pool_handle, &request).unwrap();
+
+            let mut reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+            let data = reply["result"]["data"].as_str().unwrap().replace(DID_TRUSTEE, DID);
+            reply["result"]["data"] = json!(data);
+
+            let verdict = pool::verify_reply_state_proof(setup.pool_handle, None, &request, &reply.to_string()).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+
+            assert_eq!(false, verdict["verified"]);
+            assert_eq!("invalid_proof", verdict["failure"]);
+        }
+
+        #[test]
+        fn indy_verify_reply_state_proof_works_for_simulated_get_nym() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::create_and_open_simulated_pool_ledger(&setup.name, json!({})).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let request = ledger::build_nym_request(&trustee_did, &my_did, Some(&my_verkey), None, None).unwrap();
+            let response = ledger::sign_and_submit_request(pool_handle, setup.wallet_handle, &trustee_did, &request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+
+            let request = ledger::build_get_nym_request(None, &my_did).unwrap();
+            let reply = ledger::submit_request(pool_handle, &request).unwrap();
+
+            let nym_data = ledger::parse_get_nym_response(&reply).unwrap();
+            let nym_data: serde_json::Value = serde_json::from_str(&nym_data).unwrap();
+            assert_eq!(my_verkey, nym_data["verkey"].as_str().unwrap());
+
+            let verdict = pool::verify_reply_state_proof(pool_handle, None, &request, &reply).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+            assert_eq!(true, verdict["verified"]);
+            assert_eq!(4, verdict["participants"].as_array().unwrap().len());
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_verify_reply_state_proof_works_for_simulated_get_schema() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::create_and_open_simulated_pool_ledger(&setup.name, json!({})).unwrap();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let request = ledger::build_schema_request(&did, SCHEMA_DATA).unwrap();
+            let response = ledger::sign_and_submit_request(pool_handle, setup.wallet_handle, &did, &request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+
+            let schema_id = format!("{}:2:{}:{}", did, GVT_SCHEMA_NAME, SCHEMA_VERSION);
+            let request = ledger::build_get_schema_request(None, &schema_id).unwrap();
+            let reply = ledger::submit_request(pool_handle, &request).unwrap();
+
+            let (parsed_schema_id, _) = ledger::parse_get_schema_response(&reply).unwrap();
+            assert_eq!(schema_id, parsed_schema_id);
+
+            let verdict = pool::verify_reply_state_proof(pool_handle, None, &request, &reply).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+            assert_eq!(true, verdict["verified"]);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod validate_genesis {
+        use super::*;
+
+        #[test]
+        fn indy_validate_pool_genesis_works() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let report = pool::validate_genesis(txn_file_path.to_str().unwrap(), PROTOCOL_VERSION).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert_eq!(4, report["validators_count"]);
+            assert_eq!(1, report["f"]);
+            assert_eq!(2, report["weak_quorum"]);
+            assert_eq!(3, report["strong_quorum"]);
+            assert_eq!(true, report["nodes"][0]["bls_key_pop_valid"]);
+        }
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_empty_lines() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_empty_lines(&setup.name, None);
+
+            let report = pool::validate_genesis(txn_file_path.to_str().unwrap(), PROTOCOL_VERSION).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert_eq!(4, report["txns_count"]);
+        }
+    }
+
+    mod pool_group {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_create_pool_group_works() {
+            let setup = Setup::pool();
+
+            let group_handle = pool::create_group(&json!({"routes": {"sov": setup.pool_handle}}).to_string()).unwrap();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let mut request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            request["operation"]["dest"] = json!(format!("did:sov:{}", DID_TRUSTEE));
+
+            let reply = ledger::submit_request(group_handle, &request.to_string()).unwrap();
+            let reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+            assert_eq!(DID_TRUSTEE, reply["result"]["dest"]);
+
+            pool::close_group(group_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_create_pool_group_works_for_default_pool() {
+            let setup = Setup::pool();
+
+            let group_handle = pool::create_group(&json!({"default": setup.pool_handle}).to_string()).unwrap();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let reply = ledger::submit_request(group_handle, &request).unwrap();
+            let reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+            assert_eq!("REPLY", reply["op"]);
+
+            pool::close_group(group_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_create_pool_group_works_for_sign_and_submit_request() {
+            let setup = Setup::trustee();
+
+            let group_handle = pool::create_group(&json!({"routes": {"sov": setup.pool_handle}}).to_string()).unwrap();
+
+            let request = ledger::build_get_nym_request(Some(&setup.did), DID_TRUSTEE).unwrap();
+            let mut request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            request["operation"]["dest"] = json!(format!("did:sov:{}", DID_TRUSTEE));
+
+            let reply = ledger::sign_and_submit_request(group_handle, setup.wallet_handle, &setup.did, &request.to_string()).unwrap();
+            let reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+            assert_eq!(DID_TRUSTEE, reply["result"]["dest"]);
+
+            pool::close_group(group_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_create_pool_group_works_for_submit_action() {
+            let setup = Setup::trustee();
+
+            let group_handle = pool::create_group(&json!({"default": setup.pool_handle}).to_string()).unwrap();
+
+            let request = ledger::build_get_validator_info_request(&setup.did).unwrap();
+            let request = ledger::sign_request(setup.wallet_handle, &setup.did, &request).unwrap();
+
+            let response = ledger::submit_action(group_handle, &request, Some(r#"["Node1"]"#), None).unwrap();
+            let responses: serde_json::Value = serde_json::from_str(&response).unwrap();
+            assert!(responses.get("Node1").is_some());
+
+            pool::close_group(group_handle).unwrap();
+        }
     }
 
     mod close {
@@ -331,6 +663,21 @@ mod medium_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn open_pool_ledger_works_for_invalid_refresh_interval() {
+            let setup = Setup::empty();
+
+            let config = r#"{"refresh_interval": 0}"#;
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let res = pool::open_pool_ledger(&setup.name, Some(config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn open_pool_ledger_works_for_incompatible_protocol_version() {
@@ -477,6 +824,184 @@ mod medium_cases {
         }
     }
 
+    mod set_nodes_update_callback {
+        use super::*;
+
+        #[test]
+        fn indy_set_pool_nodes_update_callback_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::set_nodes_update_callback(0, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
+    mod get_stats {
+        use super::*;
+
+        #[test]
+        fn indy_get_pool_stats_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::get_stats(0);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
+    mod verify_reply_state_proof {
+        use super::*;
+
+        #[test]
+        fn indy_verify_reply_state_proof_works_for_reply_without_state_proof() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let reply = json!({"op": "REPLY", "result": {"type": "105", "dest": DID_TRUSTEE, "data": null}}).to_string();
+
+            let verdict = pool::verify_reply_state_proof(INVALID_POOL_HANDLE, Some(txn_file_path.to_str().unwrap()), &request, &reply).unwrap();
+            let verdict: serde_json::Value = serde_json::from_str(&verdict).unwrap();
+
+            assert_eq!(false, verdict["verified"]);
+            assert_eq!(false, verdict["state_proof_present"]);
+            assert_eq!("no_state_proof", verdict["failure"]);
+        }
+
+        #[test]
+        fn indy_verify_reply_state_proof_works_for_no_pool_and_genesis_txn() {
+            Setup::empty();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+
+            let res = pool::verify_reply_state_proof(INVALID_POOL_HANDLE, None, &request, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_verify_reply_state_proof_works_for_invalid_handle() {
+            Setup::empty();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+
+            let res = pool::verify_reply_state_proof(i32::max_value(), None, &request, "{}");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
+    mod validate_genesis {
+        use super::*;
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_invalid_nodes() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool_with_invalid_nodes(&setup.name, None);
+
+            let report = pool::validate_genesis(txn_file_path.to_str().unwrap(), PROTOCOL_VERSION).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert!(report["errors"].as_array().unwrap().iter()
+                .any(|err| err.as_str().unwrap().contains("client address is not specified")));
+        }
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_wrong_ips() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool_with_wrong_ips(&setup.name, None);
+
+            let report = pool::validate_genesis(txn_file_path.to_str().unwrap(), PROTOCOL_VERSION).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(8, report["errors"].as_array().unwrap().len());
+        }
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_missed_file() {
+            Setup::empty();
+
+            let res = pool::validate_genesis("not_existing_genesis.txn", PROTOCOL_VERSION);
+            assert_code!(ErrorCode::CommonIOError, res);
+        }
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_other_protocol_version() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let report = pool::validate_genesis(txn_file_path.to_str().unwrap(), 1).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(false, report["valid"]);
+        }
+
+        #[test]
+        fn indy_validate_pool_genesis_works_for_unsupported_protocol_version() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let res = pool::validate_genesis(txn_file_path.to_str().unwrap(), 3);
+            assert_code!(ErrorCode::PoolIncompatibleProtocolVersion, res);
+        }
+    }
+
+    mod pool_group {
+        use super::*;
+
+        #[test]
+        fn indy_create_pool_group_works_for_empty_config() {
+            Setup::empty();
+
+            let res = pool::create_group("{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_pool_group_works_for_invalid_method() {
+            Setup::empty();
+
+            let res = pool::create_group(r#"{"routes": {"Sov": 1}}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_pool_group_works_for_invalid_pool_handle() {
+            Setup::empty();
+
+            let res = pool::create_group(&json!({"routes": {"sov": i32::max_value()}}).to_string());
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_create_pool_group_works_for_unknown_method() {
+            let setup = Setup::pool();
+
+            let group_handle = pool::create_group(&json!({"routes": {"sov": setup.pool_handle}}).to_string()).unwrap();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let mut request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            request["operation"]["dest"] = json!(format!("did:other:{}", DID_TRUSTEE));
+
+            let res = ledger::submit_request(group_handle, &request.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            pool::close_group(group_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_close_pool_group_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::close_group(i32::max_value());
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
     mod set_protocol_version {
         use super::*;
 
@@ -78,10 +78,56 @@ pub fn issuer_create_credential(wallet_handle: i32, cred_offer_json: &str, cred_
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credential_with_tags(wallet_handle: i32, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str,
+                                          rev_reg_id: Option<&str>, blob_storage_reader_handle: Option<i32>, record_tags_json: Option<&str>) -> Result<(String, Option<String>, Option<String>), IndyError> {
+    anoncreds::issuer_create_credential_with_tags(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1), record_tags_json).wait()
+}
+
+pub fn issuer_create_credentials(wallet_handle: i32, cred_offer_json: &str, cred_issuance_requests_json: &str,
+                                 rev_reg_id: Option<&str>, blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>), IndyError> {
+    anoncreds::issuer_create_credentials(wallet_handle, cred_offer_json, cred_issuance_requests_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
 pub fn issuer_revoke_credential(wallet_handle: i32, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
 
+pub fn issuer_revoke_credentials(wallet_handle: i32, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_ids_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids_json).wait()
+}
+
+pub fn issuer_create_revocation_registry_pool(wallet_handle: i32, issuer_did: &str, cred_def_id: &str, config_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_create_revocation_registry_pool(wallet_handle, issuer_did, cred_def_id, config_json).wait()
+}
+
+pub fn issuer_create_credential_from_pool(wallet_handle: i32, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_create_credential_from_pool(wallet_handle, cred_offer_json, cred_req_json, cred_values_json).wait()
+}
+
+pub fn issuer_get_revocation_registry_pool(wallet_handle: i32, cred_def_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_revocation_registry_pool(wallet_handle, cred_def_id).wait()
+}
+
+pub fn issuer_revoke_credential_by_record(wallet_handle: i32, blob_storage_reader_handle: i32, record_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_revoke_credential_by_record(wallet_handle, blob_storage_reader_handle, record_id).wait()
+}
+
+pub fn issuer_get_credential_record(wallet_handle: i32, record_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_credential_record(wallet_handle, record_id).wait()
+}
+
+pub fn issuer_search_credential_records(wallet_handle: i32, query_json: &str) -> Result<(i32, usize), IndyError> {
+    anoncreds::issuer_search_credential_records(wallet_handle, Some(query_json)).wait()
+}
+
+pub fn issuer_fetch_credential_records(search_handle: i32, count: usize) -> Result<String, IndyError> {
+    anoncreds::issuer_fetch_credential_records(search_handle, count).wait()
+}
+
+pub fn issuer_close_credential_records_search(search_handle: i32) -> Result<(), IndyError> {
+    anoncreds::issuer_close_credential_records_search(search_handle).wait()
+}
+
 pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_reg_delta: &str) -> Result<String, IndyError> {
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
@@ -163,6 +209,11 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                         cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_report(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -181,6 +232,10 @@ pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
     anoncreds::to_unqualified(entity).wait()
 }
 
+pub fn encode_credential_attr_values(raw_values_json: &str) -> Result<String, IndyError> {
+    anoncreds::encode_credential_attr_values(raw_values_json).wait()
+}
+
 pub fn default_cred_def_config() -> String {
     serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
 }
@@ -1,14 +1,69 @@
 extern crate futures;
 
-use indy::IndyError;
+use indy::{ErrorCode, IndyError};
 use indy::crypto;
 use self::futures::Future;
 
+use crate::utils::callback;
+use crate::utils::inmem_key_provider::InmemKeyProvider;
+
+use indy_api_types::{CommandHandle, IndyHandle};
+
+use std::ffi::CString;
+use std::sync::Mutex;
+use super::libc::c_char;
+
+pub const KEY_PROVIDER_CRYPTO_TYPE: &'static str = "inmem_key_provider";
+
+pub fn register_key_provider() -> Result<(), ErrorCode> {
+    lazy_static! {
+        static ref KEY_PROVIDER_REGISTERED: Mutex<bool> = Default::default();
+    }
+
+    let mut registered = KEY_PROVIDER_REGISTERED.lock().unwrap();
+
+    if *registered {
+        return Ok(());
+    }
+
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let crypto_type = CString::new(KEY_PROVIDER_CRYPTO_TYPE).unwrap();
+
+    let err = unsafe {
+        indy_register_key_provider(
+            command_handle,
+            crypto_type.as_ptr(),
+            Some(InmemKeyProvider::create_key),
+            Some(InmemKeyProvider::sign),
+            Some(InmemKeyProvider::crypto_box),
+            Some(InmemKeyProvider::crypto_box_open),
+            Some(InmemKeyProvider::crypto_box_seal_open),
+            Some(InmemKeyProvider::free_result),
+            cb
+        )
+    };
+
+    *registered = true;
+
+    super::results::result_to_empty(err as i32, receiver)
+}
+
+pub fn create_key_for_key_provider(wallet_handle: i32, seed: Option<&str>) -> Result<String, IndyError> {
+    register_key_provider().unwrap();
+    create_key_with_crypto_type(wallet_handle, seed, KEY_PROVIDER_CRYPTO_TYPE)
+}
+
 pub fn create_key(wallet_handle: i32, seed: Option<&str>) -> Result<String, IndyError> {
     let key_json = json!({"seed": seed}).to_string();
     crypto::create_key(wallet_handle, Some(&key_json)).wait()
 }
 
+pub fn create_key_with_crypto_type(wallet_handle: i32, seed: Option<&str>, crypto_type: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "crypto_type": crypto_type}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
 pub fn set_key_metadata(wallet_handle: i32, verkey: &str, metadata: &str) -> Result<(), IndyError> {
     crypto::set_key_metadata(wallet_handle, verkey, metadata).wait()
 }
@@ -47,4 +102,54 @@ pub fn pack_message(wallet_handle: i32, message: &[u8], receiver_keys: &str, sen
 
 pub fn unpack_message(wallet_handle: i32, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::unpack_message(wallet_handle, jwe).wait()
-}
\ No newline at end of file
+}
+
+extern {
+    #[no_mangle]
+    pub fn indy_register_key_provider(command_handle: CommandHandle,
+                                      crypto_type: *const c_char,
+                                      create_key: Option<KeyProviderCreateKey>,
+                                      sign: Option<KeyProviderSign>,
+                                      crypto_box: Option<KeyProviderCryptoBox>,
+                                      crypto_box_open: Option<KeyProviderCryptoBoxOpen>,
+                                      crypto_box_seal_open: Option<KeyProviderCryptoBoxSealOpen>,
+                                      free_result: Option<KeyProviderFreeResult>,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: i32)>) -> ErrorCode;
+}
+
+pub type KeyProviderCreateKey = extern fn(seed: *const u8,
+                                          seed_len: u32,
+                                          verkey_p: *mut *const c_char,
+                                          result_handle_p: *mut IndyHandle) -> ErrorCode;
+pub type KeyProviderSign = extern fn(verkey: *const c_char,
+                                     message: *const u8,
+                                     message_len: u32,
+                                     signature_p: *mut *const u8,
+                                     signature_len_p: *mut u32,
+                                     result_handle_p: *mut IndyHandle) -> ErrorCode;
+pub type KeyProviderCryptoBox = extern fn(verkey: *const c_char,
+                                          their_vk: *const c_char,
+                                          message: *const u8,
+                                          message_len: u32,
+                                          nonce: *const u8,
+                                          nonce_len: u32,
+                                          encrypted_p: *mut *const u8,
+                                          encrypted_len_p: *mut u32,
+                                          result_handle_p: *mut IndyHandle) -> ErrorCode;
+pub type KeyProviderCryptoBoxOpen = extern fn(verkey: *const c_char,
+                                              their_vk: *const c_char,
+                                              encrypted: *const u8,
+                                              encrypted_len: u32,
+                                              nonce: *const u8,
+                                              nonce_len: u32,
+                                              decrypted_p: *mut *const u8,
+                                              decrypted_len_p: *mut u32,
+                                              result_handle_p: *mut IndyHandle) -> ErrorCode;
+pub type KeyProviderCryptoBoxSealOpen = extern fn(verkey: *const c_char,
+                                                  encrypted: *const u8,
+                                                  encrypted_len: u32,
+                                                  decrypted_p: *mut *const u8,
+                                                  decrypted_len_p: *mut u32,
+                                                  result_handle_p: *mut IndyHandle) -> ErrorCode;
+pub type KeyProviderFreeResult = extern fn(result_handle: IndyHandle) -> ErrorCode;
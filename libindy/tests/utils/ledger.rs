@@ -36,6 +36,10 @@ pub fn submit_request(pool_handle: i32, request_json: &str) -> Result<String, In
     ledger::submit_request(pool_handle, request_json).wait()
 }
 
+pub fn submit_cached_request(pool_handle: i32, request_json: &str, options_json: &str) -> Result<String, IndyError> {
+    ledger::submit_cached_request(pool_handle, request_json, options_json).wait()
+}
+
 pub fn submit_action(pool_handle: i32, request_json: &str, nodes: Option<&str>, timeout: Option<i32>) -> Result<String, IndyError> {
     ledger::submit_action(pool_handle, request_json, nodes, timeout).wait()
 }
@@ -129,6 +133,10 @@ pub fn build_get_txn_request(submitter_did: Option<&str>, data: i32, ledger_type
     ledger::build_get_txn_request(submitter_did, ledger_type, data).wait()
 }
 
+pub fn get_verified_txn(pool_handle: i32, submitter_did: Option<&str>, seq_no: i32, ledger_type: Option<&str>) -> Result<String, IndyError> {
+    ledger::get_verified_txn(pool_handle, submitter_did, ledger_type, seq_no).wait()
+}
+
 pub fn build_pool_config_request(submitter_did: &str, writes: bool, force: bool) -> Result<String, IndyError> {
     ledger::build_pool_config_request(submitter_did, writes, force).wait()
 }
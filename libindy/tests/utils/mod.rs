@@ -45,6 +45,8 @@ pub mod ctypes;
 pub mod qualifier;
 
 pub(crate) use indy::ErrorCode;
+#[path = "../../indy-utils/src/inmem_key_provider.rs"]
+pub mod inmem_key_provider;
 #[path = "../../indy-utils/src/inmem_wallet.rs"]
 pub mod inmem_wallet;
 
@@ -60,6 +60,10 @@ pub fn delete_wallet_record(wallet_handle: i32, type_: &str, id: &str) -> Result
     wallet::delete_wallet_record(wallet_handle, type_, id).wait()
 }
 
+pub fn batch_wallet_records(wallet_handle: i32, operations_json: &str) -> Result<(), IndyError> {
+    wallet::batch_wallet_records(wallet_handle, operations_json).wait()
+}
+
 pub fn get_wallet_record(wallet_handle: i32, type_: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
     wallet::get_wallet_record(wallet_handle, type_, id, options_json).wait()
 }
@@ -76,6 +80,14 @@ pub fn close_wallet_search(wallet_search_handle: i32) -> Result<(), IndyError> {
     wallet::close_wallet_search(wallet_search_handle).wait()
 }
 
+pub fn subscribe_wallet_records(wallet_handle: i32, type_: &str, query_json: &str, event_cb: wallet::WalletRecordsEventCB) -> Result<i32, IndyError> {
+    wallet::subscribe_wallet_records(wallet_handle, type_, query_json, event_cb).wait()
+}
+
+pub fn unsubscribe_wallet_records(subscription_handle: i32) -> Result<(), IndyError> {
+    wallet::unsubscribe_wallet_records(subscription_handle).wait()
+}
+
 pub fn tags_1() -> HashMap<String, String> {
     serde_json::from_str(TAGS).unwrap()
 }
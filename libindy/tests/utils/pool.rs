@@ -11,12 +11,18 @@ use std::path::{Path, PathBuf};
 use indy::{ErrorCode, IndyError};
 use indy::pool;
 use self::futures::Future;
+use openssl::hash::{hash, MessageDigest};
+use rust_base58::{FromBase58, ToBase58};
 use serde_json;
+use ursa::bls::{Generator, ProofOfPossession, SignKey, VerKey};
 
 use crate::utils::types::{Response, ResponseType};
 use crate::utils::{environment, test};
 use crate::api::PoolHandle;
 
+// BLS generator the simulated nodes use, the same as the one libindy verifies multi signatures with
+const SIMULATED_BLS_GENERATOR: &str = "3LHpUjiyFC2q2hD7MnwwNmVXiuaFbQx2XkAFJWzswCjgN1utjsCeLzHsKk1nJvFEaS4fcrUmVAkdhtPCYbrVyATZcmzwJReTcJqwqBCPTmTQ9uWPwz6rEncKb2pYYYFcdHa8N17HzVyTqKfgPi4X9pMetfT3A5xCHq54R2pDNYWVLDX";
+
 #[derive(Serialize, Deserialize)]
 struct PoolConfig {
     pub genesis_txn: String
@@ -56,6 +62,31 @@ pub fn create_genesis_txn_file_for_test_pool(pool_name: &str,
     create_genesis_txn_file(pool_name, txn_file_data.as_str(), txn_file_path)
 }
 
+// Same nodes as the test pool but with BLS keys the simulated nodes sign state proofs with
+pub fn create_genesis_txn_file_for_simulated_pool(pool_name: &str,
+                                                  txn_file_path: Option<&Path>) -> PathBuf {
+    let generator = Generator::from_bytes(&SIMULATED_BLS_GENERATOR.from_base58().unwrap()).unwrap();
+
+    let node_txns = test::gen_txns().iter()
+        .map(|txn| {
+            let mut txn: serde_json::Value = serde_json::from_str(txn).unwrap();
+
+            let alias = txn["txn"]["data"]["data"]["alias"].as_str().unwrap().to_string();
+            let seed = hash(MessageDigest::sha256(), format!("simulated node {}", alias).as_bytes()).unwrap();
+            let sign_key = SignKey::new(Some(&seed)).unwrap();
+            let ver_key = VerKey::new(&generator, &sign_key).unwrap();
+            let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+
+            txn["txn"]["data"]["data"]["blskey"] = json!(ver_key.as_bytes().to_base58());
+            txn["txn"]["data"]["data"]["blskey_pop"] = json!(pop.as_bytes().to_base58());
+            txn.to_string()
+        })
+        .collect::<Vec<String>>();
+
+    let txn_file_data = node_txns.join("\n");
+    create_genesis_txn_file(pool_name, txn_file_data.as_str(), txn_file_path)
+}
+
 pub fn create_genesis_txn_file_for_test_pool_with_invalid_nodes(pool_name: &str,
                                                                 txn_file_path: Option<&Path>) -> PathBuf {
     let test_pool_ip = environment::test_pool_ip();
@@ -170,14 +201,51 @@ pub fn create_and_open_pool_ledger(pool_name: &str) -> Result<PoolHandle, IndyEr
     open_pool_ledger(pool_name, None)
 }
 
+// Opens the pool with in-process simulated nodes, so it doesn't require the local nodes pool
+pub fn create_and_open_simulated_pool_ledger(pool_name: &str, config: serde_json::Value) -> Result<PoolHandle, IndyError> {
+    let txn_file_path = create_genesis_txn_file_for_simulated_pool(pool_name, None);
+    let pool_config = pool_config_json(txn_file_path.as_path());
+    create_pool_ledger_config(pool_name, Some(pool_config.as_str()))?;
+
+    let mut config = config;
+    if config["simulation"].is_null() {
+        config["simulation"] = json!({});
+    }
+    pool::open_pool_ledger(pool_name, Some(&config.to_string())).wait()
+}
+
 pub fn refresh(pool_handle: PoolHandle) -> Result<(), IndyError> {
     pool::refresh_pool_ledger(pool_handle).wait()
 }
 
+pub fn get_stats(pool_handle: PoolHandle) -> Result<String, IndyError> {
+    pool::get_pool_stats(pool_handle).wait()
+}
+
+pub fn set_nodes_update_callback(pool_handle: PoolHandle, update_cb: Option<pool::PoolNodesUpdateCB>) -> Result<(), IndyError> {
+    pool::set_pool_nodes_update_callback(pool_handle, update_cb).wait()
+}
+
+pub fn verify_reply_state_proof(pool_handle: PoolHandle, genesis_txn: Option<&str>, request_json: &str, reply_json: &str) -> Result<String, IndyError> {
+    pool::verify_reply_state_proof(pool_handle, genesis_txn, request_json, reply_json).wait()
+}
+
+pub fn validate_genesis(genesis_txn: &str, protocol_version: usize) -> Result<String, IndyError> {
+    pool::validate_pool_genesis(genesis_txn, protocol_version).wait()
+}
+
 pub fn close(pool_handle: PoolHandle) -> Result<(), IndyError> {
     pool::close_pool_ledger(pool_handle).wait()
 }
 
+pub fn create_group(config: &str) -> Result<PoolHandle, IndyError> {
+    pool::create_pool_group(config).wait()
+}
+
+pub fn close_group(pool_group_handle: PoolHandle) -> Result<(), IndyError> {
+    pool::close_pool_group(pool_group_handle).wait()
+}
+
 pub fn delete(pool_name: &str) -> Result<(), IndyError> {
     pool::delete_pool_ledger(pool_name).wait()
 }
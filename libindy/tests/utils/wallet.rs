@@ -10,7 +10,8 @@ use self::futures::Future;
 use crate::utils::{callback, sequence, environment};
 use crate::utils::inmem_wallet::InmemWallet;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::slice;
 use std::sync::Mutex;
 use std::ffi::CString;
 use super::libc::c_char;
@@ -18,7 +19,7 @@ use super::libc::c_char;
 use crate::utils::constants::{TYPE, INMEM_TYPE, WALLET_CREDENTIALS};
 
 use std::path::{Path, PathBuf};
-use indy_api_types::{WalletHandle, CommandHandle};
+use indy_api_types::{WalletHandle, CommandHandle, IndyHandle};
 
 pub fn register_wallet_storage(xtype: &str, force_create: bool) -> Result<(), ErrorCode> {
     lazy_static! {
@@ -98,7 +99,7 @@ pub fn create_and_open_plugged_wallet() -> Result<(WalletHandle, String), IndyEr
             "storage_type": INMEM_TYPE
         }).to_string();
 
-    register_wallet_storage("inmem", false).unwrap();
+    register_wallet_storage(INMEM_TYPE, false).unwrap();
     create_wallet(&config, WALLET_CREDENTIALS)?;
     let wallet_handle = open_wallet(&config, WALLET_CREDENTIALS).unwrap();
     Ok((wallet_handle, config))
@@ -108,6 +109,10 @@ pub fn delete_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::delete_wallet(config, credentials).wait()
 }
 
+pub fn check_wallet(config: &str, credentials: &str, options: Option<&str>) -> Result<String, IndyError> {
+    wallet::check_wallet(config, credentials, options).wait()
+}
+
 pub fn close_wallet(wallet_handle: WalletHandle) -> Result<(), IndyError> {
     wallet::close_wallet(wallet_handle).wait()
 }
@@ -121,6 +126,62 @@ pub fn export_wallet(wallet_handle: WalletHandle, export_config_json: &str) -> R
     wallet::export_wallet(wallet_handle, export_config_json).wait()
 }
 
+lazy_static! {
+    static ref EXPORT_STREAMS: Mutex<HashMap<IndyHandle, Vec<u8>>> = Default::default();
+}
+
+extern "C" fn _write_export_stream(stream_handle: IndyHandle, data_raw: *const u8, data_len: u32) -> i32 {
+    let data = unsafe { slice::from_raw_parts(data_raw, data_len as usize) };
+    EXPORT_STREAMS.lock().unwrap().get_mut(&stream_handle).unwrap().extend_from_slice(data);
+    ErrorCode::Success as i32
+}
+
+pub fn export_wallet_to_stream(wallet_handle: WalletHandle, export_config_json: &str) -> Result<Vec<u8>, IndyError> {
+    let stream_handle = sequence::get_next_id();
+    EXPORT_STREAMS.lock().unwrap().insert(stream_handle, Vec::new());
+
+    let res = wallet::export_wallet_to_stream(wallet_handle, export_config_json, stream_handle, Some(_write_export_stream)).wait();
+
+    let data = EXPORT_STREAMS.lock().unwrap().remove(&stream_handle).unwrap();
+    res.map(|()| data)
+}
+
+pub fn import_wallet_records(wallet_handle: WalletHandle, import_config: &str) -> Result<(), IndyError> {
+    wallet::import_wallet_records(wallet_handle, import_config).wait()
+}
+
+lazy_static! {
+    static ref IMPORT_STREAMS: Mutex<HashMap<IndyHandle, Vec<u8>>> = Default::default();
+}
+
+extern "C" fn _read_import_stream(stream_handle: IndyHandle, data_raw: *mut u8, data_len: u32, read_len: *mut u32) -> i32 {
+    let mut streams = IMPORT_STREAMS.lock().unwrap();
+    let data = streams.get_mut(&stream_handle).unwrap();
+
+    let len = ::std::cmp::min(data.len(), data_len as usize);
+    let chunk: Vec<u8> = data.drain(..len).collect();
+
+    unsafe {
+        slice::from_raw_parts_mut(data_raw, len).copy_from_slice(&chunk);
+        *read_len = len as u32;
+    }
+    ErrorCode::Success as i32
+}
+
+pub fn import_wallet_records_from_stream(wallet_handle: WalletHandle, import_config: &str, data: &[u8]) -> Result<(), IndyError> {
+    let stream_handle = sequence::get_next_id();
+    IMPORT_STREAMS.lock().unwrap().insert(stream_handle, data.to_vec());
+
+    let res = wallet::import_wallet_records_from_stream(wallet_handle, import_config, stream_handle, Some(_read_import_stream)).wait();
+
+    IMPORT_STREAMS.lock().unwrap().remove(&stream_handle);
+    res
+}
+
+pub fn rekey_wallet(wallet_handle: WalletHandle, credentials: &str) -> Result<(), IndyError> {
+    wallet::rekey_wallet(wallet_handle, credentials).wait()
+}
+
 pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Result<(), IndyError> {
     wallet::import_wallet(config, credentials, import_config).wait()
 }
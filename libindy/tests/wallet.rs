@@ -147,6 +147,48 @@ mod high_cases {
         }
     }
 
+    mod check_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::create_my_did(wallet_handle, "{}").unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let report = wallet::check_wallet(&config, WALLET_CREDENTIALS, None).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert!(report["checked_records"].as_u64().unwrap() > 0);
+            assert_eq!(json!([]), report["invalid_records"]);
+            assert_eq!(0, report["orphaned_tags"]);
+            assert_eq!(false, report["repaired"]);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_repair() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let report = wallet::check_wallet(&config, WALLET_CREDENTIALS, Some(r#"{"repair": true}"#)).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert_eq!(false, report["repaired"]);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+    }
+
     mod open_wallet {
         use super::*;
 
@@ -173,6 +215,45 @@ mod high_cases {
             wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
         }
 
+        #[test]
+        fn indy_open_wallet_works_for_shared_locking() {
+            let setup = Setup::empty();
+
+            let config = json!({
+                "id": &setup.name,
+                "storage_config": {
+                    "locking": "shared",
+                }
+            }).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_open_wallet_works_for_inmem_storage() {
+            let setup = Setup::empty();
+
+            let config = json!({
+                "id": &setup.name,
+                "storage_type": "in_memory",
+            }).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+        }
+
         #[test]
         fn indy_open_wallet_works_for_plugged() {
             Setup::empty();
@@ -246,6 +327,34 @@ mod high_cases {
         }
     }
 
+    mod rekey_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_wallet_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            wallet::rekey_wallet(wallet_handle, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+        }
+    }
+
     mod import_wallet {
         use super::*;
 
@@ -283,6 +392,109 @@ mod high_cases {
         }
     }
 
+    mod export_wallet_to_stream {
+        use super::*;
+
+        #[test]
+        fn indy_export_wallet_to_stream_works() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_imported", setup.name));
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            let (did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let data = wallet::export_wallet_to_stream(setup.wallet_handle, r#"{"key": "export_key"}"#).unwrap();
+
+            cleanup_file(&path);
+            fs::write(&path, &data).unwrap();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
+    }
+
+    mod import_wallet_records {
+        use super::*;
+
+        #[test]
+        fn indy_import_wallet_records_works_for_incremental_export() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_restored", setup.name));
+
+            let full_path = wallet::export_wallet_path(&format!("{}_full", setup.name));
+            let incremental_path = wallet::export_wallet_path(&format!("{}_incremental", setup.name));
+            let export_config = |path: &PathBuf| json!({"path": path.to_str().unwrap(), "key": "export_key", "marker": "backup"}).to_string();
+
+            let (did1, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            cleanup_file(&full_path);
+            wallet::export_wallet(setup.wallet_handle, &export_config(&full_path)).unwrap();
+
+            let (did2, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            cleanup_file(&incremental_path);
+            wallet::export_wallet(setup.wallet_handle, &export_config(&incremental_path)).unwrap();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &wallet::prepare_export_wallet_config(&full_path)).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            did::key_for_local_did(wallet_handle, &did1).unwrap();
+            let res = did::key_for_local_did(wallet_handle, &did2);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::import_wallet_records(wallet_handle, &wallet::prepare_export_wallet_config(&incremental_path)).unwrap();
+
+            did::key_for_local_did(wallet_handle, &did1).unwrap();
+            did::key_for_local_did(wallet_handle, &did2).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&full_path);
+            cleanup_file(&incremental_path);
+        }
+    }
+
+    mod import_wallet_records_from_stream {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        const RECORD_TYPE: &str = "TestType";
+
+        #[test]
+        fn indy_import_wallet_records_from_stream_works_for_deleted_records() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_restored", setup.name));
+            let export_config = r#"{"key": "export_key", "marker": "backup"}"#;
+            let import_config = r#"{"key": "export_key"}"#;
+
+            non_secrets::add_wallet_record(setup.wallet_handle, RECORD_TYPE, "RecordId1", "RecordValue1", None).unwrap();
+            non_secrets::add_wallet_record(setup.wallet_handle, RECORD_TYPE, "RecordId2", "RecordValue2", None).unwrap();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let data = wallet::export_wallet_to_stream(setup.wallet_handle, export_config).unwrap();
+            wallet::import_wallet_records_from_stream(wallet_handle, import_config, &data).unwrap();
+
+            non_secrets::delete_wallet_record(setup.wallet_handle, RECORD_TYPE, "RecordId2").unwrap();
+
+            let data = wallet::export_wallet_to_stream(setup.wallet_handle, export_config).unwrap();
+            wallet::import_wallet_records_from_stream(wallet_handle, import_config, &data).unwrap();
+
+            non_secrets::get_wallet_record(wallet_handle, RECORD_TYPE, "RecordId1", "{}").unwrap();
+            let res = non_secrets::get_wallet_record(wallet_handle, RECORD_TYPE, "RecordId2", "{}");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+        }
+    }
+
     mod generate_wallet_key {
         use super::*;
         use rust_base58::FromBase58;
@@ -461,6 +673,53 @@ mod medium_cases {
         }
     }
 
+    mod check_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_works_for_opened() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let res = wallet::check_wallet(&config, WALLET_CREDENTIALS, None);
+            assert_code!(ErrorCode::WalletAlreadyOpenedError, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_not_created() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let res = wallet::check_wallet(&config, WALLET_CREDENTIALS, None);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_wrong_credentials() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, r#"{"key":"key"}"#).unwrap();
+            let res = wallet::check_wallet(&config, r#"{"key":"other_key"}"#, None);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_invalid_options() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let res = wallet::check_wallet(&config, WALLET_CREDENTIALS, Some(r#"{"repair": "yes"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod open_wallet {
         use super::*;
 
@@ -635,6 +894,26 @@ mod medium_cases {
         }
     }
 
+    mod rekey_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_wallet_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::rekey_wallet(INVALID_WALLET_HANDLE, WALLET_CREDENTIALS_ARGON2I_INT);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_rekey_wallet_works_for_invalid_credentials() {
+            let setup = Setup::wallet();
+
+            let res = wallet::rekey_wallet(setup.wallet_handle, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod import_wallet {
         use super::*;
 
@@ -718,6 +997,85 @@ mod medium_cases {
             cleanup_file(&path);
         }
     }
+
+    mod export_wallet_to_stream {
+        use super::*;
+
+        #[test]
+        fn indy_export_wallet_to_stream_returns_error_if_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::export_wallet_to_stream(INVALID_WALLET_HANDLE, r#"{"key": "export_key"}"#);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_export_wallet_to_stream_returns_error_for_query_without_types() {
+            let setup = Setup::wallet();
+
+            let res = wallet::export_wallet_to_stream(setup.wallet_handle, r#"{"key": "export_key", "query": {"tag": "value"}}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod import_wallet_records {
+        use super::*;
+
+        #[test]
+        fn indy_import_wallet_records_returns_error_if_invalid_handle() {
+            let setup = Setup::empty();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            let res = wallet::import_wallet_records(INVALID_WALLET_HANDLE, &config_json);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_import_wallet_records_returns_error_if_path_doesnt_exist() {
+            let setup = Setup::wallet();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            cleanup_file(&path);
+            let res = wallet::import_wallet_records(setup.wallet_handle, &config_json);
+            assert_code!(ErrorCode::CommonIOError, res);
+        }
+
+        #[test]
+        fn indy_import_wallet_records_works_for_existing_records() {
+            let setup = Setup::wallet();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(setup.wallet_handle, &config_json).unwrap();
+
+            let res = wallet::import_wallet_records(setup.wallet_handle, &config_json);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let config_json = json!({"path": path.to_str().unwrap(), "key": "export_key", "on_conflict": "skip"}).to_string();
+            wallet::import_wallet_records(setup.wallet_handle, &config_json).unwrap();
+
+            cleanup_file(&path);
+        }
+
+        #[test]
+        fn indy_import_wallet_records_returns_error_for_unknown_conflict_policy() {
+            let setup = Setup::wallet();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = json!({"path": path.to_str().unwrap(), "key": "export_key", "on_conflict": "merge"}).to_string();
+
+            let res = wallet::import_wallet_records(setup.wallet_handle, &config_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
 
 fn _custom_path(name: &str) -> String {
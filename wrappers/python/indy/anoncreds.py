@@ -497,6 +497,136 @@ async def issuer_create_credential(wallet_handle: int,
     return res
 
 
+async def issuer_create_credential_with_tags(wallet_handle: int,
+                                             cred_offer_json: str,
+                                             cred_req_json: str,
+                                             cred_values_json: str,
+                                             rev_reg_id: Optional[str],
+                                             blob_storage_reader_handle: Optional[int],
+                                             record_tags_json: Optional[str]) -> (str, Optional[str], Optional[str]):
+    """
+    Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request
+    storing the issuance record with the given tags.
+
+    Works like issuer_create_credential, record_tags_json are attached to the issuance record
+    stored for a credential issued from revocation registry (see issuer_get_credential_record).
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param cred_offer_json: a cred offer created by issuer_create_credential_offer
+    :param cred_req_json: a credential request created by prover_create_credential_req
+    :param cred_values_json: a credential containing attribute values for each of requested attribute names.
+    :param rev_reg_id: (Optional) id of revocation registry definition stored in the wallet
+    :param blob_storage_reader_handle: pre-configured blob storage reader instance handle that
+    will allow to read revocation tails
+    :param record_tags_json: (Optional) tags of the issuance record:
+     {"tagName1": string, "~tagName2": string}
+     Tag names "cred_def_id", "rev_reg_id", "cred_rev_id", "prover_did", "revoked" and "~timestamp" are reserved.
+    :return:
+     cred_json: Credential json containing signed credential values
+     cred_revoc_id: local id for revocation info (Can be used for revocation of this cred)
+     revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_create_credential_with_tags: >>> wallet_handle: %r, cred_offer_json: %r, cred_req_json: %r,"
+                 " cred_values_json: %r, rev_reg_id: %r, blob_storage_reader_handle: %r, record_tags_json: %r",
+                 wallet_handle,
+                 cred_offer_json,
+                 cred_req_json,
+                 cred_values_json,
+                 rev_reg_id,
+                 blob_storage_reader_handle,
+                 record_tags_json)
+
+    if not hasattr(issuer_create_credential_with_tags, "cb"):
+        logger.debug("issuer_create_credential_with_tags: Creating callback")
+        issuer_create_credential_with_tags.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p, c_char_p, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_cred_offer_json = c_char_p(cred_offer_json.encode('utf-8'))
+    c_cred_req_json = c_char_p(cred_req_json.encode('utf-8'))
+    c_cred_values_json = c_char_p(cred_values_json.encode('utf-8'))
+    c_rev_reg_id = c_char_p(rev_reg_id.encode('utf-8')) if rev_reg_id is not None else None
+    c_blob_storage_reader_handle = c_int32(blob_storage_reader_handle) if blob_storage_reader_handle else -1
+    c_record_tags_json = c_char_p(record_tags_json.encode('utf-8')) if record_tags_json is not None else None
+
+    (cred_json, cred_revoc_id, revoc_reg_delta_json) = await do_call('indy_issuer_create_credential_with_tags',
+                                                                     c_wallet_handle,
+                                                                     c_cred_offer_json,
+                                                                     c_cred_req_json,
+                                                                     c_cred_values_json,
+                                                                     c_rev_reg_id,
+                                                                     c_blob_storage_reader_handle,
+                                                                     c_record_tags_json,
+                                                                     issuer_create_credential_with_tags.cb)
+    cred_json = cred_json.decode()
+    cred_revoc_id = cred_revoc_id.decode() if cred_revoc_id else None
+    revoc_reg_delta_json = revoc_reg_delta_json.decode() if revoc_reg_delta_json else None
+    res = (cred_json, cred_revoc_id, revoc_reg_delta_json)
+
+    logger.debug("issuer_create_credential_with_tags: <<< res: %r", res)
+    return res
+
+
+async def issuer_create_credentials(wallet_handle: int,
+                                    cred_offer_json: str,
+                                    cred_issuance_requests_json: str,
+                                    rev_reg_id: Optional[str],
+                                    blob_storage_reader_handle: Optional[int]) -> (str, Optional[str]):
+    """
+    Check Cred Request for the given Cred Offer and issue Credentials for a batch of Cred Requests.
+
+    Works like issuer_create_credential called for every request of the batch, but loads
+    the revocation registry and opens the tails reader only once and signs credentials in parallel.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param cred_offer_json: a cred offer created by issuer_create_credential_offer
+    :param cred_issuance_requests_json: a list of credential requests with the values to issue for each of them
+     [{"cred_req": <credential request>, "cred_values": <see cred_values_json of issuer_create_credential>,
+       "record_tags": Optional<see record_tags_json of issuer_create_credential_with_tags>}, ...]
+    :param rev_reg_id: (Optional) id of revocation registry definition stored in the wallet
+    :param blob_storage_reader_handle: pre-configured blob storage reader instance handle that
+    will allow to read revocation tails
+    :return:
+     creds_json: issued credentials in the order of requests
+     [{"cred": <credential json>, "cred_rev_id": Optional<string>}, ...]
+     revoc_reg_delta_json: Revocation registry delta json with all newly issued credentials
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_create_credentials: >>> wallet_handle: %r, cred_offer_json: %r, cred_issuance_requests_json: %r,"
+                 " rev_reg_id: %r, blob_storage_reader_handle: %r",
+                 wallet_handle,
+                 cred_offer_json,
+                 cred_issuance_requests_json,
+                 rev_reg_id,
+                 blob_storage_reader_handle)
+
+    if not hasattr(issuer_create_credentials, "cb"):
+        logger.debug("issuer_create_credentials: Creating callback")
+        issuer_create_credentials.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_cred_offer_json = c_char_p(cred_offer_json.encode('utf-8'))
+    c_cred_issuance_requests_json = c_char_p(cred_issuance_requests_json.encode('utf-8'))
+    c_rev_reg_id = c_char_p(rev_reg_id.encode('utf-8')) if rev_reg_id is not None else None
+    c_blob_storage_reader_handle = c_int32(blob_storage_reader_handle) if blob_storage_reader_handle else -1
+
+    (creds_json, revoc_reg_delta_json) = await do_call('indy_issuer_create_credentials',
+                                                       c_wallet_handle,
+                                                       c_cred_offer_json,
+                                                       c_cred_issuance_requests_json,
+                                                       c_rev_reg_id,
+                                                       c_blob_storage_reader_handle,
+                                                       issuer_create_credentials.cb)
+    creds_json = creds_json.decode()
+    revoc_reg_delta_json = revoc_reg_delta_json.decode() if revoc_reg_delta_json else None
+    res = (creds_json, revoc_reg_delta_json)
+
+    logger.debug("issuer_create_credentials: <<< res: %r", res)
+    return res
+
+
 async def issuer_revoke_credential(wallet_handle: int,
                                    blob_storage_reader_handle: int,
                                    rev_reg_id: str,
@@ -547,6 +677,367 @@ async def issuer_revoke_credential(wallet_handle: int,
     return res
 
 
+async def issuer_revoke_credentials(wallet_handle: int,
+                                    blob_storage_reader_handle: int,
+                                    rev_reg_id: str,
+                                    cred_revoc_ids_json: str) -> str:
+    """
+    Revoke a batch of credentials of the same revocation registry identified by cred_revoc_ids.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param blob_storage_reader_handle: pre-configured blob storage reader instance handle that will allow
+    to read revocation tails
+    :param rev_reg_id: id of revocation registry stored in wallet
+    :param cred_revoc_ids_json: list of local ids for revocation info: ["1", "2", ...]
+    :return: Revocation registry delta json with all revoked credentials.
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug(
+        "issuer_revoke_credentials: >>> wallet_handle: %r, blob_storage_reader_handle: %r, rev_reg_id: %r, "
+        "cred_revoc_ids_json: %r",
+        wallet_handle,
+        blob_storage_reader_handle,
+        rev_reg_id,
+        cred_revoc_ids_json)
+
+    if not hasattr(issuer_revoke_credentials, "cb"):
+        logger.debug("issuer_revoke_credentials: Creating callback")
+        issuer_revoke_credentials.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_blob_storage_reader_handle = c_int32(blob_storage_reader_handle)
+    c_rev_reg_id = c_char_p(rev_reg_id.encode('utf-8'))
+    c_cred_revoc_ids_json = c_char_p(cred_revoc_ids_json.encode('utf-8'))
+
+    revoc_reg_delta_json = await do_call('indy_issuer_revoke_credentials',
+                                         c_wallet_handle,
+                                         c_blob_storage_reader_handle,
+                                         c_rev_reg_id,
+                                         c_cred_revoc_ids_json,
+                                         issuer_revoke_credentials.cb)
+    res = revoc_reg_delta_json.decode()
+    logger.debug("issuer_revoke_credentials: <<< res: %r", res)
+    return res
+
+
+async def issuer_create_revocation_registry_pool(wallet_handle: int,
+                                                 issuer_did: str,
+                                                 cred_def_id: str,
+                                                 config_json: str) -> str:
+    """
+    Create a pool of revocation registries for a credential definition used by issuer_create_credential_from_pool.
+
+    The active registry of the pool is replaced by the next pre-generated one as soon as it is full.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param issuer_did: a DID of the issuer
+    :param cred_def_id: id of stored in ledger credential definition
+    :param config_json: configuration of the pool as json:
+     {
+         "rev_reg_type": (optional) type of revocation registries, "CL_ACCUM" by default,
+         "issuance_type": (optional) type of issuance of revocation registries, "ISSUANCE_ON_DEMAND" by default,
+         "max_cred_num": (optional) maximum number of credentials of each registry, 100000 by default,
+         "pregenerated": (optional) number of registries kept ready besides the active one, 1 by default,
+         "threshold": (optional) number of free indexes of the active registry at which missing ready
+                      registries are created, 0 by default,
+         "tails_writer_type": (optional) type of blob storage used for tails, "default" by default,
+         "tails_writer_config": config of blob storage used for tails
+     }
+    :return: created revocation registries to be published to the ledger:
+     [{"rev_reg_id": string, "rev_reg_def": object, "rev_reg_entry": object}]
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_create_revocation_registry_pool: >>> wallet_handle: %r, issuer_did: %r, cred_def_id: %r, "
+                 "config_json: %r",
+                 wallet_handle,
+                 issuer_did,
+                 cred_def_id,
+                 config_json)
+
+    if not hasattr(issuer_create_revocation_registry_pool, "cb"):
+        logger.debug("issuer_create_revocation_registry_pool: Creating callback")
+        issuer_create_revocation_registry_pool.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_issuer_did = c_char_p(issuer_did.encode('utf-8'))
+    c_cred_def_id = c_char_p(cred_def_id.encode('utf-8'))
+    c_config_json = c_char_p(config_json.encode('utf-8'))
+
+    rev_regs_json = await do_call('indy_issuer_create_revocation_registry_pool',
+                                  c_wallet_handle,
+                                  c_issuer_did,
+                                  c_cred_def_id,
+                                  c_config_json,
+                                  issuer_create_revocation_registry_pool.cb)
+    res = rev_regs_json.decode()
+    logger.debug("issuer_create_revocation_registry_pool: <<< res: %r", res)
+    return res
+
+
+async def issuer_create_credential_from_pool(wallet_handle: int,
+                                             cred_offer_json: str,
+                                             cred_req_json: str,
+                                             cred_values_json: str) -> str:
+    """
+    Check Cred Request for the given Cred Offer and issue Credential from the revocation registry pool
+    of the credential definition.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param cred_offer_json: a cred offer created by issuer_create_credential_offer
+    :param cred_req_json: a credential request created by prover_create_credential_req
+    :param cred_values_json: a credential containing attribute values for each of requested attribute names.
+    :return: Credential issued from the pool:
+     {
+         "cred": object - credential json,
+         "cred_rev_id": string - local id for revocation info,
+         "rev_reg_id": string - id of revocation registry the credential was issued from,
+         "rev_reg_delta": (optional) object - revocation registry delta to be published,
+         "created_rev_regs": array - revocation registries created by the pool during issuance
+     }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_create_credential_from_pool: >>> wallet_handle: %r, cred_offer_json: %r, cred_req_json: %r, "
+                 "cred_values_json: %r",
+                 wallet_handle,
+                 cred_offer_json,
+                 cred_req_json,
+                 cred_values_json)
+
+    if not hasattr(issuer_create_credential_from_pool, "cb"):
+        logger.debug("issuer_create_credential_from_pool: Creating callback")
+        issuer_create_credential_from_pool.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_cred_offer_json = c_char_p(cred_offer_json.encode('utf-8'))
+    c_cred_req_json = c_char_p(cred_req_json.encode('utf-8'))
+    c_cred_values_json = c_char_p(cred_values_json.encode('utf-8'))
+
+    issued_cred_json = await do_call('indy_issuer_create_credential_from_pool',
+                                     c_wallet_handle,
+                                     c_cred_offer_json,
+                                     c_cred_req_json,
+                                     c_cred_values_json,
+                                     issuer_create_credential_from_pool.cb)
+    res = issued_cred_json.decode()
+    logger.debug("issuer_create_credential_from_pool: <<< res: %r", res)
+    return res
+
+
+async def issuer_get_revocation_registry_pool(wallet_handle: int,
+                                              cred_def_id: str) -> str:
+    """
+    Get the state of the revocation registry pool of the credential definition.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param cred_def_id: id of credential definition the pool was created for
+    :return: state of the pool:
+     {
+         "cred_def_id": string,
+         "config": object,
+         "registries": [{"rev_reg_id": string, "state": "ready"|"active"|"full", "max_cred_num": number,
+                         "issued": number, "revoked": array<number>}]
+     }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_get_revocation_registry_pool: >>> wallet_handle: %r, cred_def_id: %r",
+                 wallet_handle,
+                 cred_def_id)
+
+    if not hasattr(issuer_get_revocation_registry_pool, "cb"):
+        logger.debug("issuer_get_revocation_registry_pool: Creating callback")
+        issuer_get_revocation_registry_pool.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_cred_def_id = c_char_p(cred_def_id.encode('utf-8'))
+
+    pool_json = await do_call('indy_issuer_get_revocation_registry_pool',
+                              c_wallet_handle,
+                              c_cred_def_id,
+                              issuer_get_revocation_registry_pool.cb)
+    res = pool_json.decode()
+    logger.debug("issuer_get_revocation_registry_pool: <<< res: %r", res)
+    return res
+
+
+async def issuer_revoke_credential_by_record(wallet_handle: int,
+                                             blob_storage_reader_handle: int,
+                                             record_id: str) -> str:
+    """
+    Revoke a credential identified by its issuance record.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param blob_storage_reader_handle: pre-configured blob storage reader instance handle that will allow
+    to read revocation tails
+    :param record_id: id of issuance record
+    :return: Revocation registry delta json with a revoked credential.
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_revoke_credential_by_record: >>> wallet_handle: %r, blob_storage_reader_handle: %r, "
+                 "record_id: %r",
+                 wallet_handle,
+                 blob_storage_reader_handle,
+                 record_id)
+
+    if not hasattr(issuer_revoke_credential_by_record, "cb"):
+        logger.debug("issuer_revoke_credential_by_record: Creating callback")
+        issuer_revoke_credential_by_record.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_blob_storage_reader_handle = c_int32(blob_storage_reader_handle)
+    c_record_id = c_char_p(record_id.encode('utf-8'))
+
+    revoc_reg_delta_json = await do_call('indy_issuer_revoke_credential_by_record',
+                                         c_wallet_handle,
+                                         c_blob_storage_reader_handle,
+                                         c_record_id,
+                                         issuer_revoke_credential_by_record.cb)
+    res = revoc_reg_delta_json.decode()
+    logger.debug("issuer_revoke_credential_by_record: <<< res: %r", res)
+    return res
+
+
+async def issuer_get_credential_record(wallet_handle: int,
+                                       record_id: str) -> str:
+    """
+    Get issuance record of a credential issued from a revocation registry.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param record_id: id of issuance record: "<rev_reg_id>:<cred_rev_id>"
+    :return: issuance record
+     {
+         "record_id": string,
+         "cred_def_id": string,
+         "rev_reg_id": string,
+         "cred_rev_id": string,
+         "prover_did": string,
+         "timestamp": number,
+         "revoked": bool,
+         "revoked_timestamp": Optional<number>,
+         "tags": {"tagName": string}
+     }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_get_credential_record: >>> wallet_handle: %r, record_id: %r",
+                 wallet_handle,
+                 record_id)
+
+    if not hasattr(issuer_get_credential_record, "cb"):
+        logger.debug("issuer_get_credential_record: Creating callback")
+        issuer_get_credential_record.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_record_id = c_char_p(record_id.encode('utf-8'))
+
+    record_json = await do_call('indy_issuer_get_credential_record',
+                                c_wallet_handle,
+                                c_record_id,
+                                issuer_get_credential_record.cb)
+    res = record_json.decode()
+    logger.debug("issuer_get_credential_record: <<< res: %r", res)
+    return res
+
+
+async def issuer_search_credential_records(wallet_handle: int,
+                                           query_json: str) -> (int, int):
+    """
+    Search for issuance records of credentials issued from revocation registries.
+
+    :param wallet_handle: wallet handle (created by open_wallet).
+    :param query_json: wql style filter for issuance records searching based on tags
+        ("cred_def_id", "rev_reg_id", "cred_rev_id", "prover_did", "revoked", "~timestamp" and Issuer's tags).
+        where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
+    :return:
+        search_handle: Search handle that can be used later to fetch records by small batches
+            (with issuer_fetch_credential_records)
+        total_count: Total count of records
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_search_credential_records: >>> wallet_handle: %r, query_json: %r",
+                 wallet_handle,
+                 query_json)
+
+    if not hasattr(issuer_search_credential_records, "cb"):
+        logger.debug("issuer_search_credential_records: Creating callback")
+        issuer_search_credential_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_int32, c_uint))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_query_json = c_char_p(query_json.encode('utf-8'))
+
+    res = await do_call('indy_issuer_search_credential_records',
+                        c_wallet_handle,
+                        c_query_json,
+                        issuer_search_credential_records.cb)
+
+    logger.debug("issuer_search_credential_records: <<< res: %r", res)
+    return res
+
+
+async def issuer_fetch_credential_records(search_handle: int,
+                                          count: int) -> str:
+    """
+    Fetch next issuance records for search.
+
+    :param search_handle: Search handle (created by issuer_search_credential_records)
+    :param count: Count of records to fetch
+    :return: records_json: List of issuance records (see issuer_get_credential_record)
+    NOTE: The list of length less than the requested count means records search iterator is completed.
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_fetch_credential_records: >>> search_handle: %r, count: %r",
+                 search_handle,
+                 count)
+
+    if not hasattr(issuer_fetch_credential_records, "cb"):
+        logger.debug("issuer_fetch_credential_records: Creating callback")
+        issuer_fetch_credential_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_search_handle = c_int32(search_handle)
+    c_count = c_uint(count)
+
+    records_json = await do_call('indy_issuer_fetch_credential_records',
+                                 c_search_handle,
+                                 c_count,
+                                 issuer_fetch_credential_records.cb)
+
+    res = records_json.decode()
+    logger.debug("issuer_fetch_credential_records: <<< res: %r", res)
+    return res
+
+
+async def issuer_close_credential_records_search(search_handle: int) -> None:
+    """
+    Close issuance records search (make search handle invalid)
+
+    :param search_handle: Search handle (created by issuer_search_credential_records)
+    :return: None
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("issuer_close_credential_records_search: >>> search_handle: %r",
+                 search_handle)
+
+    if not hasattr(issuer_close_credential_records_search, "cb"):
+        logger.debug("issuer_close_credential_records_search: Creating callback")
+        issuer_close_credential_records_search.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_search_handle = c_int32(search_handle)
+
+    res = await do_call('indy_issuer_close_credential_records_search',
+                        c_search_handle,
+                        issuer_close_credential_records_search.cb)
+
+    logger.debug("issuer_close_credential_records_search: <<< res: %r", res)
+    return res
+
+
 # async def issuer_recover_credential(wallet_handle: int,
 #                                     blob_storage_reader_handle: int,
 #                                     rev_reg_id: str,
@@ -1727,6 +2218,75 @@ async def verifier_verify_proof(proof_request_json: str,
     return res
 
 
+async def verifier_verify_proof_with_report(proof_request_json: str,
+                                            proof_json: str,
+                                            schemas_json: str,
+                                            credential_defs_json: str,
+                                            rev_reg_defs_json: str,
+                                            rev_regs_json: str) -> str:
+    """
+    Verifies a proof the same way as verifier_verify_proof, but returns a report explaining the result.
+
+    :param proof_request_json: proof request json (see verifier_verify_proof)
+    :param proof_json: created for request proof json (see verifier_verify_proof)
+    :param schemas_json: all schemas participating in the proof (see verifier_verify_proof)
+    :param credential_defs_json: all credential definitions participating in the proof (see verifier_verify_proof)
+    :param rev_reg_defs_json: all revocation registry definitions participating in the proof (see verifier_verify_proof)
+    :param rev_regs_json: all revocation registries participating in the proof (see verifier_verify_proof)
+    :return: report_json: proof verification report
+        {
+            "valid": bool, // same result as verifier_verify_proof returns
+            "requested_attributes": {"attr_referent": <referent_report>},
+            "requested_predicates": {"predicate_referent": <referent_report>},
+            "errors": [string], // all reasons why the proof was rejected
+        }
+        referent_report: {
+            "valid": bool,
+            "revealed": bool,
+            "self_attested": bool,
+            "values": Optional<{"attr_name": raw value}>,
+            "identifier": Optional<{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}>,
+            "restrictions_satisfied": Optional<bool>,
+            "non_revoked": Optional<non_revoc_interval>,
+            "errors": [string],
+        }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("verifier_verify_proof_with_report: >>> proof_request_json: %r, proof_json: %r, schemas_json: %r, "
+                 "credential_defs_jsons: %r, rev_reg_defs_json: %r, rev_regs_json: %r",
+                 proof_request_json,
+                 proof_json,
+                 schemas_json,
+                 credential_defs_json,
+                 rev_reg_defs_json,
+                 rev_regs_json)
+
+    if not hasattr(verifier_verify_proof_with_report, "cb"):
+        logger.debug("verifier_verify_proof_with_report: Creating callback")
+        verifier_verify_proof_with_report.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_proof_request_json = c_char_p(proof_request_json.encode('utf-8'))
+    c_proof_json = c_char_p(proof_json.encode('utf-8'))
+    c_schemas_json = c_char_p(schemas_json.encode('utf-8'))
+    c_credential_defs_json = c_char_p(credential_defs_json.encode('utf-8'))
+    c_rev_reg_defs_json = c_char_p(rev_reg_defs_json.encode('utf-8'))
+    c_rev_regs_json = c_char_p(rev_regs_json.encode('utf-8'))
+
+    report_json = await do_call('indy_verifier_verify_proof_with_report',
+                                c_proof_request_json,
+                                c_proof_json,
+                                c_schemas_json,
+                                c_credential_defs_json,
+                                c_rev_reg_defs_json,
+                                c_rev_regs_json,
+                                verifier_verify_proof_with_report.cb)
+
+    res = report_json.decode()
+    logger.debug("verifier_verify_proof_with_report: <<< res: %r", res)
+    return res
+
+
 async def create_revocation_state(blob_storage_reader_handle: int,
                                   rev_reg_def_json: str,
                                   rev_reg_delta_json: str,
@@ -1899,3 +2459,35 @@ async def to_unqualified(entity: str) -> str:
     res = res.decode()
     logger.debug("to_unqualified: <<< res: %r", res)
     return res
+
+
+async def encode_credential_attr_values(raw_values_json: str) -> str:
+    """
+    Encode raw credential attribute values in the canonical form expected by issuer_create_credential.
+
+    :param raw_values_json: raw credential values json. Each value is either a plain raw value (string, number or boolean)
+        or an object with an explicit encoding type (int, string, date or boolean):
+        {
+         "name": "Alex",
+         "birthdate": {"raw": "1991-10-18", "type": "date"}
+        }
+
+    :return: credential values json with raw and encoded values for each attribute
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("encode_credential_attr_values: >>> raw_values_json: %r", raw_values_json)
+
+    if not hasattr(encode_credential_attr_values, "cb"):
+        logger.debug("encode_credential_attr_values: Creating callback")
+        encode_credential_attr_values.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_raw_values_json = c_char_p(raw_values_json.encode('utf-8'))
+
+    cred_values_json = await do_call('indy_encode_credential_attr_values',
+                                     c_raw_values_json,
+                                     encode_credential_attr_values.cb)
+
+    res = cred_values_json.decode()
+    logger.debug("encode_credential_attr_values: <<< res: %r", res)
+    return res
@@ -85,6 +85,54 @@ async def submit_request(pool_handle: int,
     return res
 
 
+async def submit_cached_request(pool_handle: int,
+                                request_json: str,
+                                options_json: str) -> str:
+    """
+    Publishes GET request message to validator pool using the persistent read cache of the pool.
+    If a fresh enough reply on the same request is present inside of the cache, cached reply is returned.
+    Otherwise the request is sent to the validator pool as is and the reply is stored inside of the cache
+    if its state proof is verified.
+
+    EXPERIMENTAL
+
+    :param pool_handle: pool handle (created by open_pool_ledger).
+    :param request_json: Request data json.
+    :param options_json:
+     {
+        noCache: (bool, optional, false by default) Skip usage of cache,
+        noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+        noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+        minFresh: (int, optional) Return cached data if not older than this many seconds. -1 means do not check age.
+     }
+    :return: Request result as json.
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("submit_cached_request: >>> pool_handle: %r, request_json: %r, options_json: %r",
+                 pool_handle,
+                 request_json,
+                 options_json)
+
+    if not hasattr(submit_cached_request, "cb"):
+        logger.debug("submit_cached_request: Creating callback")
+        submit_cached_request.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_pool_handle = c_int32(pool_handle)
+    c_request_json = c_char_p(request_json.encode('utf-8'))
+    c_options_json = c_char_p(options_json.encode('utf-8'))
+
+    request_result = await do_call('indy_submit_cached_request',
+                                   c_pool_handle,
+                                   c_request_json,
+                                   c_options_json,
+                                   submit_cached_request.cb)
+
+    res = request_result.decode()
+    logger.debug("submit_cached_request: <<< res: %r", res)
+    return res
+
+
 async def submit_action(pool_handle: int,
                         request_json: str,
                         nodes: Optional[str],
@@ -800,6 +848,59 @@ async def build_get_txn_request(submitter_did: Optional[str],
     return res
 
 
+async def get_verified_txn(pool_handle: int,
+                           submitter_did: Optional[str],
+                           ledger_type: Optional[str],
+                           seq_no: int) -> str:
+    """
+    Gets a transaction by its seq_no with the proof of its inclusion into the ledger.
+    The audit path of the reply is checked against the ledger root hash signed by the pool validators.
+
+    :param pool_handle: pool handle (created by open_pool_ledger).
+    :param submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+    :param ledger_type: (Optional) type of the ledger the requested transaction belongs to:
+        DOMAIN - used default,
+        POOL,
+        CONFIG
+        any number
+    :param seq_no: requested transaction sequence number as it's stored on Ledger.
+    :return: Verified transaction json:
+        {
+            "txn": {"txn": {...}, "txnMetadata": {...}, "ver": string, "reqSignature": {...}},
+            "seq_no": int,
+            "root_hash": string,
+            "verified": true
+        }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("get_verified_txn: >>> pool_handle: %r, submitter_did: %r, ledger_type: %r, seq_no: %r",
+                 pool_handle,
+                 submitter_did,
+                 ledger_type,
+                 seq_no)
+
+    if not hasattr(get_verified_txn, "cb"):
+        logger.debug("get_verified_txn: Creating callback")
+        get_verified_txn.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_pool_handle = c_int32(pool_handle)
+    c_submitter_did = c_char_p(submitter_did.encode('utf-8')) if submitter_did is not None else None
+    c_ledger_type = c_char_p(ledger_type.encode('utf-8')) if ledger_type is not None else None
+    c_seq_no = c_int32(seq_no)
+
+    txn_json = await do_call('indy_get_verified_txn',
+                             c_pool_handle,
+                             c_submitter_did,
+                             c_ledger_type,
+                             c_seq_no,
+                             get_verified_txn.cb)
+
+    res = txn_json.decode()
+    logger.debug("get_verified_txn: <<< res: %r", res)
+    return res
+
+
 async def build_pool_config_request(submitter_did: str,
                                     writes: bool,
                                     force: bool) -> str:
@@ -1,8 +1,9 @@
 from .libindy import do_call, create_cb
 
-from typing import Optional
+from typing import Callable, Optional
 from ctypes import *
 
+import asyncio
 import logging
 
 
@@ -270,6 +271,46 @@ async def delete_wallet_record(wallet_handle: int,
     return res
 
 
+async def batch_wallet_records(wallet_handle: int,
+                               operations_json: str) -> None:
+    """
+    Apply a list of changes of wallet records atomically:
+    either all of them are applied or the wallet is left untouched.
+
+    :param wallet_handle: wallet handler (created by open_wallet).
+    :param operations_json: list of operations applied in the given order:
+      [
+        {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": <optional json>},
+        {"op": "update", "type": <str>, "id": <str>, "value": <str>},
+        {"op": "add_tags", "type": <str>, "id": <str>, "tags": <json>},
+        {"op": "update_tags", "type": <str>, "id": <str>, "tags": <json>},
+        {"op": "delete_tags", "type": <str>, "id": <str>, "tag_names": [<str>, ...]},
+        {"op": "delete", "type": <str>, "id": <str>},
+      ]
+    :return: None
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("batch_wallet_records: >>> wallet_handle: %r, operations_json: %r",
+                 wallet_handle,
+                 operations_json)
+
+    if not hasattr(batch_wallet_records, "cb"):
+        logger.debug("batch_wallet_records: Creating callback")
+        batch_wallet_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_operations_json = c_char_p(operations_json.encode('utf-8'))
+
+    res = await do_call('indy_batch_wallet_records',
+                        c_wallet_handle,
+                        c_operations_json,
+                        batch_wallet_records.cb)
+
+    logger.debug("batch_wallet_records: <<< res: %r", res)
+    return res
+
+
 async def get_wallet_record(wallet_handle: int,
                             type_: str,
                             id: str,
@@ -347,6 +388,12 @@ async def open_wallet_search(wallet_handle: int,
         retrieveType: (optional, false by default) Retrieve record type,
         retrieveValue: (optional, true by default) Retrieve record value,
         retrieveTags: (optional, true by default) Retrieve record tags,
+        sortBy: (optional) Name of the plaintext tag ("~" prefixed) to sort records by,
+                records without this tag go first in ascending order
+        sortOrder: (optional, "asc" by default) Sort order, "asc" or "desc",
+        offset: (optional, 0 by default) Number of records to skip,
+        limit: (optional) Maximum number of records to return,
+        tagNames: (optional) Names of the tags to retrieve with retrieveTags, all tags by default,
       }
     :return: search_handle: Wallet search handle that can be used later
              to fetch records by small batches (with fetch_wallet_search_next_records)
@@ -450,3 +497,91 @@ async def close_wallet_search(wallet_search_handle: int) -> None:
 
     logger.debug("close_wallet_search: <<< res: %r", res)
     return res
+
+
+# event callbacks must be alive while subscriptions exist
+_subscription_callbacks = {}
+
+
+async def subscribe_wallet_records(wallet_handle: int,
+                                   type_: str,
+                                   query_json: str,
+                                   event_cb: Callable[[int, str], None]) -> int:
+    """
+    Subscribe to changes of wallet records.
+    The event callback is called in the event loop of the caller after each change of the records
+    of the type which tags match the query until the subscription is removed or the wallet is closed.
+
+    :param wallet_handle: wallet handler (created by open_wallet).
+    :param type_: type of the records to watch
+    :param query_json: WQL query to the record tags, the same as for open_wallet_search.
+    :param event_cb: callback that takes the subscription handle and the event json:
+      {
+        "event": "add" | "update" | "add_tags" | "update_tags" | "delete_tags" | "delete",
+        "type": "Some type",
+        "id": "Some id",
+        "tags": <tags json>, // tags after the change (before it for "delete")
+      }
+    :return: subscription_handle: Wallet subscription handle that can be used to remove the subscription
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("subscribe_wallet_records: >>> wallet_handle: %r, type_: %r, query_json: %r",
+                 wallet_handle,
+                 type_,
+                 query_json)
+
+    if not hasattr(subscribe_wallet_records, "cb"):
+        logger.debug("subscribe_wallet_records: Creating callback")
+        subscribe_wallet_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_int32))
+
+    event_loop = asyncio.get_event_loop()
+
+    def _event_cb(subscription_handle: int, event_json: bytes):
+        event_loop.call_soon_threadsafe(event_cb, subscription_handle, event_json.decode())
+
+    c_event_cb = CFUNCTYPE(None, c_int32, c_char_p)(_event_cb)
+
+    c_wallet_handle = c_int32(wallet_handle)
+    c_type = c_char_p(type_.encode('utf-8'))
+    c_query_json = c_char_p(query_json.encode('utf-8'))
+
+    subscription_handle = await do_call('indy_subscribe_wallet_records',
+                                        c_wallet_handle,
+                                        c_type,
+                                        c_query_json,
+                                        c_event_cb,
+                                        subscribe_wallet_records.cb)
+
+    _subscription_callbacks[subscription_handle] = c_event_cb
+
+    logger.debug("subscribe_wallet_records: <<< res: %r", subscription_handle)
+    return subscription_handle
+
+
+async def unsubscribe_wallet_records(subscription_handle: int) -> None:
+    """
+    Remove wallet records subscription (make subscription handle invalid)
+
+    :param subscription_handle: wallet subscription handle (created by subscribe_wallet_records)
+    :return: None
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("unsubscribe_wallet_records: >>> subscription_handle: %r",
+                 subscription_handle)
+
+    if not hasattr(unsubscribe_wallet_records, "cb"):
+        logger.debug("unsubscribe_wallet_records: Creating callback")
+        unsubscribe_wallet_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_subscription_handle = c_int32(subscription_handle)
+
+    res = await do_call('indy_unsubscribe_wallet_records',
+                        c_subscription_handle,
+                        unsubscribe_wallet_records.cb)
+
+    _subscription_callbacks.pop(subscription_handle, None)
+
+    logger.debug("unsubscribe_wallet_records: <<< res: %r", res)
+    return res
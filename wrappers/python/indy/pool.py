@@ -61,6 +61,13 @@ async def open_pool_ledger(config_name: str,
                 ["name_of_1st_prior_node",  "name_of_2nd_prior_node", .... ]
                 Note: Not specified nodes will be placed in a random way.
             "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
+            "refresh_interval": int (optional) - if set, the pool ledger is refreshed in background every `refresh_interval` sec.
+                Requests sent during the refresh are processed after it.
+            "adaptive_ordering": bool (optional) - rank nodes by observed response time and success rate
+                and send single read requests to the best nodes first (false by default).
+                Note: nodes without observations go first; `preordered_nodes` order is used for ties.
+            "simulation": object (optional) - replaces network connections with in-process simulated nodes (for testing only):
+                {"nodes": {"<node name>": {"behavior": "honest" | "faulty" | "malicious" | "slow", "delay": <ms, for slow>}}, "pool_txns": [<txn json>, ...]}
         }
     :return: Handle to opened pool to use in methods that require pool connection.
     """
@@ -111,6 +118,128 @@ async def refresh_pool_ledger(handle: int) -> None:
     logger.debug("refresh_pool_ledger: <<< res: %r", res)
     return res
 
+async def get_pool_stats(handle: int) -> str:
+    """
+    Returns health metrics of the nodes of an opened pool ledger: per-node reply, NACK and timeout
+    counters, latency histograms, current connections count and time of the last catchup.
+
+    :param handle: pool handle returned by indy_open_pool_ledger
+    :return: pool statistics json
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("get_pool_stats: >>> handle: %r",
+                 handle)
+
+    if not hasattr(get_pool_stats, "cb"):
+        logger.debug("get_pool_stats: Creating callback")
+        get_pool_stats.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_handle = c_int32(handle)
+
+    res = await do_call('indy_get_pool_stats',
+                        c_handle,
+                        get_pool_stats.cb)
+
+    res = res.decode()
+    logger.debug("get_pool_stats: <<< res: %r", res)
+    return res
+
+async def verify_reply_state_proof(handle: int,
+                                   genesis_txn: Optional[str],
+                                   request_json: str,
+                                   reply_json: str) -> str:
+    """
+    Verifies BLS multi-signature and state proof of a previously received reply on a GET_* request
+    without sending anything to the pool.
+
+    :param handle: pool handle returned by indy_open_pool_ledger or 0 if genesis_txn is passed
+    :param genesis_txn: (optional) path to a genesis transactions file to take validators from
+    :param request_json: the request the reply was received on
+    :param reply_json: the reply received from the ledger
+    :return: verdict json:
+        {
+            "verified": bool,
+            "txn_type": string, // (optional)
+            "state_proof_present": bool,
+            "failure": string, // (optional) "no_state_proof", "root_hash_mismatch", "invalid_signature", "invalid_proof" or "unsupported_format"
+            "participants": [string],
+            "last_signed_time": int, // (optional)
+            "nodes_count": int,
+            "f": int
+        }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("verify_reply_state_proof: >>> handle: %r, genesis_txn: %r, request_json: %r, reply_json: %r",
+                 handle,
+                 genesis_txn,
+                 request_json,
+                 reply_json)
+
+    if not hasattr(verify_reply_state_proof, "cb"):
+        logger.debug("verify_reply_state_proof: Creating callback")
+        verify_reply_state_proof.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_handle = c_int32(handle)
+    c_genesis_txn = c_char_p(genesis_txn.encode('utf-8')) if genesis_txn is not None else None
+    c_request_json = c_char_p(request_json.encode('utf-8'))
+    c_reply_json = c_char_p(reply_json.encode('utf-8'))
+
+    res = await do_call('indy_verify_reply_state_proof',
+                        c_handle,
+                        c_genesis_txn,
+                        c_request_json,
+                        c_reply_json,
+                        verify_reply_state_proof.cb)
+
+    res = res.decode()
+    logger.debug("verify_reply_state_proof: <<< res: %r", res)
+    return res
+
+async def validate_pool_genesis(genesis_txn: str,
+                                protocol_version: int) -> str:
+    """
+    Validates a genesis transactions file without connecting to the nodes.
+
+    :param genesis_txn: path to a genesis transactions file
+    :param protocol_version: protocol version NODE transactions are checked against (1 or 2),
+        0 to use the version set by set_protocol_version
+    :return: report json:
+        {
+            "valid": bool,
+            "txns_count": int,
+            "nodes": [{"alias": string, "dest": string, "validator": bool, "client_address": string, "node_address": string,
+                       "bls_key_valid": bool, "bls_key_pop_valid": bool}],
+            "validators_count": int,
+            "f": int,
+            "weak_quorum": int,
+            "strong_quorum": int,
+            "errors": [string],
+            "warnings": [string]
+        }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("validate_pool_genesis: >>> genesis_txn: %r, protocol_version: %r",
+                 genesis_txn,
+                 protocol_version)
+
+    if not hasattr(validate_pool_genesis, "cb"):
+        logger.debug("validate_pool_genesis: Creating callback")
+        validate_pool_genesis.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_genesis_txn = c_char_p(genesis_txn.encode('utf-8'))
+
+    res = await do_call('indy_validate_pool_genesis',
+                        c_genesis_txn,
+                        protocol_version,
+                        validate_pool_genesis.cb)
+
+    res = res.decode()
+    logger.debug("validate_pool_genesis: <<< res: %r", res)
+    return res
+
 async def list_pools() -> None:
     """
     Lists names of created pool ledgers
@@ -156,6 +285,66 @@ async def close_pool_ledger(handle: int) -> None:
     return res
 
 
+async def create_pool_group(config: str) -> int:
+    """
+    Creates a group of opened pool ledgers of several networks registered against DID method names.
+
+    The returned handle can be passed instead of a pool handle to ledger functions submitting requests
+    and to cache functions: requests are sent to the pool registered for the method of the fully-qualified
+    identifier they reference. Requests referencing unqualified identifiers only are sent to the default pool.
+
+    :param config: Pool group configuration json:
+        {
+            "routes": {"<DID method>": <pool handle>, ...} (optional), pools by DID method names, e.g. "sov" or "sov:staging".
+            "default": <pool handle> (optional), pool for unqualified identifiers and DID methods without route.
+        }
+    :return: Handle of the pool group.
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("create_pool_group: >>> config: %r",
+                 config)
+
+    if not hasattr(create_pool_group, "cb"):
+        logger.debug("create_pool_group: Creating callback")
+        create_pool_group.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_int32))
+
+    c_config = c_char_p(config.encode('utf-8'))
+
+    res = await do_call('indy_create_pool_group',
+                        c_config,
+                        create_pool_group.cb)
+
+    logger.debug("create_pool_group: <<< res: %r", res)
+    return res
+
+
+async def close_pool_group(handle: int) -> None:
+    """
+    Closes the pool group. Pool ledgers of the group stay opened.
+
+    :param handle: pool group handle returned by create_pool_group.
+    :return: Error code
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("close_pool_group: >>> handle: %r",
+                 handle)
+
+    if not hasattr(close_pool_group, "cb"):
+        logger.debug("close_pool_group: Creating callback")
+        close_pool_group.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_handle = c_int32(handle)
+
+    res = await do_call('indy_close_pool_group',
+                        c_handle,
+                        close_pool_group.cb)
+
+    logger.debug("close_pool_group: <<< res: %r", res)
+    return res
+
+
 async def delete_pool_ledger_config(config_name: str) -> None:
     """
     Deletes created pool ledger configuration.
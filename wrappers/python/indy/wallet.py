@@ -1,7 +1,8 @@
+from .error import ErrorCode
 from .libindy import do_call, create_cb
 
 from ctypes import *
-from typing import Optional
+from typing import Callable, Optional
 
 import logging
 
@@ -17,6 +18,7 @@ async def create_wallet(config: str,
              Configured storage uses this identifier to lookup exact wallet data placement.
        "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
                       'Default' storage type allows to store wallet data in the local file.
+                      'in_memory' storage type keeps wallet data in the memory of the process.
                       Custom storage types can be registered with indy_register_wallet_storage call.
        "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
                          Can be optional if storage supports default configuration.
@@ -26,6 +28,11 @@ async def create_wallet(config: str,
                  Defaults to $HOME/.indy_client/wallet.
                  Wallet will be stored in the file {path}/{id}/sqlite.db
        }
+       For 'in_memory' storage type configuration is:
+       {
+         "snapshot_path": optional<string>, Path to the file the wallet is saved to on close.
+                          Wallet is restored from this file on open if it isn't in memory yet.
+       }
      }
     :param credentials: Wallet credentials json
      {
@@ -85,6 +92,11 @@ async def open_wallet(config: str,
               "path": optional<string>, Path to the directory with wallet files.
                       Defaults to $HOME/.indy_client/wallet.
                       Wallet will be stored in the file {path}/{id}/sqlite.db
+              "locking": optional<string>, Locking mode of the wallet file:
+                         "exclusive" - wallet is used by a single process (default),
+                         "shared" - wallet can be opened by several processes at the same time.
+              "busy_timeout": optional<int>, Time in milliseconds to wait for the wallet file locked by another process
+                              in "shared" mode. Defaults to 5000.
            }
 
     }
@@ -211,6 +223,56 @@ async def delete_wallet(config: str,
     logger.debug("delete_wallet: <<<")
 
 
+async def check_wallet(config: str,
+                       credentials: str,
+                       options: Optional[str] = None) -> str:
+    """
+    Checks integrity of created wallet. The wallet has to be closed.
+
+    :param config: Wallet configuration json. The same as for delete_wallet call.
+    :param credentials: Wallet credentials json. The same as for delete_wallet call.
+    :param options: (optional) check options json.
+     {
+       "repair": optional<bool>, Remove invalid records and orphaned tags from the wallet. Defaults to false.
+       "quarantine_path": optional<string>, Path to the file where removed records are appended before repair.
+     }
+    :return: check report json
+     {
+       "valid": bool, true if no problems are found,
+       "metadata_errors": [string], problems of wallet metadata, records aren't checked if there are any,
+       "checked_records": int, number of checked records,
+       "invalid_records": [{"type": optional<string>, "id": optional<string>, "error": string}],
+       "orphaned_tags": int, number of tags that don't belong to any record,
+       "repaired": bool, true if found problems have been removed
+     }
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("check_wallet: >>> config: %r, credentials: %r, options: %r",
+                 config,
+                 credentials,
+                 options)
+
+    if not hasattr(check_wallet, "cb"):
+        logger.debug("check_wallet: Creating callback")
+        check_wallet.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32, c_char_p))
+
+    c_config = c_char_p(config.encode('utf-8'))
+    c_credentials = c_char_p(credentials.encode('utf-8'))
+    c_options = c_char_p(options.encode('utf-8')) if options is not None else None
+
+    report = await do_call('indy_check_wallet',
+                           c_config,
+                           c_credentials,
+                           c_options,
+                           check_wallet.cb)
+
+    res = report.decode()
+
+    logger.debug("check_wallet: <<< res: %r", res)
+    return res
+
+
 async def export_wallet(handle: int,
                         export_config_json: str) -> None:
     """
@@ -227,6 +289,11 @@ async def export_wallet(handle: int,
                                 ARGON2I_INT - derive secured wallet export key (less secured but faster)
                                 RAW - raw wallet export key provided (skip derivation).
                                       RAW keys can be generated with generate_wallet_key call
+          "types": optional<[string]> types of records to export, all records are exported by default
+          "query": optional<object> WQL query records of each of the types have to match (requires "types" to be set)
+          "marker": optional<string> name of the export marker, only records added or changed
+                    since the previous export with the same marker are exported,
+                    deleted records are exported as tombstones that delete them on import
        }
     :return:
     """
@@ -250,6 +317,97 @@ async def export_wallet(handle: int,
     logger.debug("export_wallet: <<<")
 
 
+async def export_wallet_to_stream(handle: int,
+                                  export_config_json: str,
+                                  write: Callable[[bytes], None]) -> None:
+    """
+    Exports opened wallet passing the exported content to the write function instead of the file.
+
+    :param handle: wallet handle returned by indy_open_wallet.
+    :param export_config_json: JSON containing settings for input operation.
+       {
+          "key": string, Key or passphrase used for wallet export key derivation.
+          "key_derivation_method": optional<string> ARGON2I_MOD (used by default), ARGON2I_INT or RAW
+          "types": optional<[string]> types of records to export, all records are exported by default
+          "query": optional<object> WQL query records of each of the types have to match (requires "types" to be set)
+          "marker": optional<string> name of the export marker
+       }
+    :param write: function receiving chunks of the exported content in order.
+                  It's called from the libindy thread, export fails if it raises.
+    :return:
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("export_wallet_to_stream: >>> handle: %r, export_config_json: %r",
+                 handle,
+                 export_config_json)
+
+    if not hasattr(export_wallet_to_stream, "cb"):
+        logger.debug("export_wallet_to_stream: Creating callback")
+        export_wallet_to_stream.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    def _write(_stream_handle: int, data_ptr: POINTER(c_uint8), data_len: int) -> int:
+        try:
+            write(bytes(data_ptr[:data_len]))
+            return ErrorCode.Success.value
+        except Exception:
+            logger.exception("export_wallet_to_stream: write failed")
+            return ErrorCode.CommonIOError.value
+
+    # must be kept referenced until the export is finished
+    c_write = CFUNCTYPE(c_int32, c_int32, POINTER(c_uint8), c_uint32)(_write)
+
+    c_export_config_json = c_char_p(export_config_json.encode('utf-8'))
+
+    await do_call('indy_export_wallet_to_stream',
+                  handle,
+                  c_export_config_json,
+                  c_int32(0),
+                  c_write,
+                  export_wallet_to_stream.cb)
+
+    logger.debug("export_wallet_to_stream: <<<")
+
+
+async def rekey_wallet(handle: int,
+                       credentials: str) -> None:
+    """
+    Rotates master key of opened wallet.
+    Wallet records aren't re-encrypted, so the call doesn't depend on the wallet size
+    and the wallet stays accessible by either the previous or the new key if the process is interrupted.
+
+    :param handle: wallet handle returned by indy_open_wallet.
+    :param credentials: JSON containing new wallet credentials.
+       {
+          "key": string, New key or passphrase used for wallet key derivation.
+                         Look to key_derivation_method param for information about supported key derivation methods.
+          "key_derivation_method": optional<string> algorithm to use for wallet key derivation:
+                                ARGON2I_MOD - derive secured wallet master key (used by default)
+                                ARGON2I_INT - derive secured wallet master key (less secured but faster)
+                                RAW - raw wallet master key provided (skip derivation).
+                                      RAW keys can be generated with generate_wallet_key call
+       }
+    :return:
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("rekey_wallet: >>> handle: %r",
+                 handle)
+
+    if not hasattr(rekey_wallet, "cb"):
+        logger.debug("rekey_wallet: Creating callback")
+        rekey_wallet.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_credentials = c_char_p(credentials.encode('utf-8'))
+
+    await do_call('indy_rekey_wallet',
+                  handle,
+                  c_credentials,
+                  rekey_wallet.cb)
+
+    logger.debug("rekey_wallet: <<<")
+
+
 async def import_wallet(config: str,
                         credentials: str,
                         import_config_json: str) -> None:
@@ -317,6 +475,96 @@ async def import_wallet(config: str,
     logger.debug("import_wallet: <<<")
 
 
+async def import_wallet_records(handle: int,
+                                import_config_json: str) -> None:
+    """
+    Imports content of the wallet export file into the opened wallet.
+    The wallet is left unchanged if the import fails.
+
+    :param handle: wallet handle returned by indy_open_wallet.
+    :param import_config_json: JSON containing settings for input operation.
+     {
+       "path": path of the file that contains exported wallet content
+       "key": key used for export of the wallet
+       "on_conflict": optional<string> what to do with records that already exist in the wallet:
+                      "fail" (used by default), "skip" or "overwrite"
+     }
+    :return:
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("import_wallet_records: >>> handle: %r, import_config_json: %r",
+                 handle,
+                 import_config_json)
+
+    if not hasattr(import_wallet_records, "cb"):
+        logger.debug("import_wallet_records: Creating callback")
+        import_wallet_records.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    c_import_config_json = c_char_p(import_config_json.encode('utf-8'))
+
+    await do_call('indy_import_wallet_records',
+                  handle,
+                  c_import_config_json,
+                  import_wallet_records.cb)
+
+    logger.debug("import_wallet_records: <<<")
+
+
+async def import_wallet_records_from_stream(handle: int,
+                                            import_config_json: str,
+                                            read: Callable[[int], bytes]) -> None:
+    """
+    Imports wallet content taken from the read function into the opened wallet.
+    The wallet is left unchanged if the import fails.
+
+    :param handle: wallet handle returned by indy_open_wallet.
+    :param import_config_json: JSON containing settings for input operation.
+     {
+       "key": key used for export of the wallet
+       "on_conflict": optional<string> what to do with records that already exist in the wallet:
+                      "fail" (used by default), "skip" or "overwrite"
+     }
+    :param read: function returning the next chunk of the content not longer than the requested size,
+                 empty bytes mean the end of the content.
+                 It's called from the libindy thread, import fails if it raises.
+    :return:
+    """
+
+    logger = logging.getLogger(__name__)
+    logger.debug("import_wallet_records_from_stream: >>> handle: %r, import_config_json: %r",
+                 handle,
+                 import_config_json)
+
+    if not hasattr(import_wallet_records_from_stream, "cb"):
+        logger.debug("import_wallet_records_from_stream: Creating callback")
+        import_wallet_records_from_stream.cb = create_cb(CFUNCTYPE(None, c_int32, c_int32))
+
+    def _read(_stream_handle: int, data_ptr: POINTER(c_uint8), data_len: int, read_len_ptr: POINTER(c_uint32)) -> int:
+        try:
+            data = read(data_len)[:data_len]
+            memmove(data_ptr, data, len(data))
+            read_len_ptr[0] = len(data)
+            return ErrorCode.Success.value
+        except Exception:
+            logger.exception("import_wallet_records_from_stream: read failed")
+            return ErrorCode.CommonIOError.value
+
+    # must be kept referenced until the import is finished
+    c_read = CFUNCTYPE(c_int32, c_int32, POINTER(c_uint8), c_uint32, POINTER(c_uint32))(_read)
+
+    c_import_config_json = c_char_p(import_config_json.encode('utf-8'))
+
+    await do_call('indy_import_wallet_records_from_stream',
+                  handle,
+                  c_import_config_json,
+                  c_int32(0),
+                  c_read,
+                  import_wallet_records_from_stream.cb)
+
+    logger.debug("import_wallet_records_from_stream: <<<")
+
+
 async def generate_wallet_key(config: Optional[str]) -> str:
     """
     Generate wallet master key.
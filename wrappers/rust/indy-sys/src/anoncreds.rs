@@ -62,6 +62,26 @@ extern {
                                          blob_storage_reader_handle: BlobStorageReaderHandle,
                                          cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_credential_with_tags(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   cred_offer_json: CString,
+                                                   cred_req_json: CString,
+                                                   cred_values_json: CString,
+                                                   rev_reg_id: CString,
+                                                   blob_storage_reader_handle: BlobStorageReaderHandle,
+                                                   record_tags_json: CString,
+                                                   cb: Option<ResponseStringStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cred_offer_json: CString,
+                                          cred_issuance_requests_json: CString,
+                                          rev_reg_id: CString,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          cb: Option<ResponseStringStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
@@ -70,6 +90,66 @@ extern {
                                          cred_revoc_id: CString,
                                          cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                                          rev_reg_id: CString,
+                                          cred_revoc_ids_json: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_create_revocation_registry_pool(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       issuer_did: CString,
+                                                       cred_def_id: CString,
+                                                       config_json: CString,
+                                                       cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_create_credential_from_pool(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   cred_offer_json: CString,
+                                                   cred_req_json: CString,
+                                                   cred_values_json: CString,
+                                                   cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_get_revocation_registry_pool(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    cred_def_id: CString,
+                                                    cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_revoke_credential_by_record(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                                                   record_id: CString,
+                                                   cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_get_credential_record(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             record_id: CString,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_search_credential_records(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 query_json: CString,
+                                                 cb: Option<ResponseI32UsizeCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_fetch_credential_records(command_handle: CommandHandle,
+                                                search_handle: SearchHandle,
+                                                count: usize,
+                                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_close_credential_records_search(command_handle: CommandHandle,
+                                                       search_handle: SearchHandle,
+                                                       cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                         rev_reg_delta_json: CString,
@@ -196,6 +276,16 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                  proof_request_json: CString,
+                                                  proof_json: CString,
+                                                  schemas_json: CString,
+                                                  credential_defs_json: CString,
+                                                  rev_reg_defs_json: CString,
+                                                  rev_regs_json: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
@@ -222,5 +312,9 @@ extern {
     pub fn indy_to_unqualified(command_handle: CommandHandle,
                                entity: CString,
                                cb: Option<ResponseStringCB>) -> Error;
+    #[no_mangle]
+    pub fn indy_encode_credential_attr_values(command_handle: CommandHandle,
+                                              raw_values_json: CString,
+                                              cb: Option<ResponseStringCB>) -> Error;
 }
 
@@ -1,9 +1,20 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, WalletHandle};
+use {BString, CString, Error, CommandHandle, IndyHandle, WalletHandle};
 
 extern {
 
+    #[no_mangle]
+    pub fn indy_register_key_provider(command_handle: CommandHandle,
+                                      crypto_type: CString,
+                                      create_key: Option<KeyProviderCreateKey>,
+                                      sign: Option<KeyProviderSign>,
+                                      crypto_box: Option<KeyProviderCryptoBox>,
+                                      crypto_box_open: Option<KeyProviderCryptoBoxOpen>,
+                                      crypto_box_seal_open: Option<KeyProviderCryptoBoxSealOpen>,
+                                      free_result: Option<KeyProviderFreeResult>,
+                                      cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_key(command_handle: CommandHandle,
                            wallet_handle: WalletHandle,
@@ -89,3 +100,38 @@ extern {
                                cb: Option<ResponseSliceCB>) -> Error;
 }
 
+pub type KeyProviderCreateKey = extern fn(seed: BString,
+                                          seed_len: u32,
+                                          verkey_p: *mut CString,
+                                          result_handle_p: *mut IndyHandle) -> Error;
+pub type KeyProviderSign = extern fn(verkey: CString,
+                                     message: BString,
+                                     message_len: u32,
+                                     signature_p: *mut BString,
+                                     signature_len_p: *mut u32,
+                                     result_handle_p: *mut IndyHandle) -> Error;
+pub type KeyProviderCryptoBox = extern fn(verkey: CString,
+                                          their_vk: CString,
+                                          message: BString,
+                                          message_len: u32,
+                                          nonce: BString,
+                                          nonce_len: u32,
+                                          encrypted_p: *mut BString,
+                                          encrypted_len_p: *mut u32,
+                                          result_handle_p: *mut IndyHandle) -> Error;
+pub type KeyProviderCryptoBoxOpen = extern fn(verkey: CString,
+                                              their_vk: CString,
+                                              encrypted: BString,
+                                              encrypted_len: u32,
+                                              nonce: BString,
+                                              nonce_len: u32,
+                                              decrypted_p: *mut BString,
+                                              decrypted_len_p: *mut u32,
+                                              result_handle_p: *mut IndyHandle) -> Error;
+pub type KeyProviderCryptoBoxSealOpen = extern fn(verkey: CString,
+                                                  encrypted: BString,
+                                                  encrypted_len: u32,
+                                                  decrypted_p: *mut BString,
+                                                  decrypted_len_p: *mut u32,
+                                                  result_handle_p: *mut IndyHandle) -> Error;
+pub type KeyProviderFreeResult = extern fn(result_handle: IndyHandle) -> Error;
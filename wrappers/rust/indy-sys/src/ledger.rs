@@ -17,6 +17,13 @@ extern {
                                request_json: CString,
                                cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_submit_cached_request(command_handle: CommandHandle,
+                                      pool_handle: PoolHandle,
+                                      request_json: CString,
+                                      options_json: CString,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_submit_action(command_handle: CommandHandle,
                               pool_handle: PoolHandle,
@@ -136,6 +143,14 @@ extern {
                                       seq_no: i32,
                                       cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_get_verified_txn(command_handle: CommandHandle,
+                                 pool_handle: PoolHandle,
+                                 submitter_did: CString,
+                                 ledger_type: CString,
+                                 seq_no: i32,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_build_pool_config_request(command_handle: CommandHandle,
                                           submitter_did: CString,
@@ -30,6 +30,7 @@ pub type IndyHandle = i32;
 pub type CommandHandle = i32;
 pub type PoolHandle = i32;
 pub type SearchHandle = i32;
+pub type SubscriptionHandle = i32;
 pub type RecordHandle = i32;
 pub type TailWriterHandle = i32;
 pub type StorageHandle = i32;
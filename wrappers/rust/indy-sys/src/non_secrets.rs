@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle};
+use {CString, Error, CommandHandle, WalletHandle, SubscriptionHandle};
 
 extern {
 
@@ -52,6 +52,12 @@ extern {
                                      id: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_batch_wallet_records(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     operations_json: CString,
+                                     cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_get_wallet_record(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -79,4 +85,19 @@ extern {
     pub fn indy_close_wallet_search(command_handle: CommandHandle,
                                     wallet_search_handle: SearchHandle,
                                     cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_subscribe_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         type_: CString,
+                                         query_json: CString,
+                                         event_cb: Option<WalletRecordsEventCB>,
+                                         cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_unsubscribe_wallet_records(command_handle: CommandHandle,
+                                           subscription_handle: SubscriptionHandle,
+                                           cb: Option<ResponseEmptyCB>) -> Error;
 }
+
+pub type WalletRecordsEventCB = extern fn(subscription_handle: SubscriptionHandle, event_json: CString);
@@ -21,6 +21,31 @@ extern {
                                     handle: PoolHandle,
                                     cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_get_pool_stats(command_handle: CommandHandle,
+                               handle: PoolHandle,
+                               cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_set_pool_nodes_update_callback(command_handle: CommandHandle,
+                                               handle: PoolHandle,
+                                               update_cb: Option<PoolNodesUpdateCB>,
+                                               cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_reply_state_proof(command_handle: CommandHandle,
+                                         handle: PoolHandle,
+                                         genesis_txn: CString,
+                                         request_json: CString,
+                                         reply_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_validate_pool_genesis(command_handle: CommandHandle,
+                                      genesis_txn: CString,
+                                      protocol_version: usize,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_list_pools(command_handle: CommandHandle,
                            cb: Option<ResponseStringCB>) -> Error;
@@ -30,6 +55,16 @@ extern {
                                   handle: PoolHandle,
                                   cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_create_pool_group(command_handle: CommandHandle,
+                                  config: CString,
+                                  cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_close_pool_group(command_handle: CommandHandle,
+                                 handle: PoolHandle,
+                                 cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_delete_pool_ledger_config(command_handle: CommandHandle,
                                           config_name: CString,
@@ -41,3 +76,4 @@ extern {
                                      cb: Option<ResponseEmptyCB>) -> Error;
 }
 
+pub type PoolNodesUpdateCB = extern fn(pool_handle: PoolHandle, nodes_diff_json: CString);
@@ -1,6 +1,6 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, StorageHandle};
+use {BString, CString, Error, CommandHandle, IndyHandle, StorageHandle};
 
 extern {
 
@@ -51,6 +51,20 @@ extern {
                               export_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_export_wallet_to_stream(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        export_config: CString,
+                                        stream_handle: IndyHandle,
+                                        write_cb: Option<WalletExportWrite>,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_rekey_wallet(command_handle: CommandHandle,
+                             wallet_handle: WalletHandle,
+                             credentials: CString,
+                             cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_import_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -58,6 +72,20 @@ extern {
                               import_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_import_wallet_records(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      import_config: CString,
+                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_import_wallet_records_from_stream(command_handle: CommandHandle,
+                                                  wallet_handle: WalletHandle,
+                                                  import_config: CString,
+                                                  stream_handle: IndyHandle,
+                                                  read_cb: Option<WalletImportRead>,
+                                                  cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_close_wallet(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
@@ -69,6 +97,13 @@ extern {
                               credentials: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_check_wallet(command_handle: CommandHandle,
+                             config: CString,
+                             credentials: CString,
+                             options: CString,
+                             cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_generate_wallet_key(command_handle: CommandHandle,
                                     config: CString,
@@ -155,3 +190,10 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: StorageHandle,
                                                  record_handle_p: *mut RecordHandle) -> Error;
 pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                       search_handle: SearchHandle) -> Error;
+pub type WalletExportWrite = extern fn(stream_handle: IndyHandle,
+                                       data_raw: BString,
+                                       data_len: u32) -> Error;
+pub type WalletImportRead = extern fn(stream_handle: IndyHandle,
+                                     data_raw: *mut u8,
+                                     data_len: u32,
+                                     read_len: *mut u32) -> Error;
@@ -405,6 +405,111 @@ fn _issuer_create_credential(
     })
 }
 
+/// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request
+/// storing the issuance record with the given tags.
+///
+/// Acts like `issuer_create_credential`, `record_tags_json` are attached to the issuance record
+/// stored for a credential issued from revocation registry.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_offer_json`: a cred offer created by issuer_create_credential_offer
+/// * `cred_req_json`: a credential request created by prover_create_credential_req
+/// * `cred_values_json`: a credential containing attribute values for each of requested attribute names.
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+/// * `record_tags_json`: (optional) tags of the issuance record: {"tagName1": string, "~tagName2": string}
+///
+/// # Returns
+/// * `cred_json`: Credential json containing signed credential values
+/// * `cred_revoc_id`: local id for revocation info (Can be used for revocation of this credential)
+/// * `revoc_reg_delta_json`: Revocation registry delta json with a newly issued credential
+pub fn issuer_create_credential_with_tags(wallet_handle: WalletHandle,
+                                          cred_offer_json: &str,
+                                          cred_req_json: &str,
+                                          cred_values_json: &str,
+                                          rev_reg_id: Option<&str>,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          record_tags_json: Option<&str>) -> Box<dyn Future<Item=(String, Option<String>, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string_opt_string();
+
+    let err = _issuer_create_credential_with_tags(command_handle, wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle, record_tags_json, cb);
+
+    ResultHandler::str_optstr_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credential_with_tags(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    cred_offer_json: &str,
+    cred_req_json: &str,
+    cred_values_json: &str,
+    rev_reg_id: Option<&str>,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    record_tags_json: Option<&str>,
+    cb: Option<ResponseStringStringStringCB>
+) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_req_json = c_str!(cred_req_json);
+    let cred_values_json = c_str!(cred_values_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+    let record_tags_json_str = opt_c_str!(record_tags_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credential_with_tags(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_req_json.as_ptr(), cred_values_json.as_ptr(),
+                                                           opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle,
+                                                           opt_c_ptr!(record_tags_json, record_tags_json_str), cb)
+    })
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credentials for a batch of Cred Requests.
+///
+/// Works like `issuer_create_credential` called for every request of the batch, but loads
+/// the revocation registry and opens the tails reader only once and signs credentials in parallel.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_offer_json`: a cred offer created by create_credential_offer
+/// * `cred_issuance_requests_json`: a list of credential requests with the values to issue for each of them
+///     [{"cred_req": <credential request>, "cred_values": <see cred_values_json of issuer_create_credential>,
+///       "record_tags": Optional<see record_tags_json of issuer_create_credential_with_tags>}, ...]
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `creds_json`: issued credentials in the order of requests
+///     [{"cred": <credential json>, "cred_rev_id": Optional<string>}, ...]
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all newly issued credentials
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 cred_offer_json: &str,
+                                 cred_issuance_requests_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = _issuer_create_credentials(command_handle, wallet_handle, cred_offer_json, cred_issuance_requests_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    cred_offer_json: &str,
+    cred_issuance_requests_json: &str,
+    rev_reg_id: Option<&str>,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    cb: Option<ResponseStringStringCB>
+) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_issuance_requests_json = c_str!(cred_issuance_requests_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_issuance_requests_json.as_ptr(), opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle, cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -443,6 +548,265 @@ fn _issuer_revoke_credential(command_handle: CommandHandle,
     })
 }
 
+/// Revoke a batch of credentials of the same revocation registry identified by cred_revoc_ids.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `blob_storage_reader_cfg_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+/// * `cred_revoc_ids_json`: list of local ids for revocation info: ["1", "2", ...]
+///
+/// # Returns
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all revoked credentials
+pub fn issuer_revoke_credentials(wallet_handle: WalletHandle, blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle, rev_reg_id: &str, cred_revoc_ids_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_ids_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_revoke_credentials(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                              rev_reg_id: &str,
+                              cred_revoc_ids_json: &str,
+                              cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+    let cred_revoc_ids_json = c_str!(cred_revoc_ids_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id.as_ptr(), cred_revoc_ids_json.as_ptr(), cb)
+    })
+}
+
+/// Create a pool of revocation registries for a credential definition used by `issuer_create_credential_from_pool`.
+///
+/// The active registry of the pool is replaced by the next pre-generated one as soon as it is full.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `issuer_did`: a DID of the issuer
+/// * `cred_def_id`: id of stored in ledger credential definition
+/// * `config_json`: configuration of the pool (see `indy_issuer_create_revocation_registry_pool`)
+///
+/// # Returns
+/// * `rev_regs_json`: created revocation registries to be published to the ledger
+pub fn issuer_create_revocation_registry_pool(wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_create_revocation_registry_pool(command_handle, wallet_handle, issuer_did, cred_def_id, config_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_create_revocation_registry_pool(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           issuer_did: &str,
+                                           cred_def_id: &str,
+                                           config_json: &str,
+                                           cb: Option<ResponseStringCB>) -> ErrorCode {
+    let issuer_did = c_str!(issuer_did);
+    let cred_def_id = c_str!(cred_def_id);
+    let config_json = c_str!(config_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_revocation_registry_pool(command_handle, wallet_handle, issuer_did.as_ptr(), cred_def_id.as_ptr(), config_json.as_ptr(), cb)
+    })
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential from the revocation registry pool
+/// of the credential definition.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_offer_json`: a cred offer created by issuer_create_credential_offer
+/// * `cred_req_json`: a credential request created by prover_create_credential_req
+/// * `cred_values_json`: a credential containing attribute values for each of requested attribute names.
+///
+/// # Returns
+/// * `issued_cred_json`: Credential json with `cred_rev_id`, `rev_reg_id`, optional `rev_reg_delta`
+///   and revocation registries created by the pool during issuance
+pub fn issuer_create_credential_from_pool(wallet_handle: WalletHandle, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_create_credential_from_pool(command_handle, wallet_handle, cred_offer_json, cred_req_json, cred_values_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_create_credential_from_pool(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       cred_offer_json: &str,
+                                       cred_req_json: &str,
+                                       cred_values_json: &str,
+                                       cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_req_json = c_str!(cred_req_json);
+    let cred_values_json = c_str!(cred_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credential_from_pool(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_req_json.as_ptr(), cred_values_json.as_ptr(), cb)
+    })
+}
+
+/// Get the state of the revocation registry pool of the credential definition.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_def_id`: id of credential definition the pool was created for
+///
+/// # Returns
+/// * `pool_json`: config of the pool and issued/revoked indices of its registries
+pub fn issuer_get_revocation_registry_pool(wallet_handle: WalletHandle, cred_def_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_revocation_registry_pool(command_handle, wallet_handle, cred_def_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_revocation_registry_pool(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        cred_def_id: &str,
+                                        cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_def_id = c_str!(cred_def_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_revocation_registry_pool(command_handle, wallet_handle, cred_def_id.as_ptr(), cb)
+    })
+}
+
+/// Revoke a credential identified by its issuance record.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `blob_storage_reader_cfg_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+/// * `record_id`: id of issuance record
+///
+/// # Returns
+/// * `revoc_reg_delta_json`: Revocation registry delta json with a revoked credential
+pub fn issuer_revoke_credential_by_record(wallet_handle: WalletHandle, blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle, record_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_revoke_credential_by_record(command_handle, wallet_handle, blob_storage_reader_cfg_handle, record_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_revoke_credential_by_record(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                                       record_id: &str,
+                                       cb: Option<ResponseStringCB>) -> ErrorCode {
+    let record_id = c_str!(record_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_revoke_credential_by_record(command_handle, wallet_handle, blob_storage_reader_cfg_handle, record_id.as_ptr(), cb)
+    })
+}
+
+/// Get issuance record of a credential issued from a revocation registry.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `record_id`: id of issuance record: "<rev_reg_id>:<cred_rev_id>"
+///
+/// # Returns
+/// * `record_json`: issuance record
+///     {
+///         "record_id": string,
+///         "cred_def_id": string,
+///         "rev_reg_id": string,
+///         "cred_rev_id": string,
+///         "prover_did": string,
+///         "timestamp": number,
+///         "revoked": bool,
+///         "revoked_timestamp": Optional<number>,
+///         "tags": {"tagName": string}
+///     }
+pub fn issuer_get_credential_record(wallet_handle: WalletHandle, record_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_credential_record(command_handle, wallet_handle, record_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_credential_record(command_handle: CommandHandle, wallet_handle: WalletHandle, record_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let record_id = c_str!(record_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_credential_record(command_handle, wallet_handle, record_id.as_ptr(), cb)
+    })
+}
+
+/// Search for issuance records of credentials issued from revocation registries.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `query_json`: Wql query filter for issuance records searching based on tags
+///     ("cred_def_id", "rev_reg_id", "cred_rev_id", "prover_did", "revoked", "~timestamp" and Issuer's tags).
+///
+/// # Returns
+/// * `search_handle`: Search handle that can be used later to fetch records by small batches (with issuer_fetch_credential_records)
+/// * `total_count`: Total count of records
+pub fn issuer_search_credential_records(wallet_handle: WalletHandle, query_json: Option<&str>) -> Box<dyn Future<Item=(SearchHandle, usize), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle_usize();
+
+    let err = _issuer_search_credential_records(command_handle, wallet_handle, query_json, cb);
+
+    ResultHandler::handle_usize(command_handle, err, receiver)
+}
+
+fn _issuer_search_credential_records(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseI32UsizeCB>) -> ErrorCode {
+    let query_json_str = opt_c_str!(query_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_search_credential_records(command_handle, wallet_handle, opt_c_ptr!(query_json, query_json_str), cb)
+    })
+}
+
+/// Fetch next issuance records for search.
+///
+/// # Arguments
+/// * `search_handle`: Search handle (created by issuer_search_credential_records)
+/// * `count`: Count of records to fetch
+///
+/// # Returns
+/// * `records_json`: List of issuance records (see `issuer_get_credential_record`)
+pub fn issuer_fetch_credential_records(search_handle: SearchHandle, count: usize) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_fetch_credential_records(command_handle, search_handle, count, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_fetch_credential_records(command_handle: CommandHandle, search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_fetch_credential_records(command_handle, search_handle, count, cb)
+    })
+}
+
+/// Close issuance records search (make search handle invalid)
+///
+/// # Arguments
+/// * `search_handle`: Search handle (created by issuer_search_credential_records)
+pub fn issuer_close_credential_records_search(search_handle: SearchHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_close_credential_records_search(command_handle, search_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_close_credential_records_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_close_credential_records_search(command_handle, search_handle, cb)
+    })
+}
+
 /// Merge two revocation registry deltas (returned by create_credential or revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -1344,6 +1708,50 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Verifies a proof the same way as `verifier_verify_proof`, but returns a report explaining the result.
+///
+/// # Arguments
+/// Same as for `verifier_verify_proof`.
+///
+/// # Returns
+/// * `report_json`: proof verification report
+///     {
+///         "valid": bool, // same result as `verifier_verify_proof` returns
+///         "requested_attributes": {"attr_referent": <referent_report>},
+///         "requested_predicates": {"predicate_referent": <referent_report>},
+///         "errors": [string], // all reasons why the proof was rejected
+///     }
+///     referent_report: {
+///         "valid": bool,
+///         "revealed": bool,
+///         "self_attested": bool,
+///         "values": Optional<{"attr_name": raw value}>,
+///         "identifier": Optional<{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}>,
+///         "restrictions_satisfied": Optional<bool>,
+///         "non_revoked": Optional<non_revoc_interval>,
+///         "errors": [string],
+///     }
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_report(command_handle, proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_report(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_report(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    })
+}
+
 
 /// Create revocation state for a credential in the particular time moment.
 ///
@@ -1481,3 +1889,31 @@ fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<Respo
         anoncreds::indy_to_unqualified(command_handle, entity.as_ptr(), cb)
     })
 }
+
+/// Encode raw credential attribute values in the canonical form expected by `issuer_create_credential`.
+///
+/// # Arguments
+/// * `raw_values_json`: raw credential values. Each value is either a plain raw value (string, number or boolean)
+///     or an object with an explicit encoding type (`int`, `string`, `date` or `boolean`):
+///     {
+///      "name": "Alex",
+///      "birthdate": {"raw": "1991-10-18", "type": "date"}
+///     }
+///
+/// # Returns
+/// * `cred_values_json`: credential values with `raw` and `encoded` values for each attribute
+pub fn encode_credential_attr_values(raw_values_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_credential_attr_values(command_handle, raw_values_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _encode_credential_attr_values(command_handle: CommandHandle, raw_values_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let raw_values_json = c_str!(raw_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_credential_attr_values(command_handle, raw_values_json.as_ptr(), cb)
+    })
+}
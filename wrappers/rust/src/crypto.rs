@@ -14,6 +14,57 @@ use {ErrorCode, IndyError};
 use utils::callbacks::{ClosureHandler, ResultHandler};
 use {WalletHandle, CommandHandle};
 
+/// Register custom key provider implementation.
+///
+/// Keys created with the registered crypto type live in the key provider (For example, in external KMS process)
+/// and only verkeys are stored in the wallet. Signing and decryption with these keys are delegated to the key provider.
+///
+/// # Arguments
+/// * `crypto_type` - Crypto type name. Verkeys of the keys created by the provider have "<verkey>:<crypto_type>" form.
+/// * `create_key` - "create_key" operation handler
+/// * `sign` - "sign" operation handler
+/// * `crypto_box` - "crypto_box" (authenticated encryption) operation handler
+/// * `crypto_box_open` - "crypto_box_open" (authenticated decryption) operation handler
+/// * `crypto_box_seal_open` - "crypto_box_seal_open" (anonymous decryption) operation handler
+/// * `free_result` - Handler that allows to de-allocate results allocated by the handlers above
+pub fn register_key_provider(crypto_type: &str,
+                             create_key: Option<crypto::KeyProviderCreateKey>,
+                             sign: Option<crypto::KeyProviderSign>,
+                             crypto_box: Option<crypto::KeyProviderCryptoBox>,
+                             crypto_box_open: Option<crypto::KeyProviderCryptoBoxOpen>,
+                             crypto_box_seal_open: Option<crypto::KeyProviderCryptoBoxSealOpen>,
+                             free_result: Option<crypto::KeyProviderFreeResult>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_provider(command_handle, crypto_type, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, free_result, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_key_provider(command_handle: CommandHandle,
+                          crypto_type: &str,
+                          create_key: Option<crypto::KeyProviderCreateKey>,
+                          sign: Option<crypto::KeyProviderSign>,
+                          crypto_box: Option<crypto::KeyProviderCryptoBox>,
+                          crypto_box_open: Option<crypto::KeyProviderCryptoBoxOpen>,
+                          crypto_box_seal_open: Option<crypto::KeyProviderCryptoBoxSealOpen>,
+                          free_result: Option<crypto::KeyProviderFreeResult>,
+                          cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let crypto_type = c_str!(crypto_type);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_register_key_provider(command_handle,
+                                           crypto_type.as_ptr(),
+                                           create_key,
+                                           sign,
+                                           crypto_box,
+                                           crypto_box_open,
+                                           crypto_box_seal_open,
+                                           free_result,
+                                           cb)
+    })
+}
+
 /// Creates key pair in wallet
 /// # Arguments
 /// * `wallet_handle` - wallet handle (created by Wallet::open)
@@ -24,7 +75,8 @@ use {WalletHandle, CommandHandle};
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value
+///                            // and crypto types of key providers registered with `register_key_provider` are supported for this field.
 /// }
 /// # Returns
 /// verkey of generated key pair, also used as key identifier
@@ -73,6 +73,42 @@ fn _submit_request(command_handle: CommandHandle, pool_handle: PoolHandle, reque
     ErrorCode::from(unsafe { ledger::indy_submit_request(command_handle, pool_handle, request_json.as_ptr(), cb) })
 }
 
+/// Publishes GET request message to validator pool using the persistent read cache of the pool.
+///
+/// If a fresh enough reply on the same request is present inside of the cache, cached reply is returned.
+/// Otherwise the request is sent to the validator pool as is and the reply is stored inside of the cache
+/// if its state proof is verified.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `request_json` - Request data json.
+/// * `options_json` - cache options json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+///
+/// # Returns
+/// Request result as json.
+pub fn submit_cached_request(pool_handle: PoolHandle, request_json: &str, options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _submit_cached_request(command_handle, pool_handle, request_json, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _submit_cached_request(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, options_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let request_json = c_str!(request_json);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { ledger::indy_submit_cached_request(command_handle, pool_handle, request_json.as_ptr(), options_json.as_ptr(), cb) })
+}
+
 pub fn submit_action(pool_handle: PoolHandle, request_json: &str, nodes: Option<&str>, wait_timeout: Option<i32>) -> Box<dyn Future<Item=String, Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
 
@@ -301,6 +337,35 @@ fn _build_get_txn_request(command_handle: CommandHandle, submitter_did: Option<&
     ErrorCode::from(unsafe { ledger::indy_build_get_txn_request(command_handle, opt_c_ptr!(submitter_did, submitter_did_str), opt_c_ptr!(ledger_type, ledger_type_str), seq_no, cb) })
 }
 
+/// Gets a transaction by its seq_no with the proof of its inclusion into the ledger.
+/// The audit path of the reply is checked against the ledger root hash signed by the pool validators.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `submitter_did` - (Optional) DID of the read request sender.
+/// * `ledger_type` - (Optional) type of the ledger the requested transaction belongs to:
+///     DOMAIN - used default,
+///     POOL,
+///     CONFIG
+/// * `seq_no` - seq_no of transaction in ledger.
+///
+/// # Returns
+/// Verified transaction json: {"txn": {...}, "seq_no": int, "root_hash": string, "verified": true}
+pub fn get_verified_txn(pool_handle: PoolHandle, submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_verified_txn(command_handle, pool_handle, submitter_did, ledger_type, seq_no, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_verified_txn(command_handle: CommandHandle, pool_handle: PoolHandle, submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did_str = opt_c_str!(submitter_did);
+    let ledger_type_str = opt_c_str!(ledger_type);
+
+    ErrorCode::from(unsafe { ledger::indy_get_verified_txn(command_handle, pool_handle, opt_c_ptr!(submitter_did, submitter_did_str), opt_c_ptr!(ledger_type, ledger_type_str), seq_no, cb) })
+}
+
 /// Builds an ATTRIB request. Request to add attribute to a NYM record.
 ///
 /// # Arguments
@@ -46,6 +46,7 @@ pub use ffi::{
     WalletHandle,
     PoolHandle,
     SearchHandle,
+    SubscriptionHandle,
     RecordHandle,
     TailWriterHandle,
     StorageHandle,
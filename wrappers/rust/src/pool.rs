@@ -6,6 +6,7 @@ use std::ptr::null;
 use utils::callbacks::{ClosureHandler, ResultHandler};
 
 use ffi::pool;
+pub use ffi::pool::PoolNodesUpdateCB;
 use ffi::{ResponseEmptyCB,
           ResponseStringCB,
           ResponseI32CB};
@@ -53,6 +54,13 @@ fn _create_pool_ledger_config(command_handle: CommandHandle, pool_name: &str, po
 ///         ["name_of_1st_prior_node",  "name_of_2nd_prior_node", .... ]
 ///         Note: Not specified nodes will be placed in a random way.
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
+///     "refresh_interval": int (optional) - if set, the pool ledger is refreshed in background every `refresh_interval` sec.
+///         Requests sent during the refresh are processed after it.
+///     "adaptive_ordering": bool (optional) - rank nodes by observed response time and success rate
+///         and send single read requests to the best nodes first (false by default).
+///         Note: nodes without observations go first; `preordered_nodes` order is used for ties.
+///     "simulation": object (optional) - replaces network connections with in-process simulated nodes (for testing only):
+///         {"nodes": {"<node name>": {"behavior": "honest" | "faulty" | "malicious" | "slow", "delay": <ms, for slow>}}, "pool_txns": [<txn json>, ...]}
 /// }
 ///
 /// # Returns
@@ -88,6 +96,122 @@ fn _refresh_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle,
     ErrorCode::from(unsafe { pool::indy_refresh_pool_ledger(command_handle, pool_handle, cb) })
 }
 
+/// Returns health metrics of the nodes of an opened pool ledger: per-node reply, NACK and timeout
+/// counters, latency histograms, current connections count and time of the last catchup.
+///
+/// # Arguments
+/// * `handle` - pool handle returned by open_ledger
+pub fn get_pool_stats(pool_handle: PoolHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_pool_stats(command_handle, pool_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_pool_stats(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { pool::indy_get_pool_stats(command_handle, pool_handle, cb) })
+}
+
+/// Registers a callback to be called with the description of changes when the set of pool validators
+/// changes after a refresh of the pool ledger. `None` removes registered callback.
+///
+/// # Arguments
+/// * `handle` - pool handle returned by open_ledger
+/// * `update_cb` - callback that takes the pool handle and the nodes diff json:
+/// {
+///     "added": {"<node alias>": "<BLS key>"|null, ...},
+///     "removed": ["<node alias>", ...],
+///     "updated": {"<node alias>": "<BLS key>"|null, ...}
+/// }
+pub fn set_pool_nodes_update_callback(pool_handle: PoolHandle, update_cb: Option<PoolNodesUpdateCB>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_pool_nodes_update_callback(command_handle, pool_handle, update_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_pool_nodes_update_callback(command_handle: CommandHandle, pool_handle: PoolHandle, update_cb: Option<PoolNodesUpdateCB>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { pool::indy_set_pool_nodes_update_callback(command_handle, pool_handle, update_cb, cb) })
+}
+
+/// Verifies BLS multi-signature and state proof of a previously received reply on a GET_* request
+/// without sending anything to the pool.
+///
+/// # Arguments
+/// * `handle` - pool handle returned by open_ledger or 0 if `genesis_txn` is passed
+/// * `genesis_txn` - path to a genesis transactions file to take validators from instead of the opened pool
+/// * `request_json` - the request the reply was received on
+/// * `reply_json` - the reply received from the ledger
+///
+/// # Returns
+/// verdict json:
+/// {
+///     "verified": bool,
+///     "txn_type": string, // (optional)
+///     "state_proof_present": bool,
+///     "failure": string, // (optional) "no_state_proof", "root_hash_mismatch", "invalid_signature", "invalid_proof" or "unsupported_format"
+///     "participants": [string],
+///     "last_signed_time": int, // (optional)
+///     "nodes_count": int,
+///     "f": int
+/// }
+pub fn verify_reply_state_proof(pool_handle: PoolHandle, genesis_txn: Option<&str>, request_json: &str, reply_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verify_reply_state_proof(command_handle, pool_handle, genesis_txn, request_json, reply_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verify_reply_state_proof(command_handle: CommandHandle, pool_handle: PoolHandle, genesis_txn: Option<&str>, request_json: &str, reply_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let genesis_txn_str = opt_c_str!(genesis_txn);
+    let request_json = c_str!(request_json);
+    let reply_json = c_str!(reply_json);
+
+    ErrorCode::from(unsafe {
+        pool::indy_verify_reply_state_proof(command_handle, pool_handle, opt_c_ptr!(genesis_txn, genesis_txn_str), request_json.as_ptr(), reply_json.as_ptr(), cb)
+    })
+}
+
+/// Validates a genesis transactions file without connecting to the nodes.
+///
+/// # Arguments
+/// * `genesis_txn` - path to a genesis transactions file
+/// * `protocol_version` - protocol version NODE transactions are checked against (1 or 2),
+///                        0 to use the version set by `set_protocol_version`
+///
+/// # Returns
+/// report json:
+/// {
+///     "valid": bool,
+///     "txns_count": int,
+///     "nodes": [{"alias": string, "dest": string, "validator": bool, "client_address": string, "node_address": string,
+///                "bls_key_valid": bool, "bls_key_pop_valid": bool}],
+///     "validators_count": int,
+///     "f": int,
+///     "weak_quorum": int,
+///     "strong_quorum": int,
+///     "errors": [string],
+///     "warnings": [string]
+/// }
+pub fn validate_pool_genesis(genesis_txn: &str, protocol_version: usize) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _validate_pool_genesis(command_handle, genesis_txn, protocol_version, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _validate_pool_genesis(command_handle: CommandHandle, genesis_txn: &str, protocol_version: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let genesis_txn = c_str!(genesis_txn);
+
+    ErrorCode::from(unsafe {
+        pool::indy_validate_pool_genesis(command_handle, genesis_txn.as_ptr(), protocol_version, cb)
+    })
+}
+
 /// Lists names of created pool ledgers
 pub fn list_pools() -> Box<dyn Future<Item=String, Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
@@ -117,6 +241,51 @@ fn _close_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, cb
     ErrorCode::from(unsafe { pool::indy_close_pool_ledger(command_handle, pool_handle, cb) })
 }
 
+/// Creates a group of opened pool ledgers of several networks registered against DID method names.
+///
+/// The returned handle can be passed instead of a pool handle to ledger functions submitting requests
+/// and to cache functions: requests are sent to the pool registered for the method of the fully-qualified
+/// identifier they reference. Requests referencing unqualified identifiers only are sent to the default pool.
+///
+/// # Arguments
+/// * `config` - Pool group configuration json:
+/// {
+///     "routes": {"<DID method>": <pool handle>, ...} (optional), pools by DID method names, e.g. "sov" or "sov:staging".
+///     "default": <pool handle> (optional), pool for unqualified identifiers and DID methods without route.
+/// }
+///
+/// # Returns
+/// Handle of the pool group.
+pub fn create_pool_group(config: &str) -> Box<dyn Future<Item=PoolHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _create_pool_group(command_handle, config, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _create_pool_group(command_handle: CommandHandle, config: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let config = c_str!(config);
+
+    ErrorCode::from(unsafe { pool::indy_create_pool_group(command_handle, config.as_ptr(), cb) })
+}
+
+/// Closes the pool group. Pool ledgers of the group stay opened.
+///
+/// # Arguments
+/// * `handle` - pool group handle returned by create_pool_group.
+pub fn close_pool_group(pool_group_handle: PoolHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _close_pool_group(command_handle, pool_group_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _close_pool_group(command_handle: CommandHandle, pool_group_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { pool::indy_close_pool_group(command_handle, pool_group_handle, cb) })
+}
+
 /// Deletes created pool ledger configuration.
 ///
 /// # Arguments
@@ -11,7 +11,8 @@ use ffi::{wallet, non_secrets};
 use ffi::{ResponseEmptyCB,
           ResponseStringCB,
           ResponseI32CB};
-use {CommandHandle, WalletHandle, SearchHandle};
+use {CommandHandle, IndyHandle, WalletHandle, SearchHandle, SubscriptionHandle};
+pub use ffi::non_secrets::WalletRecordsEventCB;
 
 /// Registers custom wallet implementation.
 ///
@@ -215,6 +216,10 @@ fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str,
 ///   {
 ///     "path": path of the file that contains exported wallet content
 ///     "key": passphrase used to derive export key
+///     "types": optional<[string]> types of records to export, all records are exported by default
+///     "query": optional<object> WQL query records of each of the types have to match
+///     "marker": optional<string> name of the export marker, only records added or changed
+///               since the previous export with the same marker are exported
 ///   }
 pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -232,6 +237,62 @@ fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, ex
     })
 }
 
+/// Exports opened wallet passing the exported content to the write callback instead of the file.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `export_config` - JSON containing settings for input operation.
+///   {
+///     "key": passphrase used to derive export key
+///     "types": optional<[string]> types of records to export, all records are exported by default
+///     "query": optional<object> WQL query records of each of the types have to match
+///     "marker": optional<string> name of the export marker
+///   }
+/// * `stream_handle` - caller defined handle passed to the write callback
+/// * `write_cb` - callback receiving chunks of the exported content in order
+pub fn export_wallet_to_stream(wallet_handle: WalletHandle, export_config: &str, stream_handle: IndyHandle, write_cb: Option<wallet::WalletExportWrite>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _export_wallet_to_stream(command_handle, wallet_handle, export_config, stream_handle, write_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _export_wallet_to_stream(command_handle: CommandHandle, wallet_handle: WalletHandle, export_config: &str, stream_handle: IndyHandle, write_cb: Option<wallet::WalletExportWrite>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let export_config = c_str!(export_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_export_wallet_to_stream(command_handle, wallet_handle, export_config.as_ptr(), stream_handle, write_cb, cb)
+    })
+}
+
+/// Rotates master key of opened wallet.
+/// Wallet records aren't re-encrypted, so the call doesn't depend on the wallet size.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `credentials` - JSON containing new wallet credentials.
+///   {
+///     "key": new passphrase used to derive wallet master key
+///     "key_derivation_method": optional<string> algorithm to use for master key derivation:
+///                              ARGON2I_MOD (used by default), ARGON2I_INT or RAW
+///   }
+pub fn rekey_wallet(wallet_handle: WalletHandle, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _rekey_wallet(command_handle, wallet_handle, credentials, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _rekey_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let credentials = c_str!(credentials);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_rekey_wallet(command_handle, wallet_handle, credentials.as_ptr(), cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name and then imports its content
 /// according to fields provided in import_config
 /// This can be seen as an create call with additional content import
@@ -273,6 +334,60 @@ fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Imports content of the wallet export file into the opened wallet.
+/// The wallet is left unchanged if the import fails.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `import_config` - JSON containing settings for input operation.
+///   {
+///     "path": path of the file that contains exported wallet content
+///     "key": passphrase used to derive export key
+///     "on_conflict": optional<string> "fail" (default), "skip" or "overwrite" existing records
+///   }
+pub fn import_wallet_records(wallet_handle: WalletHandle, import_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_records(command_handle, wallet_handle, import_config, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, import_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_import_wallet_records(command_handle, wallet_handle, import_config.as_ptr(), cb)
+    })
+}
+
+/// Imports wallet content taken from the read callback into the opened wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `import_config` - JSON containing settings for input operation.
+///   {
+///     "key": passphrase used to derive export key
+///     "on_conflict": optional<string> "fail" (default), "skip" or "overwrite" existing records
+///   }
+/// * `stream_handle` - caller defined handle passed to the read callback
+/// * `read_cb` - callback filling the buffer with the next chunk of the content, 0 read bytes means the end of it
+pub fn import_wallet_records_from_stream(wallet_handle: WalletHandle, import_config: &str, stream_handle: IndyHandle, read_cb: Option<wallet::WalletImportRead>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_records_from_stream(command_handle, wallet_handle, import_config, stream_handle, read_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_records_from_stream(command_handle: CommandHandle, wallet_handle: WalletHandle, import_config: &str, stream_handle: IndyHandle, read_cb: Option<wallet::WalletImportRead>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_import_wallet_records_from_stream(command_handle, wallet_handle, import_config.as_ptr(), stream_handle, read_cb, cb)
+    })
+}
+
 /// Deletes created wallet.
 pub fn delete_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -291,6 +406,33 @@ fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Checks integrity of created wallet and optionally repairs it.
+///
+/// # Arguments
+/// * `config` - wallet configuration json, the same as for `delete_wallet`.
+/// * `credentials` - wallet credentials json, the same as for `delete_wallet`.
+/// * `options` - (optional) check options json, see `indy_check_wallet` for details.
+///
+/// # Returns
+/// Check report json
+pub fn check_wallet(config: &str, credentials: &str, options: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_wallet(command_handle, config, credentials, options, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_wallet(command_handle: CommandHandle, config: &str, credentials: &str, options: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+    let options = opt_c_str_json!(options);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_check_wallet(command_handle, config.as_ptr(), credentials.as_ptr(), options.as_ptr(), cb)
+    })
+}
+
 /// Closes opened wallet and frees allocated resources.
 ///
 /// # Arguments
@@ -498,6 +640,36 @@ fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHan
     })
 }
 
+/// Apply a list of changes of wallet records atomically:
+/// either all of them are applied or the wallet is left untouched.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `operations_json` - list of operations applied in the given order:
+///   [
+///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": <optional json>},
+///     {"op": "update", "type": <str>, "id": <str>, "value": <str>},
+///     {"op": "add_tags", "type": <str>, "id": <str>, "tags": <json>},
+///     {"op": "update_tags", "type": <str>, "id": <str>, "tags": <json>},
+///     {"op": "delete_tags", "type": <str>, "id": <str>, "tag_names": [<str>, ...]},
+///     {"op": "delete", "type": <str>, "id": <str>},
+///   ]
+pub fn batch_wallet_records(wallet_handle: WalletHandle, operations_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _batch_wallet_records(command_handle, wallet_handle, operations_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _batch_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, operations_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let operations_json = c_str!(operations_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_batch_wallet_records(command_handle, wallet_handle, operations_json.as_ptr(), cb)
+    })
+}
+
 /// Get an wallet record by id
 ///
 /// # Arguments
@@ -560,6 +732,12 @@ fn _get_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle
 ///    retrieveType: (optional, false by default) Retrieve record type,
 ///    retrieveValue: (optional, true by default) Retrieve record value,
 ///    retrieveTags: (optional, false by default) Retrieve record tags,
+///    sortBy: (optional) Name of the plaintext tag ("~" prefixed) to sort records by,
+///            records without this tag go first in ascending order
+///    sortOrder: (optional, "asc" by default) Sort order, "asc" or "desc",
+///    offset: (optional, 0 by default) Number of records to skip,
+///    limit: (optional) Maximum number of records to return,
+///    tagNames: (optional) Names of the tags to retrieve with retrieveTags, all tags by default,
 ///  }
 /// # Returns
 /// * `search_handle` - Wallet search handle that can be used later
@@ -634,6 +812,58 @@ fn _close_wallet_search(command_handle: CommandHandle, wallet_search_handle: Sea
     })
 }
 
+/// Subscribe to changes of wallet records of the type which tags match the query.
+/// The event callback is called until the subscription is removed or the wallet is closed.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - type of the records to watch
+/// * `query_json` - WQL query to the record tags, the same as for `open_wallet_search`
+/// * `event_cb` - callback that takes the subscription handle and the event json:
+/// {
+///   "event": "add" | "update" | "add_tags" | "update_tags" | "delete_tags" | "delete",
+///   "type": "Some type",
+///   "id": "Some id",
+///   "tags": <tags json>, // tags after the change (before it for "delete")
+/// }
+///
+/// # Returns
+/// * `subscription_handle` - handle to remove the subscription with `unsubscribe_wallet_records`
+pub fn subscribe_wallet_records(wallet_handle: WalletHandle, xtype: &str, query_json: &str, event_cb: WalletRecordsEventCB) -> Box<dyn Future<Item=SubscriptionHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _subscribe_wallet_records(command_handle, wallet_handle, xtype, query_json, event_cb, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _subscribe_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, query_json: &str, event_cb: WalletRecordsEventCB, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+    let query_json = c_str!(query_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_subscribe_wallet_records(command_handle, wallet_handle, xtype.as_ptr(), query_json.as_ptr(), Some(event_cb), cb)
+    })
+}
+
+/// Remove wallet records subscription (make subscription handle invalid)
+///
+/// # Arguments
+/// * `subscription_handle` - wallet subscription handle (created by subscribe_wallet_records)
+pub fn unsubscribe_wallet_records(subscription_handle: SubscriptionHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _unsubscribe_wallet_records(command_handle, subscription_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _unsubscribe_wallet_records(command_handle: CommandHandle, subscription_handle: SubscriptionHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+      non_secrets::indy_unsubscribe_wallet_records(command_handle, subscription_handle, cb)
+    })
+}
+
 fn _default_credentials(credentials: Option<&str>) -> CString {
     match credentials {
         Some(s) => c_str!(s),